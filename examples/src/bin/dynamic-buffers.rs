@@ -154,7 +154,8 @@ fn main() {
     };
 
     let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
-    let descriptor_set_allocator = StandardDescriptorSetAllocator::new(device.clone());
+    let descriptor_set_allocator =
+        StandardDescriptorSetAllocator::new(device.clone(), Default::default());
     let command_buffer_allocator =
         StandardCommandBufferAllocator::new(device.clone(), Default::default());
 
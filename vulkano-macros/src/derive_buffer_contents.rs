@@ -12,7 +12,8 @@ use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::{
     parse_quote, spanned::Spanned, Data, DeriveInput, Fields, FieldsNamed, FieldsUnnamed, Ident,
-    Meta, MetaList, NestedMeta, Result, Type, TypeArray, TypeSlice, WherePredicate,
+    Lit, Meta, MetaList, NestedMeta, Result, Type, TypeArray, TypeGroup, TypeParen, TypeSlice,
+    WherePredicate,
 };
 
 pub fn derive_buffer_contents(mut ast: DeriveInput) -> Result<TokenStream> {
@@ -20,31 +21,7 @@ pub fn derive_buffer_contents(mut ast: DeriveInput) -> Result<TokenStream> {
 
     let struct_ident = &ast.ident;
 
-    if !ast
-        .attrs
-        .iter()
-        .filter_map(|attr| {
-            attr.path
-                .is_ident("repr")
-                .then(|| attr.parse_meta().unwrap())
-        })
-        .any(|meta| match meta {
-            Meta::List(MetaList { nested, .. }) => {
-                nested.iter().any(|nested_meta| match nested_meta {
-                    NestedMeta::Meta(Meta::Path(path)) => {
-                        path.is_ident("C") || path.is_ident("transparent")
-                    }
-                    _ => false,
-                })
-            }
-            _ => false,
-        })
-    {
-        bail!(
-            "deriving `BufferContents` is only supported for types that are marked `#[repr(C)]` \
-            or `#[repr(transparent)]`",
-        );
-    }
+    let repr = parse_repr(&ast)?;
 
     let (impl_generics, type_generics, where_clause) = {
         let predicates = ast
@@ -63,7 +40,7 @@ pub fn derive_buffer_contents(mut ast: DeriveInput) -> Result<TokenStream> {
         ast.generics.split_for_impl()
     };
 
-    let layout = write_layout(&crate_ident, &ast)?;
+    let layout = write_layout(&crate_ident, &ast, &repr)?;
 
     Ok(quote! {
         #[allow(unsafe_code)]
@@ -130,7 +107,60 @@ pub fn derive_buffer_contents(mut ast: DeriveInput) -> Result<TokenStream> {
     })
 }
 
-fn write_layout(crate_ident: &Ident, ast: &DeriveInput) -> Result<TokenStream> {
+/// The representation hints of the struct that influence its layout.
+#[derive(Default)]
+struct Repr {
+    packed: Option<usize>,
+    align: Option<usize>,
+}
+
+fn parse_repr(ast: &DeriveInput) -> Result<Repr> {
+    let mut is_c_or_transparent = false;
+    let mut repr = Repr::default();
+
+    for attr in ast.attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        let nested = match attr.parse_meta()? {
+            Meta::List(MetaList { nested, .. }) => nested,
+            _ => continue,
+        };
+
+        for nested_meta in nested {
+            match nested_meta {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    if path.is_ident("C") || path.is_ident("transparent") {
+                        is_c_or_transparent = true;
+                    } else if path.is_ident("packed") {
+                        repr.packed = Some(1);
+                    }
+                }
+                NestedMeta::Meta(Meta::List(MetaList { path, nested, .. })) => {
+                    let value = match nested.first() {
+                        Some(NestedMeta::Lit(Lit::Int(int))) => int.base10_parse::<usize>()?,
+                        _ => continue,
+                    };
+
+                    if path.is_ident("packed") {
+                        repr.packed = Some(value);
+                    } else if path.is_ident("align") {
+                        repr.align = Some(value);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if !is_c_or_transparent {
+        bail!(
+            "deriving `BufferContents` is only supported for types that are marked `#[repr(C)]` \
+            or `#[repr(transparent)]`",
+        );
+    }
+
+    Ok(repr)
+}
+
+fn write_layout(crate_ident: &Ident, ast: &DeriveInput, repr: &Repr) -> Result<TokenStream> {
     let data = match &ast.data {
         Data::Struct(data) => data,
         Data::Enum(_) => bail!("deriving `BufferContents` for enums is not supported"),
@@ -143,9 +173,36 @@ fn write_layout(crate_ident: &Ident, ast: &DeriveInput) -> Result<TokenStream> {
         Fields::Unit => bail!("zero-sized types are not valid buffer contents"),
     };
 
+    for field in fields {
+        check_field_type(&field.ty)?;
+    }
+
     let mut field_types = fields.iter().map(|field| &field.ty);
     let last_field_type = field_types.next_back().unwrap();
-    let mut layout = quote! { ::std::alloc::Layout::new::<()>() };
+
+    // An explicit `#[repr(align(N))]` raises the alignment of the whole struct, which we account
+    // for by starting out with a zero-sized layout of that alignment. This way the head is padded
+    // to the right size in the end.
+    let mut layout = if let Some(align) = repr.align {
+        quote! {
+            if let Ok(layout) = ::std::alloc::Layout::from_size_align(0, #align) {
+                layout
+            } else {
+                ::std::unreachable!()
+            }
+        }
+    } else {
+        quote! { ::std::alloc::Layout::new::<()>() }
+    };
+
+    // With `#[repr(packed(N))]`, the alignment of every field is capped at `N`.
+    let field_layout = |ty: &Type| {
+        if let Some(packed) = repr.packed {
+            quote! { pack_layout(::std::alloc::Layout::new::<#ty>(), #packed) }
+        } else {
+            quote! { ::std::alloc::Layout::new::<#ty>() }
+        }
+    };
 
     let mut bound_types = Vec::new();
 
@@ -154,8 +211,9 @@ fn write_layout(crate_ident: &Ident, ast: &DeriveInput) -> Result<TokenStream> {
     for field_type in field_types {
         bound_types.push(find_innermost_element_type(field_type));
 
+        let next = field_layout(field_type);
         layout = quote! {
-            extend_layout(#layout, ::std::alloc::Layout::new::<#field_type>())
+            extend_layout(#layout, #next)
         };
     }
 
@@ -175,10 +233,23 @@ fn write_layout(crate_ident: &Ident, ast: &DeriveInput) -> Result<TokenStream> {
         // `BufferContents::extend_from_layout` on it either.
         Type::Slice(TypeSlice { elem, .. }) => {
             bound_types.push(find_innermost_element_type(elem));
+            let element_layout = field_layout(elem);
             layout = quote! {
                 ::#crate_ident::buffer::BufferContentsLayout::from_head_element_layout(
                     #layout,
-                    ::std::alloc::Layout::new::<#elem>(),
+                    #element_layout,
+                )
+            };
+        }
+        // The layout of a packed field can only be determined if it is sized, as the alignment of
+        // a user-defined DST can't be capped after the fact. Requiring the field to be sized here
+        // means that using a DST is a compile error instead of a silently wrong layout.
+        ty if repr.packed.is_some() => {
+            bound_types.push(ty);
+            let next = field_layout(ty);
+            layout = quote! {
+                ::#crate_ident::buffer::BufferContentsLayout::from_sized(
+                    extend_layout(#layout, #next)
                 )
             };
         }
@@ -243,6 +314,25 @@ fn write_layout(crate_ident: &Ident, ast: &DeriveInput) -> Result<TokenStream> {
                 }
             }
 
+            #[allow(unused)]
+            const fn pack_layout(
+                layout: ::std::alloc::Layout,
+                packed: usize,
+            ) -> ::std::alloc::Layout {
+                // TODO: Replace with `Ord::min` once its constness is stabilized.
+                let align = if layout.align() <= packed {
+                    layout.align()
+                } else {
+                    packed
+                };
+
+                if let Ok(layout) = ::std::alloc::Layout::from_size_align(layout.size(), align) {
+                    layout
+                } else {
+                    ::std::unreachable!()
+                }
+            }
+
             if let Some(layout) = #layout {
                 if let Some(layout) = layout.pad_to_alignment() {
                     layout
@@ -258,6 +348,35 @@ fn write_layout(crate_ident: &Ident, ast: &DeriveInput) -> Result<TokenStream> {
     Ok(layout)
 }
 
+// Types that don't have a layout that is guaranteed to be stable and C-compatible can never be
+// laid out correctly in a buffer. Most of them also don't implement `BufferContents`, but catching
+// them here gives a more helpful error message than the trait bound failing.
+fn check_field_type(field_type: &Type) -> Result<()> {
+    match field_type {
+        Type::Array(TypeArray { elem, .. }) | Type::Slice(TypeSlice { elem, .. }) => {
+            check_field_type(elem)
+        }
+        Type::Group(TypeGroup { elem, .. }) | Type::Paren(TypeParen { elem, .. }) => {
+            check_field_type(elem)
+        }
+        Type::Tuple(tuple) if !tuple.elems.is_empty() => bail!(
+            field_type,
+            "tuples don't have a stable layout and are not valid buffer contents; use a \
+            `#[repr(C)]` struct or an array instead",
+        ),
+        Type::Reference(_) | Type::Ptr(_) => bail!(
+            field_type,
+            "pointers and references are not valid buffer contents; use a device address \
+            instead",
+        ),
+        Type::BareFn(_) | Type::TraitObject(_) | Type::ImplTrait(_) | Type::Never(_) => bail!(
+            field_type,
+            "this type doesn't have a layout that is valid for buffer contents",
+        ),
+        _ => Ok(()),
+    }
+}
+
 // HACK: This works around an inherent limitation of bytemuck, namely that an array where the
 // element is `AnyBitPattern` is itself not `AnyBitPattern`, by only requiring that the innermost
 // type in the array implements `BufferContents`.
@@ -309,6 +428,39 @@ mod tests {
         assert!(derive_buffer_contents(unit).is_err());
     }
 
+    #[test]
+    fn invalid_field_types() {
+        let tuple = parse_quote! {
+            #[repr(C)]
+            struct Test(u32, (f32, f32));
+        };
+        assert!(derive_buffer_contents(tuple).is_err());
+
+        let nested_tuple = parse_quote! {
+            #[repr(C)]
+            struct Test(u32, [[(f32, f32); 2]]);
+        };
+        assert!(derive_buffer_contents(nested_tuple).is_err());
+
+        let reference = parse_quote! {
+            #[repr(C)]
+            struct Test<'a>(&'a u32, u32);
+        };
+        assert!(derive_buffer_contents(reference).is_err());
+
+        let pointer = parse_quote! {
+            #[repr(C)]
+            struct Test(*const u32, u32);
+        };
+        assert!(derive_buffer_contents(pointer).is_err());
+
+        let arrays = parse_quote! {
+            #[repr(C)]
+            struct Test([[f32; 4]; 4], [u32; 3], [[f32; 2]]);
+        };
+        assert!(derive_buffer_contents(arrays).is_ok());
+    }
+
     #[test]
     fn unsupported_datatype() {
         let enum_ = parse_quote! {
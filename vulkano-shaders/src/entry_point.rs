@@ -10,11 +10,13 @@
 use ahash::HashMap;
 use proc_macro2::TokenStream;
 use vulkano::{
+    device::physical::SubgroupFeatures,
     pipeline::layout::PushConstantRange,
     shader::{
         DescriptorBindingRequirements, DescriptorIdentifier, DescriptorRequirements,
-        EntryPointInfo, ShaderExecution, ShaderInterface, ShaderInterfaceEntry,
-        ShaderInterfaceEntryType, ShaderStages, SpecializationConstant,
+        EntryPointInfo, FloatControls, LocalSize, ShaderExecution, ShaderInterface,
+        ShaderInterfaceEntry, ShaderInterfaceEntryType, ShaderStages, SpecializationConstant,
+        TessellationShaderExecution, WorkgroupMemorySize,
     },
 };
 
@@ -28,6 +30,9 @@ pub(super) fn write_entry_point(info: &EntryPointInfo) -> TokenStream {
     let specialization_constants = write_specialization_constants(&info.specialization_constants);
     let input_interface = write_interface(&info.input_interface);
     let output_interface = write_interface(&info.output_interface);
+    let subgroup_operations = subgroup_features_to_items(info.subgroup_operations);
+    let workgroup_memory_size = write_workgroup_memory_size(&info.workgroup_memory_size);
+    let float_controls = write_float_controls(&info.float_controls);
 
     quote! {
         ::vulkano::shader::EntryPointInfo {
@@ -38,18 +43,69 @@ pub(super) fn write_entry_point(info: &EntryPointInfo) -> TokenStream {
             specialization_constants: #specialization_constants.into_iter().collect(),
             input_interface: #input_interface,
             output_interface: #output_interface,
+            subgroup_operations: #subgroup_operations,
+            workgroup_memory_size: #workgroup_memory_size,
+            float_controls: #float_controls.into_iter().collect(),
         },
     }
 }
 
+fn write_tessellation_shader_execution(execution: &TessellationShaderExecution) -> TokenStream {
+    let &TessellationShaderExecution {
+        output_vertices,
+        subdivision,
+        spacing,
+        vertex_order,
+        point_mode,
+    } = execution;
+
+    let output_vertices = match output_vertices {
+        Some(output_vertices) => quote! { Some(#output_vertices) },
+        None => quote! { None },
+    };
+    let subdivision = match subdivision {
+        Some(subdivision) => {
+            let subdivision = format_ident!("{}", format!("{:?}", subdivision));
+            quote! { Some(::vulkano::shader::TessellationShaderSubdivision::#subdivision) }
+        }
+        None => quote! { None },
+    };
+    let spacing = match spacing {
+        Some(spacing) => {
+            let spacing = format_ident!("{}", format!("{:?}", spacing));
+            quote! { Some(::vulkano::shader::TessellationShaderSpacing::#spacing) }
+        }
+        None => quote! { None },
+    };
+    let vertex_order = match vertex_order {
+        Some(vertex_order) => {
+            let vertex_order = format_ident!("{}", format!("{:?}", vertex_order));
+            quote! { Some(::vulkano::shader::TessellationShaderVertexOrder::#vertex_order) }
+        }
+        None => quote! { None },
+    };
+
+    quote! {
+        ::vulkano::shader::TessellationShaderExecution {
+            output_vertices: #output_vertices,
+            subdivision: #subdivision,
+            spacing: #spacing,
+            vertex_order: #vertex_order,
+            point_mode: #point_mode,
+        }
+    }
+}
+
 fn write_shader_execution(execution: &ShaderExecution) -> TokenStream {
     match execution {
         ShaderExecution::Vertex => quote! { ::vulkano::shader::ShaderExecution::Vertex },
-        ShaderExecution::TessellationControl => {
-            quote! { ::vulkano::shader::ShaderExecution::TessellationControl }
+        ShaderExecution::TessellationControl(execution) => {
+            let execution = write_tessellation_shader_execution(execution);
+            quote! { ::vulkano::shader::ShaderExecution::TessellationControl(#execution) }
         }
-        ShaderExecution::TessellationEvaluation => {
-            quote! { ::vulkano::shader::ShaderExecution::TessellationEvaluation }
+        ShaderExecution::TessellationEvaluation(execution) => {
+            let execution = write_tessellation_shader_execution(execution);
+            quote! { ::vulkano::shader::ShaderExecution::TessellationEvaluation(#execution) }
         }
         ShaderExecution::Geometry(::vulkano::shader::GeometryShaderExecution { input }) => {
             let input = format_ident!("{}", format!("{:?}", input));
@@ -73,7 +129,29 @@ fn write_shader_execution(execution: &ShaderExecution) -> TokenStream {
                 )
             }
         }
-        ShaderExecution::Compute => quote! { ::vulkano::shader::ShaderExecution::Compute },
+        ShaderExecution::Compute(::vulkano::shader::ComputeShaderExecution { local_size }) => {
+            let local_size = match local_size {
+                Some(local_size) => {
+                    let local_size = local_size.iter().map(|size| match *size {
+                        LocalSize::Literal(value) => {
+                            quote! { ::vulkano::shader::LocalSize::Literal(#value) }
+                        }
+                        LocalSize::SpecId(constant_id) => {
+                            quote! { ::vulkano::shader::LocalSize::SpecId(#constant_id) }
+                        }
+                    });
+                    quote! { Some([#(#local_size),*]) }
+                }
+                None => quote! { None },
+            };
+            quote! {
+                ::vulkano::shader::ShaderExecution::Compute(
+                    ::vulkano::shader::ComputeShaderExecution {
+                        local_size: #local_size,
+                    }
+                )
+            }
+        }
         ShaderExecution::RayGeneration => {
             quote! { ::vulkano::shader::ShaderExecution::RayGeneration }
         }
@@ -92,6 +170,62 @@ fn write_shader_execution(execution: &ShaderExecution) -> TokenStream {
     }
 }
 
+fn write_workgroup_memory_size(workgroup_memory_size: &Option<WorkgroupMemorySize>) -> TokenStream {
+    match workgroup_memory_size {
+        Some(WorkgroupMemorySize { terms }) => {
+            let terms = terms.iter().map(|term| {
+                let size = term.size;
+                let spec_ids = &term.spec_ids;
+
+                quote! {
+                    ::vulkano::shader::WorkgroupMemorySizeTerm {
+                        size: #size,
+                        spec_ids: vec![#(#spec_ids),*],
+                    }
+                }
+            });
+
+            quote! {
+                Some(::vulkano::shader::WorkgroupMemorySize {
+                    terms: vec![#(#terms),*],
+                })
+            }
+        }
+        None => quote! { None },
+    }
+}
+
+fn write_float_controls(float_controls: &HashMap<u32, FloatControls>) -> TokenStream {
+    let float_controls = float_controls.iter().map(|(&width, controls)| {
+        let &FloatControls {
+            denorm_preserve,
+            denorm_flush_to_zero,
+            signed_zero_inf_nan_preserve,
+            rounding_mode_rte,
+            rounding_mode_rtz,
+        } = controls;
+
+        quote! {
+            (
+                #width,
+                ::vulkano::shader::FloatControls {
+                    denorm_preserve: #denorm_preserve,
+                    denorm_flush_to_zero: #denorm_flush_to_zero,
+                    signed_zero_inf_nan_preserve: #signed_zero_inf_nan_preserve,
+                    rounding_mode_rte: #rounding_mode_rte,
+                    rounding_mode_rtz: #rounding_mode_rtz,
+                },
+            )
+        }
+    });
+
+    quote! {
+        [
+            #( #float_controls ),*
+        ]
+    }
+}
+
 fn write_descriptor_binding_requirements(
     descriptor_binding_requirements: &HashMap<(u32, u32), DescriptorBindingRequirements>,
 ) -> TokenStream {
@@ -375,3 +509,45 @@ fn stages_to_items(stages: ShaderStages) -> TokenStream {
         quote! { #( #stages_items )|* }
     }
 }
+
+fn subgroup_features_to_items(features: SubgroupFeatures) -> TokenStream {
+    if features.is_empty() {
+        quote! { ::vulkano::device::physical::SubgroupFeatures::empty() }
+    } else {
+        let features_items = [
+            features.intersects(SubgroupFeatures::BASIC).then(|| {
+                quote! { ::vulkano::device::physical::SubgroupFeatures::BASIC }
+            }),
+            features.intersects(SubgroupFeatures::VOTE).then(|| {
+                quote! { ::vulkano::device::physical::SubgroupFeatures::VOTE }
+            }),
+            features.intersects(SubgroupFeatures::ARITHMETIC).then(|| {
+                quote! { ::vulkano::device::physical::SubgroupFeatures::ARITHMETIC }
+            }),
+            features.intersects(SubgroupFeatures::BALLOT).then(|| {
+                quote! { ::vulkano::device::physical::SubgroupFeatures::BALLOT }
+            }),
+            features.intersects(SubgroupFeatures::SHUFFLE).then(|| {
+                quote! { ::vulkano::device::physical::SubgroupFeatures::SHUFFLE }
+            }),
+            features
+                .intersects(SubgroupFeatures::SHUFFLE_RELATIVE)
+                .then(|| {
+                    quote! { ::vulkano::device::physical::SubgroupFeatures::SHUFFLE_RELATIVE }
+                }),
+            features.intersects(SubgroupFeatures::CLUSTERED).then(|| {
+                quote! { ::vulkano::device::physical::SubgroupFeatures::CLUSTERED }
+            }),
+            features.intersects(SubgroupFeatures::QUAD).then(|| {
+                quote! { ::vulkano::device::physical::SubgroupFeatures::QUAD }
+            }),
+            features.intersects(SubgroupFeatures::PARTITIONED).then(|| {
+                quote! { ::vulkano::device::physical::SubgroupFeatures::PARTITIONED }
+            }),
+        ]
+        .into_iter()
+        .flatten();
+
+        quote! { #( #features_items )|* }
+    }
+}
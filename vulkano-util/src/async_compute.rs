@@ -0,0 +1,163 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A helper for running compute work on a separate queue, overlapping with graphics work.
+
+use crate::context::VulkanoContext;
+use std::sync::Arc;
+use vulkano::{
+    command_buffer::{CommandBufferExecError, PrimaryCommandBufferAbstract},
+    device::{DeviceOwned, Queue},
+    sync::{future::JoinFuture, GpuFuture, Sharing},
+};
+
+/// Submits compute work to the compute queue of a [`VulkanoContext`], and makes graphics work
+/// wait for it on the graphics queue.
+///
+/// If the device has a compute-capable queue family that is distinct from the graphics queue
+/// family, the compute work can run asynchronously. Otherwise, both queues are the same, and the
+/// work is simply executed in order.
+///
+/// Vulkano does not insert queue family ownership transfers. Resources that are used by both the
+/// compute and the graphics work must be created with the sharing mode returned by
+/// [`sharing`](AsyncCompute::sharing).
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use vulkano::{command_buffer::PrimaryAutoCommandBuffer, sync::GpuFuture};
+/// # use vulkano_util::{async_compute::AsyncCompute, context::VulkanoContext};
+/// # let context: VulkanoContext = return;
+/// # let compute_command_buffer: Arc<PrimaryAutoCommandBuffer> = return;
+/// # let graphics_command_buffer: Arc<PrimaryAutoCommandBuffer> = return;
+/// # let previous_frame_end: Box<dyn GpuFuture> = return;
+/// let async_compute = AsyncCompute::new(&context);
+///
+/// let future = async_compute
+///     .submit(compute_command_buffer)
+///     .unwrap()
+///     .signal_to(previous_frame_end)
+///     .then_execute(async_compute.graphics_queue().clone(), graphics_command_buffer)
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct AsyncCompute {
+    compute_queue: Arc<Queue>,
+    graphics_queue: Arc<Queue>,
+}
+
+impl AsyncCompute {
+    /// Creates a new `AsyncCompute` from the compute and graphics queues of `context`.
+    #[inline]
+    pub fn new(context: &VulkanoContext) -> Self {
+        Self::from_queues(
+            context.compute_queue().clone(),
+            context.graphics_queue().clone(),
+        )
+    }
+
+    /// Creates a new `AsyncCompute` from the given queues.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `compute_queue` and `graphics_queue` don't belong to the same device.
+    #[inline]
+    pub fn from_queues(compute_queue: Arc<Queue>, graphics_queue: Arc<Queue>) -> Self {
+        assert_eq!(compute_queue.device(), graphics_queue.device());
+
+        AsyncCompute {
+            compute_queue,
+            graphics_queue,
+        }
+    }
+
+    /// Returns the queue that compute work is submitted to.
+    #[inline]
+    pub fn compute_queue(&self) -> &Arc<Queue> {
+        &self.compute_queue
+    }
+
+    /// Returns the queue that graphics work is submitted to.
+    #[inline]
+    pub fn graphics_queue(&self) -> &Arc<Queue> {
+        &self.graphics_queue
+    }
+
+    /// Returns whether the compute queue belongs to a different queue family than the graphics
+    /// queue.
+    #[inline]
+    pub fn is_async(&self) -> bool {
+        self.compute_queue.queue_family_index() != self.graphics_queue.queue_family_index()
+    }
+
+    /// Returns the sharing mode that resources must be created with if they are used by both
+    /// the compute and the graphics work.
+    ///
+    /// This is `Sharing::Concurrent` with both queue families if [`is_async`](Self::is_async)
+    /// returns `true`, and `Sharing::Exclusive` otherwise.
+    #[inline]
+    pub fn sharing<I>(&self) -> Sharing<I>
+    where
+        I: IntoIterator<Item = u32> + FromIterator<u32>,
+    {
+        if self.is_async() {
+            Sharing::Concurrent(
+                [
+                    self.compute_queue.queue_family_index(),
+                    self.graphics_queue.queue_family_index(),
+                ]
+                .into_iter()
+                .collect(),
+            )
+        } else {
+            Sharing::Exclusive
+        }
+    }
+
+    /// Submits `command_buffer` to the compute queue, and signals a semaphore when it has
+    /// finished executing.
+    ///
+    /// Use [`AsyncComputeSubmission::signal_to`] to make graphics work wait for the semaphore.
+    pub fn submit(
+        &self,
+        command_buffer: Arc<impl PrimaryCommandBufferAbstract + 'static>,
+    ) -> Result<AsyncComputeSubmission, CommandBufferExecError> {
+        let future = command_buffer
+            .execute(self.compute_queue.clone())?
+            .then_signal_semaphore();
+
+        Ok(AsyncComputeSubmission {
+            future: Box::new(future),
+        })
+    }
+}
+
+/// Compute work that was submitted with [`AsyncCompute::submit`].
+pub struct AsyncComputeSubmission {
+    future: Box<dyn GpuFuture>,
+}
+
+impl AsyncComputeSubmission {
+    /// Joins the submission with `graphics_future`, so that anything that is submitted after
+    /// the returned future waits for the semaphore signaled by the compute work.
+    #[inline]
+    pub fn signal_to<F>(self, graphics_future: F) -> JoinFuture<F, Box<dyn GpuFuture>>
+    where
+        F: GpuFuture,
+    {
+        graphics_future.join(self.future)
+    }
+
+    /// Returns the future of the submission, which signals a semaphore.
+    #[inline]
+    pub fn into_future(self) -> Box<dyn GpuFuture> {
+        self.future
+    }
+}
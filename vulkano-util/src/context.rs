@@ -128,7 +128,17 @@ impl VulkanoContext {
     // FIXME:
     #[allow(deprecated)]
     pub fn new(mut config: VulkanoConfig) -> Self {
-        let library = match VulkanLibrary::new() {
+        let library = Self::load_library();
+
+        // Append required extensions
+        config.instance_create_info.enabled_extensions = vulkano_win::required_extensions(&library)
+            .union(&config.instance_create_info.enabled_extensions);
+
+        Self::with_library(library, config)
+    }
+
+    pub(crate) fn load_library() -> Arc<VulkanLibrary> {
+        match VulkanLibrary::new() {
             Ok(x) => x,
             #[cfg(target_os = "macos")]
             Err(vulkano::library::LoadingError::LibraryLoadFailure(err)) => panic!(
@@ -136,12 +146,10 @@ impl VulkanoContext {
                 https://vulkan.lunarg.com/sdk/home?",
             ),
             Err(err) => panic!("failed to load Vulkan library: {err}"),
-        };
-
-        // Append required extensions
-        config.instance_create_info.enabled_extensions = vulkano_win::required_extensions(&library)
-            .union(&config.instance_create_info.enabled_extensions);
+        }
+    }
 
+    pub(crate) fn with_library(library: Arc<VulkanLibrary>, mut config: VulkanoConfig) -> Self {
         // Create instance
         let instance =
             Instance::new(library, config.instance_create_info).expect("failed to create instance");
@@ -0,0 +1,156 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A helper for freeing resources in batches once the GPU has finished using them.
+
+use std::sync::Arc;
+use vulkano::{
+    sync::{fence::Fence, future::FenceSignalFuture, GpuFuture},
+    Validated, VulkanError,
+};
+
+/// Collects resources that are still in use by the GPU, and frees them in batches once the
+/// fences of their submissions have been signaled.
+///
+/// Instead of keeping a chain of futures per frame and calling
+/// [`cleanup_finished`](GpuFuture::cleanup_finished) on each of them, submissions are signaled
+/// with a fence and handed to the graveyard, which is then [collected](Self::collect) once per
+/// frame. Each collection only checks the status of one fence per entry, and drops all entries
+/// that have finished at once.
+///
+/// Dropping the graveyard blocks the current thread until all entries have finished, like
+/// dropping a [`FenceSignalFuture`] does.
+///
+/// # Examples
+///
+/// ```
+/// # use vulkano::sync::GpuFuture;
+/// # use vulkano_util::graveyard::ResourceGraveyard;
+/// # let future: Box<dyn GpuFuture> = return;
+/// let mut graveyard = ResourceGraveyard::new();
+///
+/// // Each frame, hand the submitted work to the graveyard...
+/// graveyard.bury(future.then_signal_fence_and_flush().unwrap());
+///
+/// // ...and free everything that has finished.
+/// graveyard.collect().unwrap();
+/// ```
+#[derive(Default)]
+pub struct ResourceGraveyard {
+    graves: Vec<Box<dyn Grave>>,
+    collected_count: u64,
+}
+
+impl ResourceGraveyard {
+    /// Creates a new, empty `ResourceGraveyard`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a submission to the graveyard. The future, and all resources that it keeps alive,
+    /// are dropped by [`collect`](Self::collect) once its fence has been signaled.
+    #[inline]
+    pub fn bury<F>(&mut self, future: FenceSignalFuture<F>)
+    where
+        F: GpuFuture + 'static,
+    {
+        self.graves.push(Box::new(future));
+    }
+
+    /// Adds `resources` to the graveyard. They are dropped by [`collect`](Self::collect) once
+    /// `fence` has been signaled.
+    ///
+    /// `fence` must be signaled by a submission that has already been made, or that will be made
+    /// before the next call to `collect`. Otherwise, the graveyard blocks forever when it is
+    /// dropped.
+    #[inline]
+    pub fn bury_with_fence<T>(&mut self, fence: Arc<Fence>, resources: T)
+    where
+        T: 'static,
+    {
+        self.graves.push(Box::new(FencedResources {
+            fence,
+            _resources: resources,
+        }));
+    }
+
+    /// Drops all entries whose fence has been signaled, and returns how many were dropped.
+    ///
+    /// This should be called once per frame. Entries whose fence has not been signaled yet are
+    /// kept until the next call.
+    pub fn collect(&mut self) -> Result<usize, Validated<VulkanError>> {
+        let mut result = Ok(());
+        let pending_count = self.graves.len();
+
+        self.graves.retain(|grave| {
+            if result.is_err() {
+                return true;
+            }
+
+            match grave.is_finished() {
+                Ok(finished) => !finished,
+                Err(err) => {
+                    result = Err(err);
+                    true
+                }
+            }
+        });
+
+        let collected = pending_count - self.graves.len();
+        self.collected_count += collected as u64;
+        result.map(|()| collected)
+    }
+
+    /// Returns the number of entries that are waiting for their fence to be signaled.
+    #[inline]
+    pub fn pending_count(&self) -> usize {
+        self.graves.len()
+    }
+
+    /// Returns the total number of entries that have been dropped by [`collect`](Self::collect)
+    /// so far.
+    #[inline]
+    pub fn collected_count(&self) -> u64 {
+        self.collected_count
+    }
+}
+
+trait Grave {
+    fn is_finished(&self) -> Result<bool, Validated<VulkanError>>;
+}
+
+impl<F> Grave for FenceSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    fn is_finished(&self) -> Result<bool, Validated<VulkanError>> {
+        self.poll()
+    }
+}
+
+struct FencedResources<T> {
+    fence: Arc<Fence>,
+    _resources: T,
+}
+
+impl<T> Grave for FencedResources<T> {
+    fn is_finished(&self) -> Result<bool, Validated<VulkanError>> {
+        Ok(self.fence.is_signaled()?)
+    }
+}
+
+impl<T> Drop for FencedResources<T> {
+    fn drop(&mut self) {
+        // Keep the resources alive until the GPU is done with them.
+        if !self.fence.is_signaled().unwrap_or(true) {
+            self.fence.wait(None).ok();
+        }
+    }
+}
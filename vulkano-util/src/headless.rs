@@ -0,0 +1,183 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A helper for rendering without a window, for example for automated tests or server-side
+//! rendering.
+
+use crate::context::{VulkanoConfig, VulkanoContext};
+use std::sync::Arc;
+use vulkano::{
+    buffer::{Buffer, BufferCreateInfo, BufferUsage},
+    command_buffer::{
+        allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+        CopyImageToBufferInfo, PrimaryAutoCommandBuffer, PrimaryCommandBufferAbstract,
+    },
+    device::{DeviceExtensions, QueueFlags},
+    format::{Format, FormatFeatures},
+    image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage},
+    memory::allocator::{AllocationCreateInfo, MemoryTypeFilter},
+    sync::GpuFuture,
+    DeviceSize,
+};
+
+/// A [`VulkanoContext`] without surface extensions, that can render into an image and read the
+/// result back to the host.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use vulkano::format::Format;
+/// use vulkano_util::headless::HeadlessContext;
+///
+/// let context = HeadlessContext::default();
+/// let pixels = context.render_to_image([64, 64], Format::R8G8B8A8_UNORM, |builder, view| {
+///     // Begin rendering to `view`, draw, and end rendering.
+/// });
+/// assert_eq!(pixels.len(), 64 * 64 * 4);
+/// ```
+pub struct HeadlessContext {
+    context: VulkanoContext,
+    command_buffer_allocator: StandardCommandBufferAllocator,
+}
+
+impl Default for HeadlessContext {
+    /// Creates a `HeadlessContext` that picks the highest priority device with a graphics queue,
+    /// without requiring any device extensions.
+    #[inline]
+    fn default() -> Self {
+        HeadlessContext::new(VulkanoConfig {
+            device_filter_fn: Arc::new(|p| {
+                p.queue_family_properties()
+                    .iter()
+                    .any(|q| q.queue_flags.intersects(QueueFlags::GRAPHICS))
+            }),
+            device_extensions: DeviceExtensions::empty(),
+            ..Default::default()
+        })
+    }
+}
+
+impl HeadlessContext {
+    /// Creates a new `HeadlessContext`.
+    ///
+    /// Unlike [`VulkanoContext::new`], the instance extensions that are required for surface
+    /// creation are not appended to `config`. Note that the default [`VulkanoConfig`] requires
+    /// the `khr_swapchain` device extension, which is not needed for headless rendering.
+    ///
+    /// # Panics
+    ///
+    /// - Panics where the underlying Vulkano struct creations fail
+    pub fn new(config: VulkanoConfig) -> Self {
+        let context = VulkanoContext::with_library(VulkanoContext::load_library(), config);
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(context.device().clone(), Default::default());
+
+        HeadlessContext {
+            context,
+            command_buffer_allocator,
+        }
+    }
+
+    /// Returns the underlying context.
+    #[inline]
+    pub fn context(&self) -> &VulkanoContext {
+        &self.context
+    }
+
+    /// Returns the command buffer allocator.
+    #[inline]
+    pub fn command_buffer_allocator(&self) -> &StandardCommandBufferAllocator {
+        &self.command_buffer_allocator
+    }
+
+    /// Creates a color image with the given `extent` and `format`, records the commands of
+    /// `record` into a command buffer on the graphics queue, then copies the image to host memory
+    /// and returns its contents.
+    ///
+    /// `record` is given a view of the image, which it should render to. The image starts out
+    /// with undefined contents. The returned bytes are tightly packed rows of texels.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `format` does not support both [`FormatFeatures::COLOR_ATTACHMENT`] and
+    ///   [`FormatFeatures::TRANSFER_SRC`] with optimal tiling on the device.
+    /// - Panics where the underlying Vulkano struct creations, the recording or the execution of
+    ///   the command buffer fail.
+    pub fn render_to_image<F>(&self, extent: [u32; 2], format: Format, record: F) -> Vec<u8>
+    where
+        F: FnOnce(&mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>, &Arc<ImageView>),
+    {
+        let device = self.context.device();
+        let queue = self.context.graphics_queue();
+        let memory_allocator = self.context.memory_allocator();
+
+        let format_features = device
+            .physical_device()
+            .format_properties(format)
+            .unwrap()
+            .optimal_tiling_features;
+        assert!(
+            format_features
+                .contains(FormatFeatures::COLOR_ATTACHMENT | FormatFeatures::TRANSFER_SRC),
+            "`format` does not support both `FormatFeatures::COLOR_ATTACHMENT` and \
+            `FormatFeatures::TRANSFER_SRC` with optimal tiling",
+        );
+
+        let image = Image::new(
+            memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format,
+                extent: [extent[0], extent[1], 1],
+                usage: ImageUsage::COLOR_ATTACHMENT | ImageUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let view = ImageView::new_default(image.clone()).unwrap();
+
+        let buffer = Buffer::new_slice::<u8>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            extent[0] as DeviceSize * extent[1] as DeviceSize * format.block_size(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &self.command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        record(&mut builder, &view);
+        builder
+            .copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(image, buffer.clone()))
+            .unwrap();
+        let command_buffer = builder.build().unwrap();
+
+        command_buffer
+            .execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        buffer.read().unwrap().to_vec()
+    }
+}
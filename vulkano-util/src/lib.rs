@@ -10,6 +10,10 @@
 #![allow(clippy::missing_safety_doc)]
 #![warn(rust_2018_idioms, rust_2021_compatibility)]
 
+pub mod async_compute;
 pub mod context;
+pub mod graveyard;
+pub mod headless;
+pub mod query;
 pub mod renderer;
 pub mod window;
@@ -0,0 +1,402 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A helper for reading back the results of queries that are written every frame.
+
+use std::{collections::VecDeque, ops::Range, sync::Arc};
+use vulkano::{
+    command_buffer::{allocator::CommandBufferAllocator, AutoCommandBufferBuilder},
+    device::Device,
+    query::{QueryPool, QueryPoolCreateInfo, QueryResultFlags, QueryType},
+    sync::{fence::Fence, future::FenceSignalFuture, GpuFuture},
+    DeviceSize, Validated, VulkanError,
+};
+
+/// Parameters to create a new [`QueryResultPool`].
+#[derive(Clone, Debug)]
+pub struct QueryResultPoolCreateInfo {
+    /// The number of queries that are written each frame.
+    ///
+    /// The default value is `1`.
+    pub queries_per_frame: u32,
+
+    /// The number of frames that can be in flight at the same time. Each of them gets its own
+    /// range of queries in the query pool.
+    ///
+    /// The default value is `2`.
+    pub frames_in_flight: u32,
+
+    /// The number of finished frames whose results are kept around, for
+    /// [`get_frame_results`](QueryResultPool::get_frame_results) and
+    /// [`rolling_average`](QueryResultPool::rolling_average).
+    ///
+    /// The default value is `16`.
+    pub history_len: usize,
+}
+
+impl Default for QueryResultPoolCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        QueryResultPoolCreateInfo {
+            queries_per_frame: 1,
+            frames_in_flight: 2,
+            history_len: 16,
+        }
+    }
+}
+
+/// Rotates through the query slots of several frames in flight, and collects the results of each
+/// frame once the GPU has finished executing it.
+///
+/// Each frame, [`begin_frame`](Self::begin_frame) records a reset of the queries that the frame
+/// can use and returns their range. They are then written by the commands of the frame, and the
+/// submission is handed back with [`end_frame`](Self::end_frame). A range of queries is only
+/// reused after the fence of the previous submission that used it has been signaled, so that its
+/// results are never overwritten before they have been read.
+///
+/// The results are read as `u64` values, with [`QueryType::result_len`] values per query. For
+/// timestamp queries, the difference between two values must be multiplied by the
+/// [`timestamp_period`] property to get nanoseconds.
+///
+/// [`timestamp_period`]: vulkano::device::Properties::timestamp_period
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use vulkano::{
+/// #     command_buffer::{AutoCommandBufferBuilder, PrimaryAutoCommandBuffer},
+/// #     device::Device,
+/// #     query::QueryType,
+/// #     sync::{future::FenceSignalFuture, GpuFuture, PipelineStage},
+/// # };
+/// # use vulkano_util::query::{QueryResultPool, QueryResultPoolCreateInfo};
+/// # let device: Arc<Device> = return;
+/// # let mut builder: AutoCommandBufferBuilder<PrimaryAutoCommandBuffer> = return;
+/// # let future: Arc<FenceSignalFuture<Box<dyn GpuFuture>>> = return;
+/// let mut timestamps = QueryResultPool::new(
+///     device,
+///     QueryType::Timestamp,
+///     QueryResultPoolCreateInfo {
+///         queries_per_frame: 2,
+///         ..Default::default()
+///     },
+/// )
+/// .unwrap();
+///
+/// // Each frame, before beginning a render pass...
+/// let queries = timestamps.begin_frame(&mut builder).unwrap();
+///
+/// unsafe {
+///     builder
+///         .write_timestamp(
+///             timestamps.query_pool().clone(),
+///             queries.start,
+///             PipelineStage::TopOfPipe,
+///         )
+///         .unwrap();
+///     // Record the work to be measured, then write the second timestamp.
+///     builder
+///         .write_timestamp(
+///             timestamps.query_pool().clone(),
+///             queries.start + 1,
+///             PipelineStage::BottomOfPipe,
+///         )
+///         .unwrap();
+/// }
+///
+/// // ...and after submitting the command buffer.
+/// timestamps.end_frame(future);
+///
+/// if let Some(average) = timestamps.rolling_average(8) {
+///     println!("the frame took {} ticks on average", average[1] - average[0]);
+/// }
+/// ```
+pub struct QueryResultPool {
+    query_pool: Arc<QueryPool>,
+    queries_per_frame: u32,
+    frames_in_flight: u32,
+    history_len: usize,
+
+    next_slot: u32,
+    recording_slot: Option<u32>,
+    pending: VecDeque<PendingFrame>,
+    history: VecDeque<Option<Vec<u64>>>,
+}
+
+impl QueryResultPool {
+    /// Creates a new `QueryResultPool`, with a query pool large enough for the queries of all
+    /// frames in flight.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.queries_per_frame` or `create_info.frames_in_flight` is zero.
+    /// - Panics if the total number of queries doesn't fit in a `u32`.
+    pub fn new(
+        device: Arc<Device>,
+        query_type: QueryType,
+        create_info: QueryResultPoolCreateInfo,
+    ) -> Result<Self, Validated<VulkanError>> {
+        let QueryResultPoolCreateInfo {
+            queries_per_frame,
+            frames_in_flight,
+            history_len,
+        } = create_info;
+
+        assert_ne!(queries_per_frame, 0);
+        assert_ne!(frames_in_flight, 0);
+
+        let query_pool = QueryPool::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count: queries_per_frame.checked_mul(frames_in_flight).unwrap(),
+                ..QueryPoolCreateInfo::query_type(query_type)
+            },
+        )?;
+
+        Ok(QueryResultPool {
+            query_pool,
+            queries_per_frame,
+            frames_in_flight,
+            history_len,
+
+            next_slot: 0,
+            recording_slot: None,
+            pending: VecDeque::new(),
+            history: VecDeque::new(),
+        })
+    }
+
+    /// Returns the underlying query pool.
+    #[inline]
+    pub fn query_pool(&self) -> &Arc<QueryPool> {
+        &self.query_pool
+    }
+
+    /// Returns the number of queries that are available to each frame.
+    #[inline]
+    pub fn queries_per_frame(&self) -> u32 {
+        self.queries_per_frame
+    }
+
+    /// Returns the number of frames that can be in flight at the same time.
+    #[inline]
+    pub fn frames_in_flight(&self) -> u32 {
+        self.frames_in_flight
+    }
+
+    /// Starts a new frame, and returns the range of queries in
+    /// [`query_pool`](Self::query_pool) that the frame can use.
+    ///
+    /// The results of the frames that have finished are collected first. If all frames are still
+    /// in flight, this blocks the current thread until the oldest one has finished, so that its
+    /// queries can be reused. A reset of the queries is then recorded into `builder`, so this must
+    /// be called outside of a render pass, before any of the queries are written.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the previous frame was not ended with [`end_frame`](Self::end_frame) or
+    ///   [`end_frame_with_fence`](Self::end_frame_with_fence), or cancelled with
+    ///   [`cancel_frame`](Self::cancel_frame).
+    pub fn begin_frame<L, A>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+    ) -> Result<Range<u32>, Validated<VulkanError>>
+    where
+        A: CommandBufferAllocator,
+    {
+        assert!(
+            self.recording_slot.is_none(),
+            "the previous frame has not been ended or cancelled",
+        );
+
+        self.collect()?;
+
+        if self.pending.len() == self.frames_in_flight as usize {
+            self.pending.front().unwrap().submission.wait()?;
+            self.collect()?;
+        }
+
+        let slot = self.next_slot;
+        let queries = self.slot_queries(slot);
+
+        // Safety: the previous submission that used these queries has finished, and the queries
+        // are only going to be used by the command buffer that is being recorded.
+        unsafe { builder.reset_query_pool(self.query_pool.clone(), queries.clone()) }?;
+
+        self.recording_slot = Some(slot);
+        self.next_slot = (slot + 1) % self.frames_in_flight;
+
+        Ok(queries)
+    }
+
+    /// Ends the current frame. Its results are collected once `future` has been signaled.
+    ///
+    /// `future` must signal the execution of the command buffer that was passed to
+    /// [`begin_frame`](Self::begin_frame).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is no current frame.
+    #[inline]
+    pub fn end_frame<F>(&mut self, future: Arc<FenceSignalFuture<F>>)
+    where
+        F: GpuFuture + 'static,
+    {
+        self.end_frame_inner(Box::new(future));
+    }
+
+    /// Ends the current frame. Its results are collected once `fence` has been signaled.
+    ///
+    /// `fence` must be signaled by a submission of the command buffer that was passed to
+    /// [`begin_frame`](Self::begin_frame), which has already been made. Otherwise, the next call
+    /// to `begin_frame` that reuses the queries of this frame blocks forever.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is no current frame.
+    #[inline]
+    pub fn end_frame_with_fence(&mut self, fence: Arc<Fence>) {
+        self.end_frame_inner(Box::new(fence));
+    }
+
+    fn end_frame_inner(&mut self, submission: Box<dyn FrameSubmission>) {
+        let slot = self
+            .recording_slot
+            .take()
+            .expect("there is no current frame");
+
+        self.pending.push_back(PendingFrame { slot, submission });
+    }
+
+    /// Cancels the current frame, for example if its command buffer is not going to be submitted.
+    /// The queries of the frame are used again by the next frame.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is no current frame.
+    #[inline]
+    pub fn cancel_frame(&mut self) {
+        let slot = self
+            .recording_slot
+            .take()
+            .expect("there is no current frame");
+
+        self.next_slot = slot;
+    }
+
+    /// Collects the results of the frames that have finished, in the order that they were
+    /// submitted, and returns how many were collected.
+    ///
+    /// This is called by [`begin_frame`](Self::begin_frame), but can also be called on its own to
+    /// get the most recent results.
+    pub fn collect(&mut self) -> Result<usize, Validated<VulkanError>> {
+        let mut collected = 0;
+
+        while let Some(frame) = self.pending.front() {
+            if !frame.submission.is_finished()? {
+                break;
+            }
+
+            let queries = self.slot_queries(frame.slot);
+            let result_len = self.query_pool.query_type().result_len();
+            let mut results = vec![0; (self.queries_per_frame as DeviceSize * result_len) as usize];
+
+            // The results of queries that the frame did not write are never available.
+            let available =
+                self.query_pool
+                    .get_results(queries, &mut results, QueryResultFlags::empty())?;
+
+            self.pending.pop_front();
+            self.history.push_front(available.then_some(results));
+            self.history.truncate(self.history_len);
+            collected += 1;
+        }
+
+        Ok(collected)
+    }
+
+    /// Returns the results of a finished frame, where `frames_ago` is 0 for the most recent frame
+    /// that has been collected, 1 for the one before it, and so on.
+    ///
+    /// Returns `None` if there is no such frame in the history, or if not all of the queries of
+    /// the frame were written.
+    #[inline]
+    pub fn get_frame_results(&self, frames_ago: usize) -> Option<&[u64]> {
+        self.history.get(frames_ago)?.as_deref()
+    }
+
+    /// Returns the average of the results of the last `window` collected frames, per result.
+    ///
+    /// Frames whose results are not available are skipped. Returns `None` if none of the frames
+    /// in the window have results available.
+    pub fn rolling_average(&self, window: usize) -> Option<Vec<f64>> {
+        let mut sums = Vec::new();
+        let mut count = 0;
+
+        for results in self.history.iter().take(window).flatten() {
+            sums.resize(results.len(), 0.0);
+
+            for (sum, &result) in sums.iter_mut().zip(results) {
+                *sum += result as f64;
+            }
+
+            count += 1;
+        }
+
+        if count == 0 {
+            return None;
+        }
+
+        for sum in &mut sums {
+            *sum /= count as f64;
+        }
+
+        Some(sums)
+    }
+
+    fn slot_queries(&self, slot: u32) -> Range<u32> {
+        let start = slot * self.queries_per_frame;
+        start..start + self.queries_per_frame
+    }
+}
+
+struct PendingFrame {
+    slot: u32,
+    submission: Box<dyn FrameSubmission>,
+}
+
+trait FrameSubmission {
+    fn is_finished(&self) -> Result<bool, Validated<VulkanError>>;
+
+    fn wait(&self) -> Result<(), Validated<VulkanError>>;
+}
+
+impl<F> FrameSubmission for Arc<FenceSignalFuture<F>>
+where
+    F: GpuFuture,
+{
+    fn is_finished(&self) -> Result<bool, Validated<VulkanError>> {
+        self.poll()
+    }
+
+    fn wait(&self) -> Result<(), Validated<VulkanError>> {
+        FenceSignalFuture::wait(self, None)
+    }
+}
+
+impl FrameSubmission for Arc<Fence> {
+    fn is_finished(&self) -> Result<bool, Validated<VulkanError>> {
+        Ok(self.is_signaled()?)
+    }
+
+    fn wait(&self) -> Result<(), Validated<VulkanError>> {
+        Ok(Fence::wait(self, None)?)
+    }
+}
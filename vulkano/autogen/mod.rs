@@ -227,6 +227,16 @@ impl<'r> VkRegistryData<'r> {
                                     },
                                 ..
                             }) if extends == "VkResult" => Some(name.as_str()),
+                            // Treat PipelineCompileRequired as an error condition
+                            InterfaceItem::Enum(Enum {
+                                name,
+                                spec: EnumSpec::Offset { extends, .. },
+                                ..
+                            }) if extends == "VkResult"
+                                && name == "VK_PIPELINE_COMPILE_REQUIRED" =>
+                            {
+                                Some(name.as_str())
+                            }
                             _ => None,
                         }));
                     }
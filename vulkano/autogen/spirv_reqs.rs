@@ -325,7 +325,7 @@ fn make_requires(enables: &[vk_parse::Enable]) -> (RequiresOneOf, Vec<RequiresPr
                 if version != "VK_VERSION_1_0" {
                     let captures = VK_API_VERSION.captures(version).unwrap();
                     let major = captures.get(1).unwrap().as_str();
-                    let minor = captures.get(1).unwrap().as_str();
+                    let minor = captures.get(2).unwrap().as_str();
 
                     requires_one_of.api_version =
                         Some((major.parse().unwrap(), minor.parse().unwrap()));
@@ -8,13 +8,18 @@
 // according to those terms.
 
 //! Efficiently suballocates buffers into smaller subbuffers.
+//!
+//! [`SubbufferAllocator`] is a general-purpose allocator for short-lived subbuffers, while
+//! [`StagingBelt`] builds on it to provide staging memory for uploads to buffers and images.
 
 use super::{
     sys::BufferCreateInfo, Buffer, BufferAllocateError, BufferContents, BufferMemory, BufferUsage,
     Subbuffer,
 };
 use crate::{
+    command_buffer::{BufferImageCopy, CopyBufferToImageInfo},
     device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
+    image::{Image, ImageAspects, ImageSubresourceLayers},
     memory::{
         allocator::{
             align_up, AllocationCreateInfo, DeviceLayout, MemoryAllocator, MemoryAllocatorError,
@@ -22,14 +27,14 @@ use crate::{
         },
         DeviceAlignment,
     },
-    DeviceSize, NonZeroDeviceSize, Validated,
+    DeviceSize, NonZeroDeviceSize, Validated, ValidationError,
 };
 use crossbeam_queue::ArrayQueue;
 use std::{
     cell::UnsafeCell,
     cmp,
     hash::{Hash, Hasher},
-    mem::ManuallyDrop,
+    mem::{align_of, size_of_val, ManuallyDrop},
     sync::Arc,
 };
 
@@ -231,6 +236,7 @@ where
     /// # Panics
     ///
     /// - Panics if `len` is zero.
+    /// - Panics if the size of the slice exceeds [`DeviceLayout::MAX_SIZE`].
     pub fn allocate_slice<T>(&self, len: DeviceSize) -> Result<Subbuffer<[T]>, MemoryAllocatorError>
     where
         T: BufferContents,
@@ -243,6 +249,7 @@ where
     /// # Panics
     ///
     /// - Panics if `len` is zero.
+    /// - Panics if the size of the data exceeds [`DeviceLayout::MAX_SIZE`].
     pub fn allocate_unsized<T>(&self, len: DeviceSize) -> Result<Subbuffer<T>, MemoryAllocatorError>
     where
         T: BufferContents + ?Sized,
@@ -413,6 +420,7 @@ impl Hash for Arena {
 }
 
 /// Parameters to create a new [`SubbufferAllocator`].
+#[derive(Clone, Debug)]
 pub struct SubbufferAllocatorCreateInfo {
     /// Initial size of an arena in bytes.
     ///
@@ -448,6 +456,368 @@ impl Default for SubbufferAllocatorCreateInfo {
     }
 }
 
+/// Suballocates staging buffers for uploading data to buffers and images.
+///
+/// This is a [`SubbufferAllocator`] that allocates host-visible memory with the
+/// [`TRANSFER_SRC`] usage, and that places every allocation at an offset that is suitable for
+/// copying from. For images, [`write_texture`] also takes care of laying out the rows of texel
+/// data with the pitch preferred by the implementation, and returns the copy to record.
+///
+/// # Reclamation
+///
+/// The belt doesn't track which frame each allocation belongs to, and doesn't wait on any fences
+/// itself. Reference counting of the arenas takes the place of that: like with
+/// `SubbufferAllocator`, an arena is reused for subsequent allocations once the last
+/// [`Subbuffer`] that was allocated from it has been dropped. Command buffers keep the subbuffers
+/// that they use alive until they are dropped, and their futures keep the command buffers alive
+/// until the fence of their submission is signaled. As long as you clean up your finished futures
+/// regularly, the belt will settle on about one arena per frame in flight.
+///
+/// Memory is only reclaimed a whole arena at a time, so holding on to a single subbuffer keeps
+/// its whole arena from being reused.
+///
+/// # Examples
+///
+/// ```
+/// use vulkano::{
+///     buffer::allocator::{StagingBelt, StagingBeltCreateInfo},
+///     command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
+/// };
+///
+/// # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+/// # let memory_allocator: std::sync::Arc<vulkano::memory::allocator::StandardMemoryAllocator> = return;
+/// # let command_buffer_allocator: vulkano::command_buffer::allocator::StandardCommandBufferAllocator = return;
+/// # let image: std::sync::Arc<vulkano::image::Image> = return;
+/// # let texels: Vec<u8> = return;
+/// #
+/// let staging_belt = StagingBelt::new(memory_allocator.clone(), Default::default());
+///
+/// let mut builder = AutoCommandBufferBuilder::primary(
+///     &command_buffer_allocator,
+///     queue.queue_family_index(),
+///     CommandBufferUsage::OneTimeSubmit,
+/// )
+/// .unwrap();
+///
+/// builder
+///     .copy_buffer_to_image(staging_belt.write_texture(image, &texels).unwrap())
+///     .unwrap();
+/// ```
+///
+/// [`TRANSFER_SRC`]: BufferUsage::TRANSFER_SRC
+/// [`write_texture`]: Self::write_texture
+#[derive(Debug)]
+pub struct StagingBelt<A = Arc<StandardMemoryAllocator>> {
+    subbuffer_allocator: SubbufferAllocator<A>,
+    copy_offset_alignment: DeviceAlignment,
+    copy_row_pitch_alignment: DeviceAlignment,
+}
+
+impl<A> StagingBelt<A>
+where
+    A: MemoryAllocator,
+{
+    /// Creates a new `StagingBelt`.
+    pub fn new(memory_allocator: A, create_info: StagingBeltCreateInfo) -> Self {
+        let StagingBeltCreateInfo { arena_size, _ne: _ } = create_info;
+
+        let properties = memory_allocator.device().physical_device().properties();
+        let copy_offset_alignment = properties.optimal_buffer_copy_offset_alignment;
+        let copy_row_pitch_alignment = properties.optimal_buffer_copy_row_pitch_alignment;
+
+        StagingBelt {
+            subbuffer_allocator: SubbufferAllocator::new(
+                memory_allocator,
+                SubbufferAllocatorCreateInfo {
+                    arena_size,
+                    buffer_usage: BufferUsage::TRANSFER_SRC,
+                    memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                        | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                    ..Default::default()
+                },
+            ),
+            copy_offset_alignment,
+            copy_row_pitch_alignment,
+        }
+    }
+
+    /// Returns the underlying subbuffer allocator.
+    #[inline]
+    pub fn subbuffer_allocator(&self) -> &SubbufferAllocator<A> {
+        &self.subbuffer_allocator
+    }
+
+    /// Allocates a subbuffer, copies `data` into it and returns it.
+    ///
+    /// The subbuffer is aligned to the
+    /// [`optimal_buffer_copy_offset_alignment`](crate::device::Properties::optimal_buffer_copy_offset_alignment)
+    /// device property.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `data` is empty.
+    pub fn write_slice<T>(&self, data: &[T]) -> Result<Subbuffer<[T]>, MemoryAllocatorError>
+    where
+        T: BufferContents + Copy,
+    {
+        let size = size_of_val(data) as DeviceSize;
+        let alignment = lcm(
+            self.copy_offset_alignment.as_devicesize(),
+            align_of::<T>() as DeviceSize,
+        );
+
+        // SAFETY: The offset is aligned to `align_of::<T>()`, and the size is that of `data`.
+        let subbuffer: Subbuffer<[T]> = unsafe {
+            self.allocate_staging(size, alignment)?
+                .reinterpret_unchecked()
+        };
+
+        subbuffer
+            .write()
+            .expect("the buffer is somehow in use before we returned it to the user")
+            .copy_from_slice(data);
+
+        Ok(subbuffer)
+    }
+
+    /// Copies the texel data of the first mip level of `image` into staging memory, and returns
+    /// the copy that uploads it to `image`.
+    ///
+    /// `data` must contain the texel blocks of all array layers of the first mip level, tightly
+    /// packed: the layers follow each other, within a layer the depth slices follow each other,
+    /// and within a slice, the rows of texel blocks follow each other. When copying to the staging
+    /// buffer, rows are padded to the
+    /// [`optimal_buffer_copy_row_pitch_alignment`](crate::device::Properties::optimal_buffer_copy_row_pitch_alignment)
+    /// device property, and `buffer_row_length` of the returned region is set accordingly.
+    ///
+    /// The format of `image` must have a single plane, and must not have both a depth and a
+    /// stencil aspect. `image` must have been created with the
+    /// [`TRANSFER_DST`](crate::image::ImageUsage::TRANSFER_DST) usage for the copy to be valid.
+    pub fn write_texture(
+        &self,
+        image: Arc<Image>,
+        data: &[u8],
+    ) -> Result<CopyBufferToImageInfo, Validated<MemoryAllocatorError>> {
+        self.validate_write_texture(&image, data)?;
+
+        self.write_texture_unchecked(image, data)
+            .map_err(Validated::Error)
+    }
+
+    fn validate_write_texture(
+        &self,
+        image: &Image,
+        data: &[u8],
+    ) -> Result<(), Box<ValidationError>> {
+        let format = image.format();
+
+        if format.planes().len() > 1 {
+            return Err(Box::new(ValidationError {
+                context: "image.format()".into(),
+                problem: "is a multi-planar format".into(),
+                ..Default::default()
+            }));
+        }
+
+        if format
+            .aspects()
+            .contains(ImageAspects::DEPTH | ImageAspects::STENCIL)
+        {
+            return Err(Box::new(ValidationError {
+                context: "image.format()".into(),
+                problem: "has both a depth and a stencil aspect".into(),
+                ..Default::default()
+            }));
+        }
+
+        let layout = TextureLayout::new(image, 0);
+
+        if data.len() as DeviceSize != layout.packed_size() {
+            return Err(Box::new(ValidationError {
+                context: "data".into(),
+                problem: "the length does not equal the size of the tightly packed texel data of \
+                    the first mip level of `image`"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn write_texture_unchecked(
+        &self,
+        image: Arc<Image>,
+        data: &[u8],
+    ) -> Result<CopyBufferToImageInfo, MemoryAllocatorError> {
+        let format = image.format();
+        let layout = TextureLayout::new(&image, 0);
+
+        // Copies from depth/stencil images must be aligned to 4 bytes, copies from color images
+        // to the texel block size.
+        let mut alignment = lcm(
+            self.copy_offset_alignment.as_devicesize(),
+            layout.block_size,
+        );
+
+        if format
+            .aspects()
+            .intersects(ImageAspects::DEPTH | ImageAspects::STENCIL)
+        {
+            alignment = lcm(alignment, 4);
+        }
+
+        let row_pitch = align_multiple(
+            layout.row_size,
+            lcm(
+                self.copy_row_pitch_alignment.as_devicesize(),
+                layout.block_size,
+            ),
+        );
+        let row_count = layout.row_count();
+
+        let src_buffer = self.allocate_staging(row_pitch * row_count, alignment)?;
+
+        {
+            let mut bytes = src_buffer
+                .write()
+                .expect("the buffer is somehow in use before we returned it to the user");
+
+            if row_pitch == layout.row_size {
+                bytes.copy_from_slice(data);
+            } else {
+                let row_size = layout.row_size as usize;
+
+                for (dst_row, src_row) in bytes
+                    .chunks_exact_mut(row_pitch as usize)
+                    .zip(data.chunks_exact(row_size))
+                {
+                    dst_row[..row_size].copy_from_slice(src_row);
+                }
+            }
+        }
+
+        let region = BufferImageCopy {
+            buffer_offset: 0,
+            buffer_row_length: (row_pitch / layout.block_size) as u32 * format.block_extent()[0],
+            buffer_image_height: 0,
+            image_subresource: ImageSubresourceLayers {
+                aspects: format.aspects(),
+                mip_level: 0,
+                array_layers: 0..image.array_layers(),
+            },
+            image_offset: [0; 3],
+            image_extent: image.extent(),
+            ..Default::default()
+        };
+
+        Ok(CopyBufferToImageInfo {
+            regions: [region].into_iter().collect(),
+            ..CopyBufferToImageInfo::buffer_image(src_buffer, image)
+        })
+    }
+
+    // Allocates a subbuffer of `size` bytes whose offset within its buffer is a multiple of
+    // `alignment`, which doesn't need to be a power of two.
+    fn allocate_staging(
+        &self,
+        size: DeviceSize,
+        alignment: DeviceSize,
+    ) -> Result<Subbuffer<[u8]>, MemoryAllocatorError> {
+        let subbuffer = self
+            .subbuffer_allocator
+            .allocate_slice::<u8>(size + alignment - 1)?;
+        let padding = align_multiple(subbuffer.offset(), alignment) - subbuffer.offset();
+
+        Ok(subbuffer.slice(padding..padding + size))
+    }
+}
+
+unsafe impl<A> DeviceOwned for StagingBelt<A>
+where
+    A: MemoryAllocator,
+{
+    fn device(&self) -> &Arc<Device> {
+        self.subbuffer_allocator.device()
+    }
+}
+
+/// Parameters to create a new [`StagingBelt`].
+#[derive(Clone, Debug)]
+pub struct StagingBeltCreateInfo {
+    /// Initial size of an arena in bytes.
+    ///
+    /// Ideally this should fit all the data you need to upload per frame.
+    ///
+    /// The default value is `0`.
+    pub arena_size: DeviceSize,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for StagingBeltCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        StagingBeltCreateInfo {
+            arena_size: 0,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+// The layout of the tightly packed texel data of a mip level of an image.
+struct TextureLayout {
+    block_size: DeviceSize,
+    // The size of a row of texel blocks.
+    row_size: DeviceSize,
+    // The number of rows of texel blocks in a depth slice.
+    rows_per_slice: DeviceSize,
+    // The number of depth slices of texel blocks in an array layer.
+    slices: DeviceSize,
+    array_layers: DeviceSize,
+}
+
+impl TextureLayout {
+    fn new(image: &Image, mip_level: u32) -> Self {
+        let format = image.format();
+        let block_extent = format.block_extent();
+        let extent = image.extent().map(|x| cmp::max(x >> mip_level, 1));
+        let [blocks_x, blocks_y, blocks_z] =
+            [0, 1, 2].map(|i| (extent[i] as DeviceSize).div_ceil(block_extent[i] as DeviceSize));
+        let block_size = format.block_size();
+
+        TextureLayout {
+            block_size,
+            row_size: blocks_x * block_size,
+            rows_per_slice: blocks_y,
+            slices: blocks_z,
+            array_layers: image.array_layers() as DeviceSize,
+        }
+    }
+
+    fn row_count(&self) -> DeviceSize {
+        self.rows_per_slice * self.slices * self.array_layers
+    }
+
+    fn packed_size(&self) -> DeviceSize {
+        self.row_size * self.row_count()
+    }
+}
+
+fn align_multiple(value: DeviceSize, multiple: DeviceSize) -> DeviceSize {
+    value.div_ceil(multiple) * multiple
+}
+
+fn lcm(a: DeviceSize, b: DeviceSize) -> DeviceSize {
+    let (mut x, mut y) = (a, b);
+
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+
+    a / x * b
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -487,4 +857,71 @@ mod tests {
         buffer_allocator.allocate_sized::<u32>().unwrap();
         assert_eq!(buffer_allocator.arena_size(), 8);
     }
+
+    #[test]
+    fn write_slice() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+
+        let staging_belt = StagingBelt::new(memory_allocator, Default::default());
+        let subbuffer = staging_belt.write_slice(&[1u32, 2, 3]).unwrap();
+
+        assert_eq!(&*subbuffer.read().unwrap(), &[1, 2, 3]);
+        assert_eq!(
+            subbuffer.offset()
+                % device
+                    .physical_device()
+                    .properties()
+                    .optimal_buffer_copy_offset_alignment
+                    .as_devicesize(),
+            0,
+        );
+    }
+
+    #[test]
+    fn write_texture() {
+        use crate::{
+            format::Format,
+            image::{ImageCreateInfo, ImageType, ImageUsage},
+        };
+
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [3, 2, 1],
+                usage: ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        let staging_belt = StagingBelt::new(memory_allocator, Default::default());
+        assert!(staging_belt.write_texture(image.clone(), &[0; 20]).is_err());
+
+        let copy_info = staging_belt.write_texture(image, &[0; 24]).unwrap();
+        let properties = device.physical_device().properties();
+        let region = &copy_info.regions[0];
+
+        assert!(region.buffer_row_length >= 3);
+        assert_eq!(
+            (region.buffer_row_length as DeviceSize * 4)
+                % properties
+                    .optimal_buffer_copy_row_pitch_alignment
+                    .as_devicesize(),
+            0,
+        );
+        assert_eq!(
+            copy_info.src_buffer.offset()
+                % properties
+                    .optimal_buffer_copy_offset_alignment
+                    .as_devicesize(),
+            0,
+        );
+    }
 }
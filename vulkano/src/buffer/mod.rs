@@ -366,7 +366,15 @@ impl Buffer {
         T: BufferContents + ?Sized,
     {
         let len = NonZeroDeviceSize::new(len).expect("empty slices are not valid buffer contents");
-        let layout = T::LAYOUT.layout_for_len(len).unwrap();
+        let layout = T::LAYOUT.layout_for_len(len).ok_or_else(|| {
+            Box::new(ValidationError {
+                context: "len".into(),
+                problem: "the size of the buffer contents with this length is greater than \
+                    `DeviceLayout::MAX_SIZE`"
+                    .into(),
+                ..Default::default()
+            })
+        })?;
         let buffer = Subbuffer::new(Buffer::new(
             allocator,
             create_info,
@@ -984,9 +992,27 @@ impl IndexType {
             IndexType::U32 => 4,
         }
     }
+
+    /// Returns the index value that restarts the primitive when primitive restart is enabled.
+    ///
+    /// This is the maximum value that can be represented by the index type: `0xff`, `0xffff` or
+    /// `0xffffffff`.
+    #[inline]
+    pub fn primitive_restart_index(self) -> u32 {
+        match self {
+            IndexType::U8 => u8::MAX as u32,
+            IndexType::U16 => u16::MAX as u32,
+            IndexType::U32 => u32::MAX,
+        }
+    }
 }
 
 /// A buffer holding index values, which index into buffers holding vertex data.
+///
+/// If [primitive restart] is enabled, then the maximum value of the index type restarts the
+/// primitive instead of referring to a vertex. See [`IndexType::primitive_restart_index`].
+///
+/// [primitive restart]: crate::pipeline::graphics::input_assembly::InputAssemblyState::primitive_restart_enable
 #[derive(Clone, Debug)]
 pub enum IndexBuffer {
     /// An index buffer containing unsigned 8-bit indices.
@@ -1043,3 +1069,32 @@ impl From<Subbuffer<[u32]>> for IndexBuffer {
         Self::U32(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Buffer, BufferCreateInfo, BufferUsage};
+    use crate::{
+        memory::allocator::{AllocationCreateInfo, DeviceLayout, StandardMemoryAllocator},
+        Validated,
+    };
+
+    #[test]
+    fn new_slice_exceeds_max_size() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        match Buffer::new_slice::<u32>(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            DeviceLayout::MAX_SIZE / 4 + 1,
+        ) {
+            Err(Validated::ValidationError(err)) => assert_eq!(err.context, "len"),
+            _ => panic!(),
+        }
+    }
+}
@@ -201,11 +201,28 @@ where
     where
         U: BufferContents + ?Sized,
     {
-        self.validate_reinterpret(U::LAYOUT);
+        self.validate_reinterpret(U::LAYOUT).unwrap();
 
         unsafe { self.reinterpret_unchecked_inner() }
     }
 
+    /// Same as [`reinterpret`], except it returns an error instead of panicking if the subbuffer
+    /// is not correctly aligned or sized for `U`.
+    ///
+    /// This can be used to create a typed view of a part of a larger subbuffer, by first
+    /// [slicing] it to the range that contains the value of type `U`.
+    ///
+    /// [`reinterpret`]: Self::reinterpret
+    /// [slicing]: Subbuffer::slice
+    pub fn try_reinterpret<U>(self) -> Result<Subbuffer<U>, Box<ValidationError>>
+    where
+        U: BufferContents + ?Sized,
+    {
+        self.validate_reinterpret(U::LAYOUT)?;
+
+        Ok(unsafe { self.reinterpret_unchecked_inner() })
+    }
+
     /// Changes the `T` generic parameter of the subbuffer to the desired type without checking if
     /// the contents are correctly aligned and sized.
     ///
@@ -228,7 +245,7 @@ where
         U: BufferContents + ?Sized,
     {
         #[cfg(debug_assertions)]
-        self.validate_reinterpret(U::LAYOUT);
+        self.validate_reinterpret(U::LAYOUT).unwrap();
 
         self.reinterpret_unchecked_inner()
     }
@@ -240,7 +257,7 @@ where
     where
         U: BufferContents + ?Sized,
     {
-        self.validate_reinterpret(U::LAYOUT);
+        self.validate_reinterpret(U::LAYOUT).unwrap();
 
         unsafe { self.reinterpret_unchecked_ref_inner() }
     }
@@ -258,21 +275,87 @@ where
         U: BufferContents + ?Sized,
     {
         #[cfg(debug_assertions)]
-        self.validate_reinterpret(U::LAYOUT);
+        self.validate_reinterpret(U::LAYOUT).unwrap();
 
         self.reinterpret_unchecked_ref_inner()
     }
 
-    fn validate_reinterpret(&self, new_layout: BufferContentsLayout) {
-        assert!(is_aligned(self.memory_offset(), new_layout.alignment()));
+    fn validate_reinterpret(
+        &self,
+        new_layout: BufferContentsLayout,
+    ) -> Result<(), Box<ValidationError>> {
+        if !is_aligned(self.memory_offset(), new_layout.alignment()) {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "the memory offset of the subbuffer ({}) is not a multiple of the alignment \
+                    of the new type ({})",
+                    self.memory_offset(),
+                    new_layout.alignment().as_devicesize(),
+                )
+                .into(),
+                ..Default::default()
+            }));
+        }
 
         if new_layout.is_sized() {
-            assert!(self.size == new_layout.unwrap_sized().size());
+            let new_size = new_layout.unwrap_sized().size();
+
+            if self.size != new_size {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "the size of the subbuffer ({}) does not equal the size of the new type \
+                        ({})",
+                        self.size, new_size,
+                    )
+                    .into(),
+                    ..Default::default()
+                }));
+            }
         } else {
-            assert!(self.size > new_layout.head_size());
-            assert!((self.size - new_layout.head_size()) % new_layout.element_size().unwrap() == 0);
-            assert!(is_aligned(self.size(), new_layout.alignment()));
+            if self.size <= new_layout.head_size() {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "the size of the subbuffer ({}) is not greater than the size of the head \
+                        of the new type ({})",
+                        self.size,
+                        new_layout.head_size(),
+                    )
+                    .into(),
+                    ..Default::default()
+                }));
+            }
+
+            let element_size = new_layout.element_size().unwrap();
+
+            if !(self.size - new_layout.head_size()).is_multiple_of(element_size) {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "the size of the subbuffer ({}) minus the size of the head of the new \
+                        type ({}) is not a multiple of the element size of the new type ({})",
+                        self.size,
+                        new_layout.head_size(),
+                        element_size,
+                    )
+                    .into(),
+                    ..Default::default()
+                }));
+            }
+
+            if !is_aligned(self.size(), new_layout.alignment()) {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "the size of the subbuffer ({}) is not a multiple of the alignment of the \
+                        new type ({})",
+                        self.size,
+                        new_layout.alignment().as_devicesize(),
+                    )
+                    .into(),
+                    ..Default::default()
+                }));
+            }
         }
+
+        Ok(())
     }
 
     /// Locks the subbuffer in order to read its content from the host.
@@ -285,6 +368,10 @@ where
     /// that uses it in exclusive mode will fail. You can still submit this subbuffer for
     /// non-exclusive accesses (ie. reads).
     ///
+    /// If the subbuffer was written by a command buffer, record [`make_host_readable`] after the
+    /// last write, and wait for the command buffer to finish executing before calling this
+    /// function. Otherwise, the writes are not guaranteed to be visible to the host.
+    ///
     /// If the memory backing the buffer is not [host-coherent], then this function will lock a
     /// range that is potentially larger than the subbuffer, because the range given to
     /// [`invalidate_range`] must be aligned to the [`non_coherent_atom_size`]. This means that for
@@ -301,6 +388,7 @@ where
     /// [`non_coherent_atom_size`]: crate::device::Properties::non_coherent_atom_size
     /// [`write`]: Self::write
     /// [`SubbufferAllocator`]: super::allocator::SubbufferAllocator
+    /// [`make_host_readable`]: crate::command_buffer::AutoCommandBufferBuilder::make_host_readable
     pub fn read(&self) -> Result<BufferReadGuard<'_, T>, HostAccessError> {
         let allocation = match self.buffer().memory() {
             BufferMemory::Normal(a) => a,
@@ -1188,6 +1276,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn derive_buffer_contents_std_layouts() {
+        // A uniform block that is laid out identically in std140 and std430, when written with
+        // explicit padding the way GLSL expects it:
+        //
+        // struct Light {
+        //     vec4 position;  // offset 0
+        //     vec3 color;     // offset 16
+        //     float power;    // offset 28
+        // };
+        //
+        // layout(std140) uniform Lights {
+        //     mat4 view;      // offset 0
+        //     Light lights[4];// offset 64, stride 32
+        //     uint count;     // offset 192
+        // };                  // size 208 (rounded up to the base alignment of 16)
+        #[derive(BufferContents)]
+        #[repr(C, align(16))]
+        struct Light {
+            position: [f32; 4],
+            color: [f32; 3],
+            power: f32,
+        }
+
+        #[derive(BufferContents)]
+        #[repr(C, align(16))]
+        struct Lights {
+            view: [[f32; 4]; 4],
+            lights: [Light; 4],
+            count: u32,
+        }
+
+        assert_eq!(Light::LAYOUT.head_size(), 32);
+        assert_eq!(Light::LAYOUT.alignment().as_devicesize(), 16);
+        assert_eq!(Lights::LAYOUT.head_size(), 208);
+        assert_eq!(Lights::LAYOUT.alignment().as_devicesize(), 16);
+        assert_eq!(Lights::LAYOUT.head_size() as usize, size_of::<Lights>());
+
+        // In std430, an array of `float` has a stride of 4, while in std140 it's rounded up to 16.
+        //
+        // layout(std430) buffer Data {
+        //     float scale;    // offset 0
+        //     vec2 offset;    // offset 8
+        //     float values[]; // offset 16, stride 4
+        // };
+        #[derive(BufferContents)]
+        #[repr(C)]
+        struct Std430 {
+            scale: f32,
+            _pad: u32,
+            offset: [f32; 2],
+            values: [f32],
+        }
+
+        assert_eq!(Std430::LAYOUT.head_size(), 16);
+        assert_eq!(Std430::LAYOUT.element_size(), Some(4));
+        assert_eq!(Std430::LAYOUT.alignment().as_devicesize(), 4);
+
+        // layout(std140) uniform Data {
+        //     float scale;     // offset 0
+        //     float values[2]; // offset 16, stride 16
+        // };
+        #[derive(BufferContents)]
+        #[repr(C, align(16))]
+        struct Std140Element(f32);
+
+        #[derive(BufferContents)]
+        #[repr(C)]
+        struct Std140 {
+            scale: f32,
+            values: [Std140Element; 2],
+        }
+
+        assert_eq!(Std140Element::LAYOUT.head_size(), 16);
+        assert_eq!(Std140::LAYOUT.head_size(), 48);
+        assert_eq!(Std140::LAYOUT.head_size() as usize, size_of::<Std140>());
+
+        // The alignment from `#[repr(align(N))]` must be respected for every kind of last field,
+        // not just arrays.
+        #[derive(BufferContents)]
+        #[repr(C, align(16))]
+        struct Aligned<T> {
+            x: f32,
+            y: T,
+        }
+
+        assert_eq!(
+            Aligned::<f32>::LAYOUT.head_size() as usize,
+            size_of::<Aligned<f32>>()
+        );
+        assert_eq!(
+            Aligned::<f32>::LAYOUT.alignment().as_devicesize() as usize,
+            align_of::<Aligned<f32>>(),
+        );
+
+        #[derive(BufferContents)]
+        #[repr(C, packed)]
+        struct Packed(u8, u32, u16);
+
+        assert_eq!(Packed::LAYOUT.head_size() as usize, size_of::<Packed>());
+        assert_eq!(Packed::LAYOUT.alignment().as_devicesize(), 1);
+
+        #[derive(BufferContents)]
+        #[repr(C, packed(2))]
+        struct PackedSlice(u8, u32, [u32]);
+
+        assert_eq!(PackedSlice::LAYOUT.head_size(), 6);
+        assert_eq!(PackedSlice::LAYOUT.element_size(), Some(4));
+        assert_eq!(PackedSlice::LAYOUT.alignment().as_devicesize(), 2);
+    }
+
     #[test]
     fn split_at() {
         let (device, _) = gfx_dev_and_queue!();
@@ -1225,6 +1424,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn try_reinterpret() {
+        let (device, _) = gfx_dev_and_queue!();
+        let allocator = StandardMemoryAllocator::new_default(device);
+
+        let buffer = Buffer::new_slice::<u32>(
+            &allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            6,
+        )
+        .unwrap()
+        .into_bytes();
+
+        {
+            let value = buffer.clone().slice(4..8).try_reinterpret::<u32>().unwrap();
+            assert_eq!(value.offset(), 4);
+        }
+
+        {
+            let values = buffer
+                .clone()
+                .slice(4..12)
+                .try_reinterpret::<[u32]>()
+                .unwrap();
+            assert_eq!(values.len(), 2);
+        }
+
+        {
+            assert!(buffer.clone().slice(1..5).try_reinterpret::<u32>().is_err());
+        }
+
+        {
+            assert!(buffer.slice(4..12).try_reinterpret::<u32>().is_err());
+        }
+    }
+
     #[test]
     fn cast_aligned() {
         let (device, _) = gfx_dev_and_queue!();
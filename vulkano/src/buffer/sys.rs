@@ -20,7 +20,7 @@ use crate::{
     instance::InstanceOwnedDebugWrapper,
     macros::impl_id_counter,
     memory::{
-        allocator::{AllocationType, DeviceLayout, MemoryAlloc},
+        allocator::{AllocationType, MemoryAlloc},
         is_aligned, DedicatedTo, ExternalMemoryHandleTypes, MemoryAllocateFlags,
         MemoryPropertyFlags, MemoryRequirements,
     },
@@ -86,58 +86,20 @@ impl RawBuffer {
         device: Arc<Device>,
         create_info: BufferCreateInfo,
     ) -> Result<Self, VulkanError> {
-        let &BufferCreateInfo {
-            flags,
-            ref sharing,
-            size,
-            usage,
-            external_memory_handle_types,
-            _ne: _,
-        } = &create_info;
-
-        let (sharing_mode, queue_family_index_count, p_queue_family_indices) = match sharing {
-            Sharing::Exclusive => (ash::vk::SharingMode::EXCLUSIVE, 0, &[] as _),
-            Sharing::Concurrent(queue_family_indices) => (
-                ash::vk::SharingMode::CONCURRENT,
-                queue_family_indices.len() as u32,
-                queue_family_indices.as_ptr(),
-            ),
-        };
-
-        let mut create_info_vk = ash::vk::BufferCreateInfo {
-            flags: flags.into(),
-            size,
-            usage: usage.into(),
-            sharing_mode,
-            queue_family_index_count,
-            p_queue_family_indices,
-            ..Default::default()
-        };
-        let mut external_memory_info_vk = None;
-
-        if !external_memory_handle_types.is_empty() {
-            let _ = external_memory_info_vk.insert(ash::vk::ExternalMemoryBufferCreateInfo {
-                handle_types: external_memory_handle_types.into(),
-                ..Default::default()
-            });
-        }
-
-        if let Some(next) = external_memory_info_vk.as_mut() {
-            next.p_next = create_info_vk.p_next;
-            create_info_vk.p_next = next as *const _ as *const _;
-        }
-
         let handle = {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
-            (fns.v1_0.create_buffer)(
-                device.handle(),
-                &create_info_vk,
-                ptr::null(),
-                output.as_mut_ptr(),
-            )
-            .result()
-            .map_err(VulkanError::from)?;
+            create_info
+                .with_vk(|create_info_vk| {
+                    (fns.v1_0.create_buffer)(
+                        device.handle(),
+                        create_info_vk,
+                        ptr::null(),
+                        output.as_mut_ptr(),
+                    )
+                })
+                .result()
+                .map_err(VulkanError::from)?;
             output.assume_init()
         };
 
@@ -171,28 +133,7 @@ impl RawBuffer {
         debug_assert!(memory_requirements.layout.size() >= size);
         debug_assert!(memory_requirements.memory_type_bits != 0);
 
-        // We have to manually enforce some additional requirements for some buffer types.
-        let properties = device.physical_device().properties();
-        if usage.intersects(BufferUsage::UNIFORM_TEXEL_BUFFER | BufferUsage::STORAGE_TEXEL_BUFFER) {
-            memory_requirements.layout = memory_requirements
-                .layout
-                .align_to(properties.min_texel_buffer_offset_alignment)
-                .unwrap();
-        }
-
-        if usage.intersects(BufferUsage::STORAGE_BUFFER) {
-            memory_requirements.layout = memory_requirements
-                .layout
-                .align_to(properties.min_storage_buffer_offset_alignment)
-                .unwrap();
-        }
-
-        if usage.intersects(BufferUsage::UNIFORM_BUFFER) {
-            memory_requirements.layout = memory_requirements
-                .layout
-                .align_to(properties.min_uniform_buffer_offset_alignment)
-                .unwrap();
-        }
+        align_memory_requirements(&device, usage, &mut memory_requirements);
 
         RawBuffer {
             handle,
@@ -260,18 +201,10 @@ impl RawBuffer {
             }
         }
 
-        MemoryRequirements {
-            layout: DeviceLayout::from_size_alignment(
-                memory_requirements2_vk.memory_requirements.size,
-                memory_requirements2_vk.memory_requirements.alignment,
-            )
-            .unwrap(),
-            memory_type_bits: memory_requirements2_vk.memory_requirements.memory_type_bits,
-            prefers_dedicated_allocation: memory_dedicated_requirements_vk
-                .map_or(false, |dreqs| dreqs.prefers_dedicated_allocation != 0),
-            requires_dedicated_allocation: memory_dedicated_requirements_vk
-                .map_or(false, |dreqs| dreqs.requires_dedicated_allocation != 0),
-        }
+        MemoryRequirements::from_vk(
+            &memory_requirements2_vk,
+            memory_dedicated_requirements_vk.as_ref(),
+        )
     }
 
     /// Binds device memory to this buffer.
@@ -664,6 +597,52 @@ impl Default for BufferCreateInfo {
 }
 
 impl BufferCreateInfo {
+    /// Builds the Vulkan create info, including its `p_next` chain, and passes it to `f`.
+    pub(crate) fn with_vk<R>(&self, f: impl FnOnce(&ash::vk::BufferCreateInfo) -> R) -> R {
+        let &Self {
+            flags,
+            ref sharing,
+            size,
+            usage,
+            external_memory_handle_types,
+            _ne: _,
+        } = self;
+
+        let (sharing_mode, queue_family_index_count, p_queue_family_indices) = match sharing {
+            Sharing::Exclusive => (ash::vk::SharingMode::EXCLUSIVE, 0, &[] as _),
+            Sharing::Concurrent(queue_family_indices) => (
+                ash::vk::SharingMode::CONCURRENT,
+                queue_family_indices.len() as u32,
+                queue_family_indices.as_ptr(),
+            ),
+        };
+
+        let mut create_info_vk = ash::vk::BufferCreateInfo {
+            flags: flags.into(),
+            size,
+            usage: usage.into(),
+            sharing_mode,
+            queue_family_index_count,
+            p_queue_family_indices,
+            ..Default::default()
+        };
+        let mut external_memory_info_vk = None;
+
+        if !external_memory_handle_types.is_empty() {
+            let _ = external_memory_info_vk.insert(ash::vk::ExternalMemoryBufferCreateInfo {
+                handle_types: external_memory_handle_types.into(),
+                ..Default::default()
+            });
+        }
+
+        if let Some(next) = external_memory_info_vk.as_mut() {
+            next.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = next as *const _ as *const _;
+        }
+
+        f(&create_info_vk)
+    }
+
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             flags,
@@ -833,6 +812,37 @@ impl BufferCreateInfo {
     }
 }
 
+/// Enforces the additional alignment requirements of some buffer usages, which the Vulkan
+/// implementation doesn't include in the memory requirements that it returns.
+pub(crate) fn align_memory_requirements(
+    device: &Device,
+    usage: BufferUsage,
+    memory_requirements: &mut MemoryRequirements,
+) {
+    let properties = device.physical_device().properties();
+
+    if usage.intersects(BufferUsage::UNIFORM_TEXEL_BUFFER | BufferUsage::STORAGE_TEXEL_BUFFER) {
+        memory_requirements.layout = memory_requirements
+            .layout
+            .align_to(properties.min_texel_buffer_offset_alignment)
+            .unwrap();
+    }
+
+    if usage.intersects(BufferUsage::STORAGE_BUFFER) {
+        memory_requirements.layout = memory_requirements
+            .layout
+            .align_to(properties.min_storage_buffer_offset_alignment)
+            .unwrap();
+    }
+
+    if usage.intersects(BufferUsage::UNIFORM_BUFFER) {
+        memory_requirements.layout = memory_requirements
+            .layout
+            .align_to(properties.min_uniform_buffer_offset_alignment)
+            .unwrap();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{BufferCreateInfo, BufferUsage, RawBuffer};
@@ -339,14 +339,35 @@ impl BufferView {
     ) -> Arc<BufferView> {
         let &BufferViewCreateInfo { format, _ne: _ } = &create_info;
         let size = subbuffer.size();
-        let format_features = unsafe {
-            subbuffer
-                .device()
+        let device = subbuffer.device();
+        let mut format_features = unsafe {
+            device
                 .physical_device()
                 .format_properties_unchecked(format)
                 .buffer_features
         };
 
+        // Without `khr_format_feature_flags2`, the `*_WITHOUT_FORMAT` format features are not
+        // reported, but are implied for some formats by the corresponding device features.
+        if !(device.api_version() >= Version::V1_3
+            || device.enabled_extensions().khr_format_feature_flags2)
+            && format.shader_storage_image_without_format()
+        {
+            if device
+                .enabled_features()
+                .shader_storage_image_read_without_format
+            {
+                format_features |= FormatFeatures::STORAGE_READ_WITHOUT_FORMAT;
+            }
+
+            if device
+                .enabled_features()
+                .shader_storage_image_write_without_format
+            {
+                format_features |= FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT;
+            }
+        }
+
         Arc::new(BufferView {
             handle,
             subbuffer: subbuffer.into_bytes(),
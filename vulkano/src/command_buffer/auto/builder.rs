@@ -19,13 +19,15 @@ use crate::{
         CommandBufferBufferRangeUsage, CommandBufferBufferUsage, CommandBufferImageRangeUsage,
         CommandBufferImageUsage, CommandBufferInheritanceInfo,
         CommandBufferInheritanceRenderPassType, CommandBufferLevel, CommandBufferResourcesUsage,
-        CommandBufferUsage, RenderingInfo, ResourceUseRef, SecondaryAutoCommandBuffer,
-        SecondaryCommandBufferBufferUsage, SecondaryCommandBufferImageUsage,
-        SecondaryCommandBufferResourcesUsage, SubpassContents,
+        CommandBufferUsage, ImageLayoutTransition, PrimaryCommandBufferAbstract, RenderingInfo,
+        ResourceUseRef, SecondaryAutoCommandBuffer, SecondaryCommandBufferBufferUsage,
+        SecondaryCommandBufferImageUsage, SecondaryCommandBufferResourcesUsage, SubpassContents,
     },
     descriptor_set::{DescriptorSetResources, DescriptorSetWithOffsets},
-    device::{Device, DeviceOwned},
-    image::{view::ImageView, Image, ImageAspects, ImageLayout, ImageSubresourceRange},
+    device::{Device, DeviceOwned, Queue},
+    image::{
+        view::ImageView, Image, ImageAspect, ImageAspects, ImageLayout, ImageSubresourceRange,
+    },
     pipeline::{
         graphics::{
             color_blend::LogicOp,
@@ -42,8 +44,8 @@ use crate::{
     range_set::RangeSet,
     render_pass::{Framebuffer, Subpass},
     sync::{
-        AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo, ImageMemoryBarrier,
-        PipelineStageAccessFlags, PipelineStages,
+        AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo, GpuFuture,
+        ImageMemoryBarrier, PipelineStageAccessFlags, PipelineStages,
     },
     DeviceSize, Validated, ValidationError, VulkanError,
 };
@@ -68,11 +70,13 @@ where
     A: CommandBufferAllocator,
 {
     pub(in crate::command_buffer) inner: UnsafeCommandBufferBuilder<A>,
-    commands: Vec<(
-        CommandInfo,
-        Box<dyn Fn(&mut UnsafeCommandBufferBuilder<A>) + Send + Sync + 'static>,
-    )>,
+    // The information about each command, used for automatic synchronization.
+    commands: Vec<CommandInfo>,
+    // The functions that record each command. They are kept separately from `commands`, so that
+    // they can be handed to the built command buffer without allocating a new `Vec`.
+    record_funcs: Vec<Box<dyn Fn(&mut UnsafeCommandBufferBuilder<A>) + Send + Sync + 'static>>,
     pub(in crate::command_buffer) builder_state: CommandBufferBuilderState,
+    pub(in crate::command_buffer) automatic_sync: bool,
     _data: PhantomData<L>,
 }
 
@@ -102,6 +106,25 @@ where
         }
     }
 
+    /// Starts recording a primary command buffer, with capacity for at least `capacity`
+    /// commands.
+    ///
+    /// This is the same as calling [`primary`](Self::primary) followed by
+    /// [`reserve`](AutoCommandBufferBuilder::reserve).
+    #[inline]
+    pub fn primary_with_capacity(
+        allocator: &A,
+        queue_family_index: u32,
+        usage: CommandBufferUsage,
+        capacity: usize,
+    ) -> Result<AutoCommandBufferBuilder<PrimaryAutoCommandBuffer<A>, A>, Validated<VulkanError>>
+    {
+        let mut builder = AutoCommandBufferBuilder::primary(allocator, queue_family_index, usage)?;
+        builder.reserve(capacity);
+
+        Ok(builder)
+    }
+
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     #[inline]
     pub unsafe fn primary_unchecked(
@@ -149,6 +172,31 @@ where
         }
     }
 
+    /// Starts recording a secondary command buffer, with capacity for at least `capacity`
+    /// commands.
+    ///
+    /// This is the same as calling [`secondary`](Self::secondary) followed by
+    /// [`reserve`](AutoCommandBufferBuilder::reserve).
+    #[inline]
+    pub fn secondary_with_capacity(
+        allocator: &A,
+        queue_family_index: u32,
+        usage: CommandBufferUsage,
+        inheritance_info: CommandBufferInheritanceInfo,
+        capacity: usize,
+    ) -> Result<AutoCommandBufferBuilder<SecondaryAutoCommandBuffer<A>, A>, Validated<VulkanError>>
+    {
+        let mut builder = AutoCommandBufferBuilder::secondary(
+            allocator,
+            queue_family_index,
+            usage,
+            inheritance_info,
+        )?;
+        builder.reserve(capacity);
+
+        Ok(builder)
+    }
+
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     #[inline]
     pub unsafe fn secondary_unchecked(
@@ -244,7 +292,9 @@ where
         Ok(AutoCommandBufferBuilder {
             inner,
             commands: Vec::new(),
+            record_funcs: Vec::new(),
             builder_state,
+            automatic_sync: true,
             _data: PhantomData,
         })
     }
@@ -260,17 +310,10 @@ where
         ),
         Validated<VulkanError>,
     > {
-        let mut auto_sync_state = AutoSyncState::new(
-            self.device().clone(),
-            self.inner.level(),
-            self.inner
-                .inheritance_info()
-                .as_ref()
-                .map_or(false, |info| info.render_pass.is_some()),
-        );
+        let mut auto_sync_state = self.new_auto_sync_state();
 
         // Add barriers between the commands.
-        for (command_info, _) in self.commands.iter() {
+        for command_info in self.commands.iter() {
             auto_sync_state.add_command(command_info).map_err(|err| {
                 Box::new(ValidationError {
                     problem: format!(
@@ -288,8 +331,14 @@ where
         let (mut barriers, resources_usage, secondary_resources_usage) = auto_sync_state.build();
         let final_barrier_index = self.commands.len();
 
+        // With manual synchronization, the resource usage is still tracked for the checks at
+        // submission time, but the barriers are left to the user.
+        if !self.automatic_sync {
+            barriers.clear();
+        }
+
         // Record all the commands and barriers to the inner command buffer.
-        for (command_index, (_, record_func)) in self.commands.iter().enumerate() {
+        for (command_index, record_func) in self.record_funcs.iter().enumerate() {
             if let Some(barriers) = barriers.remove(&command_index) {
                 for dependency_info in barriers {
                     unsafe {
@@ -326,10 +375,7 @@ where
 
         Ok((
             self.inner.build()?,
-            self.commands
-                .into_iter()
-                .map(|(_, record_func)| record_func)
-                .collect(),
+            self.record_funcs,
             resources_usage,
             secondary_resources_usage,
         ))
@@ -372,6 +418,68 @@ where
             state: Mutex::new(Default::default()),
         }))
     }
+
+    /// Builds the command buffer, executes it on `queue`, and blocks the current thread until
+    /// the execution has finished.
+    ///
+    /// This is a shortcut for calling [`build`](Self::build), [`execute`], then
+    /// [`then_signal_fence_and_flush`] and [`wait`] on the resulting future. It is meant for
+    /// one-off work such as uploading data during initialization, and should not be used for
+    /// work that happens every frame. The command buffer and the resources that it uses are
+    /// released once the function returns.
+    ///
+    /// The command buffer must have been started with [`CommandBufferUsage::OneTimeSubmit`].
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `queue` does not belong to the same device as the command buffer.
+    ///
+    /// [`execute`]: crate::command_buffer::PrimaryCommandBufferAbstract::execute
+    /// [`then_signal_fence_and_flush`]: crate::sync::GpuFuture::then_signal_fence_and_flush
+    /// [`wait`]: crate::sync::future::FenceSignalFuture::wait
+    pub fn build_and_execute_blocking(self, queue: Arc<Queue>) -> Result<(), Validated<VulkanError>>
+    where
+        A: 'static,
+    {
+        self.validate_build_and_execute_blocking(&queue)?;
+
+        let command_buffer = self.build()?;
+        let future = command_buffer
+            .execute(queue)
+            .map_err(|err| Box::new(ValidationError::from_error(err)))?
+            .then_signal_fence_and_flush()?;
+        future.wait(None)?;
+
+        Ok(())
+    }
+
+    fn validate_build_and_execute_blocking(
+        &self,
+        queue: &Queue,
+    ) -> Result<(), Box<ValidationError>> {
+        assert_eq!(self.device(), queue.device());
+
+        if self.inner.usage() != CommandBufferUsage::OneTimeSubmit {
+            return Err(Box::new(ValidationError {
+                problem: "the command buffer was not started with \
+                    `CommandBufferUsage::OneTimeSubmit`"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        if queue.queue_family_index() != self.inner.queue_family_index() {
+            return Err(Box::new(ValidationError {
+                problem: "`queue` does not belong to the queue family that the command buffer \
+                    was allocated for"
+                    .into(),
+                vuids: &["VUID-vkQueueSubmit2-commandBuffer-03878"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
 }
 
 impl<A> AutoCommandBufferBuilder<SecondaryAutoCommandBuffer<A>, A>
@@ -410,24 +518,143 @@ where
     }
 }
 
+/// # Inspecting the automatic synchronization
+///
+/// These functions are meant for debugging. Each call replays the automatic synchronization of
+/// all commands that have been recorded so far, so they should not be used every frame.
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Returns the layout that a subresource of `image` will be in after the commands that have
+    /// been recorded so far, according to the automatic synchronization.
+    ///
+    /// Returns `None` if `image` is not used by any of the commands that have been recorded so
+    /// far. The subresource will then be in whatever layout it has when the command buffer is
+    /// executed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `aspect` is not one of the aspects of `image.format()`.
+    /// - Panics if `mip_level` is not less than `image.mip_levels()`.
+    /// - Panics if `array_layer` is not less than `image.array_layers()`.
+    pub fn current_layout(
+        &self,
+        image: &Image,
+        aspect: ImageAspect,
+        mip_level: u32,
+        array_layer: u32,
+    ) -> Option<ImageLayout> {
+        let range = image
+            .iter_ranges(ImageSubresourceRange {
+                aspects: aspect.into(),
+                mip_levels: mip_level..mip_level + 1,
+                array_layers: array_layer..array_layer + 1,
+            })
+            .next()
+            .unwrap();
+
+        let auto_sync_state = self.replay_auto_sync_state();
+        let range_map = auto_sync_state.images.get(image)?;
+        let (_, state) = range_map.range(&range).next()?;
+
+        Some(state.current_layout)
+    }
+
+    /// Returns the image layout transitions that the automatic synchronization will perform for
+    /// the commands that have been recorded so far, in the order that they are performed.
+    ///
+    /// For a primary command buffer, this includes the transitions at the end of the command
+    /// buffer that put images back into the layout that they are expected to have afterwards.
+    /// If a layout mismatch error is returned when executing the command buffer, these
+    /// transitions can help to find out where the image was left in the wrong layout.
+    pub fn layout_transitions(&self) -> Vec<ImageLayoutTransition> {
+        let (barriers, _, _) = self.replay_auto_sync_state().build();
+        let mut barriers: Vec<_> = barriers.into_iter().collect();
+        barriers.sort_by_key(|&(command_index, _)| command_index);
+
+        barriers
+            .into_iter()
+            .flat_map(|(command_index, dependency_infos)| {
+                let command_name = self
+                    .commands
+                    .get(command_index)
+                    .map(|command_info| command_info.name);
+
+                dependency_infos
+                    .into_iter()
+                    .flat_map(|dependency_info| dependency_info.image_memory_barriers)
+                    .filter(|barrier| barrier.old_layout != barrier.new_layout)
+                    .map(move |barrier| ImageLayoutTransition {
+                        command_index,
+                        command_name,
+                        image: barrier.image,
+                        subresource_range: barrier.subresource_range,
+                        old_layout: barrier.old_layout,
+                        new_layout: barrier.new_layout,
+                    })
+            })
+            .collect()
+    }
+
+    fn new_auto_sync_state(&self) -> AutoSyncState {
+        AutoSyncState::new(
+            self.device().clone(),
+            self.inner.level(),
+            self.inner
+                .inheritance_info()
+                .as_ref()
+                .map_or(false, |info| info.render_pass.is_some()),
+        )
+    }
+
+    fn replay_auto_sync_state(&self) -> AutoSyncState {
+        let mut auto_sync_state = self.new_auto_sync_state();
+
+        for command_info in self.commands.iter() {
+            // A conflict will be reported when the command buffer is built. Report the state up
+            // to the conflicting command.
+            if auto_sync_state.add_command(command_info).is_err() {
+                break;
+            }
+        }
+
+        auto_sync_state
+    }
+}
+
 impl<L, A> AutoCommandBufferBuilder<L, A>
 where
     A: CommandBufferAllocator,
 {
+    pub(in crate::command_buffer) fn has_commands(&self) -> bool {
+        !self.commands.is_empty()
+    }
+
+    /// Reserves capacity for at least `additional` more commands to be recorded.
+    ///
+    /// The builder keeps a list of the commands that have been recorded, which grows as commands
+    /// are added. If the number of commands is known in advance, for example because the same
+    /// commands are recorded every frame, reserving the capacity up front avoids reallocating the
+    /// list while recording.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.commands.reserve(additional);
+        self.record_funcs.reserve(additional);
+    }
+
     pub(in crate::command_buffer) fn add_command(
         &mut self,
         name: &'static str,
         used_resources: Vec<(ResourceUseRef2, Resource)>,
         record_func: impl Fn(&mut UnsafeCommandBufferBuilder<A>) + Send + Sync + 'static,
     ) {
-        self.commands.push((
-            CommandInfo {
-                name,
-                used_resources,
-                render_pass: RenderPassCommand::None,
-            },
-            Box::new(record_func),
-        ));
+        self.commands.push(CommandInfo {
+            name,
+            used_resources,
+            render_pass: RenderPassCommand::None,
+        });
+        self.record_funcs.push(Box::new(record_func));
     }
 
     pub(in crate::command_buffer) fn add_render_pass_begin(
@@ -436,14 +663,12 @@ where
         used_resources: Vec<(ResourceUseRef2, Resource)>,
         record_func: impl Fn(&mut UnsafeCommandBufferBuilder<A>) + Send + Sync + 'static,
     ) {
-        self.commands.push((
-            CommandInfo {
-                name,
-                used_resources,
-                render_pass: RenderPassCommand::Begin,
-            },
-            Box::new(record_func),
-        ));
+        self.commands.push(CommandInfo {
+            name,
+            used_resources,
+            render_pass: RenderPassCommand::Begin,
+        });
+        self.record_funcs.push(Box::new(record_func));
     }
 
     pub(in crate::command_buffer) fn add_render_pass_end(
@@ -452,14 +677,12 @@ where
         used_resources: Vec<(ResourceUseRef2, Resource)>,
         record_func: impl Fn(&mut UnsafeCommandBufferBuilder<A>) + Send + Sync + 'static,
     ) {
-        self.commands.push((
-            CommandInfo {
-                name,
-                used_resources,
-                render_pass: RenderPassCommand::End,
-            },
-            Box::new(record_func),
-        ));
+        self.commands.push(CommandInfo {
+            name,
+            used_resources,
+            render_pass: RenderPassCommand::End,
+        });
+        self.record_funcs.push(Box::new(record_func));
     }
 }
 
@@ -1543,6 +1766,7 @@ impl RenderPassState {
 
                 render_pass: BeginRenderingState {
                     pipeline_used: false,
+                    end_dependency_info: Default::default(),
                 }
                 .into(),
             },
@@ -1576,6 +1800,7 @@ pub(in crate::command_buffer) struct BeginRenderPassState {
 
 pub(in crate::command_buffer) struct BeginRenderingState {
     pub(in crate::command_buffer) pipeline_used: bool,
+    pub(in crate::command_buffer) end_dependency_info: DependencyInfo,
 }
 
 pub(in crate::command_buffer) struct RenderPassStateAttachments {
@@ -1760,3 +1985,82 @@ pub(in crate::command_buffer) struct QueryState {
     pub(in crate::command_buffer) flags: QueryControlFlags,
     pub(in crate::command_buffer) in_subpass: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            CommandBufferInheritanceInfo, CommandBufferUsage,
+        },
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+    };
+
+    #[test]
+    fn with_capacity_does_not_reallocate() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let buffer = Buffer::new_slice::<u32>(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
+                ..Default::default()
+            },
+            16,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        let mut primary = AutoCommandBufferBuilder::primary_with_capacity(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+            16,
+        )
+        .unwrap();
+        let capacity = (primary.commands.capacity(), primary.record_funcs.capacity());
+        assert!(capacity.0 >= 16 && capacity.1 >= 16);
+
+        for _ in 0..16 {
+            primary.fill_buffer(buffer.clone(), 0).unwrap();
+        }
+
+        assert_eq!(
+            (primary.commands.capacity(), primary.record_funcs.capacity()),
+            capacity,
+        );
+
+        let mut secondary = AutoCommandBufferBuilder::secondary_with_capacity(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+            CommandBufferInheritanceInfo::default(),
+            16,
+        )
+        .unwrap();
+        let capacity = (
+            secondary.commands.capacity(),
+            secondary.record_funcs.capacity(),
+        );
+        assert!(capacity.0 >= 16 && capacity.1 >= 16);
+
+        for _ in 0..16 {
+            secondary.fill_buffer(buffer.clone(), 0).unwrap();
+        }
+
+        assert_eq!(
+            (
+                secondary.commands.capacity(),
+                secondary.record_funcs.capacity()
+            ),
+            capacity,
+        );
+    }
+}
@@ -322,7 +322,9 @@ mod tests {
         buffer::{Buffer, BufferCreateInfo, BufferUsage},
         command_buffer::{
             allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, BufferCopy,
-            CommandBufferUsage, CopyBufferInfoTyped, PrimaryCommandBufferAbstract,
+            ClearColorImageInfo, CommandBufferUsage, CopyBufferInfoTyped, MultiDrawInfo,
+            PrimaryCommandBufferAbstract, RenderPassBeginInfo, RenderingAttachmentInfo,
+            RenderingInfo,
         },
         descriptor_set::{
             allocator::StandardDescriptorSetAllocator,
@@ -333,25 +335,951 @@ mod tests {
             PersistentDescriptorSet, WriteDescriptorSet,
         },
         device::{Device, DeviceCreateInfo, QueueCreateInfo},
-        image::sampler::{Sampler, SamplerCreateInfo},
+        format::{ClearValue, Format, FormatFeatures},
+        image::{
+            sampler::{Sampler, SamplerCreateInfo},
+            view::ImageView,
+            Image, ImageAspect, ImageAspects, ImageCreateInfo, ImageLayout, ImageTiling, ImageType,
+            ImageUsage,
+        },
         memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
-        pipeline::{layout::PipelineLayoutCreateInfo, PipelineBindPoint, PipelineLayout},
+        pipeline::{
+            graphics::viewport::{Scissor, Viewport},
+            layout::PipelineLayoutCreateInfo,
+            PipelineBindPoint, PipelineLayout,
+        },
+        query::{QueryControlFlags, QueryPool, QueryPoolCreateInfo, QueryType},
+        render_pass::{AttachmentLoadOp, AttachmentStoreOp, Framebuffer, FramebufferCreateInfo},
         shader::ShaderStages,
-        sync::GpuFuture,
+        sync::{
+            AccessFlags, BufferMemoryBarrier, DependencyInfo, GpuFuture, PipelineStages,
+            QueueFamilyOwnershipTransfer, ResourceUse,
+        },
+        Requires, RequiresAllOf, RequiresOneOf,
     };
 
     #[test]
-    fn basic_creation() {
+    fn basic_creation() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        AutoCommandBufferBuilder::primary(
+            &allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::MultipleSubmit,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn build_and_execute_blocking() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let buffer = Buffer::from_iter(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            [0_u32; 4],
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        let builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::MultipleSubmit,
+        )
+        .unwrap();
+        assert!(builder.build_and_execute_blocking(queue.clone()).is_err());
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder.fill_buffer(buffer.clone(), 7).unwrap();
+        builder.build_and_execute_blocking(queue).unwrap();
+
+        assert_eq!(*buffer.read().unwrap(), [7_u32; 4]);
+    }
+
+    #[test]
+    fn reserve_and_execute() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let create_buffer = || {
+            Buffer::from_iter(
+                &memory_allocator,
+                BufferCreateInfo {
+                    usage: BufferUsage::TRANSFER_SRC | BufferUsage::TRANSFER_DST,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                        | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                    ..Default::default()
+                },
+                [0_u32; 4],
+            )
+            .unwrap()
+        };
+        let source = create_buffer();
+        let first = create_buffer();
+        let second = create_buffer();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder.reserve(4);
+
+        // Each command depends on the previous one, so the commands must be recorded in order
+        // and with the barriers that were computed for them.
+        builder
+            .fill_buffer(source.clone(), 7)
+            .unwrap()
+            .copy_buffer(CopyBufferInfoTyped::buffers(source.clone(), first.clone()))
+            .unwrap()
+            .fill_buffer(source.clone(), 9)
+            .unwrap()
+            .copy_buffer(CopyBufferInfoTyped::buffers(source, second.clone()))
+            .unwrap();
+        builder.build_and_execute_blocking(queue).unwrap();
+
+        assert_eq!(*first.read().unwrap(), [7_u32; 4]);
+        assert_eq!(*second.read().unwrap(), [9_u32; 4]);
+    }
+
+    #[test]
+    fn layout_transitions() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [16, 16, 1],
+                usage: ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        assert_eq!(
+            builder.current_layout(&image, ImageAspect::Color, 0, 0),
+            None
+        );
+        assert!(builder.layout_transitions().is_empty());
+
+        builder
+            .clear_color_image(ClearColorImageInfo::image(image.clone()))
+            .unwrap();
+        assert_eq!(
+            builder.current_layout(&image, ImageAspect::Color, 0, 0),
+            Some(ImageLayout::TransferDstOptimal),
+        );
+
+        let transitions = builder.layout_transitions();
+        assert_eq!(transitions[0].command_index, 0);
+        assert_eq!(transitions[0].command_name, Some("clear_color_image"));
+        assert_eq!(transitions[0].new_layout, ImageLayout::TransferDstOptimal);
+        assert!(transitions[1..]
+            .iter()
+            .all(|transition| transition.command_index == 1
+                && transition.command_name.is_none()
+                && transition.old_layout == ImageLayout::TransferDstOptimal));
+    }
+
+    #[test]
+    fn rendering_layout_hints() {
+        let (device, queue) = gfx_dev_and_queue!(dynamic_rendering);
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [16, 16, 1],
+                usage: ImageUsage::COLOR_ATTACHMENT | ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let image_view = ImageView::new_default(image.clone()).unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // The image doesn't have the `TRANSFER_SRC` usage.
+        assert!(builder
+            .begin_rendering(RenderingInfo {
+                color_attachments: vec![Some(RenderingAttachmentInfo {
+                    final_layout: Some(ImageLayout::TransferSrcOptimal),
+                    ..RenderingAttachmentInfo::image_view(image_view.clone())
+                })],
+                ..Default::default()
+            })
+            .is_err());
+
+        builder
+            .begin_rendering(RenderingInfo {
+                color_attachments: vec![Some(RenderingAttachmentInfo {
+                    load_op: AttachmentLoadOp::Clear,
+                    store_op: AttachmentStoreOp::Store,
+                    clear_value: Some(ClearValue::Float([0.0; 4])),
+                    initial_layout: Some(ImageLayout::Undefined),
+                    final_layout: Some(ImageLayout::ShaderReadOnlyOptimal),
+                    ..RenderingAttachmentInfo::image_view(image_view)
+                })],
+                ..Default::default()
+            })
+            .unwrap();
+        builder.end_rendering().unwrap();
+        assert_eq!(
+            builder.current_layout(&image, ImageAspect::Color, 0, 0),
+            Some(ImageLayout::ShaderReadOnlyOptimal),
+        );
+        builder.build_and_execute_blocking(queue).unwrap();
+    }
+
+    #[test]
+    fn rendering_layout_hints_separate_depth_stencil() {
+        let (device, queue) = gfx_dev_and_queue!(dynamic_rendering, separate_depth_stencil_layouts);
+
+        let format = match device.physical_device().find_supported_depth_format(
+            Format::DEPTH_STENCIL_FORMATS,
+            ImageTiling::Optimal,
+            FormatFeatures::DEPTH_STENCIL_ATTACHMENT | FormatFeatures::SAMPLED_IMAGE,
+        ) {
+            Some(format) => format,
+            None => return,
+        };
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format,
+                extent: [16, 16, 1],
+                usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT | ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let image_view = ImageView::new_default(image).unwrap();
+
+        let rendering_info = RenderingInfo {
+            depth_attachment: Some(RenderingAttachmentInfo {
+                image_layout: ImageLayout::DepthAttachmentOptimal,
+                initial_layout: Some(ImageLayout::Undefined),
+                final_layout: Some(ImageLayout::ShaderReadOnlyOptimal),
+                ..RenderingAttachmentInfo::image_view(image_view.clone())
+            }),
+            stencil_attachment: Some(RenderingAttachmentInfo {
+                image_layout: ImageLayout::StencilReadOnlyOptimal,
+                initial_layout: Some(ImageLayout::Undefined),
+                final_layout: Some(ImageLayout::ShaderReadOnlyOptimal),
+                ..RenderingAttachmentInfo::image_view(image_view.clone())
+            }),
+            ..Default::default()
+        };
+
+        // Each aspect is transitioned to and from the layout of its own attachment.
+        let (begin_dependency_info, end_dependency_info) = rendering_info.layout_transitions();
+        let transitions = |dependency_info: &DependencyInfo| {
+            (dependency_info.image_memory_barriers.iter())
+                .map(|barrier| {
+                    (
+                        barrier.subresource_range.aspects,
+                        barrier.old_layout,
+                        barrier.new_layout,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            transitions(&begin_dependency_info),
+            [
+                (
+                    ImageAspects::DEPTH,
+                    ImageLayout::Undefined,
+                    ImageLayout::DepthAttachmentOptimal,
+                ),
+                (
+                    ImageAspects::STENCIL,
+                    ImageLayout::Undefined,
+                    ImageLayout::StencilReadOnlyOptimal,
+                ),
+            ],
+        );
+        assert_eq!(
+            transitions(&end_dependency_info),
+            [
+                (
+                    ImageAspects::DEPTH,
+                    ImageLayout::DepthAttachmentOptimal,
+                    ImageLayout::ShaderReadOnlyOptimal,
+                ),
+                (
+                    ImageAspects::STENCIL,
+                    ImageLayout::StencilReadOnlyOptimal,
+                    ImageLayout::ShaderReadOnlyOptimal,
+                ),
+            ],
+        );
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // The image doesn't have the `COLOR_ATTACHMENT` usage.
+        assert!(builder
+            .begin_rendering(RenderingInfo {
+                depth_attachment: Some(RenderingAttachmentInfo {
+                    final_layout: Some(ImageLayout::ColorAttachmentOptimal),
+                    ..RenderingAttachmentInfo::image_view(image_view)
+                }),
+                ..Default::default()
+            })
+            .is_err());
+
+        builder.begin_rendering(rendering_info).unwrap();
+        builder.end_rendering().unwrap();
+        builder.build_and_execute_blocking(queue).unwrap();
+    }
+
+    #[test]
+    fn pipeline_barrier_ownership_transfer_queue_family() {
+        let (device, queue) = gfx_dev_and_queue!(extensions: [khr_external_memory], features: []);
+
+        // A queue family other than the one of the command buffer.
+        let other_queue_family_index =
+            match (0..device.physical_device().queue_family_properties().len() as u32)
+                .find(|&index| index != queue.queue_family_index())
+            {
+                Some(index) => index,
+                None => return,
+            };
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let buffer = Buffer::new_slice::<u32>(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            builder.disable_automatic_sync().unwrap();
+
+            // The release operation must be recorded on a queue of the source queue family.
+            match builder.pipeline_barrier(DependencyInfo {
+                buffer_memory_barriers: [BufferMemoryBarrier {
+                    src_stages: PipelineStages::ALL_COMMANDS,
+                    src_access: AccessFlags::MEMORY_WRITE,
+                    queue_family_ownership_transfer: Some(
+                        QueueFamilyOwnershipTransfer::ExclusiveToExternal {
+                            src_index: other_queue_family_index,
+                        },
+                    ),
+                    range: 0..buffer.size(),
+                    ..BufferMemoryBarrier::buffer(buffer.buffer().clone())
+                }]
+                .into_iter()
+                .collect(),
+                ..Default::default()
+            }) {
+                Err(err) => assert_eq!(
+                    err.context,
+                    "dependency_info.buffer_memory_barriers[0].queue_family_ownership_transfer",
+                ),
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[test]
+    fn buffer_handoff_requires_manual_sync() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let buffer = Buffer::new_slice::<u32>(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER | BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let builder = || {
+            AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap()
+        };
+
+        unsafe {
+            let mut builder_auto = builder();
+            assert!(builder_auto
+                .buffer_handoff(
+                    &buffer,
+                    ResourceUse::ComputeShaderWrite,
+                    ResourceUse::VertexRead,
+                )
+                .is_err());
+
+            let mut builder_manual = builder();
+            builder_manual.disable_automatic_sync().unwrap();
+            assert!(builder_manual
+                .buffer_handoff(
+                    &buffer,
+                    ResourceUse::ComputeShaderWrite,
+                    ResourceUse::VertexRead,
+                )
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn begin_query_primitives_generated() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_primitives_generated_query],
+            features: [primitives_generated_query],
+        );
+
+        let query_pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: 1,
+                ..QueryPoolCreateInfo::query_type(QueryType::PrimitivesGenerated)
+            },
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // `gfx_dev_and_queue!` always selects a queue family with graphics support.
+        unsafe {
+            builder
+                .reset_query_pool(query_pool.clone(), 0..1)
+                .unwrap()
+                .begin_query(query_pool.clone(), 0, QueryControlFlags::empty())
+                .unwrap()
+                .end_query(query_pool, 0)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn rendering_scope() {
+        let (device, queue) = gfx_dev_and_queue!(dynamic_rendering);
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [16, 16, 1],
+                usage: ImageUsage::COLOR_ATTACHMENT,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let image_view = ImageView::new_default(image).unwrap();
+        let rendering_info = RenderingInfo {
+            color_attachments: vec![Some(RenderingAttachmentInfo::image_view(image_view))],
+            ..Default::default()
+        };
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .rendering(rendering_info.clone(), |_| Ok(()))
+            .unwrap();
+        assert!(builder.builder_state.render_pass.is_none());
+
+        // The render pass is ended even if `record` returns an error.
+        assert!(builder
+            .rendering(rendering_info, |rendering| {
+                // No pipeline is bound.
+                rendering.draw(3, 1, 0, 0)?;
+                Ok(())
+            })
+            .is_err());
+        assert!(builder.builder_state.render_pass.is_none());
+
+        builder.build_and_execute_blocking(queue).unwrap();
+    }
+
+    #[test]
+    fn render_pass_scope() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let render_pass = crate::ordered_passes_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: DontCare,
+                    store_op: Store,
+                },
+            },
+            passes: [
+                { color: [color], depth_stencil: {}, input: [] },
+                { color: [color], depth_stencil: {}, input: [] },
+            ],
+        )
+        .unwrap();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [16, 16, 1],
+                usage: ImageUsage::COLOR_ATTACHMENT,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![ImageView::new_default(image).unwrap()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let render_pass_begin_info = RenderPassBeginInfo {
+            clear_values: vec![None],
+            ..RenderPassBeginInfo::framebuffer(framebuffer)
+        };
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .render_pass(render_pass_begin_info.clone(), Default::default(), |pass| {
+                pass.next_subpass(Default::default(), Default::default())?;
+                Ok(())
+            })
+            .unwrap();
+        assert!(builder.builder_state.render_pass.is_none());
+
+        // The second subpass was not advanced to, but the render pass is still ended.
+        assert!(builder
+            .render_pass(render_pass_begin_info, Default::default(), |_| Ok(()))
+            .is_err());
+        assert!(builder.builder_state.render_pass.is_none());
+
+        builder.build_and_execute_blocking(queue).unwrap();
+    }
+
+    #[test]
+    fn draw_multi_requires_feature() {
         let (device, queue) = gfx_dev_and_queue!();
 
-        let allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
 
-        AutoCommandBufferBuilder::primary(
-            &allocator,
+        assert!(builder
+            .draw_multi(
+                &[MultiDrawInfo {
+                    first_vertex: 0,
+                    vertex_count: 3,
+                }],
+                1,
+                0,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn set_viewport_validates_viewports() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
             queue.queue_family_index(),
-            CommandBufferUsage::MultipleSubmit,
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // The width of a viewport must be greater than zero.
+        assert!(builder
+            .set_viewport(
+                0,
+                [Viewport {
+                    offset: [0.0, 0.0],
+                    extent: [0.0, 16.0],
+                    depth_range: 0.0..=1.0,
+                }]
+                .into_iter()
+                .collect(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn set_viewport_all_requires_multi_viewport() {
+        let viewports = || {
+            [0.0, 16.0]
+                .into_iter()
+                .map(|x| Viewport {
+                    offset: [x, 0.0],
+                    extent: [16.0, 16.0],
+                    depth_range: 0.0..=1.0,
+                })
+                .collect()
+        };
+        let scissors = || {
+            [0, 16]
+                .into_iter()
+                .map(|x| Scissor {
+                    offset: [x, 0],
+                    extent: [16, 16],
+                })
+                .collect()
+        };
+
+        {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+
+            let requires_one_of =
+                RequiresOneOf(&[RequiresAllOf(&[Requires::Feature("multi_viewport")])]);
+
+            match builder.set_viewport_all(viewports()) {
+                Err(err) => assert_eq!(err.requires_one_of, requires_one_of),
+                Ok(_) => panic!(),
+            }
+
+            match builder.set_scissor_all(scissors()) {
+                Err(err) => assert_eq!(err.requires_one_of, requires_one_of),
+                Ok(_) => panic!(),
+            }
+        }
+
+        {
+            let (device, queue) = gfx_dev_and_queue!(multi_viewport);
+
+            let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+
+            builder.set_viewport_all(viewports()).unwrap();
+            builder.set_scissor_all(scissors()).unwrap();
+        }
+    }
+
+    #[test]
+    fn set_viewport_all_max_viewports() {
+        let (device, queue) = gfx_dev_and_queue!(multi_viewport);
+
+        let max_viewports = device.physical_device().properties().max_viewports;
+        let viewports = |count| {
+            (0..count)
+                .map(|_| Viewport {
+                    offset: [0.0, 0.0],
+                    extent: [16.0, 16.0],
+                    depth_range: 0.0..=1.0,
+                })
+                .collect()
+        };
+        let scissors = |count| {
+            (0..count)
+                .map(|_| Scissor {
+                    offset: [0, 0],
+                    extent: [16, 16],
+                })
+                .collect()
+        };
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder.set_viewport_all(viewports(max_viewports)).unwrap();
+        builder.set_scissor_all(scissors(max_viewports)).unwrap();
+
+        match builder.set_viewport_all(viewports(max_viewports + 1)) {
+            Err(err) => assert_eq!(err.vuids, &["VUID-vkCmdSetViewport-firstViewport-01223"]),
+            Ok(_) => panic!(),
+        }
+
+        match builder.set_scissor_all(scissors(max_viewports + 1)) {
+            Err(err) => assert_eq!(err.vuids, &["VUID-vkCmdSetScissor-firstScissor-00592"]),
+            Ok(_) => panic!(),
+        }
+    }
+
+    #[test]
+    fn rendering_clear_value_type() {
+        let (device, queue) = gfx_dev_and_queue!(dynamic_rendering);
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [16, 16, 1],
+                usage: ImageUsage::COLOR_ATTACHMENT,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let image_view = ImageView::new_default(image).unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // A UNORM format requires a float clear value.
+        assert!(builder
+            .begin_rendering(RenderingInfo {
+                color_attachments: vec![Some(RenderingAttachmentInfo {
+                    load_op: AttachmentLoadOp::Clear,
+                    clear_value: Some(ClearValue::Uint([0; 4])),
+                    ..RenderingAttachmentInfo::image_view(image_view)
+                })],
+                ..Default::default()
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn manual_sync() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let buffer = Buffer::from_iter(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            [0_u32; 4],
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        unsafe {
+            assert!(builder.pipeline_barrier(Default::default()).is_err());
+        }
+        builder.fill_buffer(buffer.clone(), 1).unwrap();
+        unsafe {
+            assert!(builder.disable_automatic_sync().is_err());
+        }
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        unsafe {
+            builder.disable_automatic_sync().unwrap();
+            builder
+                .pipeline_barrier(DependencyInfo {
+                    buffer_memory_barriers: [BufferMemoryBarrier {
+                        src_stages: PipelineStages::ALL_COMMANDS,
+                        src_access: AccessFlags::MEMORY_READ | AccessFlags::MEMORY_WRITE,
+                        dst_stages: PipelineStages::ALL_TRANSFER,
+                        dst_access: AccessFlags::TRANSFER_WRITE,
+                        range: buffer.offset()..buffer.offset() + buffer.size(),
+                        ..BufferMemoryBarrier::buffer(buffer.buffer().clone())
+                    }]
+                    .into_iter()
+                    .collect(),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        builder.fill_buffer(buffer.clone(), 2).unwrap();
+        builder.build_and_execute_blocking(queue).unwrap();
+
+        assert_eq!(*buffer.read().unwrap(), [2_u32; 4]);
+    }
+
+    #[test]
+    fn make_host_readable() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let buffer = Buffer::from_iter(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            [0_u32; 4],
         )
         .unwrap();
+        let device_local_buffer = Buffer::from_iter(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
+                ..Default::default()
+            },
+            [0_u32; 4],
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        if device_local_buffer.mapped_ptr().is_none() {
+            assert!(builder.make_host_readable(&device_local_buffer).is_err());
+        }
+        builder.fill_buffer(buffer.clone(), 1).unwrap();
+        builder.make_host_readable(&buffer).unwrap();
+        builder.build_and_execute_blocking(queue.clone()).unwrap();
+        assert_eq!(*buffer.read().unwrap(), [1_u32; 4]);
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        unsafe {
+            builder.disable_automatic_sync().unwrap();
+        }
+        builder.fill_buffer(buffer.clone(), 2).unwrap();
+        builder.make_host_readable(&buffer).unwrap();
+        builder.build_and_execute_blocking(queue).unwrap();
+        assert_eq!(*buffer.read().unwrap(), [2_u32; 4]);
     }
 
     #[test]
@@ -599,6 +1527,59 @@ mod tests {
             .is_err());
     }
 
+    #[test]
+    fn buffer_self_copy_overlapping_regions() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let source = Buffer::from_iter(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC | BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            [0_u32, 1, 2, 3, 4, 5].iter().copied(),
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // Neither region overlaps with itself, but the source of the second region overlaps with
+        // the destination of the first.
+        assert!(builder
+            .copy_buffer(CopyBufferInfoTyped {
+                regions: [
+                    BufferCopy {
+                        src_offset: 0,
+                        dst_offset: 3,
+                        size: 2,
+                        ..Default::default()
+                    },
+                    BufferCopy {
+                        src_offset: 4,
+                        dst_offset: 2,
+                        size: 1,
+                        ..Default::default()
+                    },
+                ]
+                .into_iter()
+                .collect(),
+                ..CopyBufferInfoTyped::buffers(source.clone(), source)
+            })
+            .is_err());
+    }
+
     #[test]
     fn secondary_conflicting_writes() {
         unsafe {
@@ -772,7 +1753,8 @@ mod tests {
             )
             .unwrap();
 
-            let ds_allocator = StandardDescriptorSetAllocator::new(device.clone());
+            let ds_allocator =
+                StandardDescriptorSetAllocator::new(device.clone(), Default::default());
 
             let set = PersistentDescriptorSet::new(
                 &ds_allocator,
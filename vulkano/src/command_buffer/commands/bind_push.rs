@@ -38,6 +38,10 @@ where
     A: CommandBufferAllocator,
 {
     /// Binds descriptor sets for future dispatch or draw calls.
+    ///
+    /// The descriptor sets are only checked against the requirements of the shaders, such as the
+    /// view type and format of image views, when a dispatch or draw command is recorded, because
+    /// the pipeline that will use them may not be bound yet.
     pub fn bind_descriptor_sets(
         &mut self,
         pipeline_bind_point: PipelineBindPoint,
@@ -124,6 +128,14 @@ where
     }
 
     /// Binds an index buffer for future indexed draw calls.
+    ///
+    /// The type of the indices is determined by the variant of `index_buffer`. Only the range of
+    /// the subbuffer is considered to be bound: indexed draw calls are validated against the
+    /// number of indices that fit in it, not against the size of the whole buffer.
+    ///
+    /// Using [`IndexBuffer::U8`] requires the [`index_type_uint8`] feature.
+    ///
+    /// [`index_type_uint8`]: crate::device::Features::index_type_uint8
     pub fn bind_index_buffer(
         &mut self,
         index_buffer: impl Into<IndexBuffer>,
@@ -706,6 +718,8 @@ where
             .flat_map(|x| x.as_ref().1.iter().copied())
             .collect();
 
+        // TODO: use `vkCmdBindDescriptorSets2KHR` with explicit stages once `khr_maintenance6` is
+        // available in the Vulkan bindings.
         let fns = self.device().fns();
         (fns.v1_0.cmd_bind_descriptor_sets)(
             self.handle(),
@@ -779,8 +793,19 @@ where
             }));
         }
 
-        // TODO:
-        // VUID-vkCmdBindIndexBuffer-offset-00432
+        if !index_buffer_bytes
+            .offset()
+            .is_multiple_of(index_buffer.index_type().size())
+        {
+            return Err(Box::new(ValidationError {
+                context: "index_buffer".into(),
+                problem: "the offset of the subbuffer is not a multiple of the size of the \
+                    index type"
+                    .into(),
+                vuids: &["VUID-vkCmdBindIndexBuffer-offset-00432"],
+                ..Default::default()
+            }));
+        }
 
         Ok(())
     }
@@ -1123,6 +1148,8 @@ where
             debug_assert!(data_offset < size as usize);
             let data = (push_constants as *const Pc as *const c_void).add(data_offset);
 
+            // TODO: use `vkCmdPushConstants2KHR` once `khr_maintenance6` is available in the
+            // Vulkan bindings.
             (fns.v1_0.cmd_push_constants)(
                 self.handle(),
                 pipeline_layout.handle(),
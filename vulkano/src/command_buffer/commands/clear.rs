@@ -95,6 +95,13 @@ where
     }
 
     /// Clears a depth/stencil image with a specific value.
+    ///
+    /// This command must be recorded outside a render pass instance. To clear a depth/stencil
+    /// attachment inside a render pass instance, use
+    /// [`clear_attachments`](Self::clear_attachments) instead.
+    ///
+    /// Unlike [`clear_color_image`](Self::clear_color_image), this command can only be recorded
+    /// in a command buffer whose queue family supports graphics operations.
     pub fn clear_depth_stencil_image(
         &mut self,
         clear_info: ClearDepthStencilImageInfo,
@@ -512,6 +512,14 @@ where
 
     /// Resolves a multisampled image into a single-sampled image.
     ///
+    /// Only color images can be resolved with this command. For images with a floating-point or
+    /// normalized format, the samples of each pixel are combined in an implementation-dependent
+    /// way, while for images with an integer format, a single sample is selected for each pixel.
+    /// Depth/stencil images must instead be resolved at the end of a subpass, using the
+    /// [`depth_stencil_resolve_attachment`] of the subpass.
+    ///
+    /// [`depth_stencil_resolve_attachment`]: crate::render_pass::SubpassDescription::depth_stencil_resolve_attachment
+    ///
     /// # Panics
     ///
     /// - Panics if `src_image` or `dst_image` were not created from the same device
@@ -2233,7 +2241,7 @@ impl CopyBufferInfo {
                 _ne: _,
             } = region;
 
-            if src_offset + size > src_buffer.size() {
+            if src_offset.saturating_add(size) > src_buffer.size() {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].src_offset + regions[{0}].size` is greater than \
@@ -2249,7 +2257,7 @@ impl CopyBufferInfo {
                 }));
             }
 
-            if dst_offset + size > dst_buffer.size() {
+            if dst_offset.saturating_add(size) > dst_buffer.size() {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].dst_offset + regions[{0}].size` is greater than \
@@ -2272,10 +2280,14 @@ impl CopyBufferInfo {
                     src_buffer.offset() + src_offset..src_buffer.offset() + src_offset + size;
 
                 for (dst_region_index, dst_region) in regions.iter().enumerate() {
-                    let &BufferCopy { dst_offset, .. } = dst_region;
+                    let &BufferCopy {
+                        dst_offset,
+                        size: dst_size,
+                        ..
+                    } = dst_region;
 
-                    let dst_range =
-                        dst_buffer.offset() + dst_offset..dst_buffer.offset() + dst_offset + size;
+                    let dst_range = dst_buffer.offset() + dst_offset
+                        ..dst_buffer.offset() + dst_offset + dst_size;
 
                     if src_range.start >= dst_range.end || dst_range.start >= src_range.end {
                         // The regions do not overlap
@@ -3867,6 +3879,61 @@ impl CopyBufferToImageInfo {
         }
     }
 
+    /// Returns a `CopyBufferToImageInfo` with the specified `src_buffer` and `dst_image`, with
+    /// one region for each mip level of the image.
+    ///
+    /// The data of the mip levels is expected to be tightly packed in `src_buffer`, one after
+    /// another starting with mip level 0. For compressed formats, the data of each mip level
+    /// consists of whole blocks, so mip levels whose extent is not a multiple of the block extent
+    /// are rounded up to a whole number of blocks. For depth/stencil formats, the start of each
+    /// mip level is aligned to 4 bytes. All aspects of the image are selected, or `plane0` if the
+    /// image is multi-planar.
+    pub fn buffer_image_all_mip_levels(
+        src_buffer: Subbuffer<impl ?Sized>,
+        dst_image: Arc<Image>,
+    ) -> Self {
+        let image_subresource = dst_image.subresource_layers();
+        let format = dst_image
+            .format()
+            .planes()
+            .first()
+            .copied()
+            .unwrap_or_else(|| dst_image.format());
+        let is_depth_stencil = format
+            .aspects()
+            .intersects(ImageAspects::DEPTH | ImageAspects::STENCIL);
+        let mut buffer_offset = 0;
+
+        let regions = (0..dst_image.mip_levels())
+            .map(|mip_level| {
+                if is_depth_stencil && buffer_offset % 4 != 0 {
+                    buffer_offset += 4 - buffer_offset % 4;
+                }
+
+                let region = BufferImageCopy {
+                    buffer_offset,
+                    image_subresource: ImageSubresourceLayers {
+                        mip_level,
+                        ..image_subresource.clone()
+                    },
+                    image_extent: mip_level_extent(dst_image.extent(), mip_level).unwrap(),
+                    ..Default::default()
+                };
+                buffer_offset += region.buffer_copy_size(format);
+
+                region
+            })
+            .collect();
+
+        Self {
+            src_buffer: src_buffer.into_bytes(),
+            dst_image,
+            dst_image_layout: ImageLayout::TransferDstOptimal,
+            regions,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             ref src_buffer,
@@ -4403,13 +4470,17 @@ impl CopyBufferToImageInfo {
                 }));
             }
 
-            if buffer_offset + region.buffer_copy_size(image_subresource_format) > src_buffer.size()
-            {
+            let buffer_copy_size = region.buffer_copy_size(image_subresource_format);
+
+            if buffer_offset.saturating_add(buffer_copy_size) > src_buffer.size() {
                 return Err(Box::new(ValidationError {
                     problem: format!(
-                        "`regions[{0}].buffer_offset` plus the number of bytes being copied \
-                        is greater than `src_buffer.size()`",
+                        "`regions[{0}].buffer_offset` ({1}) plus the number of bytes being \
+                        copied ({2}) is greater than `src_buffer.size()` ({3})",
                         region_index,
+                        buffer_offset,
+                        buffer_copy_size,
+                        src_buffer.size(),
                     )
                     .into(),
                     vuids: &["VUID-VkCopyBufferToImageInfo2-pRegions-00171"],
@@ -5015,7 +5086,8 @@ impl CopyImageToBufferInfo {
                 }));
             }
 
-            if buffer_offset + region.buffer_copy_size(image_subresource_format) > dst_buffer.size()
+            if buffer_offset.saturating_add(region.buffer_copy_size(image_subresource_format))
+                > dst_buffer.size()
             {
                 return Err(Box::new(ValidationError {
                     problem: format!(
@@ -5047,14 +5119,16 @@ pub struct BufferImageCopy {
 
     /// The number of texels between successive rows of image data in the buffer.
     ///
-    /// If set to `0`, the width of the image is used.
+    /// If set to `0`, the width of the image is used. Otherwise, this must be a multiple of the
+    /// width of the texel block of the format, for example 4 for BC, ETC2 and 4x4 ASTC formats.
     ///
     /// The default value is `0`.
     pub buffer_row_length: u32,
 
     /// The number of rows between successive depth slices of image data in the buffer.
     ///
-    /// If set to `0`, the height of the image is used.
+    /// If set to `0`, the height of the image is used. Otherwise, this must be a multiple of the
+    /// height of the texel block of the format.
     ///
     /// The default value is `0`.
     pub buffer_image_height: u32,
@@ -5099,6 +5173,9 @@ impl Default for BufferImageCopy {
 impl BufferImageCopy {
     // Following
     // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap20.html#copies-buffers-images-addressing
+    //
+    // The arithmetic saturates instead of overflowing, so that a region that is too large to be
+    // addressed is always reported as being out of range of the buffer.
     pub(crate) fn buffer_copy_size(&self, format: Format) -> DeviceSize {
         let &BufferImageCopy {
             buffer_offset: _,
@@ -5106,7 +5183,7 @@ impl BufferImageCopy {
             mut buffer_image_height,
             ref image_subresource,
             image_offset: _,
-            mut image_extent,
+            image_extent,
             _ne: _,
         } = self;
 
@@ -5119,28 +5196,34 @@ impl BufferImageCopy {
         }
 
         // Scale down from texels to texel blocks, rounding up if needed.
-        let block_extent = format.block_extent();
-        buffer_row_length = (buffer_row_length + block_extent[0] - 1) / block_extent[0];
-        buffer_image_height = (buffer_image_height + block_extent[1] - 1) / block_extent[1];
-
-        for i in 0..3 {
-            image_extent[i] = (image_extent[i] + block_extent[i] - 1) / block_extent[i];
-        }
-
-        // Only one of these is greater than 1, take the greater number.
-        image_extent[2] = max(
-            image_extent[2],
-            image_subresource.array_layers.end - image_subresource.array_layers.start,
-        );
-
-        let blocks_to_last_slice = (image_extent[2] as DeviceSize - 1)
-            * buffer_image_height as DeviceSize
-            * buffer_row_length as DeviceSize;
-        let blocks_to_last_row =
-            (image_extent[1] as DeviceSize - 1) * buffer_row_length as DeviceSize;
-        let num_blocks = blocks_to_last_slice + blocks_to_last_row + image_extent[0] as DeviceSize;
-
-        num_blocks * format.block_size()
+        let block_extent = format.block_extent().map(DeviceSize::from);
+        let blocks_up =
+            |texels: u32, block_extent: DeviceSize| (texels as DeviceSize).div_ceil(block_extent);
+        let buffer_row_length = blocks_up(buffer_row_length, block_extent[0]);
+        let buffer_image_height = blocks_up(buffer_image_height, block_extent[1]);
+        let image_extent = [
+            blocks_up(image_extent[0], block_extent[0]),
+            blocks_up(image_extent[1], block_extent[1]),
+            // Only one of these is greater than 1, take the greater number.
+            max(
+                blocks_up(image_extent[2], block_extent[2]),
+                image_subresource.array_layers.end as DeviceSize
+                    - image_subresource.array_layers.start as DeviceSize,
+            ),
+        ];
+
+        let blocks_to_last_slice = image_extent[2]
+            .saturating_sub(1)
+            .saturating_mul(buffer_image_height)
+            .saturating_mul(buffer_row_length);
+        let blocks_to_last_row = image_extent[1]
+            .saturating_sub(1)
+            .saturating_mul(buffer_row_length);
+        let num_blocks = blocks_to_last_slice
+            .saturating_add(blocks_to_last_row)
+            .saturating_add(image_extent[0]);
+
+        num_blocks.saturating_mul(format.block_size())
     }
 
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
@@ -6242,6 +6325,20 @@ impl ResolveImageInfo {
         let src_image_format = src_image.format();
         let dst_image_format = dst_image.format();
 
+        if src_image_format
+            .aspects()
+            .intersects(ImageAspects::DEPTH | ImageAspects::STENCIL)
+        {
+            return Err(Box::new(ValidationError {
+                context: "src_image.format()".into(),
+                problem: "is a depth/stencil format; depth/stencil images can only be resolved \
+                    within a render pass, using a depth/stencil resolve attachment"
+                    .into(),
+                vuids: &["VUID-VkImageResolve2-aspectMask-00266"],
+                ..Default::default()
+            }));
+        }
+
         if src_image.samples() == SampleCount::Sample1 {
             return Err(Box::new(ValidationError {
                 context: "src_image.samples()".into(),
@@ -6305,17 +6402,24 @@ impl ResolveImageInfo {
 
         if !dst_image
             .format_features()
-            .contains(FormatFeatures::TRANSFER_DST | FormatFeatures::COLOR_ATTACHMENT)
+            .intersects(FormatFeatures::TRANSFER_DST)
         {
             return Err(Box::new(ValidationError {
                 context: "dst_image.format_features()".into(),
-                problem: "does not contain both `FormatFeatures::TRANSFER_DST` and \
-                    `FormatFeatures::COLOR_ATTACHMENT`"
-                    .into(),
-                vuids: &[
-                    "VUID-VkResolveImageInfo2-dstImage-06765",
-                    "VUID-VkResolveImageInfo2-dstImage-02003",
-                ],
+                problem: "does not contain `FormatFeatures::TRANSFER_DST`".into(),
+                vuids: &["VUID-VkResolveImageInfo2-dstImage-06765"],
+                ..Default::default()
+            }));
+        }
+
+        if !dst_image
+            .format_features()
+            .intersects(FormatFeatures::COLOR_ATTACHMENT)
+        {
+            return Err(Box::new(ValidationError {
+                context: "dst_image.format_features()".into(),
+                problem: "does not contain `FormatFeatures::COLOR_ATTACHMENT`".into(),
+                vuids: &["VUID-VkResolveImageInfo2-dstImage-02003"],
                 ..Default::default()
             }));
         }
@@ -6830,7 +6934,12 @@ impl ImageResolve {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::format::Format;
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo},
+        format::Format,
+        image::{ImageCreateInfo, ImageType},
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    };
 
     /// Computes the minimum required len in elements for buffer with image data in specified
     /// format of specified size.
@@ -6886,4 +6995,117 @@ mod tests {
             29584
         );
     }
+
+    #[test]
+    fn buffer_copy_size_compressed() {
+        let region = |buffer_row_length, image_extent| BufferImageCopy {
+            buffer_row_length,
+            image_subresource: ImageSubresourceLayers {
+                aspects: ImageAspects::COLOR,
+                mip_level: 0,
+                array_layers: 0..1,
+            },
+            image_extent,
+            ..Default::default()
+        };
+
+        // A mip level that is smaller than a block still takes up a whole block.
+        assert_eq!(
+            region(0, [2, 2, 1]).buffer_copy_size(Format::BC1_RGB_UNORM_BLOCK),
+            8
+        );
+        assert_eq!(
+            region(0, [8, 4, 1]).buffer_copy_size(Format::BC1_RGB_UNORM_BLOCK),
+            16
+        );
+        assert_eq!(
+            region(8, [4, 8, 1]).buffer_copy_size(Format::BC7_UNORM_BLOCK),
+            48
+        );
+    }
+
+    #[test]
+    fn buffer_image_all_mip_levels() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [16, 8, 1],
+                mip_levels: 5,
+                usage: ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let buffer = Buffer::new_slice::<u8>(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            684,
+        )
+        .unwrap();
+
+        let copy_info = CopyBufferToImageInfo::buffer_image_all_mip_levels(buffer, image);
+
+        // Each mip level follows the previous one, with 4 bytes per texel.
+        let expected = [
+            (0, [16, 8, 1]),
+            (512, [8, 4, 1]),
+            (640, [4, 2, 1]),
+            (672, [2, 1, 1]),
+            (680, [1, 1, 1]),
+        ];
+        assert_eq!(copy_info.regions.len(), expected.len());
+
+        for (mip_level, (region, (buffer_offset, image_extent))) in
+            copy_info.regions.iter().zip(expected).enumerate()
+        {
+            assert_eq!(region.image_subresource.mip_level, mip_level as u32);
+            assert_eq!(region.image_subresource.aspects, ImageAspects::COLOR);
+            assert_eq!(region.buffer_offset, buffer_offset);
+            assert_eq!(region.image_extent, image_extent);
+        }
+    }
+
+    #[test]
+    fn buffer_copy_size_extreme_extent() {
+        let region = |buffer_row_length, image_extent| BufferImageCopy {
+            buffer_row_length,
+            image_subresource: ImageSubresourceLayers {
+                aspects: ImageAspects::COLOR,
+                mip_level: 0,
+                array_layers: 0..1,
+            },
+            image_extent,
+            ..Default::default()
+        };
+
+        // Rounding up to whole blocks must not overflow `u32`.
+        assert_eq!(
+            region(u32::MAX, [4, 4, 1]).buffer_copy_size(Format::BC1_RGB_UNORM_BLOCK),
+            8
+        );
+        assert_eq!(
+            region(0, [u32::MAX, 1, 1]).buffer_copy_size(Format::BC1_RGB_UNORM_BLOCK),
+            (u32::MAX as DeviceSize).div_ceil(4) * 8
+        );
+
+        // A size that doesn't fit in `DeviceSize` saturates, so that it is always out of range.
+        assert_eq!(
+            region(0, [u32::MAX; 3]).buffer_copy_size(Format::R32G32B32A32_SFLOAT),
+            DeviceSize::MAX
+        );
+        assert_eq!(
+            region(u32::MAX, [1, 2, 1]).buffer_copy_size(Format::R8_UNORM),
+            u32::MAX as DeviceSize + 1
+        );
+    }
 }
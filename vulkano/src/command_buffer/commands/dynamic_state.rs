@@ -49,9 +49,12 @@ where
             })
         {
             return Err(Box::new(ValidationError {
-                problem: "the state for this value in the currently bound graphics pipeline \
-                    is fixed, and cannot be set"
-                    .into(),
+                problem: format!(
+                    "the currently bound graphics pipeline does not have \
+                    `DynamicState::{:?}` enabled, so this state is fixed and cannot be set",
+                    state,
+                )
+                .into(),
                 vuids: &["VUID-vkCmdDispatch-None-08608", "VUID-vkCmdDraw-None-08608"],
                 ..Default::default()
             }));
@@ -745,6 +748,12 @@ where
     }
 
     /// Sets the dynamic scissors for future draw calls.
+    ///
+    /// Only the scissors starting at `first_scissor` are replaced, any others that were set
+    /// previously are kept. To replace all scissors at once, use
+    /// [`set_scissor_all`](Self::set_scissor_all). If the currently bound graphics pipeline has
+    /// a dynamic scissor count, use [`set_scissor_with_count`](Self::set_scissor_with_count)
+    /// instead.
     pub fn set_scissor(
         &mut self,
         first_scissor: u32,
@@ -791,7 +800,41 @@ where
         self
     }
 
+    /// Sets all dynamic scissors for future draw calls, starting at index 0.
+    ///
+    /// This replaces any scissors that were set previously with
+    /// [`set_scissor`](Self::set_scissor). When drawing, `scissors.len()` must be equal to the
+    /// fixed scissor count of the bound graphics pipeline. This command does not set the
+    /// scissor count itself; if the pipeline has a dynamic scissor count, use
+    /// [`set_scissor_with_count`](Self::set_scissor_with_count) instead.
+    pub fn set_scissor_all(
+        &mut self,
+        scissors: SmallVec<[Scissor; 2]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_scissor_all(&scissors)?;
+
+        unsafe { Ok(self.set_scissor_all_unchecked(scissors)) }
+    }
+
+    fn validate_set_scissor_all(&self, scissors: &[Scissor]) -> Result<(), Box<ValidationError>> {
+        self.validate_set_scissor(0, scissors)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_scissor_all_unchecked(
+        &mut self,
+        scissors: SmallVec<[Scissor; 2]>,
+    ) -> &mut Self {
+        self.builder_state.scissor.clear();
+        self.set_scissor_unchecked(0, scissors)
+    }
+
     /// Sets the dynamic scissors with count for future draw calls.
+    ///
+    /// Unlike [`set_scissor_all`](Self::set_scissor_all), this also sets the scissor count, and
+    /// can only be used if the currently bound graphics pipeline has a dynamic scissor count.
     pub fn set_scissor_with_count(
         &mut self,
         scissors: SmallVec<[Scissor; 2]>,
@@ -1087,6 +1130,12 @@ where
     }
 
     /// Sets the dynamic viewports for future draw calls.
+    ///
+    /// Only the viewports starting at `first_viewport` are replaced, any others that were set
+    /// previously are kept. To replace all viewports at once, use
+    /// [`set_viewport_all`](Self::set_viewport_all). If the currently bound graphics pipeline has
+    /// a dynamic viewport count, use [`set_viewport_with_count`](Self::set_viewport_with_count)
+    /// instead.
     pub fn set_viewport(
         &mut self,
         first_viewport: u32,
@@ -1132,7 +1181,44 @@ where
         self
     }
 
+    /// Sets all dynamic viewports for future draw calls, starting at index 0.
+    ///
+    /// This replaces any viewports that were set previously with
+    /// [`set_viewport`](Self::set_viewport). When drawing, `viewports.len()` must be equal to the
+    /// fixed viewport count of the bound graphics pipeline. This command does not set the
+    /// viewport count itself; if the pipeline has a dynamic viewport count, use
+    /// [`set_viewport_with_count`](Self::set_viewport_with_count) instead.
+    pub fn set_viewport_all(
+        &mut self,
+        viewports: SmallVec<[Viewport; 2]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_viewport_all(&viewports)?;
+
+        unsafe { Ok(self.set_viewport_all_unchecked(viewports)) }
+    }
+
+    fn validate_set_viewport_all(
+        &self,
+        viewports: &[Viewport],
+    ) -> Result<(), Box<ValidationError>> {
+        self.validate_set_viewport(0, viewports)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_viewport_all_unchecked(
+        &mut self,
+        viewports: SmallVec<[Viewport; 2]>,
+    ) -> &mut Self {
+        self.builder_state.viewport.clear();
+        self.set_viewport_unchecked(0, viewports)
+    }
+
     /// Sets the dynamic viewports with count for future draw calls.
+    ///
+    /// Unlike [`set_viewport_all`](Self::set_viewport_all), this also sets the viewport count, and
+    /// can only be used if the currently bound graphics pipeline has a dynamic viewport count.
     pub fn set_viewport_with_count(
         &mut self,
         viewports: SmallVec<[Viewport; 2]>,
@@ -2931,6 +3017,12 @@ where
             }
         }
 
+        for (index, viewport) in viewports.iter().enumerate() {
+            viewport
+                .validate(self.device())
+                .map_err(|err| err.add_context(format!("viewports[{}]", index)))?;
+        }
+
         Ok(())
     }
 
@@ -3020,6 +3112,12 @@ where
             }));
         }
 
+        for (index, viewport) in viewports.iter().enumerate() {
+            viewport
+                .validate(self.device())
+                .map_err(|err| err.add_context(format!("viewports[{}]", index)))?;
+        }
+
         Ok(())
     }
 
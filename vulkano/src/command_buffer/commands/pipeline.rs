@@ -13,9 +13,13 @@ use crate::{
     command_buffer::{
         allocator::CommandBufferAllocator,
         auto::{RenderPassState, RenderPassStateType, Resource, ResourceUseRef2},
+        generated_commands::{
+            GeneratedCommandsInfo, IndirectCommandsLayoutUsageFlags, IndirectCommandsTokenType,
+        },
         sys::UnsafeCommandBufferBuilder,
         AutoCommandBufferBuilder, DispatchIndirectCommand, DrawIndexedIndirectCommand,
-        DrawIndirectCommand, ResourceInCommand, SubpassContents,
+        DrawIndirectCommand, MultiDrawIndexedInfo, MultiDrawInfo, ResourceInCommand,
+        SubpassContents,
     },
     descriptor_set::{
         layout::DescriptorType, DescriptorBindingResources, DescriptorBufferInfo,
@@ -29,13 +33,14 @@ use crate::{
             input_assembly::PrimitiveTopology, subpass::PipelineSubpassType,
             vertex_input::VertexInputRate,
         },
-        DynamicState, GraphicsPipeline, PartialStateMode, Pipeline, PipelineLayout,
+        DynamicState, GraphicsPipeline, PartialStateMode, Pipeline, PipelineLayout, StateMode,
     },
     shader::{DescriptorBindingRequirements, DescriptorIdentifier, ShaderStage, ShaderStages},
     sync::{PipelineStageAccess, PipelineStageAccessFlags},
-    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanObject,
 };
-use std::{mem::size_of, sync::Arc};
+use smallvec::SmallVec;
+use std::{mem::size_of, ptr, sync::Arc};
 
 macro_rules! vuids {
     ($vuid_type:ident, $($id:literal),+ $(,)?) => {
@@ -46,6 +51,9 @@ macro_rules! vuids {
             VUIDType::DrawIndirect => &[$(concat!("VUID-vkCmdDrawIndirect-", $id)),+],
             VUIDType::DrawIndexed => &[$(concat!("VUID-vkCmdDrawIndexed-", $id)),+],
             VUIDType::DrawIndexedIndirect => &[$(concat!("VUID-vkCmdDrawIndexedIndirect-", $id)),+],
+            VUIDType::DrawMulti => &[$(concat!("VUID-vkCmdDrawMultiEXT-", $id)),+],
+            VUIDType::DrawMultiIndexed => &[$(concat!("VUID-vkCmdDrawMultiIndexedEXT-", $id)),+],
+            VUIDType::ExecuteGeneratedCommands => &[$(concat!("VUID-vkCmdExecuteGeneratedCommandsNV-", $id)),+],
         }
     };
 }
@@ -120,12 +128,18 @@ where
         self
     }
 
-    /// Perform multiple compute operations using a compute pipeline. One dispatch is performed for
-    /// each [`DispatchIndirectCommand`] struct in `indirect_buffer`.
+    /// Perform a single compute operation using a compute pipeline, with the group counts read
+    /// from the first [`DispatchIndirectCommand`] struct in `indirect_buffer`.
     ///
     /// A compute pipeline must have been bound using
     /// [`bind_pipeline_compute`](Self::bind_pipeline_compute). Any resources used by the compute
     /// pipeline, such as descriptor sets, must have been set beforehand.
+    ///
+    /// The group counts are only known when the command is executed, so unlike with
+    /// [`dispatch`](Self::dispatch), they can't be checked against the
+    /// [`max_compute_work_group_count`](crate::device::Properties::max_compute_work_group_count)
+    /// limit. Ensuring that they don't exceed it is the responsibility of whatever writes the
+    /// indirect buffer.
     pub fn dispatch_indirect(
         &mut self,
         indirect_buffer: Subbuffer<[DispatchIndirectCommand]>,
@@ -184,7 +198,7 @@ where
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
 
         self.add_command(
-            "dispatch",
+            "dispatch_indirect",
             used_resources,
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
                 out.dispatch_indirect_unchecked(&indirect_buffer);
@@ -256,6 +270,7 @@ where
         self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
         self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
         self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
 
         let view_mask = match pipeline.subpass() {
             PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
@@ -433,6 +448,7 @@ where
         self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
         self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
         self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
 
         Ok(())
     }
@@ -561,6 +577,7 @@ where
         self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
         self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
         self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
 
         let index_buffer =
             self.builder_state
@@ -763,6 +780,7 @@ where
         self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
         self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
         self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
 
         let _index_buffer =
             self.builder_state
@@ -814,102 +832,818 @@ where
         self
     }
 
-    fn validate_pipeline_descriptor_sets<Pl: Pipeline>(
+    /// Perform multiple draw operations using a graphics pipeline, with the parameters of each
+    /// draw provided from the host.
+    ///
+    /// One draw is performed for each element of `draw_infos`, with the vertex range given by the
+    /// element, and the instance range given by `instance_count` and `first_instance`. This
+    /// has the same effect as calling [`draw`](Self::draw) once for each element, but is only a
+    /// single command. The number of draws is limited by the
+    /// [`max_multi_draw_count`](crate::device::Properties::max_multi_draw_count) limit.
+    ///
+    /// The [`multi_draw`](crate::device::Features::multi_draw) feature must be enabled on the
+    /// device.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided vertex and
+    /// instance ranges must be in range of the bound vertex buffers.
+    pub fn draw_multi(
+        &mut self,
+        draw_infos: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi(draw_infos, instance_count, first_instance)?;
+
+        unsafe { Ok(self.draw_multi_unchecked(draw_infos, instance_count, first_instance)) }
+    }
+
+    fn validate_draw_multi(
         &self,
-        vuid_type: VUIDType,
-        pipeline: &Pl,
+        draw_infos: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
     ) -> Result<(), Box<ValidationError>> {
-        fn validate_resources<T>(
-            vuid_type: VUIDType,
-            set_num: u32,
-            binding_num: u32,
-            binding_reqs: &DescriptorBindingRequirements,
-            elements: &[Option<T>],
-            mut extra_check: impl FnMut(u32, u32, u32, &T) -> Result<(), Box<ValidationError>>,
-        ) -> Result<(), Box<ValidationError>> {
-            let elements_to_check = if let Some(descriptor_count) = binding_reqs.descriptor_count {
-                // The shader has a fixed-sized array, so it will never access more than
-                // the first `descriptor_count` elements.
-                elements.get(..descriptor_count as usize).ok_or({
-                    // There are less than `descriptor_count` elements in `elements`
-                    Box::new(ValidationError {
-                        problem: format!(
-                            "the currently bound pipeline accesses the resource bound to \
-                            descriptor set {set_num}, binding {binding_num}, \
-                            descriptor index {}, but no descriptor was written to the \
-                            descriptor set currently bound to set {set_num}",
-                            elements.len()
-                        )
+        self.inner
+            .validate_draw_multi(draw_infos, instance_count, first_instance)?;
+
+        let render_pass_state =
+            self.builder_state
+                .render_pass
+                .as_ref()
+                .ok_or(Box::new(ValidationError {
+                    problem: "a render pass instance is not active".into(),
+                    vuids: &["VUID-vkCmdDrawMultiEXT-renderpass"],
+                    ..Default::default()
+                }))?;
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(Box::new(ValidationError {
+                problem: "no graphics pipeline is currently bound".into(),
+                vuids: &["VUID-vkCmdDrawMultiEXT-None-08606"],
+                ..Default::default()
+            }))?
+            .as_ref();
+
+        const VUID_TYPE: VUIDType = VUIDType::DrawMulti;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
+
+        let view_mask = match pipeline.subpass() {
+            PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
+            PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+        };
+
+        if view_mask != 0 {
+            let properties = self.device().physical_device().properties();
+
+            if (first_instance + instance_count).saturating_sub(1)
+                > properties.max_multiview_instance_index.unwrap_or(0)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the current render pass instance has a nonzero view mask, but \
+                        `first_instance + instance_count - 1` is greater than the \
+                        `max_multiview_instance_index` limit"
                         .into(),
-                        vuids: vuids!(vuid_type, "None-02699"),
-                        ..Default::default()
-                    })
-                })?
-            } else {
-                // The shader has a runtime-sized array, so any element could potentially
-                // be accessed. We must check them all.
-                elements
-            };
+                    vuids: &["VUID-vkCmdDrawMultiEXT-maxMultiviewInstanceIndex-02688"],
+                    ..Default::default()
+                }));
+            }
+        }
 
-            for (index, element) in elements_to_check.iter().enumerate() {
-                let index = index as u32;
+        for (&binding_num, binding_desc) in &pipeline.vertex_input_state().bindings {
+            let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
 
-                let element = match element {
-                    Some(x) => x,
-                    None => {
+            // Per spec:
+            // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap22.html#fxvertex-input-address-calculation
+            match binding_desc.input_rate {
+                VertexInputRate::Vertex => {
+                    for (index, draw_info) in draw_infos.iter().enumerate() {
+                        let &MultiDrawInfo {
+                            first_vertex,
+                            vertex_count,
+                        } = draw_info;
+
+                        let max_vertex_offset = (first_vertex as DeviceSize
+                            + vertex_count as DeviceSize)
+                            * binding_desc.stride as DeviceSize;
+
+                        if max_vertex_offset > vertex_buffer.size() {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the size of the vertex buffer bound to binding {} is less \
+                                    than the minimum size required, for the \
+                                    `draw_infos[{}].first_vertex` and \
+                                    `draw_infos[{1}].vertex_count` values, and the vertex \
+                                    input state bindings of the currently bound graphics \
+                                    pipeline",
+                                    binding_num, index,
+                                )
+                                .into(),
+                                vuids: &["VUID-vkCmdDrawMultiEXT-None-02721"],
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+                VertexInputRate::Instance { divisor } => {
+                    let max_vertex_offset = if divisor == 0 {
+                        (first_instance as DeviceSize + 1) * binding_desc.stride as DeviceSize
+                    } else {
+                        (first_instance as DeviceSize
+                            + instance_count as DeviceSize / divisor as DeviceSize)
+                            * binding_desc.stride as DeviceSize
+                    };
+
+                    if max_vertex_offset > vertex_buffer.size() {
                         return Err(Box::new(ValidationError {
                             problem: format!(
-                                "the currently bound pipeline accesses the resource bound to \
-                                descriptor set {set_num}, binding {binding_num}, \
-                                descriptor index {index}, but no descriptor was written to the \
-                                descriptor set currently bound to set {set_num}"
+                                "the size of the vertex buffer bound to binding {} is less \
+                                than the minimum size required, for the provided \
+                                `first_instance` and `instance_count` values, and the vertex \
+                                input state bindings of the currently bound graphics pipeline",
+                                binding_num
                             )
                             .into(),
-                            vuids: vuids!(vuid_type, "None-02699"),
+                            vuids: &["VUID-vkCmdDrawMultiEXT-None-02721"],
                             ..Default::default()
                         }));
                     }
-                };
+                }
+            };
+        }
 
-                extra_check(set_num, binding_num, index, element)?;
-            }
+        Ok(())
+    }
 
-            Ok(())
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_unchecked(
+        &mut self,
+        draw_infos: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
         }
 
-        if pipeline.num_used_descriptor_sets() == 0 {
-            return Ok(());
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut used_resources = Vec::new();
+        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+
+        let draw_infos = draw_infos.to_vec();
+
+        self.add_command(
+            "draw_multi",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_multi_unchecked(&draw_infos, instance_count, first_instance);
+            },
+        );
+
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer, with
+    /// the parameters of each draw provided from the host.
+    ///
+    /// One draw is performed for each element of `draw_infos`, with the index range and vertex
+    /// offset given by the element, and the instance range given by `instance_count` and
+    /// `first_instance`. If `vertex_offset` is `Some`, then it is used as the vertex offset of
+    /// every draw instead, and the `vertex_offset` members of `draw_infos` are ignored. This has
+    /// the same effect as calling [`draw_indexed`](Self::draw_indexed) once for each element, but
+    /// is only a single command. The number of draws is limited by the
+    /// [`max_multi_draw_count`](crate::device::Properties::max_multi_draw_count) limit.
+    ///
+    /// The [`multi_draw`](crate::device::Features::multi_draw) feature must be enabled on the
+    /// device.
+    ///
+    /// An index buffer must have been bound using
+    /// [`bind_index_buffer`](Self::bind_index_buffer), and the provided index ranges must be in
+    /// range of the bound index buffer.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided instance
+    /// range must be in range of the bound vertex buffers. The vertex indices in the index buffer
+    /// must be in range of the bound vertex buffers.
+    pub fn draw_multi_indexed(
+        &mut self,
+        draw_infos: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi_indexed(
+            draw_infos,
+            instance_count,
+            first_instance,
+            vertex_offset,
+        )?;
+
+        unsafe {
+            Ok(self.draw_multi_indexed_unchecked(
+                draw_infos,
+                instance_count,
+                first_instance,
+                vertex_offset,
+            ))
         }
+    }
 
-        let descriptor_set_state = self
+    fn validate_draw_multi_indexed(
+        &self,
+        draw_infos: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_draw_multi_indexed(
+            draw_infos,
+            instance_count,
+            first_instance,
+            vertex_offset,
+        )?;
+
+        let render_pass_state =
+            self.builder_state
+                .render_pass
+                .as_ref()
+                .ok_or(Box::new(ValidationError {
+                    problem: "a render pass instance is not active".into(),
+                    vuids: &["VUID-vkCmdDrawMultiIndexedEXT-renderpass"],
+                    ..Default::default()
+                }))?;
+
+        let pipeline = self
             .builder_state
-            .descriptor_sets
-            .get(&pipeline.bind_point())
+            .pipeline_graphics
+            .as_ref()
             .ok_or(Box::new(ValidationError {
-                problem: "the currently bound pipeline accesses descriptor sets, but no \
-                    descriptor sets were previously bound"
-                    .into(),
-                vuids: vuids!(vuid_type, "None-02697"),
+                problem: "no graphics pipeline is currently bound".into(),
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-None-08606"],
                 ..Default::default()
-            }))?;
+            }))?
+            .as_ref();
 
-        if !pipeline.layout().is_compatible_with(
-            &descriptor_set_state.pipeline_layout,
-            pipeline.num_used_descriptor_sets(),
-        ) {
-            return Err(Box::new(ValidationError {
-                problem: "the currently bound pipeline accesses descriptor sets, but the \
-                    pipeline layouts that were used to bind the descriptor sets are \
-                    not compatible with the pipeline layout of the currently bound pipeline"
-                    .into(),
-                vuids: vuids!(vuid_type, "None-02697"),
-                ..Default::default()
-            }));
-        }
+        const VUID_TYPE: VUIDType = VUIDType::DrawMultiIndexed;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
 
-        for (&(set_num, binding_num), binding_reqs) in pipeline.descriptor_binding_requirements() {
-            let layout_binding =
-                &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
+        let index_buffer =
+            self.builder_state
+                .index_buffer
+                .as_ref()
+                .ok_or(Box::new(ValidationError {
+                    problem: "no index buffer is currently bound".into(),
+                    vuids: &["VUID-vkCmdDrawMultiIndexedEXT-None-07312"],
+                    ..Default::default()
+                }))?;
+
+        let index_buffer_bytes = index_buffer.as_bytes();
+
+        if !self.device().enabled_features().robust_buffer_access2 {
+            for (index, draw_info) in draw_infos.iter().enumerate() {
+                let &MultiDrawIndexedInfo {
+                    first_index,
+                    index_count,
+                    vertex_offset: _,
+                } = draw_info;
+
+                if index_buffer.index_type().size()
+                    * (first_index as DeviceSize + index_count as DeviceSize)
+                    > index_buffer_bytes.size()
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`draw_infos[{0}].first_index + draw_infos[{0}].index_count`, \
+                            multiplied by the size of the indices in the bound index buffer, \
+                            is greater than the size of the bound index buffer",
+                            index,
+                        )
+                        .into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "robust_buffer_access2",
+                        )])]),
+                        vuids: &["VUID-vkCmdDrawMultiIndexedEXT-robustBufferAccess2-07825"],
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
+        let view_mask = match pipeline.subpass() {
+            PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
+            PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+        };
+
+        if view_mask != 0 {
+            let properties = self.device().physical_device().properties();
+
+            if (first_instance + instance_count).saturating_sub(1)
+                > properties.max_multiview_instance_index.unwrap_or(0)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the current render pass instance has a nonzero view mask, but \
+                        `first_instance + instance_count - 1` is greater than the \
+                        `max_multiview_instance_index` limit"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawMultiIndexedEXT-maxMultiviewInstanceIndex-02688"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        for (&binding_num, binding_desc) in &pipeline.vertex_input_state().bindings {
+            let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
+
+            // Per spec:
+            // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap22.html#fxvertex-input-address-calculation
+            match binding_desc.input_rate {
+                VertexInputRate::Vertex => (),
+                VertexInputRate::Instance { divisor } => {
+                    let max_vertex_offset = if divisor == 0 {
+                        (first_instance as DeviceSize + 1) * binding_desc.stride as DeviceSize
+                    } else {
+                        (first_instance as DeviceSize
+                            + instance_count as DeviceSize / divisor as DeviceSize)
+                            * binding_desc.stride as DeviceSize
+                    };
+
+                    if max_vertex_offset > vertex_buffer.size() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the size of the vertex buffer bound to binding {} is less \
+                                than the minimum size required, for the provided \
+                                `first_instance` and `instance_count` values, and the vertex \
+                                input state bindings of the currently bound graphics pipeline",
+                                binding_num
+                            )
+                            .into(),
+                            vuids: &["VUID-vkCmdDrawMultiIndexedEXT-None-02721"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_indexed_unchecked(
+        &mut self,
+        draw_infos: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut used_resources = Vec::new();
+        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_index_buffer_resources(&mut used_resources);
+
+        let draw_infos = draw_infos.to_vec();
+
+        self.add_command(
+            "draw_multi_indexed",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_multi_indexed_unchecked(
+                    &draw_infos,
+                    instance_count,
+                    first_instance,
+                    vertex_offset,
+                );
+            },
+        );
+
+        self
+    }
+
+    /// Executes commands that are generated on the device, using a graphics pipeline.
+    ///
+    /// The commands are read from `info.streams`, according to the layout given by
+    /// `info.indirect_commands_layout`. See
+    /// [the documentation of the module](crate::command_buffer::generated_commands) for more
+    /// info.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics), and it must equal
+    /// `info.pipeline`. Any resources used by the graphics pipeline that are not set by the
+    /// tokens of the layout, such as descriptor sets, vertex buffers and dynamic state, must have
+    /// been set beforehand.
+    ///
+    /// If `is_preprocessed` is `true`, the commands must have been preprocessed with
+    /// [`preprocess_generated_commands`](Self::preprocess_generated_commands) first.
+    ///
+    /// # Safety
+    ///
+    /// - The contents of the streams must be valid for the tokens of
+    ///   `info.indirect_commands_layout`. Buffer addresses in the streams must refer to buffers
+    ///   that are in range and have the appropriate usage, and the draw commands must be in range
+    ///   of the bound or generated vertex and index buffers.
+    /// - If `info.sequences_index_buffer` is `Some`, then its values must be less than
+    ///   `info.sequences_count`.
+    /// - If `is_preprocessed` is `true`, then `preprocess_generated_commands` must have been
+    ///   executed with the same `info`, and the contents of the buffers in `info` must not have
+    ///   been modified since then.
+    pub unsafe fn execute_generated_commands(
+        &mut self,
+        is_preprocessed: bool,
+        info: GeneratedCommandsInfo,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_execute_generated_commands(is_preprocessed, &info)?;
+
+        Ok(self.execute_generated_commands_unchecked(is_preprocessed, info))
+    }
+
+    fn validate_execute_generated_commands(
+        &self,
+        is_preprocessed: bool,
+        info: &GeneratedCommandsInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_execute_generated_commands(is_preprocessed, info)?;
+
+        let render_pass_state =
+            self.builder_state
+                .render_pass
+                .as_ref()
+                .ok_or(Box::new(ValidationError {
+                    problem: "a render pass instance is not active".into(),
+                    vuids: &["VUID-vkCmdExecuteGeneratedCommandsNV-renderpass"],
+                    ..Default::default()
+                }))?;
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or(Box::new(ValidationError {
+                problem: "no graphics pipeline is currently bound".into(),
+                vuids: &["VUID-vkCmdExecuteGeneratedCommandsNV-None-08606"],
+                ..Default::default()
+            }))?
+            .as_ref();
+
+        if pipeline != info.pipeline.as_ref() {
+            return Err(Box::new(ValidationError {
+                context: "info.pipeline".into(),
+                problem: "is not the currently bound graphics pipeline".into(),
+                vuids: &["VUID-VkGeneratedCommandsInfoNV-pipeline-02912"],
+                ..Default::default()
+            }));
+        }
+
+        let tokens = info.indirect_commands_layout.tokens();
+        let has_token = |f: fn(&IndirectCommandsTokenType) -> bool| {
+            tokens.iter().any(|token| f(&token.token_type))
+        };
+
+        const VUID_TYPE: VUIDType = VUIDType::ExecuteGeneratedCommands;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+
+        if !has_token(|t| matches!(t, IndirectCommandsTokenType::PushConstant { .. })) {
+            self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+        }
+
+        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+
+        if !has_token(|t| matches!(t, IndirectCommandsTokenType::VertexBuffer { .. })) {
+            self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+        }
+
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
+
+        if has_token(|t| matches!(t, IndirectCommandsTokenType::DrawIndexed))
+            && !has_token(|t| matches!(t, IndirectCommandsTokenType::IndexBuffer { .. }))
+            && self.builder_state.index_buffer.is_none()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`info.indirect_commands_layout` contains an \
+                    `IndirectCommandsTokenType::DrawIndexed` token but no \
+                    `IndirectCommandsTokenType::IndexBuffer` token, and \
+                    no index buffer is currently bound"
+                    .into(),
+                vuids: vuids!(VUID_TYPE, "None-07312"),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn execute_generated_commands_unchecked(
+        &mut self,
+        is_preprocessed: bool,
+        info: GeneratedCommandsInfo,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let tokens = info.indirect_commands_layout.tokens();
+        let has_token = |f: fn(&IndirectCommandsTokenType) -> bool| {
+            tokens.iter().any(|token| f(&token.token_type))
+        };
+
+        let mut used_resources = Vec::new();
+        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+
+        if !has_token(|t| matches!(t, IndirectCommandsTokenType::VertexBuffer { .. })) {
+            self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        }
+
+        if has_token(|t| matches!(t, IndirectCommandsTokenType::DrawIndexed))
+            && !has_token(|t| matches!(t, IndirectCommandsTokenType::IndexBuffer { .. }))
+        {
+            self.add_index_buffer_resources(&mut used_resources);
+        }
+
+        self.add_generated_commands_resources(
+            &mut used_resources,
+            &info,
+            PipelineStageAccessFlags::DrawIndirect_IndirectCommandRead,
+            PipelineStageAccessFlags::DrawIndirect_IndirectCommandRead,
+        );
+
+        self.add_command(
+            "execute_generated_commands",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.execute_generated_commands_unchecked(is_preprocessed, &info);
+            },
+        );
+
+        self
+    }
+
+    /// Preprocesses commands that are generated on the device, so that they can later be
+    /// executed with [`execute_generated_commands`](Self::execute_generated_commands) with
+    /// `is_preprocessed` set to `true`.
+    ///
+    /// The layout in `info.indirect_commands_layout` must have been created with
+    /// [`IndirectCommandsLayoutUsageFlags::EXPLICIT_PREPROCESS`]. The state that is set by the
+    /// tokens of the layout must not be changed between preprocessing and execution.
+    ///
+    /// # Safety
+    ///
+    /// - The contents of the streams must be valid for the tokens of
+    ///   `info.indirect_commands_layout`.
+    /// - If `info.sequences_index_buffer` is `Some`, then its values must be less than
+    ///   `info.sequences_count`.
+    pub unsafe fn preprocess_generated_commands(
+        &mut self,
+        info: GeneratedCommandsInfo,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_preprocess_generated_commands(&info)?;
+
+        Ok(self.preprocess_generated_commands_unchecked(info))
+    }
+
+    fn validate_preprocess_generated_commands(
+        &self,
+        info: &GeneratedCommandsInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_preprocess_generated_commands(info)?;
+
+        if self.builder_state.render_pass.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is active".into(),
+                vuids: &["VUID-vkCmdPreprocessGeneratedCommandsNV-renderpass"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn preprocess_generated_commands_unchecked(
+        &mut self,
+        info: GeneratedCommandsInfo,
+    ) -> &mut Self {
+        let mut used_resources = Vec::new();
+        self.add_generated_commands_resources(
+            &mut used_resources,
+            &info,
+            PipelineStageAccessFlags::CommandPreprocess_CommandPreprocessRead,
+            PipelineStageAccessFlags::CommandPreprocess_CommandPreprocessWrite,
+        );
+
+        self.add_command(
+            "preprocess_generated_commands",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.preprocess_generated_commands_unchecked(&info);
+            },
+        );
+
+        self
+    }
+
+    fn validate_pipeline_descriptor_sets<Pl: Pipeline>(
+        &self,
+        vuid_type: VUIDType,
+        pipeline: &Pl,
+    ) -> Result<(), Box<ValidationError>> {
+        fn validate_resources<T>(
+            vuid_type: VUIDType,
+            set_num: u32,
+            binding_num: u32,
+            binding_reqs: &DescriptorBindingRequirements,
+            elements: &[Option<T>],
+            mut extra_check: impl FnMut(u32, u32, u32, &T) -> Result<(), Box<ValidationError>>,
+        ) -> Result<(), Box<ValidationError>> {
+            let elements_to_check = if let Some(descriptor_count) = binding_reqs.descriptor_count {
+                // The shader has a fixed-sized array, so it will never access more than
+                // the first `descriptor_count` elements.
+                elements.get(..descriptor_count as usize).ok_or({
+                    // There are less than `descriptor_count` elements in `elements`
+                    Box::new(ValidationError {
+                        problem: format!(
+                            "the currently bound pipeline accesses the resource bound to \
+                            descriptor set {set_num}, binding {binding_num}, \
+                            descriptor index {}, but no descriptor was written to the \
+                            descriptor set currently bound to set {set_num}",
+                            elements.len()
+                        )
+                        .into(),
+                        vuids: vuids!(vuid_type, "None-02699"),
+                        ..Default::default()
+                    })
+                })?
+            } else {
+                // The shader has a runtime-sized array, so any element could potentially
+                // be accessed. We must check them all.
+                elements
+            };
+
+            for (index, element) in elements_to_check.iter().enumerate() {
+                let index = index as u32;
+
+                let element = match element {
+                    Some(x) => x,
+                    None => {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound pipeline accesses the resource bound to \
+                                descriptor set {set_num}, binding {binding_num}, \
+                                descriptor index {index}, but no descriptor was written to the \
+                                descriptor set currently bound to set {set_num}"
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-02699"),
+                            ..Default::default()
+                        }));
+                    }
+                };
+
+                extra_check(set_num, binding_num, index, element)?;
+            }
+
+            Ok(())
+        }
+
+        if pipeline.num_used_descriptor_sets() == 0 {
+            return Ok(());
+        }
+
+        let descriptor_set_state = self
+            .builder_state
+            .descriptor_sets
+            .get(&pipeline.bind_point())
+            .ok_or(Box::new(ValidationError {
+                problem: "the currently bound pipeline accesses descriptor sets, but no \
+                    descriptor sets were previously bound"
+                    .into(),
+                vuids: vuids!(vuid_type, "None-02697"),
+                ..Default::default()
+            }))?;
+
+        let pipeline_layout = pipeline.layout();
+        let bound_pipeline_layout = &descriptor_set_state.pipeline_layout;
+
+        if pipeline_layout != bound_pipeline_layout {
+            if pipeline_layout.push_constant_ranges()
+                != bound_pipeline_layout.push_constant_ranges()
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the currently bound pipeline accesses descriptor sets, but the \
+                        push constant ranges of the pipeline layout that was used to bind the \
+                        descriptor sets are not equal to those of the pipeline layout of the \
+                        currently bound pipeline"
+                        .into(),
+                    vuids: vuids!(vuid_type, "None-02697"),
+                    ..Default::default()
+                }));
+            }
+
+            // The layouts must be compatible for every set up to the highest one that is used.
+            for set_num in 0..pipeline.num_used_descriptor_sets() {
+                let set_layout = &pipeline_layout.set_layouts()[set_num as usize];
+                let is_compatible = match bound_pipeline_layout.set_layouts().get(set_num as usize)
+                {
+                    Some(bound_set_layout) => set_layout.is_compatible_with(bound_set_layout),
+                    None => false,
+                };
+
+                if !is_compatible {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "the currently bound pipeline accesses descriptor sets, but the \
+                            pipeline layout that was used to bind the descriptor sets is not \
+                            compatible with the pipeline layout of the currently bound \
+                            pipeline for set {set_num}"
+                        )
+                        .into(),
+                        vuids: vuids!(vuid_type, "None-02697"),
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
+        // Without `khr_format_feature_flags2`, the `*_WITHOUT_FORMAT` format features are only
+        // implied by device features, so report those as requirements when they're not enabled.
+        let device = self.device();
+        let has_format_feature_flags2 = device.api_version() >= Version::V1_3
+            || device.enabled_extensions().khr_format_feature_flags2;
+        let read_without_format_requires_one_of = if !has_format_feature_flags2
+            && !device
+                .enabled_features()
+                .shader_storage_image_read_without_format
+        {
+            RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "shader_storage_image_read_without_format",
+            )])])
+        } else {
+            RequiresOneOf::default()
+        };
+        let write_without_format_requires_one_of = if !has_format_feature_flags2
+            && !device
+                .enabled_features()
+                .shader_storage_image_write_without_format
+        {
+            RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "shader_storage_image_write_without_format",
+            )])])
+        } else {
+            RequiresOneOf::default()
+        };
+
+        for (&(set_num, binding_num), binding_reqs) in pipeline.descriptor_binding_requirements() {
+            let layout_binding =
+                &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
 
             let check_buffer =
                 |_set_num: u32,
@@ -938,6 +1672,7 @@ where
                                         not contain `FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT`"
                                     )
                                     .into(),
+                                    requires_one_of: write_without_format_requires_one_of,
                                     vuids: vuids!(vuid_type, "OpTypeImage-06423"),
                                     ..Default::default()
                                 }));
@@ -958,6 +1693,7 @@ where
                                         not contain `FormatFeatures::STORAGE_READ_WITHOUT_FORMAT`"
                                     )
                                     .into(),
+                                    requires_one_of: read_without_format_requires_one_of,
                                     vuids: vuids!(vuid_type, "OpTypeImage-06424"),
                                     ..Default::default()
                                 }));
@@ -1008,6 +1744,7 @@ where
                                         not contain `FormatFeatures::STORAGE_WRITE_WITHOUT_FORMAT`"
                                     )
                                     .into(),
+                                    requires_one_of: write_without_format_requires_one_of,
                                     vuids: vuids!(vuid_type, "OpTypeImage-06423"),
                                     ..Default::default()
                                 }));
@@ -1028,6 +1765,7 @@ where
                                         not contain `FormatFeatures::STORAGE_READ_WITHOUT_FORMAT`"
                                     )
                                     .into(),
+                                    requires_one_of: read_without_format_requires_one_of,
                                     vuids: vuids!(vuid_type, "OpTypeImage-06424"),
                                     ..Default::default()
                                 }));
@@ -1048,10 +1786,13 @@ where
                                     "the currently bound pipeline accesses the image view \
                                     bound to descriptor set {set_num}, binding {binding_num}, \
                                     descriptor index {index}, but the format of the image view \
-                                    is not equal to the format required by the pipeline"
+                                    ({:?}) is not equal to the format required by the pipeline \
+                                    ({:?})",
+                                    image_view.format(),
+                                    format,
                                 )
                                 .into(),
-                                // vuids?
+                                vuids: vuids!(vuid_type, "format-07753"),
                                 ..Default::default()
                             }));
                         }
@@ -1065,10 +1806,13 @@ where
                                     "the currently bound pipeline accesses the image view \
                                     bound to descriptor set {set_num}, binding {binding_num}, \
                                     descriptor index {index}, but the view type of the image view \
-                                    is not equal to the view type required by the pipeline"
+                                    ({:?}) is not equal to the view type required by the \
+                                    pipeline ({:?})",
+                                    image_view.view_type(),
+                                    image_view_type,
                                 )
                                 .into(),
-                                // vuids?
+                                vuids: vuids!(vuid_type, "viewType-07752"),
                                 ..Default::default()
                             }));
                         }
@@ -2173,6 +2917,20 @@ where
                         }
                     }
 
+                    let swizzle_count = pipeline.viewport_state().unwrap().swizzles.len() as u32;
+
+                    if swizzle_count != 0 && viewport_count > swizzle_count {
+                        return Err(Box::new(ValidationError {
+                            problem: "the currently bound graphics pipeline requires the \
+                                `DynamicState::ViewportWithCount` dynamic state, and was created \
+                                with viewport swizzles, but the dynamic viewport count is \
+                                greater than the number of viewport swizzles"
+                                .into(),
+                            vuids: vuids!(vuid_type, "viewportCount-04139"),
+                            ..Default::default()
+                        }));
+                    }
+
                     // TODO: VUID-vkCmdDrawIndexed-primitiveFragmentShadingRateWithMultipleViewports-04552
                     // If the primitiveFragmentShadingRateWithMultipleViewports limit is not supported,
                     // the bound graphics pipeline was created with the
@@ -2397,27 +3155,76 @@ where
         Ok(())
     }
 
-    fn validate_pipeline_graphics_vertex_buffers(
+    fn validate_pipeline_graphics_vertex_buffers(
+        &self,
+        vuid_type: VUIDType,
+        pipeline: &GraphicsPipeline,
+    ) -> Result<(), Box<ValidationError>> {
+        let vertex_input = pipeline.vertex_input_state();
+
+        for &binding_num in vertex_input.bindings.keys() {
+            if !self.builder_state.vertex_buffers.contains_key(&binding_num) {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "the currently bound graphics pipeline uses \
+                        vertex buffer binding {0}, but \
+                        no vertex buffer is currently bound to binding {0}",
+                        binding_num
+                    )
+                    .into(),
+                    vuids: vuids!(vuid_type, "None-04007"),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_graphics_queries(
         &self,
         vuid_type: VUIDType,
         pipeline: &GraphicsPipeline,
     ) -> Result<(), Box<ValidationError>> {
-        let vertex_input = pipeline.vertex_input_state();
+        let device = pipeline.device();
 
-        for &binding_num in vertex_input.bindings.keys() {
-            if !self.builder_state.vertex_buffers.contains_key(&binding_num) {
+        if self
+            .builder_state
+            .queries
+            .contains_key(&ash::vk::QueryType::PRIMITIVES_GENERATED_EXT)
+        {
+            let rasterizer_discard_enable =
+                match pipeline.rasterization_state().rasterizer_discard_enable {
+                    StateMode::Fixed(enable) => enable,
+                    StateMode::Dynamic => self
+                        .builder_state
+                        .rasterizer_discard_enable
+                        .unwrap_or(false),
+                };
+
+            if rasterizer_discard_enable
+                && !device
+                    .enabled_features()
+                    .primitives_generated_query_with_rasterizer_discard
+            {
                 return Err(Box::new(ValidationError {
-                    problem: format!(
-                        "the currently bound graphics pipeline uses \
-                        vertex buffer binding {0}, but \
-                        no vertex buffer is currently bound to binding {0}",
-                        binding_num
-                    )
-                    .into(),
-                    vuids: vuids!(vuid_type, "None-04007"),
+                    problem: "a `QueryType::PrimitivesGenerated` query is active, and \
+                        rasterizer discard is enabled"
+                        .into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "primitives_generated_query_with_rasterizer_discard",
+                    )])]),
+                    vuids: vuids!(
+                        vuid_type,
+                        "primitivesGeneratedQueryWithRasterizerDiscard-06708"
+                    ),
                     ..Default::default()
                 }));
             }
+
+            // VUID-vkCmdDraw-primitivesGeneratedQueryWithNonZeroStreams-06709
+            // Ensured by graphics pipelines always using rasterization stream zero, because
+            // selecting another stream is not supported yet.
         }
 
         Ok(())
@@ -2678,6 +3485,72 @@ where
             },
         ));
     }
+
+    fn add_generated_commands_resources(
+        &self,
+        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
+        info: &GeneratedCommandsInfo,
+        input_memory_access: PipelineStageAccessFlags,
+        preprocess_memory_access: PipelineStageAccessFlags,
+    ) {
+        let &GeneratedCommandsInfo {
+            pipeline: _,
+            indirect_commands_layout: _,
+            ref streams,
+            sequences_count: _,
+            ref preprocess_buffer,
+            ref sequences_count_buffer,
+            ref sequences_index_buffer,
+            _ne: _,
+        } = info;
+
+        for (index, stream) in streams.iter().enumerate() {
+            used_resources.push((
+                ResourceInCommand::IndirectCommandsStream {
+                    index: index as u32,
+                }
+                .into(),
+                Resource::Buffer {
+                    buffer: stream.clone(),
+                    // `sequences_index_buffer` can make the sequences be read in any order, so
+                    // the whole stream is conservatively treated as used.
+                    range: 0..stream.size(),
+                    memory_access: input_memory_access,
+                },
+            ));
+        }
+
+        used_resources.push((
+            ResourceInCommand::PreprocessBuffer.into(),
+            Resource::Buffer {
+                buffer: preprocess_buffer.clone(),
+                range: 0..preprocess_buffer.size(),
+                memory_access: preprocess_memory_access,
+            },
+        ));
+
+        if let Some(sequences_count_buffer) = sequences_count_buffer {
+            used_resources.push((
+                ResourceInCommand::SequencesCountBuffer.into(),
+                Resource::Buffer {
+                    buffer: sequences_count_buffer.as_bytes().clone(),
+                    range: 0..sequences_count_buffer.size(),
+                    memory_access: input_memory_access,
+                },
+            ));
+        }
+
+        if let Some(sequences_index_buffer) = sequences_index_buffer {
+            used_resources.push((
+                ResourceInCommand::SequencesIndexBuffer.into(),
+                Resource::Buffer {
+                    buffer: sequences_index_buffer.as_bytes().clone(),
+                    range: 0..sequences_index_buffer.size(),
+                    memory_access: input_memory_access,
+                },
+            ));
+        }
+    }
 }
 
 impl<A> UnsafeCommandBufferBuilder<A>
@@ -2798,7 +3671,7 @@ where
 
         if size_of::<DispatchIndirectCommand>() as DeviceSize > indirect_buffer.size() {
             return Err(Box::new(ValidationError {
-                problem: "`size_of::<DrawIndirectCommand>()` is greater than \
+                problem: "`size_of::<DispatchIndirectCommand>()` is greater than \
                     `indirect_buffer.size()`"
                     .into(),
                 vuids: &["VUID-vkCmdDispatchIndirect-offset-00407"],
@@ -2806,8 +3679,14 @@ where
             }));
         }
 
-        // VUID-vkCmdDispatchIndirect-offset-02710
-        // TODO:
+        if !indirect_buffer.offset().is_multiple_of(4) {
+            return Err(Box::new(ValidationError {
+                context: "indirect_buffer".into(),
+                problem: "the offset of the subbuffer is not a multiple of 4".into(),
+                vuids: &["VUID-vkCmdDispatchIndirect-offset-02710"],
+                ..Default::default()
+            }));
+        }
 
         Ok(())
     }
@@ -3222,6 +4101,391 @@ where
 
         self
     }
+
+    pub unsafe fn draw_multi(
+        &mut self,
+        draw_infos: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi(draw_infos, instance_count, first_instance)?;
+
+        Ok(self.draw_multi_unchecked(draw_infos, instance_count, first_instance))
+    }
+
+    fn validate_draw_multi(
+        &self,
+        draw_infos: &[MultiDrawInfo],
+        _instance_count: u32,
+        _first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().multi_draw {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "multi_draw",
+                )])]),
+                vuids: &["VUID-vkCmdDrawMultiEXT-None-04933"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawMultiEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        if draw_infos.len() as u64 >= properties.max_multi_draw_count.unwrap_or(0) as u64 {
+            return Err(Box::new(ValidationError {
+                context: "draw_infos".into(),
+                problem: "the length is not less than the `max_multi_draw_count` limit".into(),
+                vuids: &["VUID-vkCmdDrawMultiEXT-drawCount-04934"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_unchecked(
+        &mut self,
+        draw_infos: &[MultiDrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_multi_draw.cmd_draw_multi_ext)(
+            self.handle(),
+            draw_infos.len() as u32,
+            draw_infos.as_ptr() as *const ash::vk::MultiDrawInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawInfo>() as u32,
+        );
+
+        self
+    }
+
+    pub unsafe fn draw_multi_indexed(
+        &mut self,
+        draw_infos: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi_indexed(
+            draw_infos,
+            instance_count,
+            first_instance,
+            vertex_offset,
+        )?;
+
+        Ok(self.draw_multi_indexed_unchecked(
+            draw_infos,
+            instance_count,
+            first_instance,
+            vertex_offset,
+        ))
+    }
+
+    fn validate_draw_multi_indexed(
+        &self,
+        draw_infos: &[MultiDrawIndexedInfo],
+        _instance_count: u32,
+        _first_instance: u32,
+        _vertex_offset: Option<i32>,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().multi_draw {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "multi_draw",
+                )])]),
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-None-04937"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        if draw_infos.len() as u64 >= properties.max_multi_draw_count.unwrap_or(0) as u64 {
+            return Err(Box::new(ValidationError {
+                context: "draw_infos".into(),
+                problem: "the length is not less than the `max_multi_draw_count` limit".into(),
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-drawCount-04939"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_indexed_unchecked(
+        &mut self,
+        draw_infos: &[MultiDrawIndexedInfo],
+        instance_count: u32,
+        first_instance: u32,
+        vertex_offset: Option<i32>,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_multi_draw.cmd_draw_multi_indexed_ext)(
+            self.handle(),
+            draw_infos.len() as u32,
+            draw_infos.as_ptr() as *const ash::vk::MultiDrawIndexedInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<MultiDrawIndexedInfo>() as u32,
+            vertex_offset
+                .as_ref()
+                .map_or(ptr::null(), |vertex_offset| vertex_offset),
+        );
+
+        self
+    }
+
+    pub unsafe fn execute_generated_commands(
+        &mut self,
+        is_preprocessed: bool,
+        info: &GeneratedCommandsInfo,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_execute_generated_commands(is_preprocessed, info)?;
+
+        Ok(self.execute_generated_commands_unchecked(is_preprocessed, info))
+    }
+
+    fn validate_execute_generated_commands(
+        &self,
+        _is_preprocessed: bool,
+        info: &GeneratedCommandsInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdExecuteGeneratedCommandsNV-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if !self.device().enabled_features().device_generated_commands {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "device_generated_commands",
+                )])]),
+                vuids: &["VUID-vkCmdExecuteGeneratedCommandsNV-deviceGeneratedCommands-02911"],
+                ..Default::default()
+            }));
+        }
+
+        info.validate(self.device())
+            .map_err(|err| err.add_context("info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn execute_generated_commands_unchecked(
+        &mut self,
+        is_preprocessed: bool,
+        info: &GeneratedCommandsInfo,
+    ) -> &mut Self {
+        let &GeneratedCommandsInfo {
+            ref pipeline,
+            ref indirect_commands_layout,
+            ref streams,
+            sequences_count,
+            ref preprocess_buffer,
+            ref sequences_count_buffer,
+            ref sequences_index_buffer,
+            _ne: _,
+        } = info;
+
+        let streams_vk: SmallVec<[_; 4]> = streams
+            .iter()
+            .map(|stream| ash::vk::IndirectCommandsStreamNV {
+                buffer: stream.buffer().handle(),
+                offset: stream.offset(),
+            })
+            .collect();
+
+        let info_vk = ash::vk::GeneratedCommandsInfoNV {
+            pipeline_bind_point: indirect_commands_layout.pipeline_bind_point().into(),
+            pipeline: pipeline.handle(),
+            indirect_commands_layout: indirect_commands_layout.handle(),
+            stream_count: streams_vk.len() as u32,
+            p_streams: streams_vk.as_ptr(),
+            sequences_count,
+            preprocess_buffer: preprocess_buffer.buffer().handle(),
+            preprocess_offset: preprocess_buffer.offset(),
+            preprocess_size: preprocess_buffer.size(),
+            sequences_count_buffer: sequences_count_buffer
+                .as_ref()
+                .map_or_else(Default::default, |buffer| buffer.buffer().handle()),
+            sequences_count_offset: sequences_count_buffer
+                .as_ref()
+                .map_or(0, |buffer| buffer.offset()),
+            sequences_index_buffer: sequences_index_buffer
+                .as_ref()
+                .map_or_else(Default::default, |buffer| buffer.buffer().handle()),
+            sequences_index_offset: sequences_index_buffer
+                .as_ref()
+                .map_or(0, |buffer| buffer.offset()),
+            ..Default::default()
+        };
+
+        let fns = self.device().fns();
+        (fns.nv_device_generated_commands
+            .cmd_execute_generated_commands_nv)(
+            self.handle(),
+            is_preprocessed as ash::vk::Bool32,
+            &info_vk,
+        );
+
+        self
+    }
+
+    pub unsafe fn preprocess_generated_commands(
+        &mut self,
+        info: &GeneratedCommandsInfo,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_preprocess_generated_commands(info)?;
+
+        Ok(self.preprocess_generated_commands_unchecked(info))
+    }
+
+    fn validate_preprocess_generated_commands(
+        &self,
+        info: &GeneratedCommandsInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdPreprocessGeneratedCommandsNV-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        if !self.device().enabled_features().device_generated_commands {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "device_generated_commands",
+                )])]),
+                vuids: &["VUID-vkCmdPreprocessGeneratedCommandsNV-deviceGeneratedCommands-02928"],
+                ..Default::default()
+            }));
+        }
+
+        info.validate(self.device())
+            .map_err(|err| err.add_context("info"))?;
+
+        if !info
+            .indirect_commands_layout
+            .flags()
+            .intersects(IndirectCommandsLayoutUsageFlags::EXPLICIT_PREPROCESS)
+        {
+            return Err(Box::new(ValidationError {
+                context: "info.indirect_commands_layout.flags()".into(),
+                problem: "does not contain `IndirectCommandsLayoutUsageFlags::EXPLICIT_PREPROCESS`"
+                    .into(),
+                vuids: &["VUID-vkCmdPreprocessGeneratedCommandsNV-pGeneratedCommandsInfo-02927"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn preprocess_generated_commands_unchecked(
+        &mut self,
+        info: &GeneratedCommandsInfo,
+    ) -> &mut Self {
+        let &GeneratedCommandsInfo {
+            ref pipeline,
+            ref indirect_commands_layout,
+            ref streams,
+            sequences_count,
+            ref preprocess_buffer,
+            ref sequences_count_buffer,
+            ref sequences_index_buffer,
+            _ne: _,
+        } = info;
+
+        let streams_vk: SmallVec<[_; 4]> = streams
+            .iter()
+            .map(|stream| ash::vk::IndirectCommandsStreamNV {
+                buffer: stream.buffer().handle(),
+                offset: stream.offset(),
+            })
+            .collect();
+
+        let info_vk = ash::vk::GeneratedCommandsInfoNV {
+            pipeline_bind_point: indirect_commands_layout.pipeline_bind_point().into(),
+            pipeline: pipeline.handle(),
+            indirect_commands_layout: indirect_commands_layout.handle(),
+            stream_count: streams_vk.len() as u32,
+            p_streams: streams_vk.as_ptr(),
+            sequences_count,
+            preprocess_buffer: preprocess_buffer.buffer().handle(),
+            preprocess_offset: preprocess_buffer.offset(),
+            preprocess_size: preprocess_buffer.size(),
+            sequences_count_buffer: sequences_count_buffer
+                .as_ref()
+                .map_or_else(Default::default, |buffer| buffer.buffer().handle()),
+            sequences_count_offset: sequences_count_buffer
+                .as_ref()
+                .map_or(0, |buffer| buffer.offset()),
+            sequences_index_buffer: sequences_index_buffer
+                .as_ref()
+                .map_or_else(Default::default, |buffer| buffer.buffer().handle()),
+            sequences_index_offset: sequences_index_buffer
+                .as_ref()
+                .map_or(0, |buffer| buffer.offset()),
+            ..Default::default()
+        };
+
+        let fns = self.device().fns();
+        (fns.nv_device_generated_commands
+            .cmd_preprocess_generated_commands_nv)(self.handle(), &info_vk);
+
+        self
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -3232,4 +4496,7 @@ enum VUIDType {
     DrawIndirect,
     DrawIndexed,
     DrawIndexedIndirect,
+    DrawMulti,
+    DrawMultiIndexed,
+    ExecuteGeneratedCommands,
 }
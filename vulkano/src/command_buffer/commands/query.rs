@@ -523,6 +523,21 @@ where
                     }));
                 }
             }
+            QueryType::PrimitivesGenerated => {
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS)
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "`query_pool.query_type()` is `QueryType::PrimitivesGenerated`, \
+                            but the queue family of the command buffer does not support \
+                            graphics operations"
+                            .into(),
+                        vuids: &["VUID-vkCmdBeginQuery-queryType-06687"],
+                        ..Default::default()
+                    }));
+                }
+            }
             QueryType::Timestamp
             | QueryType::AccelerationStructureCompactedSize
             | QueryType::AccelerationStructureSerializationSize
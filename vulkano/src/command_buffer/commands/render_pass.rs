@@ -8,28 +8,56 @@
 // according to those terms.
 
 use crate::{
+    buffer::{BufferContents, IndexBuffer, Subbuffer},
     command_buffer::{
         allocator::CommandBufferAllocator,
         auto::{
             BeginRenderPassState, BeginRenderingState, RenderPassState, RenderPassStateAttachments,
             RenderPassStateType, Resource,
         },
+        generated_commands::GeneratedCommandsInfo,
         sys::UnsafeCommandBufferBuilder,
-        AutoCommandBufferBuilder, CommandBufferLevel, ResourceInCommand, SubpassContents,
+        AutoCommandBufferBuilder, CommandBufferLevel, DrawIndexedIndirectCommand,
+        DrawIndirectCommand, MultiDrawIndexedInfo, MultiDrawInfo, ResourceInCommand,
+        SecondaryCommandBufferAbstract, SubpassContents,
     },
+    descriptor_set::{DescriptorSetsCollection, WriteDescriptorSet},
     device::{Device, DeviceOwned, QueueFlags},
-    format::{ClearColorValue, ClearValue, NumericType},
-    image::{view::ImageView, ImageAspects, ImageLayout, ImageUsage, SampleCount},
-    pipeline::graphics::subpass::PipelineRenderingCreateInfo,
+    format::{ClearColorValue, ClearValue, ClearValueType, NumericType},
+    image::{
+        view::ImageView, ImageAspects, ImageLayout, ImageSubresourceRange, ImageUsage, SampleCount,
+    },
+    instance::debug::DebugUtilsLabel,
+    pipeline::{
+        graphics::{
+            color_blend::LogicOp,
+            depth_stencil::{CompareOp, StencilFaces, StencilOp},
+            input_assembly::PrimitiveTopology,
+            rasterization::{CullMode, FrontFace},
+            subpass::PipelineRenderingCreateInfo,
+            vertex_input::VertexBuffersCollection,
+            viewport::{Scissor, Viewport},
+        },
+        GraphicsPipeline, PipelineBindPoint, PipelineLayout,
+    },
+    query::{QueryControlFlags, QueryPool},
     render_pass::{
         AttachmentDescription, AttachmentLoadOp, AttachmentStoreOp, Framebuffer, RenderPass,
         ResolveMode, SubpassDescription,
     },
-    sync::PipelineStageAccessFlags,
+    sync::{
+        AccessFlags, DependencyInfo, ImageMemoryBarrier, PipelineStage, PipelineStageAccessFlags,
+        PipelineStages,
+    },
     Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanObject,
 };
 use smallvec::SmallVec;
-use std::{cmp::min, ops::Range, sync::Arc};
+use std::{
+    borrow::Cow,
+    cmp::min,
+    ops::{Range, RangeInclusive},
+    sync::Arc,
+};
 
 /// # Commands for render passes.
 ///
@@ -130,19 +158,29 @@ where
                     let image_view = &framebuffer.attachments()[index];
                     let index = index as u32;
 
+                    // TODO: suboptimal
+                    let mut memory_access = PipelineStageAccessFlags::FragmentShader_InputAttachmentRead
+                        | PipelineStageAccessFlags::ColorAttachmentOutput_ColorAttachmentRead
+                        | PipelineStageAccessFlags::ColorAttachmentOutput_ColorAttachmentWrite
+                        | PipelineStageAccessFlags::EarlyFragmentTests_DepthStencilAttachmentRead
+                        | PipelineStageAccessFlags::EarlyFragmentTests_DepthStencilAttachmentWrite
+                        | PipelineStageAccessFlags::LateFragmentTests_DepthStencilAttachmentRead
+                        | PipelineStageAccessFlags::LateFragmentTests_DepthStencilAttachmentWrite;
+
+                    if render_pass
+                        .fragment_density_map_attachment()
+                        .is_some_and(|attachment_ref| attachment_ref.attachment == index)
+                    {
+                        memory_access |=
+                            PipelineStageAccessFlags::FragmentDensityProcess_FragmentDensityMapRead;
+                    }
+
                     (
                         ResourceInCommand::FramebufferAttachment { index }.into(),
                         Resource::Image {
                             image: image_view.image().clone(),
                             subresource_range: image_view.subresource_range().clone(),
-                            // TODO: suboptimal
-                            memory_access: PipelineStageAccessFlags::FragmentShader_InputAttachmentRead
-                                | PipelineStageAccessFlags::ColorAttachmentOutput_ColorAttachmentRead
-                                | PipelineStageAccessFlags::ColorAttachmentOutput_ColorAttachmentWrite
-                                | PipelineStageAccessFlags::EarlyFragmentTests_DepthStencilAttachmentRead
-                                | PipelineStageAccessFlags::EarlyFragmentTests_DepthStencilAttachmentWrite
-                                | PipelineStageAccessFlags::LateFragmentTests_DepthStencilAttachmentRead
-                                | PipelineStageAccessFlags::LateFragmentTests_DepthStencilAttachmentWrite,
+                            memory_access,
                             start_layout: desc.initial_layout,
                             end_layout: desc.final_layout,
                         },
@@ -343,6 +381,82 @@ where
 
         self
     }
+
+    /// Begins a render pass using a render pass object and framebuffer, records commands into it
+    /// with `record`, and then ends it.
+    ///
+    /// Commands are recorded through the [`RenderPassScope`] that is given to `record`, which only
+    /// provides the commands that can be recorded inside a render pass, and advances to the next
+    /// subpass with [`next_subpass`](RenderPassScope::next_subpass). Once `record` returns, the
+    /// render pass is ended. An error is returned if `record` didn't advance to the last subpass.
+    ///
+    /// The render pass is ended even if `record` returns an error, or didn't advance to the last
+    /// subpass, so that the builder is left outside of it. Any remaining subpasses are advanced
+    /// through first. If the render pass can't be ended, for example because a query that was
+    /// begun in it is still active, then it remains active, and building the command buffer will
+    /// fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use vulkano::command_buffer::{AutoCommandBufferBuilder, PrimaryAutoCommandBuffer};
+    /// # let mut builder: AutoCommandBufferBuilder<PrimaryAutoCommandBuffer> = return;
+    /// # let render_pass_begin_info: vulkano::command_buffer::RenderPassBeginInfo = return;
+    /// # let pipeline: std::sync::Arc<vulkano::pipeline::GraphicsPipeline> = return;
+    /// builder
+    ///     .render_pass(render_pass_begin_info, Default::default(), |pass| {
+    ///         pass.bind_pipeline_graphics(pipeline)?.draw(3, 1, 0, 0)?;
+    ///
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn render_pass(
+        &mut self,
+        render_pass_begin_info: RenderPassBeginInfo,
+        subpass_begin_info: SubpassBeginInfo,
+        record: impl FnOnce(&mut RenderPassScope<'_, L, A>) -> Result<(), Box<ValidationError>>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.begin_render_pass(render_pass_begin_info, subpass_begin_info)?;
+
+        let record_result = record(&mut RenderPassScope { builder: self });
+        let end_result = self.end_render_pass_scope();
+        record_result?;
+
+        if !end_result? {
+            return Err(Box::new(ValidationError {
+                context: "record".into(),
+                problem: "returned before advancing to the last subpass of the render pass".into(),
+                vuids: &["VUID-vkCmdEndRenderPass2-None-03103"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(self)
+    }
+
+    // Advances through the remaining subpasses of the current render pass, and ends it. Returns
+    // whether the render pass was already at its last subpass.
+    fn end_render_pass_scope(&mut self) -> Result<bool, Box<ValidationError>> {
+        let mut was_last_subpass = true;
+
+        while let Some(RenderPassState {
+            render_pass: RenderPassStateType::BeginRenderPass(state),
+            ..
+        }) = &self.builder_state.render_pass
+        {
+            if state.subpass.is_last_subpass() {
+                break;
+            }
+
+            was_last_subpass = false;
+            self.next_subpass(Default::default(), Default::default())?;
+        }
+
+        self.end_render_pass(Default::default())?;
+
+        Ok(was_last_subpass)
+    }
 }
 
 impl<L, A> AutoCommandBufferBuilder<L, A>
@@ -352,6 +466,13 @@ where
     /// Begins a render pass without a render pass object or framebuffer.
     ///
     /// You must call this or `begin_render_pass` before you can record draw commands.
+    ///
+    /// The attachments are automatically transitioned to their `image_layout` before rendering
+    /// begins. The [`initial_layout`] and [`final_layout`] of each attachment can be used to
+    /// discard its previous contents, or to transition it to another layout when rendering ends.
+    ///
+    /// [`initial_layout`]: RenderingAttachmentInfo::initial_layout
+    /// [`final_layout`]: RenderingAttachmentInfo::final_layout
     pub fn begin_rendering(
         &mut self,
         mut rendering_info: RenderingInfo,
@@ -398,6 +519,14 @@ where
             _ne,
         } = &rendering_info;
 
+        // With automatic synchronization, the attachments are transitioned from their
+        // `initial_layout` and to their `final_layout` around the rendering commands.
+        let (begin_dependency_info, end_dependency_info) = if self.automatic_sync {
+            rendering_info.layout_transitions()
+        } else {
+            Default::default()
+        };
+
         self.builder_state.render_pass = Some(RenderPassState {
             contents,
             render_area_offset,
@@ -410,10 +539,28 @@ where
 
             render_pass: BeginRenderingState {
                 pipeline_used: false,
+                end_dependency_info,
             }
             .into(),
         });
 
+        // With the `separate_depth_stencil_layouts` feature, the depth and stencil attachments
+        // each only use their own aspect, which can be in its own layout.
+        let separate_depth_stencil_layouts = self
+            .device()
+            .enabled_features()
+            .separate_depth_stencil_layouts;
+        let depth_stencil_subresource_range = |image_view: &ImageView, aspects| {
+            if separate_depth_stencil_layouts {
+                ImageSubresourceRange {
+                    aspects,
+                    ..image_view.subresource_range().clone()
+                }
+            } else {
+                image_view.subresource_range().clone()
+            }
+        };
+
         self.add_render_pass_begin(
             "begin_rendering",
             (color_attachments
@@ -432,6 +579,8 @@ where
                         load_op: _,
                         store_op: _,
                         clear_value: _,
+                        initial_layout,
+                        final_layout,
                         _ne: _,
                     } = attachment_info;
 
@@ -444,8 +593,8 @@ where
                                 // TODO: suboptimal
                                 memory_access: PipelineStageAccessFlags::ColorAttachmentOutput_ColorAttachmentRead
                                     | PipelineStageAccessFlags::ColorAttachmentOutput_ColorAttachmentWrite,
-                                start_layout: image_layout,
-                                end_layout: image_layout,
+                                start_layout: initial_layout.unwrap_or(image_layout),
+                                end_layout: final_layout.unwrap_or(image_layout),
                             },
                         )),
                         resolve_info.as_ref().map(|resolve_info| {
@@ -480,6 +629,8 @@ where
                     load_op: _,
                     store_op: _,
                     clear_value: _,
+                    initial_layout,
+                    final_layout,
                     _ne: _,
                 } = attachment_info;
 
@@ -488,14 +639,17 @@ where
                         ResourceInCommand::DepthStencilAttachment.into(),
                         Resource::Image {
                             image: image_view.image().clone(),
-                            subresource_range: image_view.subresource_range().clone(),
+                            subresource_range: depth_stencil_subresource_range(
+                                image_view,
+                                ImageAspects::DEPTH,
+                            ),
                             // TODO: suboptimal
                             memory_access: PipelineStageAccessFlags::EarlyFragmentTests_DepthStencilAttachmentRead
                                 | PipelineStageAccessFlags::EarlyFragmentTests_DepthStencilAttachmentWrite
                                 | PipelineStageAccessFlags::LateFragmentTests_DepthStencilAttachmentRead
                                 | PipelineStageAccessFlags::LateFragmentTests_DepthStencilAttachmentWrite,
-                            start_layout: image_layout,
-                            end_layout: image_layout,
+                            start_layout: initial_layout.unwrap_or(image_layout),
+                            end_layout: final_layout.unwrap_or(image_layout),
                         },
                     )),
                     resolve_info.as_ref().map(|resolve_info| {
@@ -532,6 +686,8 @@ where
                     load_op: _,
                     store_op: _,
                     clear_value: _,
+                    initial_layout,
+                    final_layout,
                     _ne: _,
                 } = attachment_info;
 
@@ -540,14 +696,17 @@ where
                         ResourceInCommand::DepthStencilAttachment.into(),
                         Resource::Image {
                             image: image_view.image().clone(),
-                            subresource_range: image_view.subresource_range().clone(),
+                            subresource_range: depth_stencil_subresource_range(
+                                image_view,
+                                ImageAspects::STENCIL,
+                            ),
                             // TODO: suboptimal
                             memory_access: PipelineStageAccessFlags::EarlyFragmentTests_DepthStencilAttachmentRead
                                 | PipelineStageAccessFlags::EarlyFragmentTests_DepthStencilAttachmentWrite
                                 | PipelineStageAccessFlags::LateFragmentTests_DepthStencilAttachmentRead
                                 | PipelineStageAccessFlags::LateFragmentTests_DepthStencilAttachmentWrite,
-                            start_layout: image_layout,
-                            end_layout: image_layout,
+                            start_layout: initial_layout.unwrap_or(image_layout),
+                            end_layout: final_layout.unwrap_or(image_layout),
                         },
                     )),
                     resolve_info.as_ref().map(|resolve_info| {
@@ -578,6 +737,10 @@ where
             }))
             .collect(),
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                if !begin_dependency_info.is_empty() {
+                    out.pipeline_barrier_unchecked(&begin_dependency_info);
+                }
+
                 out.begin_rendering_unchecked(&rendering_info);
             },
         );
@@ -641,19 +804,55 @@ where
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn end_rendering_unchecked(&mut self) -> &mut Self {
-        self.builder_state.render_pass = None;
+        let end_dependency_info = match self.builder_state.render_pass.take() {
+            Some(RenderPassState {
+                render_pass: RenderPassStateType::BeginRendering(state),
+                ..
+            }) => state.end_dependency_info,
+            _ => Default::default(),
+        };
 
         self.add_render_pass_end(
             "end_rendering",
             Default::default(),
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
                 out.end_rendering_unchecked();
+
+                if !end_dependency_info.is_empty() {
+                    out.pipeline_barrier_unchecked(&end_dependency_info);
+                }
             },
         );
 
         self
     }
 
+    /// Begins a render pass without a render pass object or framebuffer, records commands into
+    /// it with `record`, and then ends it.
+    ///
+    /// Commands are recorded through the [`RenderingScope`] that is given to `record`, which only
+    /// provides the commands that can be recorded inside a render pass. Once `record` returns, the
+    /// render pass is ended.
+    ///
+    /// The render pass is ended even if `record` returns an error, so that the builder is left
+    /// outside of it. If the render pass can't be ended, for example because a query that was
+    /// begun in it is still active, then it remains active, and building the command buffer will
+    /// fail.
+    pub fn rendering(
+        &mut self,
+        rendering_info: RenderingInfo,
+        record: impl FnOnce(&mut RenderingScope<'_, L, A>) -> Result<(), Box<ValidationError>>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.begin_rendering(rendering_info)?;
+
+        let record_result = record(&mut RenderingScope { builder: self });
+        let end_result = self.end_rendering().map(|_| ());
+        record_result?;
+        end_result?;
+
+        Ok(self)
+    }
+
     /// Clears specific regions of specific attachments of the framebuffer.
     ///
     /// `attachments` specify the types of attachments and their clear values.
@@ -1093,6 +1292,49 @@ where
                             }));
                         }
                     }
+                    ImageLayout::AttachmentFeedbackLoopOptimal => {
+                        if !layout.is_usage_supported(image_view.usage()) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "`framebuffer.attachments()[{0}]` is used in `render_pass` \
+                                    with the `ImageLayout::AttachmentFeedbackLoopOptimal` \
+                                    layout, but `framebuffer.attachments()[{0}].usage()` does \
+                                    not contain `ImageUsage::ATTACHMENT_FEEDBACK_LOOP`, \
+                                    at least one of `ImageUsage::COLOR_ATTACHMENT` and \
+                                    `ImageUsage::DEPTH_STENCIL_ATTACHMENT`, and at least one of \
+                                    `ImageUsage::SAMPLED` and `ImageUsage::INPUT_ATTACHMENT`",
+                                    attachment_index,
+                                )
+                                .into(),
+                                vuids: &[
+                                    "VUID-vkCmdBeginRenderPass2-initialLayout-07000",
+                                    "VUID-vkCmdBeginRenderPass2-initialLayout-07001",
+                                ],
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                    ImageLayout::FragmentDensityMapOptimal => {
+                        if !image_view
+                            .usage()
+                            .intersects(ImageUsage::FRAGMENT_DENSITY_MAP)
+                        {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "`framebuffer.attachments()[{0}]` is used in `render_pass` \
+                                    with the `ImageLayout::FragmentDensityMapOptimal` layout, \
+                                    but `framebuffer.attachments()[{0}].usage()` does not \
+                                    contain `ImageUsage::FRAGMENT_DENSITY_MAP`",
+                                    attachment_index,
+                                )
+                                .into(),
+                                vuids: &[
+                                    "VUID-vkCmdBeginRenderPass2-fragmentDensityMapAttachment-06471",
+                                ],
+                                ..Default::default()
+                            }));
+                        }
+                    }
                     ImageLayout::Undefined
                     | ImageLayout::General
                     | ImageLayout::Preinitialized
@@ -1229,6 +1471,49 @@ where
                             }));
                         }
                     }
+                    ImageLayout::AttachmentFeedbackLoopOptimal => {
+                        if !atch_ref.layout.is_usage_supported(image_view.usage()) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "`framebuffer.attachments()[{0}]` is used in `render_pass` \
+                                    with the `ImageLayout::AttachmentFeedbackLoopOptimal` \
+                                    layout, but `framebuffer.attachments()[{0}].usage()` does \
+                                    not contain `ImageUsage::ATTACHMENT_FEEDBACK_LOOP`, \
+                                    at least one of `ImageUsage::COLOR_ATTACHMENT` and \
+                                    `ImageUsage::DEPTH_STENCIL_ATTACHMENT`, and at least one of \
+                                    `ImageUsage::SAMPLED` and `ImageUsage::INPUT_ATTACHMENT`",
+                                    atch_ref.attachment,
+                                )
+                                .into(),
+                                vuids: &[
+                                    "VUID-vkCmdBeginRenderPass2-initialLayout-07000",
+                                    "VUID-vkCmdBeginRenderPass2-initialLayout-07001",
+                                ],
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                    ImageLayout::FragmentDensityMapOptimal => {
+                        if !image_view
+                            .usage()
+                            .intersects(ImageUsage::FRAGMENT_DENSITY_MAP)
+                        {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "`framebuffer.attachments()[{0}]` is used in `render_pass` \
+                                    with the `ImageLayout::FragmentDensityMapOptimal` layout, \
+                                    but `framebuffer.attachments()[{0}].usage()` does not \
+                                    contain `ImageUsage::FRAGMENT_DENSITY_MAP`",
+                                    atch_ref.attachment,
+                                )
+                                .into(),
+                                vuids: &[
+                                    "VUID-vkCmdBeginRenderPass2-fragmentDensityMapAttachment-06471",
+                                ],
+                                ..Default::default()
+                            }));
+                        }
+                    }
                     ImageLayout::Undefined
                     | ImageLayout::General
                     | ImageLayout::Preinitialized
@@ -1592,6 +1877,8 @@ where
                     load_op,
                     store_op,
                     clear_value,
+                    initial_layout: _,
+                    final_layout: _,
                     _ne: _,
                 } = attachment_info;
 
@@ -1821,6 +2108,215 @@ where
     }
 }
 
+// Defines methods on a render pass scope that forward to the command of the same name on the
+// builder.
+macro_rules! forward_commands {
+    () => {};
+    (
+        fn $name:ident $(<$gen:ident: $bound:path>)? ($($arg:ident: $ty:ty),* $(,)?);
+        $($rest:tt)*
+    ) => {
+        #[doc = concat!("See [`AutoCommandBufferBuilder::", stringify!($name), "`].")]
+        #[inline]
+        pub fn $name $(<$gen: $bound>)? (
+            &mut self,
+            $($arg: $ty),*
+        ) -> Result<&mut Self, Box<ValidationError>> {
+            self.builder.$name($($arg),*)?;
+
+            Ok(self)
+        }
+
+        forward_commands!($($rest)*);
+    };
+    (
+        unsafe fn $name:ident ($($arg:ident: $ty:ty),* $(,)?);
+        $($rest:tt)*
+    ) => {
+        #[doc = concat!("See [`AutoCommandBufferBuilder::", stringify!($name), "`].")]
+        ///
+        /// # Safety
+        ///
+        #[doc = concat!("See [`AutoCommandBufferBuilder::", stringify!($name), "`].")]
+        #[inline]
+        pub unsafe fn $name(
+            &mut self,
+            $($arg: $ty),*
+        ) -> Result<&mut Self, Box<ValidationError>> {
+            self.builder.$name($($arg),*)?;
+
+            Ok(self)
+        }
+
+        forward_commands!($($rest)*);
+    };
+}
+
+// The commands that can be recorded inside a render pass, other than the ones that change the
+// current subpass.
+macro_rules! in_pass_commands {
+    () => {
+        forward_commands! {
+            fn bind_descriptor_sets(
+                pipeline_bind_point: PipelineBindPoint,
+                pipeline_layout: Arc<PipelineLayout>,
+                first_set: u32,
+                descriptor_sets: impl DescriptorSetsCollection,
+            );
+            fn bind_index_buffer(index_buffer: impl Into<IndexBuffer>);
+            fn bind_pipeline_graphics(pipeline: Arc<GraphicsPipeline>);
+            fn bind_vertex_buffers(
+                first_binding: u32,
+                vertex_buffers: impl VertexBuffersCollection,
+            );
+            fn push_constants<Pc: BufferContents>(
+                pipeline_layout: Arc<PipelineLayout>,
+                offset: u32,
+                push_constants: Pc,
+            );
+            fn push_descriptor_set(
+                pipeline_bind_point: PipelineBindPoint,
+                pipeline_layout: Arc<PipelineLayout>,
+                set_num: u32,
+                descriptor_writes: SmallVec<[WriteDescriptorSet; 8]>,
+            );
+
+            fn set_blend_constants(constants: [f32; 4]);
+            fn set_color_write_enable(enables: SmallVec<[bool; 4]>);
+            fn set_cull_mode(cull_mode: CullMode);
+            fn set_depth_bias(constant_factor: f32, clamp: f32, slope_factor: f32);
+            fn set_depth_bias_enable(enable: bool);
+            fn set_depth_bounds(bounds: RangeInclusive<f32>);
+            fn set_depth_bounds_test_enable(enable: bool);
+            fn set_depth_compare_op(compare_op: CompareOp);
+            fn set_depth_test_enable(enable: bool);
+            fn set_depth_write_enable(enable: bool);
+            fn set_discard_rectangle(first_rectangle: u32, rectangles: SmallVec<[Scissor; 2]>);
+            fn set_front_face(face: FrontFace);
+            fn set_line_stipple(factor: u32, pattern: u16);
+            fn set_line_width(line_width: f32);
+            fn set_logic_op(logic_op: LogicOp);
+            fn set_patch_control_points(num: u32);
+            fn set_primitive_restart_enable(enable: bool);
+            fn set_primitive_topology(topology: PrimitiveTopology);
+            fn set_rasterizer_discard_enable(enable: bool);
+            fn set_scissor(first_scissor: u32, scissors: SmallVec<[Scissor; 2]>);
+            fn set_scissor_all(scissors: SmallVec<[Scissor; 2]>);
+            fn set_scissor_with_count(scissors: SmallVec<[Scissor; 2]>);
+            fn set_stencil_compare_mask(faces: StencilFaces, compare_mask: u32);
+            fn set_stencil_op(
+                faces: StencilFaces,
+                fail_op: StencilOp,
+                pass_op: StencilOp,
+                depth_fail_op: StencilOp,
+                compare_op: CompareOp,
+            );
+            fn set_stencil_reference(faces: StencilFaces, reference: u32);
+            fn set_stencil_test_enable(enable: bool);
+            fn set_stencil_write_mask(faces: StencilFaces, write_mask: u32);
+            fn set_viewport(first_viewport: u32, viewports: SmallVec<[Viewport; 2]>);
+            fn set_viewport_all(viewports: SmallVec<[Viewport; 2]>);
+            fn set_viewport_with_count(viewports: SmallVec<[Viewport; 2]>);
+
+            fn draw(
+                vertex_count: u32,
+                instance_count: u32,
+                first_vertex: u32,
+                first_instance: u32,
+            );
+            fn draw_indirect(indirect_buffer: Subbuffer<[DrawIndirectCommand]>);
+            fn draw_indexed(
+                index_count: u32,
+                instance_count: u32,
+                first_index: u32,
+                vertex_offset: i32,
+                first_instance: u32,
+            );
+            fn draw_indexed_indirect(indirect_buffer: Subbuffer<[DrawIndexedIndirectCommand]>);
+            fn draw_multi(draw_infos: &[MultiDrawInfo], instance_count: u32, first_instance: u32);
+            fn draw_multi_indexed(
+                draw_infos: &[MultiDrawIndexedInfo],
+                instance_count: u32,
+                first_instance: u32,
+                vertex_offset: Option<i32>,
+            );
+            unsafe fn execute_generated_commands(
+                is_preprocessed: bool,
+                info: GeneratedCommandsInfo,
+            );
+
+            fn clear_attachments(
+                attachments: SmallVec<[ClearAttachment; 4]>,
+                rects: SmallVec<[ClearRect; 4]>,
+            );
+            fn execute_commands(command_buffer: Arc<dyn SecondaryCommandBufferAbstract>);
+            fn execute_commands_from_vec(
+                command_buffers: Vec<Arc<dyn SecondaryCommandBufferAbstract>>,
+            );
+
+            unsafe fn begin_query(query_pool: Arc<QueryPool>, query: u32, flags: QueryControlFlags);
+            fn end_query(query_pool: Arc<QueryPool>, query: u32);
+            unsafe fn write_timestamp(query_pool: Arc<QueryPool>, query: u32, stage: PipelineStage);
+
+            fn begin_debug_utils_label(label_info: DebugUtilsLabel);
+            unsafe fn end_debug_utils_label();
+            fn insert_debug_utils_label(label_info: DebugUtilsLabel);
+        }
+    };
+}
+
+/// A render pass instance that was begun with [`AutoCommandBufferBuilder::render_pass`].
+///
+/// This provides the commands that can be recorded inside a render pass, and
+/// [`next_subpass`](Self::next_subpass). Each command is the same as the command of the same name
+/// on `AutoCommandBufferBuilder`.
+pub struct RenderPassScope<'a, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    builder: &'a mut AutoCommandBufferBuilder<L, A>,
+}
+
+impl<L, A> RenderPassScope<'_, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Advances to the next subpass of the render pass.
+    ///
+    /// This is the same as [`AutoCommandBufferBuilder::next_subpass`].
+    #[inline]
+    pub fn next_subpass(
+        &mut self,
+        subpass_end_info: SubpassEndInfo,
+        subpass_begin_info: SubpassBeginInfo,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.builder
+            .next_subpass(subpass_end_info, subpass_begin_info)?;
+
+        Ok(self)
+    }
+
+    in_pass_commands!();
+}
+
+/// A render pass instance that was begun with [`AutoCommandBufferBuilder::rendering`].
+///
+/// This provides the commands that can be recorded inside a render pass. Each command is the same
+/// as the command of the same name on `AutoCommandBufferBuilder`.
+pub struct RenderingScope<'a, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    builder: &'a mut AutoCommandBufferBuilder<L, A>,
+}
+
+impl<L, A> RenderingScope<'_, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    in_pass_commands!();
+}
+
 /// Parameters to begin a new render pass.
 #[derive(Clone, Debug)]
 pub struct RenderPassBeginInfo {
@@ -2162,6 +2658,92 @@ impl Default for RenderingInfo {
 }
 
 impl RenderingInfo {
+    /// Returns the barriers that transition the attachments from their `initial_layout` to their
+    /// `image_layout` before rendering begins, and from their `image_layout` to their
+    /// `final_layout` after rendering ends.
+    pub(crate) fn layout_transitions(&self) -> (DependencyInfo, DependencyInfo) {
+        let mut begin_dependency_info = DependencyInfo::default();
+        let mut end_dependency_info = DependencyInfo::default();
+
+        for (_, attachment_info, aspects) in self.transitioned_attachments() {
+            let (begin_barrier, end_barrier) = attachment_info.layout_transitions(aspects);
+            begin_dependency_info
+                .image_memory_barriers
+                .extend(begin_barrier);
+            end_dependency_info
+                .image_memory_barriers
+                .extend(end_barrier);
+        }
+
+        (begin_dependency_info, end_dependency_info)
+    }
+
+    /// Returns the attachments that [`Self::layout_transitions`] transitions, together with their
+    /// name and the aspects of their image view that are transitioned. If the
+    /// [`separate_depth_stencil_layouts`](crate::device::Features::separate_depth_stencil_layouts)
+    /// feature is enabled, the depth and stencil attachments only transition their own aspect.
+    fn transitioned_attachments(
+        &self,
+    ) -> impl Iterator<Item = (Cow<'static, str>, &RenderingAttachmentInfo, ImageAspects)> {
+        let color_attachments =
+            (self.color_attachments.iter().enumerate()).filter_map(|(index, attachment_info)| {
+                attachment_info.as_ref().map(|attachment_info| {
+                    (
+                        format!("color_attachments[{}]", index).into(),
+                        attachment_info,
+                        attachment_info.image_view.subresource_range().aspects,
+                    )
+                })
+            });
+
+        let depth_stencil_attachments = match (&self.depth_attachment, &self.stencil_attachment) {
+            (None, None) => [None, None],
+            (Some(attachment_info), _) | (None, Some(attachment_info))
+                if !attachment_info
+                    .image_view
+                    .device()
+                    .enabled_features()
+                    .separate_depth_stencil_layouts =>
+            {
+                // Without the `separate_depth_stencil_layouts` feature, all aspects must be
+                // transitioned together. If both are `Some`, they have the same image view and
+                // layouts.
+                let name = if self.depth_attachment.is_some() {
+                    "depth_attachment"
+                } else {
+                    "stencil_attachment"
+                };
+
+                [
+                    Some((
+                        name.into(),
+                        attachment_info,
+                        attachment_info.image_view.subresource_range().aspects,
+                    )),
+                    None,
+                ]
+            }
+            (depth_attachment, stencil_attachment) => [
+                depth_attachment.as_ref().map(|attachment_info| {
+                    (
+                        "depth_attachment".into(),
+                        attachment_info,
+                        ImageAspects::DEPTH,
+                    )
+                }),
+                stencil_attachment.as_ref().map(|attachment_info| {
+                    (
+                        "stencil_attachment".into(),
+                        attachment_info,
+                        ImageAspects::STENCIL,
+                    )
+                }),
+            ],
+        };
+
+        color_attachments.chain(depth_stencil_attachments.into_iter().flatten())
+    }
+
     pub(crate) fn set_auto_extent_layers(&mut self) {
         let &mut RenderingInfo {
             render_area_offset,
@@ -2360,7 +2942,9 @@ impl RenderingInfo {
                 resolve_info,
                 load_op: _,
                 store_op: _,
-                clear_value: _,
+                clear_value,
+                initial_layout: _,
+                final_layout: _,
                 _ne: _,
             } = attachment_info;
 
@@ -2374,6 +2958,31 @@ impl RenderingInfo {
                 }));
             }
 
+            if let (Some(clear_value), Some(numeric_format)) =
+                (clear_value, image_view.format().numeric_format_color())
+            {
+                let required_clear_value = match numeric_format.numeric_type() {
+                    NumericType::Float => ClearValueType::Float,
+                    NumericType::Int => ClearValueType::Int,
+                    NumericType::Uint => ClearValueType::Uint,
+                };
+
+                if clear_value.clear_value_type() != required_clear_value {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`color_attachments[{0}].clear_value` is `ClearValue::{1:?}`, but \
+                            `color_attachments[{0}].image_view.format()` requires a clear value \
+                            of type `ClearValue::{2:?}`",
+                            attachment_index,
+                            clear_value.clear_value_type(),
+                            required_clear_value,
+                        )
+                        .into(),
+                        ..Default::default()
+                    }));
+                }
+            }
+
             if render_area_offset[0] + render_area_extent[0] > image_view.image().extent()[0] {
                 return Err(Box::new(ValidationError {
                     problem: format!(
@@ -2505,7 +3114,9 @@ impl RenderingInfo {
                 resolve_info,
                 load_op: _,
                 store_op: _,
-                clear_value: _,
+                clear_value,
+                initial_layout: _,
+                final_layout: _,
                 _ne: _,
             } = attachment_info;
 
@@ -2534,6 +3145,24 @@ impl RenderingInfo {
                 }));
             }
 
+            if let Some(clear_value) = clear_value {
+                if !matches!(
+                    clear_value,
+                    ClearValue::Depth(_) | ClearValue::DepthStencil(_)
+                ) {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`depth_attachment.clear_value` is `ClearValue::{:?}`, but \
+                            a depth attachment requires a clear value of type \
+                            `ClearValue::Depth` or `ClearValue::DepthStencil`",
+                            clear_value.clear_value_type(),
+                        )
+                        .into(),
+                        ..Default::default()
+                    }));
+                }
+            }
+
             if render_area_offset[0] + render_area_extent[0] > image_view.image().extent()[0] {
                 return Err(Box::new(ValidationError {
                     problem: "`render_area_offset[0] + render_area_extent[0]` is greater than \
@@ -2629,7 +3258,9 @@ impl RenderingInfo {
                 resolve_info,
                 load_op: _,
                 store_op: _,
-                clear_value: _,
+                clear_value,
+                initial_layout: _,
+                final_layout: _,
                 _ne: _,
             } = attachment_info;
 
@@ -2658,6 +3289,24 @@ impl RenderingInfo {
                 }));
             }
 
+            if let Some(clear_value) = clear_value {
+                if !matches!(
+                    clear_value,
+                    ClearValue::Stencil(_) | ClearValue::DepthStencil(_)
+                ) {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`stencil_attachment.clear_value` is `ClearValue::{:?}`, but \
+                            a stencil attachment requires a clear value of type \
+                            `ClearValue::Stencil` or `ClearValue::DepthStencil`",
+                            clear_value.clear_value_type(),
+                        )
+                        .into(),
+                        ..Default::default()
+                    }));
+                }
+            }
+
             if render_area_offset[0] + render_area_extent[0] > image_view.image().extent()[0] {
                 return Err(Box::new(ValidationError {
                     problem: "`render_area_offset[0] + render_area_extent[0]` is greater than \
@@ -2754,6 +3403,20 @@ impl RenderingInfo {
                 }));
             }
 
+            if depth_attachment_info.initial_layout != stencil_attachment_info.initial_layout
+                || depth_attachment_info.final_layout != stencil_attachment_info.final_layout
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`depth_attachment` and `stencil_attachment` are both `Some`, but \
+                        `depth_attachment.initial_layout` does not equal \
+                        `stencil_attachment.initial_layout`, or \
+                        `depth_attachment.final_layout` does not equal \
+                        `stencil_attachment.final_layout`"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+
             if depth_attachment_info.image_layout != stencil_attachment_info.image_layout
                 && !device.enabled_features().separate_depth_stencil_layouts
             {
@@ -2838,6 +3501,24 @@ impl RenderingInfo {
             }
         }
 
+        // The layout transitions are recorded as image memory barriers, so they must be valid
+        // barriers.
+        for (name, attachment_info, aspects) in self.transitioned_attachments() {
+            let (begin_barrier, end_barrier) = attachment_info.layout_transitions(aspects);
+
+            if let Some(barrier) = begin_barrier {
+                barrier
+                    .validate(device)
+                    .map_err(|err| err.add_context(format!("{}.initial_layout", name)))?;
+            }
+
+            if let Some(barrier) = end_barrier {
+                barrier
+                    .validate(device)
+                    .map_err(|err| err.add_context(format!("{}.final_layout", name)))?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -2880,6 +3561,35 @@ pub struct RenderingAttachmentInfo {
     /// The default value is `None`.
     pub clear_value: Option<ClearValue>,
 
+    /// If `Some`, the automatic synchronization of [`AutoCommandBufferBuilder`] assumes that the
+    /// contents of `image_view` are in this layout when rendering begins, and transitions them to
+    /// `image_layout`, like a render pass object does with
+    /// [`AttachmentDescription::initial_layout`].
+    ///
+    /// If this is `Some(ImageLayout::Undefined)`, the previous contents of `image_view` are
+    /// discarded, which can be faster when `load_op` doesn't load them anyway. If this is `None`,
+    /// `image_view` is transitioned to `image_layout` from whatever layout it is in, and its
+    /// contents are preserved.
+    ///
+    /// This is ignored if automatic synchronization is disabled, and does not apply to
+    /// `resolve_info`.
+    ///
+    /// The default value is `None`.
+    pub initial_layout: Option<ImageLayout>,
+
+    /// If `Some`, the automatic synchronization of [`AutoCommandBufferBuilder`] transitions
+    /// `image_view` to this layout when rendering ends, like a render pass object does with
+    /// [`AttachmentDescription::final_layout`].
+    ///
+    /// If this is `None`, `image_view` stays in `image_layout` after rendering, until a later
+    /// command needs it to be in another layout.
+    ///
+    /// This is ignored if automatic synchronization is disabled, and does not apply to
+    /// `resolve_info`.
+    ///
+    /// The default value is `None`.
+    pub final_layout: Option<ImageLayout>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -2901,10 +3611,78 @@ impl RenderingAttachmentInfo {
             load_op: AttachmentLoadOp::DontCare,
             store_op: AttachmentStoreOp::DontCare,
             clear_value: None,
+            initial_layout: None,
+            final_layout: None,
             _ne: crate::NonExhaustive(()),
         }
     }
 
+    /// Returns the barriers that transition `aspects` of `image_view` from `initial_layout` to
+    /// `image_layout` before rendering begins, and from `image_layout` to `final_layout` after
+    /// rendering ends.
+    pub(crate) fn layout_transitions(
+        &self,
+        aspects: ImageAspects,
+    ) -> (Option<ImageMemoryBarrier>, Option<ImageMemoryBarrier>) {
+        let &RenderingAttachmentInfo {
+            ref image_view,
+            image_layout,
+            resolve_info: _,
+            load_op: _,
+            store_op: _,
+            clear_value: _,
+            initial_layout,
+            final_layout,
+            _ne: _,
+        } = self;
+
+        let (stages, access) = if aspects.intersects(ImageAspects::COLOR) {
+            (
+                PipelineStages::COLOR_ATTACHMENT_OUTPUT,
+                AccessFlags::COLOR_ATTACHMENT_READ | AccessFlags::COLOR_ATTACHMENT_WRITE,
+            )
+        } else {
+            (
+                PipelineStages::EARLY_FRAGMENT_TESTS | PipelineStages::LATE_FRAGMENT_TESTS,
+                AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ
+                    | AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE,
+            )
+        };
+        let subresource_range = ImageSubresourceRange {
+            aspects,
+            ..image_view.subresource_range().clone()
+        };
+
+        let begin_barrier =
+            initial_layout
+                .filter(|&layout| layout != image_layout)
+                .map(|initial_layout| ImageMemoryBarrier {
+                    src_stages: stages,
+                    src_access: AccessFlags::empty(),
+                    dst_stages: stages,
+                    dst_access: access,
+                    old_layout: initial_layout,
+                    new_layout: image_layout,
+                    subresource_range: subresource_range.clone(),
+                    ..ImageMemoryBarrier::image(image_view.image().clone())
+                });
+        let end_barrier =
+            final_layout
+                .filter(|&layout| layout != image_layout)
+                .map(|final_layout| ImageMemoryBarrier {
+                    src_stages: stages,
+                    src_access: access,
+                    dst_stages: stages,
+                    dst_access: access,
+                    old_layout: image_layout,
+                    new_layout: final_layout,
+                    subresource_range,
+                    ..ImageMemoryBarrier::image(image_view.image().clone())
+                });
+
+        (begin_barrier, end_barrier)
+    }
+
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             ref image_view,
@@ -2913,6 +3691,8 @@ impl RenderingAttachmentInfo {
             load_op,
             store_op,
             ref clear_value,
+            initial_layout,
+            final_layout,
             _ne,
         } = self;
 
@@ -3023,6 +3803,49 @@ impl RenderingAttachmentInfo {
             }
         };
 
+        if let Some(initial_layout) = initial_layout {
+            initial_layout
+                .validate_device(device)
+                .map_err(|err| ValidationError {
+                    context: "initial_layout".into(),
+                    vuids: &["VUID-VkImageMemoryBarrier2-oldLayout-parameter"],
+                    ..ValidationError::from_requirement(err)
+                })?;
+
+            if initial_layout == ImageLayout::Undefined && load_op == AttachmentLoadOp::Load {
+                return Err(Box::new(ValidationError {
+                    problem: "`initial_layout` is `Some(ImageLayout::Undefined)`, but \
+                        `load_op` is `AttachmentLoadOp::Load`"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if let Some(final_layout) = final_layout {
+            final_layout
+                .validate_device(device)
+                .map_err(|err| ValidationError {
+                    context: "final_layout".into(),
+                    vuids: &["VUID-VkImageMemoryBarrier2-newLayout-parameter"],
+                    ..ValidationError::from_requirement(err)
+                })?;
+
+            if matches!(
+                final_layout,
+                ImageLayout::Undefined | ImageLayout::Preinitialized
+            ) {
+                return Err(Box::new(ValidationError {
+                    context: "final_layout".into(),
+                    problem: "is `Some(ImageLayout::Undefined)` or \
+                        `Some(ImageLayout::Preinitialized)`"
+                        .into(),
+                    vuids: &["VUID-VkImageMemoryBarrier2-newLayout-01198"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         Ok(())
     }
 }
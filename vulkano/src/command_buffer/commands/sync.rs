@@ -8,17 +8,259 @@
 // according to those terms.
 
 use crate::{
-    command_buffer::{allocator::CommandBufferAllocator, sys::UnsafeCommandBufferBuilder},
+    buffer::Subbuffer,
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::{AutoCommandBufferBuilder, Resource},
+        sys::UnsafeCommandBufferBuilder,
+        ResourceInCommand,
+    },
     device::{DeviceOwned, QueueFlags},
     sync::{
-        event::Event, BufferMemoryBarrier, DependencyFlags, DependencyInfo, ImageMemoryBarrier,
-        MemoryBarrier, PipelineStages,
+        event::Event, AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo,
+        ImageMemoryBarrier, MemoryBarrier, PipelineStageAccessFlags, PipelineStages, ResourceUse,
     },
     Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanObject,
 };
 use smallvec::SmallVec;
 use std::{ptr, sync::Arc};
 
+/// # Commands for manual synchronization.
+///
+/// Normally, `AutoCommandBufferBuilder` inserts all the pipeline barriers that are needed
+/// between commands automatically. These commands are for advanced users who have found the
+/// automatic barriers to be too conservative, and want to insert the barriers themselves.
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Disables the automatic insertion of pipeline barriers for this command buffer.
+    ///
+    /// Vulkano still validates the commands that are recorded, and still tracks which resources
+    /// the command buffer uses, so that conflicting accesses from other command buffers are
+    /// detected when it is submitted. But the barriers between the commands of this command
+    /// buffer must be recorded manually with [`pipeline_barrier`](Self::pipeline_barrier).
+    ///
+    /// This must be called before any other command is recorded.
+    ///
+    /// # Safety
+    ///
+    /// - Between every two commands that access the same resource, where at least one of the
+    ///   accesses is a write, a memory dependency must be recorded with `pipeline_barrier`.
+    /// - In a primary command buffer, the first access to every resource must be made to wait
+    ///   for any commands that were previously submitted to the same queue and access the
+    ///   resource.
+    /// - Before a command accesses an image, the image must be transitioned to the layout that
+    ///   the command uses it in.
+    /// - In a primary command buffer, every image must be transitioned back, at the end of the
+    ///   command buffer, to the layout that it had at the start.
+    ///
+    /// [`layout_transitions`](Self::layout_transitions) returns the layout transitions that
+    /// vulkano would have performed, which can be used as a reference.
+    pub unsafe fn disable_automatic_sync(&mut self) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_disable_automatic_sync()?;
+
+        Ok(self.disable_automatic_sync_unchecked())
+    }
+
+    fn validate_disable_automatic_sync(&self) -> Result<(), Box<ValidationError>> {
+        if self.has_commands() {
+            return Err(Box::new(ValidationError {
+                problem: "commands have already been recorded".into(),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn disable_automatic_sync_unchecked(&mut self) -> &mut Self {
+        self.automatic_sync = false;
+
+        self
+    }
+
+    /// Records a pipeline barrier.
+    ///
+    /// Automatic synchronization must have been disabled with
+    /// [`disable_automatic_sync`](Self::disable_automatic_sync).
+    ///
+    /// # Safety
+    ///
+    /// - The barrier must be consistent with the layouts that vulkano expects images to be in,
+    ///   as described in the documentation of `disable_automatic_sync`.
+    pub unsafe fn pipeline_barrier(
+        &mut self,
+        dependency_info: DependencyInfo,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_pipeline_barrier(&dependency_info)?;
+
+        Ok(self.pipeline_barrier_unchecked(dependency_info))
+    }
+
+    fn validate_pipeline_barrier(
+        &self,
+        dependency_info: &DependencyInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_pipeline_barrier(dependency_info)?;
+
+        if self.automatic_sync {
+            return Err(Box::new(ValidationError {
+                problem: "automatic synchronization is enabled for the command buffer".into(),
+                ..Default::default()
+            }));
+        }
+
+        // TODO: Barriers inside a render pass must match a subpass self-dependency, which isn't
+        // checked yet.
+        if self.builder_state.render_pass.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is active".into(),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn pipeline_barrier_unchecked(
+        &mut self,
+        dependency_info: DependencyInfo,
+    ) -> &mut Self {
+        self.add_command(
+            "pipeline_barrier",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.pipeline_barrier_unchecked(&dependency_info);
+            },
+        );
+
+        self
+    }
+
+    /// Records a pipeline barrier that hands `subbuffer` over from one use to another, for
+    /// example from a compute shader that writes it to a draw command that reads it as a vertex
+    /// buffer.
+    ///
+    /// This is a shorthand for [`pipeline_barrier`](Self::pipeline_barrier) with a single
+    /// barrier created by [`BufferMemoryBarrier::handoff`]. It is only needed when automatic
+    /// synchronization has been disabled with
+    /// [`disable_automatic_sync`](Self::disable_automatic_sync); otherwise, vulkano already
+    /// inserts these barriers itself, and an error is returned.
+    ///
+    /// # Safety
+    ///
+    /// - The safety requirements of `pipeline_barrier` apply.
+    pub unsafe fn buffer_handoff(
+        &mut self,
+        subbuffer: &Subbuffer<impl ?Sized>,
+        from: ResourceUse,
+        to: ResourceUse,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.pipeline_barrier(DependencyInfo {
+            buffer_memory_barriers: [BufferMemoryBarrier::handoff(subbuffer, from, to)]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        })
+    }
+}
+
+/// # Commands for host access.
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Makes the writes that previous commands made to `subbuffer` available to the host, so
+    /// that its contents can be read with [`Subbuffer::read`] once the command buffer has
+    /// finished executing.
+    ///
+    /// Vulkan requires a memory dependency with the host as destination before the host can
+    /// read data written by the device. With automatic synchronization, this command inserts
+    /// the barrier only if there are pending writes to `subbuffer`. Otherwise, a barrier from
+    /// all previous writes is recorded.
+    ///
+    /// `subbuffer` must be host-visible. Invalidating non-coherent memory is done by
+    /// `Subbuffer::read`.
+    pub fn make_host_readable(
+        &mut self,
+        subbuffer: &Subbuffer<impl ?Sized>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_make_host_readable(subbuffer)?;
+
+        unsafe { Ok(self.make_host_readable_unchecked(subbuffer)) }
+    }
+
+    fn validate_make_host_readable(
+        &self,
+        subbuffer: &Subbuffer<impl ?Sized>,
+    ) -> Result<(), Box<ValidationError>> {
+        assert_eq!(self.device(), subbuffer.device());
+
+        if subbuffer.mapped_ptr().is_none() {
+            return Err(Box::new(ValidationError {
+                context: "subbuffer".into(),
+                problem: "is not host-visible".into(),
+                ..Default::default()
+            }));
+        }
+
+        if self.builder_state.render_pass.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is active".into(),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn make_host_readable_unchecked(
+        &mut self,
+        subbuffer: &Subbuffer<impl ?Sized>,
+    ) -> &mut Self {
+        let subbuffer = subbuffer.as_bytes().clone();
+        let automatic_sync = self.automatic_sync;
+
+        self.add_command(
+            "make_host_readable",
+            [(
+                ResourceInCommand::Source.into(),
+                Resource::Buffer {
+                    buffer: subbuffer.clone(),
+                    range: 0..subbuffer.size(),
+                    memory_access: PipelineStageAccessFlags::Host_HostRead,
+                },
+            )]
+            .into_iter()
+            .collect(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                // With automatic synchronization, the barrier is inserted before this command.
+                if !automatic_sync {
+                    out.pipeline_barrier_unchecked(&DependencyInfo {
+                        buffer_memory_barriers: [BufferMemoryBarrier {
+                            src_stages: PipelineStages::ALL_COMMANDS,
+                            src_access: AccessFlags::MEMORY_WRITE,
+                            dst_stages: PipelineStages::HOST,
+                            dst_access: AccessFlags::HOST_READ,
+                            range: subbuffer.offset()..subbuffer.offset() + subbuffer.size(),
+                            ..BufferMemoryBarrier::buffer(subbuffer.buffer().clone())
+                        }]
+                        .into_iter()
+                        .collect(),
+                        ..Default::default()
+                    });
+                }
+            },
+        );
+
+        self
+    }
+}
+
 impl<A> UnsafeCommandBufferBuilder<A>
 where
     A: CommandBufferAllocator,
@@ -114,7 +356,7 @@ where
                 src_access: _,
                 dst_stages,
                 dst_access: _,
-                queue_family_ownership_transfer: _,
+                ref queue_family_ownership_transfer,
                 buffer: _,
                 range: _,
                 _ne: _,
@@ -149,6 +391,25 @@ where
                     ..Default::default()
                 }));
             }
+
+            if let Some(transfer) = queue_family_ownership_transfer {
+                // No VUID, but the release and acquire operations must be executed on a queue of
+                // the source and destination queue family respectively.
+                if !transfer.is_valid_for_queue_family(self.queue_family_index()) {
+                    return Err(Box::new(ValidationError {
+                        context: format!(
+                            "dependency_info.buffer_memory_barriers[{}].\
+                            queue_family_ownership_transfer",
+                            barrier_index
+                        )
+                        .into(),
+                        problem: "is a transfer whose source or destination queue family is \
+                            not the queue family of the command buffer"
+                            .into(),
+                        ..Default::default()
+                    }));
+                }
+            }
         }
 
         for (barrier_index, image_memory_barrier) in image_memory_barriers.iter().enumerate() {
@@ -159,7 +420,7 @@ where
                 dst_access: _,
                 old_layout: _,
                 new_layout: _,
-                queue_family_ownership_transfer: _,
+                ref queue_family_ownership_transfer,
                 image: _,
                 subresource_range: _,
                 _ne: _,
@@ -194,6 +455,25 @@ where
                     ..Default::default()
                 }));
             }
+
+            if let Some(transfer) = queue_family_ownership_transfer {
+                // No VUID, but the release and acquire operations must be executed on a queue of
+                // the source and destination queue family respectively.
+                if !transfer.is_valid_for_queue_family(self.queue_family_index()) {
+                    return Err(Box::new(ValidationError {
+                        context: format!(
+                            "dependency_info.image_memory_barriers[{}].\
+                            queue_family_ownership_transfer",
+                            barrier_index
+                        )
+                        .into(),
+                        problem: "is a transfer whose source or destination queue family is \
+                            not the queue family of the command buffer"
+                            .into(),
+                        ..Default::default()
+                    }));
+                }
+            }
         }
 
         Ok(())
@@ -567,7 +847,7 @@ where
                 src_access: _,
                 dst_stages,
                 dst_access: _,
-                queue_family_ownership_transfer: _,
+                ref queue_family_ownership_transfer,
                 buffer: _,
                 range: _,
                 _ne: _,
@@ -602,6 +882,20 @@ where
                     ..Default::default()
                 }));
             }
+
+            if queue_family_ownership_transfer.is_some() {
+                return Err(Box::new(ValidationError {
+                    context: format!(
+                        "dependency_info.buffer_memory_barriers[{}].\
+                        queue_family_ownership_transfer",
+                        barrier_index
+                    )
+                    .into(),
+                    problem: "is `Some`".into(),
+                    vuids: &["VUID-vkCmdSetEvent2-srcQueueFamilyIndex-03803"],
+                    ..Default::default()
+                }));
+            }
         }
 
         for (barrier_index, image_memory_barrier) in image_memory_barriers.iter().enumerate() {
@@ -612,7 +906,7 @@ where
                 dst_access: _,
                 old_layout: _,
                 new_layout: _,
-                queue_family_ownership_transfer: _,
+                ref queue_family_ownership_transfer,
                 image: _,
                 subresource_range: _,
                 _ne: _,
@@ -647,6 +941,20 @@ where
                     ..Default::default()
                 }));
             }
+
+            if queue_family_ownership_transfer.is_some() {
+                return Err(Box::new(ValidationError {
+                    context: format!(
+                        "dependency_info.image_memory_barriers[{}].\
+                        queue_family_ownership_transfer",
+                        barrier_index
+                    )
+                    .into(),
+                    problem: "is `Some`".into(),
+                    vuids: &["VUID-vkCmdSetEvent2-srcQueueFamilyIndex-03803"],
+                    ..Default::default()
+                }));
+            }
         }
 
         Ok(())
@@ -0,0 +1,1375 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Commands that are generated on the device.
+//!
+//! With device-generated commands, the device reads a sequence of commands from buffers and
+//! executes them, without the host having to record each command individually. Each sequence
+//! consists of one or more state-changing commands, such as binding an index buffer or setting
+//! push constants, followed by one draw command.
+//!
+//! The layout of a sequence is described by an [`IndirectCommandsLayout`], which lists the
+//! tokens of the sequence and where their data is located in the input buffers (streams). The
+//! commands are then executed with [`execute_generated_commands`], which also needs a preprocess
+//! buffer whose size can be queried with
+//! [`generated_commands_memory_requirements`](IndirectCommandsLayout::generated_commands_memory_requirements).
+//! Optionally, the preprocessing can be done beforehand with
+//! [`preprocess_generated_commands`].
+//!
+//! Using this requires the [`nv_device_generated_commands`] extension and the
+//! [`device_generated_commands`] feature to be enabled on the device. Pipelines that are used
+//! with generated commands must be created with [`PipelineCreateFlags::INDIRECT_BINDABLE`].
+//!
+//! [`execute_generated_commands`]: crate::command_buffer::AutoCommandBufferBuilder::execute_generated_commands
+//! [`preprocess_generated_commands`]: crate::command_buffer::AutoCommandBufferBuilder::preprocess_generated_commands
+//! [`nv_device_generated_commands`]: crate::device::DeviceExtensions::nv_device_generated_commands
+//! [`device_generated_commands`]: crate::device::Features::device_generated_commands
+//! [`PipelineCreateFlags::INDIRECT_BINDABLE`]: crate::pipeline::PipelineCreateFlags::INDIRECT_BINDABLE
+
+use crate::{
+    buffer::{BufferUsage, IndexType, Subbuffer},
+    device::{Device, DeviceOwned},
+    instance::InstanceOwnedDebugWrapper,
+    macros::{impl_id_counter, vulkan_bitflags},
+    memory::{allocator::DeviceLayout, MemoryRequirements},
+    pipeline::{GraphicsPipeline, PipelineBindPoint, PipelineCreateFlags, PipelineLayout},
+    shader::ShaderStages,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError,
+    VulkanObject,
+};
+use ahash::HashSet;
+use smallvec::SmallVec;
+use std::{mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
+
+/// Describes the layout of the sequences of commands that are generated on the device.
+///
+/// See [the documentation of the module](crate::command_buffer::generated_commands) for more
+/// info.
+#[derive(Debug)]
+pub struct IndirectCommandsLayout {
+    device: InstanceOwnedDebugWrapper<Arc<Device>>,
+    handle: ash::vk::IndirectCommandsLayoutNV,
+    id: NonZeroU64,
+
+    flags: IndirectCommandsLayoutUsageFlags,
+    pipeline_bind_point: PipelineBindPoint,
+    tokens: Vec<IndirectCommandsLayoutToken>,
+    stream_strides: Vec<u32>,
+}
+
+impl IndirectCommandsLayout {
+    /// Creates a new `IndirectCommandsLayout`.
+    #[inline]
+    pub fn new(
+        device: Arc<Device>,
+        create_info: IndirectCommandsLayoutCreateInfo,
+    ) -> Result<Arc<IndirectCommandsLayout>, Validated<VulkanError>> {
+        Self::validate_new(&device, &create_info)?;
+
+        unsafe { Ok(Self::new_unchecked(device, create_info)?) }
+    }
+
+    fn validate_new(
+        device: &Device,
+        create_info: &IndirectCommandsLayoutCreateInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        if !device.enabled_extensions().nv_device_generated_commands {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "nv_device_generated_commands",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !device.enabled_features().device_generated_commands {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "device_generated_commands",
+                )])]),
+                vuids: &["VUID-vkCreateIndirectCommandsLayoutNV-deviceGeneratedCommands-02929"],
+                ..Default::default()
+            }));
+        }
+
+        create_info
+            .validate(device)
+            .map_err(|err| err.add_context("create_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: Arc<Device>,
+        create_info: IndirectCommandsLayoutCreateInfo,
+    ) -> Result<Arc<IndirectCommandsLayout>, VulkanError> {
+        let &IndirectCommandsLayoutCreateInfo {
+            flags,
+            pipeline_bind_point,
+            ref tokens,
+            ref stream_strides,
+            _ne: _,
+        } = &create_info;
+
+        let index_types_vk: SmallVec<[_; 4]> = tokens
+            .iter()
+            .map(|token| match &token.token_type {
+                IndirectCommandsTokenType::IndexBuffer { index_type_values } => (
+                    index_type_values
+                        .iter()
+                        .map(|&(_, index_type)| index_type.into())
+                        .collect::<Vec<ash::vk::IndexType>>(),
+                    index_type_values
+                        .iter()
+                        .map(|&(value, _)| value)
+                        .collect::<Vec<u32>>(),
+                ),
+                _ => (Vec::new(), Vec::new()),
+            })
+            .collect();
+
+        let tokens_vk: SmallVec<[_; 4]> = tokens
+            .iter()
+            .zip(&index_types_vk)
+            .map(|(token, (index_types, index_type_values))| {
+                let &IndirectCommandsLayoutToken {
+                    ref token_type,
+                    stream,
+                    offset,
+                    _ne: _,
+                } = token;
+
+                let mut token_vk = ash::vk::IndirectCommandsLayoutTokenNV {
+                    token_type: token_type.into(),
+                    stream,
+                    offset,
+                    ..Default::default()
+                };
+
+                match *token_type {
+                    IndirectCommandsTokenType::StateFlags { front_face } => {
+                        if front_face {
+                            token_vk.indirect_state_flags =
+                                ash::vk::IndirectStateFlagsNV::FLAG_FRONTFACE;
+                        }
+                    }
+                    IndirectCommandsTokenType::IndexBuffer { .. } => {
+                        token_vk.index_type_count = index_types.len() as u32;
+                        token_vk.p_index_types = index_types.as_ptr();
+                        token_vk.p_index_type_values = index_type_values.as_ptr();
+                    }
+                    IndirectCommandsTokenType::VertexBuffer {
+                        binding_unit,
+                        dynamic_stride,
+                    } => {
+                        token_vk.vertex_binding_unit = binding_unit;
+                        token_vk.vertex_dynamic_stride = dynamic_stride as ash::vk::Bool32;
+                    }
+                    IndirectCommandsTokenType::PushConstant {
+                        ref pipeline_layout,
+                        stages,
+                        offset,
+                        size,
+                    } => {
+                        token_vk.pushconstant_pipeline_layout = pipeline_layout.handle();
+                        token_vk.pushconstant_shader_stage_flags = stages.into();
+                        token_vk.pushconstant_offset = offset;
+                        token_vk.pushconstant_size = size;
+                    }
+                    IndirectCommandsTokenType::DrawIndexed
+                    | IndirectCommandsTokenType::Draw
+                    | IndirectCommandsTokenType::DrawTasks => (),
+                }
+
+                token_vk
+            })
+            .collect();
+
+        let create_info_vk = ash::vk::IndirectCommandsLayoutCreateInfoNV {
+            flags: flags.into(),
+            pipeline_bind_point: pipeline_bind_point.into(),
+            token_count: tokens_vk.len() as u32,
+            p_tokens: tokens_vk.as_ptr(),
+            stream_count: stream_strides.len() as u32,
+            p_stream_strides: stream_strides.as_ptr(),
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.nv_device_generated_commands
+                .create_indirect_commands_layout_nv)(
+                device.handle(),
+                &create_info_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        Ok(Self::from_handle(device, handle, create_info))
+    }
+
+    /// Creates a new `IndirectCommandsLayout` from a raw object handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid Vulkan object handle created from `device`.
+    /// - `create_info` must match the info used to create the object.
+    #[inline]
+    pub unsafe fn from_handle(
+        device: Arc<Device>,
+        handle: ash::vk::IndirectCommandsLayoutNV,
+        create_info: IndirectCommandsLayoutCreateInfo,
+    ) -> Arc<IndirectCommandsLayout> {
+        let IndirectCommandsLayoutCreateInfo {
+            flags,
+            pipeline_bind_point,
+            tokens,
+            stream_strides,
+            _ne: _,
+        } = create_info;
+
+        Arc::new(IndirectCommandsLayout {
+            device: InstanceOwnedDebugWrapper(device),
+            handle,
+            id: Self::next_id(),
+
+            flags,
+            pipeline_bind_point,
+            tokens,
+            stream_strides,
+        })
+    }
+
+    /// Returns the flags that the layout was created with.
+    #[inline]
+    pub fn flags(&self) -> IndirectCommandsLayoutUsageFlags {
+        self.flags
+    }
+
+    /// Returns the pipeline bind point that the layout was created with.
+    #[inline]
+    pub fn pipeline_bind_point(&self) -> PipelineBindPoint {
+        self.pipeline_bind_point
+    }
+
+    /// Returns the tokens that the layout was created with.
+    #[inline]
+    pub fn tokens(&self) -> &[IndirectCommandsLayoutToken] {
+        &self.tokens
+    }
+
+    /// Returns the stream strides that the layout was created with.
+    #[inline]
+    pub fn stream_strides(&self) -> &[u32] {
+        &self.stream_strides
+    }
+
+    /// Returns the memory requirements of the preprocess buffer, when executing at most
+    /// `max_sequences_count` sequences of this layout with `pipeline`.
+    ///
+    /// The [`preprocess_buffer`](GeneratedCommandsInfo::preprocess_buffer) of
+    /// [`GeneratedCommandsInfo`] must be at least as large as the returned size, and must be
+    /// allocated from one of the returned memory types.
+    pub fn generated_commands_memory_requirements(
+        &self,
+        pipeline: &GraphicsPipeline,
+        max_sequences_count: u32,
+    ) -> Result<MemoryRequirements, Box<ValidationError>> {
+        self.validate_generated_commands_memory_requirements(pipeline, max_sequences_count)?;
+
+        unsafe {
+            Ok(
+                self.generated_commands_memory_requirements_unchecked(
+                    pipeline,
+                    max_sequences_count,
+                ),
+            )
+        }
+    }
+
+    fn validate_generated_commands_memory_requirements(
+        &self,
+        pipeline: &GraphicsPipeline,
+        max_sequences_count: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        // VUID-VkGeneratedCommandsMemoryRequirementsInfoNV-commonparent
+        assert_eq!(self.device(), pipeline.device());
+
+        // No VUID, but the pipeline must be prepared by the implementation to be used with
+        // generated commands.
+        if !pipeline
+            .flags()
+            .intersects(PipelineCreateFlags::INDIRECT_BINDABLE)
+        {
+            return Err(Box::new(ValidationError {
+                context: "pipeline.flags()".into(),
+                problem: "does not contain `PipelineCreateFlags::INDIRECT_BINDABLE`".into(),
+                ..Default::default()
+            }));
+        }
+
+        let properties = self.device.physical_device().properties();
+
+        if max_sequences_count > properties.max_indirect_sequence_count.unwrap_or(0) {
+            return Err(Box::new(ValidationError {
+                context: "max_sequences_count".into(),
+                problem: "is greater than the `max_indirect_sequence_count` limit".into(),
+                vuids: &[
+                    "VUID-VkGeneratedCommandsMemoryRequirementsInfoNV-maxSequencesCount-02907",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn generated_commands_memory_requirements_unchecked(
+        &self,
+        pipeline: &GraphicsPipeline,
+        max_sequences_count: u32,
+    ) -> MemoryRequirements {
+        let info_vk = ash::vk::GeneratedCommandsMemoryRequirementsInfoNV {
+            pipeline_bind_point: self.pipeline_bind_point.into(),
+            pipeline: pipeline.handle(),
+            indirect_commands_layout: self.handle,
+            max_sequences_count,
+            ..Default::default()
+        };
+
+        let mut memory_requirements2_vk = ash::vk::MemoryRequirements2::default();
+
+        let fns = self.device.fns();
+        (fns.nv_device_generated_commands
+            .get_generated_commands_memory_requirements_nv)(
+            self.device.handle(),
+            &info_vk,
+            &mut memory_requirements2_vk,
+        );
+
+        MemoryRequirements {
+            layout: DeviceLayout::from_size_alignment(
+                memory_requirements2_vk.memory_requirements.size,
+                memory_requirements2_vk.memory_requirements.alignment,
+            )
+            .unwrap(),
+            memory_type_bits: memory_requirements2_vk.memory_requirements.memory_type_bits,
+            prefers_dedicated_allocation: false,
+            requires_dedicated_allocation: false,
+        }
+    }
+}
+
+impl Drop for IndirectCommandsLayout {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.nv_device_generated_commands
+                .destroy_indirect_commands_layout_nv)(
+                self.device.handle(),
+                self.handle,
+                ptr::null(),
+            );
+        }
+    }
+}
+
+unsafe impl VulkanObject for IndirectCommandsLayout {
+    type Handle = ash::vk::IndirectCommandsLayoutNV;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for IndirectCommandsLayout {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl_id_counter!(IndirectCommandsLayout);
+
+/// Parameters to create a new `IndirectCommandsLayout`.
+#[derive(Clone, Debug)]
+pub struct IndirectCommandsLayoutCreateInfo {
+    /// Additional properties of the layout.
+    ///
+    /// The default value is empty.
+    pub flags: IndirectCommandsLayoutUsageFlags,
+
+    /// The pipeline bind point that the generated commands are used with.
+    ///
+    /// Only [`PipelineBindPoint::Graphics`] is currently supported.
+    ///
+    /// The default value is [`PipelineBindPoint::Graphics`].
+    pub pipeline_bind_point: PipelineBindPoint,
+
+    /// The tokens that make up each sequence, in the order that they are executed.
+    ///
+    /// The last token must be a draw token, and there must be no other draw tokens.
+    ///
+    /// The default value is empty, which must be overridden.
+    pub tokens: Vec<IndirectCommandsLayoutToken>,
+
+    /// The stride in bytes between consecutive sequences, for each input stream.
+    ///
+    /// The number of elements determines the number of streams that must be provided in
+    /// [`GeneratedCommandsInfo::streams`].
+    ///
+    /// The default value is empty, which must be overridden.
+    pub stream_strides: Vec<u32>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for IndirectCommandsLayoutCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: IndirectCommandsLayoutUsageFlags::empty(),
+            pipeline_bind_point: PipelineBindPoint::Graphics,
+            tokens: Vec::new(),
+            stream_strides: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl IndirectCommandsLayoutCreateInfo {
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            flags,
+            pipeline_bind_point,
+            ref tokens,
+            ref stream_strides,
+            _ne: _,
+        } = self;
+
+        flags
+            .validate_device(device)
+            .map_err(|err| ValidationError {
+                context: "flags".into(),
+                vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-flags-parameter"],
+                ..ValidationError::from_requirement(err)
+            })?;
+
+        pipeline_bind_point
+            .validate_device(device)
+            .map_err(|err| ValidationError {
+                context: "pipeline_bind_point".into(),
+                vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-pipelineBindPoint-parameter"],
+                ..ValidationError::from_requirement(err)
+            })?;
+
+        if pipeline_bind_point != PipelineBindPoint::Graphics {
+            return Err(Box::new(ValidationError {
+                context: "pipeline_bind_point".into(),
+                problem: "is not `PipelineBindPoint::Graphics`".into(),
+                vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-pipelineBindPoint-02930"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = device.physical_device().properties();
+
+        if tokens.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "tokens".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-tokenCount-02931"],
+                ..Default::default()
+            }));
+        }
+
+        if tokens.len() as u32 > properties.max_indirect_commands_token_count.unwrap_or(0) {
+            return Err(Box::new(ValidationError {
+                context: "tokens".into(),
+                problem: "the length exceeds the `max_indirect_commands_token_count` limit".into(),
+                vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-tokenCount-02931"],
+                ..Default::default()
+            }));
+        }
+
+        if stream_strides.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "stream_strides".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-streamCount-02935"],
+                ..Default::default()
+            }));
+        }
+
+        if stream_strides.len() as u32 > properties.max_indirect_commands_stream_count.unwrap_or(0)
+        {
+            return Err(Box::new(ValidationError {
+                context: "stream_strides".into(),
+                problem: "the length exceeds the `max_indirect_commands_stream_count` limit".into(),
+                vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-streamCount-02935"],
+                ..Default::default()
+            }));
+        }
+
+        for (index, &stride) in stream_strides.iter().enumerate() {
+            if stride > properties.max_indirect_commands_stream_stride.unwrap_or(0) {
+                return Err(Box::new(ValidationError {
+                    context: format!("stream_strides[{}]", index).into(),
+                    problem: "exceeds the `max_indirect_commands_stream_stride` limit".into(),
+                    vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-pStreamStrides-02936"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        let mut has_state_flags = false;
+        let mut vertex_binding_units = HashSet::default();
+
+        for (index, token) in tokens.iter().enumerate() {
+            token
+                .validate(device, stream_strides.len() as u32)
+                .map_err(|err| err.add_context(format!("tokens[{}]", index)))?;
+
+            match token.token_type {
+                IndirectCommandsTokenType::StateFlags { .. } => {
+                    if has_state_flags {
+                        return Err(Box::new(ValidationError {
+                            context: "tokens".into(),
+                            problem: "contains more than one \
+                                `IndirectCommandsTokenType::StateFlags` token"
+                                .into(),
+                            vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-pTokens-02933"],
+                            ..Default::default()
+                        }));
+                    }
+
+                    has_state_flags = true;
+                }
+                IndirectCommandsTokenType::VertexBuffer { binding_unit, .. } => {
+                    // No VUID, but only one of the tokens would have an effect.
+                    if !vertex_binding_units.insert(binding_unit) {
+                        return Err(Box::new(ValidationError {
+                            context: "tokens".into(),
+                            problem: format!(
+                                "contains more than one \
+                                `IndirectCommandsTokenType::VertexBuffer` token with \
+                                `binding_unit` {}",
+                                binding_unit,
+                            )
+                            .into(),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                IndirectCommandsTokenType::DrawIndexed
+                | IndirectCommandsTokenType::Draw
+                | IndirectCommandsTokenType::DrawTasks => {
+                    if index != tokens.len() - 1 {
+                        return Err(Box::new(ValidationError {
+                            context: format!("tokens[{}]", index).into(),
+                            problem: "is a draw token, but is not the last element of `tokens`"
+                                .into(),
+                            vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-pTokens-02934"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        if !matches!(
+            tokens.last().unwrap().token_type,
+            IndirectCommandsTokenType::DrawIndexed
+                | IndirectCommandsTokenType::Draw
+                | IndirectCommandsTokenType::DrawTasks
+        ) {
+            return Err(Box::new(ValidationError {
+                context: "tokens".into(),
+                problem: "the last element is not a draw token".into(),
+                vuids: &["VUID-VkIndirectCommandsLayoutCreateInfoNV-pTokens-02934"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+/// A single command in a sequence of an [`IndirectCommandsLayout`].
+#[derive(Clone, Debug)]
+pub struct IndirectCommandsLayoutToken {
+    /// The type of the command, and its parameters that are fixed in the layout.
+    ///
+    /// There is no default value.
+    pub token_type: IndirectCommandsTokenType,
+
+    /// The index of the input stream that the data of the command is read from.
+    ///
+    /// The default value is `0`.
+    pub stream: u32,
+
+    /// The offset in bytes of the data of the command, relative to the start of each sequence in
+    /// the input stream.
+    ///
+    /// The default value is `0`.
+    pub offset: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl IndirectCommandsLayoutToken {
+    /// Returns an `IndirectCommandsLayoutToken` with the specified `token_type`.
+    #[inline]
+    pub fn token_type(token_type: IndirectCommandsTokenType) -> Self {
+        Self {
+            token_type,
+            stream: 0,
+            offset: 0,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn validate(
+        &self,
+        device: &Device,
+        stream_count: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            ref token_type,
+            stream,
+            offset,
+            _ne: _,
+        } = self;
+
+        let properties = device.physical_device().properties();
+
+        if stream >= stream_count {
+            return Err(Box::new(ValidationError {
+                context: "stream".into(),
+                problem: "is not less than the number of streams".into(),
+                vuids: &["VUID-VkIndirectCommandsLayoutTokenNV-stream-02951"],
+                ..Default::default()
+            }));
+        }
+
+        if offset > properties.max_indirect_commands_token_offset.unwrap_or(0) {
+            return Err(Box::new(ValidationError {
+                context: "offset".into(),
+                problem: "exceeds the `max_indirect_commands_token_offset` limit".into(),
+                vuids: &["VUID-VkIndirectCommandsLayoutTokenNV-offset-02952"],
+                ..Default::default()
+            }));
+        }
+
+        match *token_type {
+            IndirectCommandsTokenType::StateFlags { .. }
+            | IndirectCommandsTokenType::DrawIndexed
+            | IndirectCommandsTokenType::Draw
+            | IndirectCommandsTokenType::DrawTasks => (),
+            IndirectCommandsTokenType::IndexBuffer {
+                ref index_type_values,
+            } => {
+                for (index, &(_, index_type)) in index_type_values.iter().enumerate() {
+                    index_type
+                        .validate_device(device)
+                        .map_err(|err| ValidationError {
+                            context: format!("token_type.index_type_values[{}].1", index).into(),
+                            vuids: &["VUID-VkIndirectCommandsLayoutTokenNV-pIndexTypes-parameter"],
+                            ..ValidationError::from_requirement(err)
+                        })?;
+                }
+            }
+            IndirectCommandsTokenType::VertexBuffer { binding_unit, .. } => {
+                if binding_unit >= properties.max_vertex_input_bindings {
+                    return Err(Box::new(ValidationError {
+                        context: "token_type.binding_unit".into(),
+                        problem: "is not less than the `max_vertex_input_bindings` limit".into(),
+                        vuids: &["VUID-VkIndirectCommandsLayoutTokenNV-tokenType-02976"],
+                        ..Default::default()
+                    }));
+                }
+            }
+            IndirectCommandsTokenType::PushConstant {
+                ref pipeline_layout,
+                stages,
+                offset,
+                size,
+            } => {
+                // VUID-VkIndirectCommandsLayoutTokenNV-pushconstantPipelineLayout-parameter
+                assert_eq!(device, pipeline_layout.device().as_ref());
+
+                stages
+                    .validate_device(device)
+                    .map_err(|err| ValidationError {
+                        context: "token_type.stages".into(),
+                        vuids: &[
+                            "VUID-VkIndirectCommandsLayoutTokenNV-pushconstantShaderStageFlags-parameter",
+                        ],
+                        ..ValidationError::from_requirement(err)
+                    })?;
+
+                if offset % 4 != 0 {
+                    return Err(Box::new(ValidationError {
+                        context: "token_type.offset".into(),
+                        problem: "is not a multiple of 4".into(),
+                        vuids: &["VUID-VkIndirectCommandsLayoutTokenNV-tokenType-02978"],
+                        ..Default::default()
+                    }));
+                }
+
+                if size % 4 != 0 {
+                    return Err(Box::new(ValidationError {
+                        context: "token_type.size".into(),
+                        problem: "is not a multiple of 4".into(),
+                        vuids: &["VUID-VkIndirectCommandsLayoutTokenNV-tokenType-02979"],
+                        ..Default::default()
+                    }));
+                }
+
+                if offset >= properties.max_push_constants_size {
+                    return Err(Box::new(ValidationError {
+                        context: "token_type.offset".into(),
+                        problem: "is not less than the `max_push_constants_size` limit".into(),
+                        vuids: &["VUID-VkIndirectCommandsLayoutTokenNV-tokenType-02980"],
+                        ..Default::default()
+                    }));
+                }
+
+                if size > properties.max_push_constants_size - offset {
+                    return Err(Box::new(ValidationError {
+                        problem: "`token_type.size` is greater than the \
+                            `max_push_constants_size` limit minus `token_type.offset`"
+                            .into(),
+                        vuids: &["VUID-VkIndirectCommandsLayoutTokenNV-tokenType-02981"],
+                        ..Default::default()
+                    }));
+                }
+
+                let end = offset + size;
+
+                for stage in stages {
+                    if !pipeline_layout.push_constant_ranges().iter().any(|range| {
+                        range.stages.contains_enum(stage)
+                            && range.offset <= offset
+                            && range.offset + range.size >= end
+                    }) {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`token_type.stages` contains `ShaderStage::{:?}`, but the range \
+                                given by `token_type.offset` and `token_type.size` is not \
+                                contained in a push constant range of \
+                                `token_type.pipeline_layout` that includes this stage",
+                                stage,
+                            )
+                            .into(),
+                            vuids: &["VUID-VkIndirectCommandsLayoutTokenNV-tokenType-02982"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+
+                for range in pipeline_layout.push_constant_ranges() {
+                    if range.offset < end
+                        && offset < range.offset + range.size
+                        && !stages.contains(range.stages)
+                    {
+                        return Err(Box::new(ValidationError {
+                            problem: "the range given by `token_type.offset` and \
+                                `token_type.size` overlaps a push constant range of \
+                                `token_type.pipeline_layout`, but `token_type.stages` does not \
+                                contain all stages of that range"
+                                .into(),
+                            vuids: &["VUID-VkIndirectCommandsLayoutTokenNV-tokenType-02983"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The type of an [`IndirectCommandsLayoutToken`].
+///
+/// Each token reads a Vulkan-defined structure from its input stream, at the offset of the
+/// token.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum IndirectCommandsTokenType {
+    // TODO: ShaderGroup, requires graphics pipelines with multiple shader groups.
+    /// Sets state that is not otherwise dynamic.
+    ///
+    /// The stream contains a `u32` with `VkSetStateFlagsIndirectCommandNV` data.
+    StateFlags {
+        /// Whether the front face winding order can be flipped.
+        front_face: bool,
+    },
+
+    /// Binds an index buffer.
+    ///
+    /// The stream contains `VkBindIndexBufferIndirectCommandNV` data.
+    IndexBuffer {
+        /// Custom values that are used in the stream to specify the index type, paired with the
+        /// index type that they represent.
+        ///
+        /// If this is empty, the stream uses the values of `VkIndexType`.
+        index_type_values: Vec<(u32, IndexType)>,
+    },
+
+    /// Binds a vertex buffer.
+    ///
+    /// The stream contains `VkBindVertexBufferIndirectCommandNV` data.
+    VertexBuffer {
+        /// The vertex input binding that the buffer is bound to.
+        binding_unit: u32,
+
+        /// Whether the stride of the binding is read from the stream, rather than taken from the
+        /// pipeline.
+        dynamic_stride: bool,
+    },
+
+    /// Sets push constants.
+    ///
+    /// The stream contains `size` bytes of push constant data.
+    PushConstant {
+        /// The pipeline layout that the push constants are set for.
+        pipeline_layout: Arc<PipelineLayout>,
+
+        /// The shader stages that the push constants are set for.
+        stages: ShaderStages,
+
+        /// The offset in bytes of the push constants that are set.
+        offset: u32,
+
+        /// The size in bytes of the push constants that are set.
+        size: u32,
+    },
+
+    /// Performs an indexed draw.
+    ///
+    /// The stream contains a [`DrawIndexedIndirectCommand`](crate::command_buffer::DrawIndexedIndirectCommand).
+    DrawIndexed,
+
+    /// Performs a non-indexed draw.
+    ///
+    /// The stream contains a [`DrawIndirectCommand`](crate::command_buffer::DrawIndirectCommand).
+    Draw,
+
+    /// Performs a mesh tasks draw.
+    ///
+    /// The stream contains `VkDrawMeshTasksIndirectCommandNV` data.
+    DrawTasks,
+}
+
+impl From<&IndirectCommandsTokenType> for ash::vk::IndirectCommandsTokenTypeNV {
+    #[inline]
+    fn from(val: &IndirectCommandsTokenType) -> Self {
+        match val {
+            IndirectCommandsTokenType::StateFlags { .. } => Self::STATE_FLAGS,
+            IndirectCommandsTokenType::IndexBuffer { .. } => Self::INDEX_BUFFER,
+            IndirectCommandsTokenType::VertexBuffer { .. } => Self::VERTEX_BUFFER,
+            IndirectCommandsTokenType::PushConstant { .. } => Self::PUSH_CONSTANT,
+            IndirectCommandsTokenType::DrawIndexed => Self::DRAW_INDEXED,
+            IndirectCommandsTokenType::Draw => Self::DRAW,
+            IndirectCommandsTokenType::DrawTasks => Self::DRAW_TASKS,
+        }
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that control how an indirect commands layout is used.
+    IndirectCommandsLayoutUsageFlags = IndirectCommandsLayoutUsageFlagsNV(u32);
+
+    /// The generated commands are preprocessed with
+    /// [`preprocess_generated_commands`](crate::command_buffer::AutoCommandBufferBuilder::preprocess_generated_commands)
+    /// before they are executed.
+    EXPLICIT_PREPROCESS = EXPLICIT_PREPROCESS,
+
+    /// The sequences are not executed in order, but in the order given by
+    /// [`GeneratedCommandsInfo::sequences_index_buffer`].
+    INDEXED_SEQUENCES = INDEXED_SEQUENCES,
+
+    /// The sequences may be executed in any order.
+    UNORDERED_SEQUENCES = UNORDERED_SEQUENCES,
+}
+
+/// Parameters to execute or preprocess generated commands.
+#[derive(Clone, Debug)]
+pub struct GeneratedCommandsInfo {
+    /// The pipeline that the generated commands are executed with.
+    ///
+    /// When executing the commands, this must be the currently bound graphics pipeline.
+    ///
+    /// There is no default value.
+    pub pipeline: Arc<GraphicsPipeline>,
+
+    /// The layout of the sequences.
+    ///
+    /// There is no default value.
+    pub indirect_commands_layout: Arc<IndirectCommandsLayout>,
+
+    /// The input streams, one for each element of
+    /// [`stream_strides`](IndirectCommandsLayout::stream_strides) in the layout.
+    ///
+    /// The default value is empty, which must be overridden.
+    pub streams: Vec<Subbuffer<[u8]>>,
+
+    /// The maximum number of sequences to execute.
+    ///
+    /// The value must not be greater than the
+    /// [`max_indirect_sequence_count`](crate::device::Properties::max_indirect_sequence_count)
+    /// limit.
+    ///
+    /// The default value is `1`.
+    pub sequences_count: u32,
+
+    /// The buffer that is used to store the preprocessed commands.
+    ///
+    /// The size of the buffer must be at least the size returned by
+    /// [`IndirectCommandsLayout::generated_commands_memory_requirements`], with
+    /// `max_sequences_count` equal to `sequences_count`.
+    ///
+    /// There is no default value.
+    pub preprocess_buffer: Subbuffer<[u8]>,
+
+    /// A buffer containing the actual number of sequences to execute.
+    ///
+    /// If this is `Some`, the number of sequences that are executed is the minimum of its value
+    /// and `sequences_count`.
+    ///
+    /// The default value is `None`.
+    pub sequences_count_buffer: Option<Subbuffer<u32>>,
+
+    /// A buffer containing the index of each sequence to execute.
+    ///
+    /// This must be `Some` if and only if the layout was created with
+    /// [`IndirectCommandsLayoutUsageFlags::INDEXED_SEQUENCES`].
+    ///
+    /// The default value is `None`.
+    pub sequences_index_buffer: Option<Subbuffer<[u32]>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl GeneratedCommandsInfo {
+    /// Returns a `GeneratedCommandsInfo` with the specified `pipeline`,
+    /// `indirect_commands_layout` and `preprocess_buffer`.
+    #[inline]
+    pub fn new(
+        pipeline: Arc<GraphicsPipeline>,
+        indirect_commands_layout: Arc<IndirectCommandsLayout>,
+        preprocess_buffer: Subbuffer<[u8]>,
+    ) -> Self {
+        Self {
+            pipeline,
+            indirect_commands_layout,
+            streams: Vec::new(),
+            sequences_count: 1,
+            preprocess_buffer,
+            sequences_count_buffer: None,
+            sequences_index_buffer: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            ref pipeline,
+            ref indirect_commands_layout,
+            ref streams,
+            sequences_count,
+            ref preprocess_buffer,
+            ref sequences_count_buffer,
+            ref sequences_index_buffer,
+            _ne: _,
+        } = self;
+
+        // VUID-VkGeneratedCommandsInfoNV-commonparent
+        assert_eq!(device, pipeline.device().as_ref());
+        assert_eq!(device, indirect_commands_layout.device().as_ref());
+        assert_eq!(device, preprocess_buffer.device().as_ref());
+
+        let properties = device.physical_device().properties();
+
+        // No VUID, but the pipeline must be prepared by the implementation to be used with
+        // generated commands.
+        if !pipeline
+            .flags()
+            .intersects(PipelineCreateFlags::INDIRECT_BINDABLE)
+        {
+            return Err(Box::new(ValidationError {
+                context: "pipeline.flags()".into(),
+                problem: "does not contain `PipelineCreateFlags::INDIRECT_BINDABLE`".into(),
+                ..Default::default()
+            }));
+        }
+
+        if streams.len() != indirect_commands_layout.stream_strides().len() {
+            return Err(Box::new(ValidationError {
+                problem: "the length of `streams` does not equal the length of \
+                    `indirect_commands_layout.stream_strides()`"
+                    .into(),
+                vuids: &["VUID-VkGeneratedCommandsInfoNV-streamCount-02916"],
+                ..Default::default()
+            }));
+        }
+
+        let min_indirect_commands_buffer_offset_alignment = properties
+            .min_indirect_commands_buffer_offset_alignment
+            .unwrap_or(1) as DeviceSize;
+
+        for (index, stream) in streams.iter().enumerate() {
+            // VUID-VkIndirectCommandsStreamNV-buffer-parameter
+            assert_eq!(device, stream.device().as_ref());
+
+            if !stream
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::INDIRECT_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("streams[{}].buffer().usage()", index).into(),
+                    problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                    vuids: &["VUID-VkIndirectCommandsStreamNV-buffer-02942"],
+                    ..Default::default()
+                }));
+            }
+
+            if stream.offset() % min_indirect_commands_buffer_offset_alignment != 0 {
+                return Err(Box::new(ValidationError {
+                    context: format!("streams[{}].offset()", index).into(),
+                    problem: "is not a multiple of the \
+                        `min_indirect_commands_buffer_offset_alignment` limit"
+                        .into(),
+                    vuids: &["VUID-VkIndirectCommandsStreamNV-offset-02943"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if sequences_count > properties.max_indirect_sequence_count.unwrap_or(0) {
+            return Err(Box::new(ValidationError {
+                context: "sequences_count".into(),
+                problem: "is greater than the `max_indirect_sequence_count` limit".into(),
+                vuids: &["VUID-VkGeneratedCommandsInfoNV-sequencesCount-02917"],
+                ..Default::default()
+            }));
+        }
+
+        if !preprocess_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "preprocess_buffer.buffer().usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-VkGeneratedCommandsInfoNV-preprocessBuffer-02918"],
+                ..Default::default()
+            }));
+        }
+
+        if preprocess_buffer.offset() % min_indirect_commands_buffer_offset_alignment != 0 {
+            return Err(Box::new(ValidationError {
+                context: "preprocess_buffer.offset()".into(),
+                problem: "is not a multiple of the \
+                    `min_indirect_commands_buffer_offset_alignment` limit"
+                    .into(),
+                vuids: &["VUID-VkGeneratedCommandsInfoNV-preprocessOffset-02919"],
+                ..Default::default()
+            }));
+        }
+
+        let memory_requirements = unsafe {
+            indirect_commands_layout
+                .generated_commands_memory_requirements_unchecked(pipeline, sequences_count)
+        };
+
+        if preprocess_buffer.size() < memory_requirements.layout.size() {
+            return Err(Box::new(ValidationError {
+                problem: "the size of `preprocess_buffer` is less than the size returned by \
+                    `indirect_commands_layout.generated_commands_memory_requirements` for \
+                    `pipeline` and `sequences_count`"
+                    .into(),
+                vuids: &["VUID-VkGeneratedCommandsInfoNV-preprocessSize-02920"],
+                ..Default::default()
+            }));
+        }
+
+        if let Some(sequences_count_buffer) = sequences_count_buffer {
+            // VUID-VkGeneratedCommandsInfoNV-sequencesCountBuffer-parameter
+            assert_eq!(device, sequences_count_buffer.device().as_ref());
+
+            if !sequences_count_buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::INDIRECT_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    context: "sequences_count_buffer.buffer().usage()".into(),
+                    problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                    vuids: &["VUID-VkGeneratedCommandsInfoNV-sequencesCountBuffer-02921"],
+                    ..Default::default()
+                }));
+            }
+
+            if sequences_count_buffer.offset()
+                % properties
+                    .min_sequences_count_buffer_offset_alignment
+                    .unwrap_or(1) as DeviceSize
+                != 0
+            {
+                return Err(Box::new(ValidationError {
+                    context: "sequences_count_buffer.offset()".into(),
+                    problem: "is not a multiple of the \
+                        `min_sequences_count_buffer_offset_alignment` limit"
+                        .into(),
+                    vuids: &["VUID-VkGeneratedCommandsInfoNV-sequencesCountOffset-02922"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if let Some(sequences_index_buffer) = sequences_index_buffer {
+            // VUID-VkGeneratedCommandsInfoNV-sequencesIndexBuffer-parameter
+            assert_eq!(device, sequences_index_buffer.device().as_ref());
+
+            if !indirect_commands_layout
+                .flags()
+                .intersects(IndirectCommandsLayoutUsageFlags::INDEXED_SEQUENCES)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`sequences_index_buffer` is `Some`, but \
+                        `indirect_commands_layout.flags()` does not contain \
+                        `IndirectCommandsLayoutUsageFlags::INDEXED_SEQUENCES`"
+                        .into(),
+                    vuids: &["VUID-VkGeneratedCommandsInfoNV-sequencesIndexBuffer-02924"],
+                    ..Default::default()
+                }));
+            }
+
+            if !sequences_index_buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::INDIRECT_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    context: "sequences_index_buffer.buffer().usage()".into(),
+                    problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                    vuids: &["VUID-VkGeneratedCommandsInfoNV-sequencesIndexBuffer-02925"],
+                    ..Default::default()
+                }));
+            }
+
+            if sequences_index_buffer.offset()
+                % properties
+                    .min_sequences_index_buffer_offset_alignment
+                    .unwrap_or(1) as DeviceSize
+                != 0
+            {
+                return Err(Box::new(ValidationError {
+                    context: "sequences_index_buffer.offset()".into(),
+                    problem: "is not a multiple of the \
+                        `min_sequences_index_buffer_offset_alignment` limit"
+                        .into(),
+                    vuids: &["VUID-VkGeneratedCommandsInfoNV-sequencesIndexOffset-02926"],
+                    ..Default::default()
+                }));
+            }
+
+            // No VUID, but the device reads `sequences_count` indices from the buffer.
+            if sequences_index_buffer.len() < sequences_count as DeviceSize {
+                return Err(Box::new(ValidationError {
+                    problem: "the length of `sequences_index_buffer` is less than \
+                        `sequences_count`"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+        } else if indirect_commands_layout
+            .flags()
+            .intersects(IndirectCommandsLayoutUsageFlags::INDEXED_SEQUENCES)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`indirect_commands_layout.flags()` contains \
+                    `IndirectCommandsLayoutUsageFlags::INDEXED_SEQUENCES`, but \
+                    `sequences_index_buffer` is `None`"
+                    .into(),
+                vuids: &["VUID-VkGeneratedCommandsInfoNV-sequencesIndexBuffer-02924"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        IndirectCommandsLayout, IndirectCommandsLayoutCreateInfo, IndirectCommandsLayoutToken,
+        IndirectCommandsTokenType,
+    };
+    use crate::{
+        pipeline::{
+            layout::{PipelineLayoutCreateInfo, PushConstantRange},
+            PipelineLayout,
+        },
+        shader::ShaderStages,
+        Requires, RequiresAllOf, RequiresOneOf, Validated,
+    };
+
+    #[test]
+    fn requires_extension() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        if device.enabled_extensions().nv_device_generated_commands {
+            return;
+        }
+
+        match IndirectCommandsLayout::new(
+            device,
+            IndirectCommandsLayoutCreateInfo {
+                tokens: vec![IndirectCommandsLayoutToken::token_type(
+                    IndirectCommandsTokenType::Draw,
+                )],
+                stream_strides: vec![16],
+                ..Default::default()
+            },
+        ) {
+            Err(Validated::ValidationError(err)) => assert_eq!(
+                err.requires_one_of,
+                RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "nv_device_generated_commands"
+                )])]),
+            ),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn draw_token_not_last() {
+        let (device, _queue) = gfx_dev_and_queue!(
+            extensions: [nv_device_generated_commands],
+            features: [device_generated_commands],
+        );
+
+        let create_info = IndirectCommandsLayoutCreateInfo {
+            tokens: vec![
+                IndirectCommandsLayoutToken::token_type(IndirectCommandsTokenType::Draw),
+                IndirectCommandsLayoutToken {
+                    offset: 16,
+                    ..IndirectCommandsLayoutToken::token_type(IndirectCommandsTokenType::Draw)
+                },
+            ],
+            stream_strides: vec![32],
+            ..Default::default()
+        };
+
+        match create_info.validate(&device) {
+            Err(err) => assert_eq!(
+                err.vuids,
+                &["VUID-VkIndirectCommandsLayoutCreateInfoNV-pTokens-02934"],
+            ),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn duplicate_vertex_buffer_binding() {
+        let (device, _queue) = gfx_dev_and_queue!(
+            extensions: [nv_device_generated_commands],
+            features: [device_generated_commands],
+        );
+
+        let vertex_buffer = |offset| IndirectCommandsLayoutToken {
+            offset,
+            ..IndirectCommandsLayoutToken::token_type(IndirectCommandsTokenType::VertexBuffer {
+                binding_unit: 0,
+                dynamic_stride: false,
+            })
+        };
+        let create_info = IndirectCommandsLayoutCreateInfo {
+            tokens: vec![
+                vertex_buffer(0),
+                vertex_buffer(16),
+                IndirectCommandsLayoutToken {
+                    offset: 32,
+                    ..IndirectCommandsLayoutToken::token_type(IndirectCommandsTokenType::Draw)
+                },
+            ],
+            stream_strides: vec![48],
+            ..Default::default()
+        };
+
+        assert!(create_info.validate(&device).is_err());
+    }
+
+    #[test]
+    fn push_constant_range() {
+        let (device, _queue) = gfx_dev_and_queue!(
+            extensions: [nv_device_generated_commands],
+            features: [device_generated_commands],
+        );
+
+        let pipeline_layout = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                push_constant_ranges: vec![PushConstantRange {
+                    stages: ShaderStages::VERTEX,
+                    offset: 0,
+                    size: 16,
+                }],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let create_info = |offset| IndirectCommandsLayoutCreateInfo {
+            tokens: vec![
+                IndirectCommandsLayoutToken::token_type(IndirectCommandsTokenType::PushConstant {
+                    pipeline_layout: pipeline_layout.clone(),
+                    stages: ShaderStages::VERTEX,
+                    offset,
+                    size: 8,
+                }),
+                IndirectCommandsLayoutToken {
+                    offset: 8,
+                    ..IndirectCommandsLayoutToken::token_type(IndirectCommandsTokenType::Draw)
+                },
+            ],
+            stream_strides: vec![24],
+            ..Default::default()
+        };
+
+        // The range extends past the end of the push constant range of the layout.
+        match create_info(12).validate(&device) {
+            Err(err) => assert_eq!(
+                err.vuids,
+                &["VUID-VkIndirectCommandsLayoutTokenNV-tokenType-02982"],
+            ),
+            _ => panic!(),
+        }
+
+        create_info(8).validate(&device).unwrap();
+    }
+}
@@ -138,6 +138,7 @@ use std::{ops::Range, sync::Arc};
 pub mod allocator;
 pub mod auto;
 mod commands;
+pub mod generated_commands;
 pub mod pool;
 pub mod sys;
 mod traits;
@@ -161,6 +162,27 @@ pub struct DrawIndexedIndirectCommand {
     pub first_instance: u32,
 }
 
+/// The parameters of one draw in a [`draw_multi`] command.
+///
+/// [`draw_multi`]: AutoCommandBufferBuilder::draw_multi
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct MultiDrawInfo {
+    pub first_vertex: u32,
+    pub vertex_count: u32,
+}
+
+/// The parameters of one draw in a [`draw_multi_indexed`] command.
+///
+/// [`draw_multi_indexed`]: AutoCommandBufferBuilder::draw_multi_indexed
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct MultiDrawIndexedInfo {
+    pub first_index: u32,
+    pub index_count: u32,
+    pub vertex_offset: i32,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
 pub struct DispatchIndirectCommand {
@@ -760,6 +782,15 @@ pub struct SemaphoreSubmitInfo {
     /// The semaphore to signal or wait for.
     pub semaphore: Arc<Semaphore>,
 
+    /// If `semaphore` is a [timeline semaphore], specifies the value that the semaphore's counter
+    /// will be set to by a signal operation, or the value that it must reach for a wait
+    /// operation to complete. For binary semaphores, this value is ignored.
+    ///
+    /// The default value is 0.
+    ///
+    /// [timeline semaphore]: crate::sync::semaphore::SemaphoreType::Timeline
+    pub value: u64,
+
     /// For a semaphore wait operation, specifies the pipeline stages in the second synchronization
     /// scope: stages of queue operations following the wait operation that can start executing
     /// after the semaphore is signalled.
@@ -785,6 +816,7 @@ impl SemaphoreSubmitInfo {
     pub fn semaphore(semaphore: Arc<Semaphore>) -> Self {
         Self {
             semaphore,
+            value: 0,
             stages: PipelineStages::ALL_COMMANDS,
             _ne: crate::NonExhaustive(()),
         }
@@ -859,6 +891,36 @@ pub struct ResourceUseRef {
     pub secondary_use_ref: Option<SecondaryResourceUseRef>,
 }
 
+/// An image layout transition that is performed by the automatic barriers of an
+/// [`AutoCommandBufferBuilder`].
+///
+/// This is returned by [`AutoCommandBufferBuilder::layout_transitions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageLayoutTransition {
+    /// The index of the command that the transition is performed before.
+    ///
+    /// If this is equal to the number of recorded commands, then the transition is performed at
+    /// the end of the command buffer, to put the image back into the layout that it is expected
+    /// to have after the command buffer has finished executing.
+    pub command_index: usize,
+
+    /// The name of the command that the transition is performed before, or `None` if the
+    /// transition is performed at the end of the command buffer.
+    pub command_name: Option<&'static str>,
+
+    /// The image whose layout is transitioned.
+    pub image: Arc<Image>,
+
+    /// The subresources of `image` whose layout is transitioned.
+    pub subresource_range: ImageSubresourceRange,
+
+    /// The layout that the subresources are in before the transition.
+    pub old_layout: ImageLayout,
+
+    /// The layout that the subresources are in after the transition.
+    pub new_layout: ImageLayout,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct SecondaryResourceUseRef {
     pub command_index: usize,
@@ -905,8 +967,12 @@ pub enum ResourceInCommand {
     ImageMemoryBarrier { index: u32 },
     IndexBuffer,
     IndirectBuffer,
+    IndirectCommandsStream { index: u32 },
+    PreprocessBuffer,
     ScratchData,
     SecondaryCommandBuffer { index: u32 },
+    SequencesCountBuffer,
+    SequencesIndexBuffer,
     Source,
     VertexBuffer { binding: u32 },
 }
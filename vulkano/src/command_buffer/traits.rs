@@ -9,17 +9,14 @@
 
 use super::{
     CommandBufferInheritanceInfo, CommandBufferResourcesUsage, CommandBufferState,
-    CommandBufferUsage, SecondaryCommandBufferResourcesUsage, SemaphoreSubmitInfo, SubmitInfo,
+    CommandBufferUsage, SecondaryCommandBufferResourcesUsage, SubmitInfo,
 };
 use crate::{
     buffer::Buffer,
     device::{Device, DeviceOwned, Queue},
     image::{Image, ImageLayout},
     swapchain::Swapchain,
-    sync::{
-        future::{now, AccessCheckError, AccessError, GpuFuture, NowFuture, SubmitAnyBuilder},
-        PipelineStages,
-    },
+    sync::future::{now, AccessCheckError, AccessError, GpuFuture, NowFuture, SubmitAnyBuilder},
     DeviceSize, SafeDeref, Validated, ValidationError, VulkanError, VulkanObject,
 };
 use parking_lot::{Mutex, MutexGuard};
@@ -242,25 +239,14 @@ where
                 },
                 None,
             ),
-            SubmitAnyBuilder::SemaphoresWait(semaphores) => {
-                SubmitAnyBuilder::CommandBuffer(
-                    SubmitInfo {
-                        wait_semaphores: semaphores
-                            .into_iter()
-                            .map(|semaphore| {
-                                SemaphoreSubmitInfo {
-                                    // TODO: correct stages ; hard
-                                    stages: PipelineStages::ALL_COMMANDS,
-                                    ..SemaphoreSubmitInfo::semaphore(semaphore)
-                                }
-                            })
-                            .collect(),
-                        command_buffers: vec![self.command_buffer.clone()],
-                        ..Default::default()
-                    },
-                    None,
-                )
-            }
+            SubmitAnyBuilder::SemaphoresWait(semaphores) => SubmitAnyBuilder::CommandBuffer(
+                SubmitInfo {
+                    wait_semaphores: semaphores.into_vec(),
+                    command_buffers: vec![self.command_buffer.clone()],
+                    ..Default::default()
+                },
+                None,
+            ),
             SubmitAnyBuilder::CommandBuffer(mut submit_info, fence) => {
                 // FIXME: add pipeline barrier
                 submit_info
@@ -443,8 +429,12 @@ where
         if !*self.finished.get_mut() && !thread::panicking() {
             // TODO: handle errors?
             self.flush().unwrap();
-            // Block until the queue finished.
-            self.queue.with(|mut q| q.wait_idle()).unwrap();
+            // Block until the queue finished. If the device was lost, the queue won't execute
+            // anything anymore, and the resources are released anyway.
+            match self.queue.with(|mut q| q.wait_idle()) {
+                Ok(()) | Err(VulkanError::DeviceLost) => (),
+                Err(err) => panic!("{:?}", err),
+            }
             unsafe { self.previous.signal_finished() };
         }
     }
@@ -34,8 +34,6 @@ use thread_local::ThreadLocal;
 
 const MAX_POOLS: usize = 32;
 
-const MAX_SETS: usize = 256;
-
 /// Types that manage the memory of descriptor sets.
 ///
 /// # Safety
@@ -87,6 +85,12 @@ pub trait DescriptorSetAlloc: Send + Sync {
 /// dropped, even if the threads didn't exit yet, which is why you should keep the allocator alive
 /// for as long as you need to allocate so that the pools can keep being reused.
 ///
+/// Because each pool is only used for a single descriptor set layout, the pool sizes are computed
+/// from the descriptor counts of that layout, so allocation never fails with
+/// [`VulkanError::OutOfPoolMemory`] no matter how the descriptor types of different layouts are
+/// distributed. How many sets each pool holds is controlled by
+/// [`StandardDescriptorSetAllocatorCreateInfo::set_count`].
+///
 /// This allocator only needs to lock when a thread first allocates or when a thread that
 /// previously allocated exits. In all other cases, allocation is lock-free.
 ///
@@ -95,6 +99,7 @@ pub trait DescriptorSetAlloc: Send + Sync {
 pub struct StandardDescriptorSetAllocator {
     device: InstanceOwnedDebugWrapper<Arc<Device>>,
     pools: ThreadLocal<UnsafeCell<SortedMap<NonZeroU64, Entry>>>,
+    set_count: usize,
 }
 
 #[derive(Debug)]
@@ -110,11 +115,23 @@ unsafe impl Send for Entry {}
 
 impl StandardDescriptorSetAllocator {
     /// Creates a new `StandardDescriptorSetAllocator`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.set_count` is zero.
     #[inline]
-    pub fn new(device: Arc<Device>) -> StandardDescriptorSetAllocator {
+    pub fn new(
+        device: Arc<Device>,
+        create_info: StandardDescriptorSetAllocatorCreateInfo,
+    ) -> StandardDescriptorSetAllocator {
+        let StandardDescriptorSetAllocatorCreateInfo { set_count, _ne: _ } = create_info;
+
+        assert!(set_count != 0);
+
         StandardDescriptorSetAllocator {
             device: InstanceOwnedDebugWrapper(device),
             pools: ThreadLocal::new(),
+            set_count,
         }
     }
 
@@ -175,10 +192,12 @@ unsafe impl DescriptorSetAllocator for StandardDescriptorSetAllocator {
 
         let pools = self.pools.get_or(Default::default);
         let entry = unsafe { &mut *pools.get() }.get_or_try_insert(layout.id(), || {
+            let set_count = self.set_count.min(max_set_count(layout));
+
             if max_count == 0 {
-                FixedEntry::new(layout.clone()).map(Entry::Fixed)
+                FixedEntry::new(layout.clone(), set_count).map(Entry::Fixed)
             } else {
-                VariableEntry::new(layout.clone()).map(Entry::Variable)
+                VariableEntry::new(layout.clone(), set_count).map(Entry::Variable)
             }
         })?;
 
@@ -209,6 +228,46 @@ unsafe impl DeviceOwned for StandardDescriptorSetAllocator {
     }
 }
 
+/// Parameters to create a new [`StandardDescriptorSetAllocator`].
+#[derive(Clone, Debug)]
+pub struct StandardDescriptorSetAllocatorCreateInfo {
+    /// How many descriptor sets should be allocatable per pool.
+    ///
+    /// Each time a thread allocates using some descriptor set layout, and either no pools were
+    /// initialized yet or all pools are full, a new pool is created for that thread and
+    /// descriptor set layout combination. The pool is sized to hold exactly this many descriptor
+    /// sets of the layout. For layouts without a variable descriptor count, each new pool holds
+    /// twice as many sets as the previous one. If the layout contains so many descriptors that
+    /// the pool would exceed `u32::MAX` descriptors of some type, fewer sets are allocated per
+    /// pool.
+    ///
+    /// The default value is `256`.
+    pub set_count: usize,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for StandardDescriptorSetAllocatorCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        StandardDescriptorSetAllocatorCreateInfo {
+            set_count: 256,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+// Returns the maximum number of sets of `layout` that a pool can hold, such that the descriptor
+// counts of the pool fit in a `u32`.
+fn max_set_count(layout: &DescriptorSetLayout) -> usize {
+    layout
+        .descriptor_counts()
+        .values()
+        .fold(u32::MAX as usize, |max_set_count, &count| {
+            max_set_count.min((u32::MAX / count.max(1)) as usize)
+        })
+}
+
 #[derive(Debug)]
 struct FixedEntry {
     // The `FixedPool` struct contains an actual Vulkan pool. Every time it is full we create
@@ -221,10 +280,10 @@ struct FixedEntry {
 }
 
 impl FixedEntry {
-    fn new(layout: Arc<DescriptorSetLayout>) -> Result<Self, VulkanError> {
+    fn new(layout: Arc<DescriptorSetLayout>, set_count: usize) -> Result<Self, VulkanError> {
         Ok(FixedEntry {
-            pool: FixedPool::new(&layout, MAX_SETS)?,
-            set_count: MAX_SETS,
+            pool: FixedPool::new(&layout, set_count)?,
+            set_count,
             layout,
         })
     }
@@ -233,7 +292,7 @@ impl FixedEntry {
         let inner = if let Some(inner) = self.pool.reserve.pop() {
             inner
         } else {
-            self.set_count *= 2;
+            self.set_count = (self.set_count * 2).min(max_set_count(&self.layout));
             self.pool = FixedPool::new(&self.layout, self.set_count)?;
 
             self.pool.reserve.pop().unwrap()
@@ -323,17 +382,20 @@ struct VariableEntry {
     layout: Arc<DescriptorSetLayout>,
     // The number of sets currently allocated from the Vulkan pool.
     allocations: usize,
+    // The number of sets that each Vulkan pool can hold.
+    set_count: usize,
 }
 
 impl VariableEntry {
-    fn new(layout: Arc<DescriptorSetLayout>) -> Result<Self, VulkanError> {
+    fn new(layout: Arc<DescriptorSetLayout>, set_count: usize) -> Result<Self, VulkanError> {
         let reserve = Arc::new(ArrayQueue::new(MAX_POOLS));
 
         Ok(VariableEntry {
-            pool: VariablePool::new(&layout, reserve.clone())?,
+            pool: VariablePool::new(&layout, reserve.clone(), set_count)?,
             reserve,
             layout,
             allocations: 0,
+            set_count,
         })
     }
 
@@ -341,14 +403,14 @@ impl VariableEntry {
         &mut self,
         variable_descriptor_count: u32,
     ) -> Result<StandardDescriptorSetAlloc, VulkanError> {
-        if self.allocations >= MAX_SETS {
+        if self.allocations >= self.set_count {
             self.pool = if let Some(inner) = self.reserve.pop() {
                 Arc::new(VariablePool {
                     inner: ManuallyDrop::new(inner),
                     reserve: self.reserve.clone(),
                 })
             } else {
-                VariablePool::new(&self.layout, self.reserve.clone())?
+                VariablePool::new(&self.layout, self.reserve.clone(), self.set_count)?
             };
             self.allocations = 0;
         }
@@ -399,17 +461,18 @@ impl VariablePool {
     fn new(
         layout: &Arc<DescriptorSetLayout>,
         reserve: Arc<ArrayQueue<DescriptorPool>>,
+        set_count: usize,
     ) -> Result<Arc<Self>, VulkanError> {
         DescriptorPool::new(
             layout.device().clone(),
             DescriptorPoolCreateInfo {
-                max_sets: MAX_SETS as u32,
+                max_sets: set_count as u32,
                 pool_sizes: layout
                     .descriptor_counts()
                     .iter()
                     .map(|(&ty, &count)| {
                         assert!(ty != DescriptorType::InlineUniformBlock);
-                        (ty, count * MAX_SETS as u32)
+                        (ty, count * set_count as u32)
                     })
                     .collect(),
                 ..Default::default()
@@ -563,7 +626,7 @@ mod tests {
         )
         .unwrap();
 
-        let allocator = StandardDescriptorSetAllocator::new(device);
+        let allocator = StandardDescriptorSetAllocator::new(device, Default::default());
 
         let pool1 =
             if let AllocParent::Fixed(pool) = &allocator.allocate(&layout, 0).unwrap().parent {
@@ -584,4 +647,51 @@ mod tests {
         .join()
         .unwrap();
     }
+
+    #[test]
+    fn set_count_grows() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::UniformBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let allocator = StandardDescriptorSetAllocator::new(
+            device,
+            StandardDescriptorSetAllocatorCreateInfo {
+                set_count: 2,
+                ..Default::default()
+            },
+        );
+
+        let pools: Vec<_> = (0..3)
+            .map(|_| {
+                let alloc = allocator.allocate(&layout, 0).unwrap();
+
+                let max_sets = if let AllocParent::Fixed(pool) = &alloc.parent {
+                    pool._inner.max_sets()
+                } else {
+                    unreachable!()
+                };
+
+                (alloc, max_sets)
+            })
+            .collect();
+
+        assert_eq!(pools[0].1, 2);
+        assert_eq!(pools[1].1, 2);
+        assert_eq!(pools[2].1, 4);
+    }
 }
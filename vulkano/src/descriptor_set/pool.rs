@@ -184,7 +184,7 @@ impl DescriptorPool {
         self.max_inline_uniform_block_bindings
     }
 
-    /// Allocates descriptor sets from the pool, one for each element in `create_info`.
+    /// Allocates descriptor sets from the pool, one for each element in `allocate_info`.
     /// Returns an iterator to the allocated sets, or an error.
     ///
     /// The `FragmentedPool` errors often can't be prevented. If the function returns this error,
@@ -308,6 +308,12 @@ impl DescriptorPool {
     /// Resets the pool.
     ///
     /// This destroys all descriptor sets and empties the pool.
+    ///
+    /// # Safety
+    ///
+    /// - All descriptor sets that were allocated from the pool must not be in use by the GPU.
+    /// - The descriptor sets that were allocated from the pool must not be used again after this
+    ///   call, including freeing them with [`free_descriptor_sets`](Self::free_descriptor_sets).
     #[inline]
     pub unsafe fn reset(&self) -> Result<(), VulkanError> {
         let fns = self.device.fns();
@@ -537,7 +537,9 @@ impl WriteDescriptorSet {
                     {
                         // For push descriptors, we must write a dummy element.
                         if let WriteDescriptorSetElements::None(_) = elements {
-                            // Do nothing
+                            // The sampler comes from the layout, so there is nothing else to
+                            // validate.
+                            return Ok(());
                         } else {
                             return Err(Box::new(ValidationError {
                                 context: "elements".into(),
@@ -651,9 +653,8 @@ impl WriteDescriptorSet {
                             return Err(Box::new(ValidationError {
                                 context: format!("elements[{}]", index).into(),
                                 problem: "the descriptor type is \
-                                    `DescriptorType::SampledImage` or \
-                                    `DescriptorType::CombinedImageSampler`, and the image was not \
-                                    created with the `ImageUsage::SAMPLED` usage"
+                                    `DescriptorType::CombinedImageSampler`, but the usage of the \
+                                    image view does not include `ImageUsage::SAMPLED`"
                                     .into(),
                                 vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00337"],
                                 ..Default::default()
@@ -763,9 +764,8 @@ impl WriteDescriptorSet {
                             return Err(Box::new(ValidationError {
                                 context: format!("elements[{}]", index).into(),
                                 problem: "the descriptor type is \
-                                    `DescriptorType::SampledImage` or \
-                                    `DescriptorType::CombinedImageSampler`, and the image was not \
-                                    created with the `ImageUsage::SAMPLED` usage"
+                                    `DescriptorType::CombinedImageSampler`, but the usage of the \
+                                    image view does not include `ImageUsage::SAMPLED`"
                                     .into(),
                                 vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00337"],
                                 ..Default::default()
@@ -835,9 +835,8 @@ impl WriteDescriptorSet {
                         return Err(Box::new(ValidationError {
                             context: format!("elements[{}]", index).into(),
                             problem: "the descriptor type is \
-                                `DescriptorType::SampledImage` or \
-                                `DescriptorType::CombinedImageSampler`, and the image was not \
-                                created with the `ImageUsage::SAMPLED` usage"
+                                `DescriptorType::SampledImage`, but the usage of the image view \
+                                does not include `ImageUsage::SAMPLED`"
                                 .into(),
                             vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00337"],
                             ..Default::default()
@@ -913,8 +912,8 @@ impl WriteDescriptorSet {
                         return Err(Box::new(ValidationError {
                             context: format!("elements[{}]", index).into(),
                             problem: "the descriptor type is \
-                                `DescriptorType::StorageImage`, and the image was not \
-                                created with the `ImageUsage::STORAGE` usage"
+                                `DescriptorType::StorageImage`, but the usage of the image view \
+                                does not include `ImageUsage::STORAGE`"
                                 .into(),
                             vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00339"],
                             ..Default::default()
@@ -1174,8 +1173,8 @@ impl WriteDescriptorSet {
                         return Err(Box::new(ValidationError {
                             context: format!("elements[{}]", index).into(),
                             problem: "the descriptor type is \
-                                `DescriptorType::InputAttachment`, and the image was not \
-                                created with the `ImageUsage::INPUT_ATTACHMENT` usage"
+                                `DescriptorType::InputAttachment`, but the usage of the image view \
+                                does not include `ImageUsage::INPUT_ATTACHMENT`"
                                 .into(),
                             vuids: &["VUID-VkWriteDescriptorSet-descriptorType-00338"],
                             ..Default::default()
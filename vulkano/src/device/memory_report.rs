@@ -0,0 +1,175 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reports of the memory that the driver allocates for a device.
+//!
+//! With the [`device_memory_report`](crate::device::Features::device_memory_report) feature,
+//! callbacks can be registered when creating a device, through
+//! [`DeviceCreateInfo::device_memory_report_callbacks`]. The driver then calls them whenever it
+//! allocates, frees, imports or unimports memory on behalf of the device, including memory that
+//! is not visible to the application, such as the memory backing internal objects.
+//!
+//! # Examples
+//!
+//! ```
+//! # use vulkano::device::{DeviceCreateInfo, Features};
+//! use vulkano::device::memory_report::DeviceMemoryReportCallback;
+//!
+//! let create_info = DeviceCreateInfo {
+//!     enabled_features: Features {
+//!         device_memory_report: true,
+//!         ..Features::empty()
+//!     },
+//!     device_memory_report_callbacks: vec![unsafe {
+//!         DeviceMemoryReportCallback::new(|event| {
+//!             println!(
+//!                 "{:?}: {} bytes in heap {}",
+//!                 event.event_type, event.size, event.heap_index,
+//!             );
+//!         })
+//!     }],
+//!     ..Default::default()
+//! };
+//! ```
+//!
+//! [`DeviceCreateInfo::device_memory_report_callbacks`]: crate::device::DeviceCreateInfo::device_memory_report_callbacks
+
+use crate::{macros::vulkan_enum, DeviceSize};
+use std::{
+    ffi::c_void,
+    fmt::{Debug, Error as FmtError, Formatter},
+    panic::{catch_unwind, AssertUnwindSafe, RefUnwindSafe},
+    sync::Arc,
+};
+
+/// The callback function for device memory reports.
+pub struct DeviceMemoryReportCallback(CallbackData);
+
+type CallbackData = Box<dyn Fn(&MemoryReportEvent) + RefUnwindSafe + Send + Sync>;
+
+impl DeviceMemoryReportCallback {
+    /// Returns a new `DeviceMemoryReportCallback` wrapping the provided function.
+    ///
+    /// # Safety
+    ///
+    /// - `func` must not make any calls to the Vulkan API.
+    pub unsafe fn new(
+        func: impl Fn(&MemoryReportEvent) + RefUnwindSafe + Send + Sync + 'static,
+    ) -> Arc<Self> {
+        Arc::new(Self(Box::new(func)))
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const CallbackData {
+        &self.0 as _
+    }
+}
+
+impl Debug for DeviceMemoryReportCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.debug_struct("DeviceMemoryReportCallback")
+            .finish_non_exhaustive()
+    }
+}
+
+pub(super) unsafe extern "system" fn trampoline(
+    callback_data_vk: *const ash::vk::DeviceMemoryReportCallbackDataEXT,
+    user_data_vk: *mut c_void,
+) {
+    // Since we box the closure, the type system doesn't detect that the `UnwindSafe`
+    // bound is enforced. Therefore we enforce it manually.
+    let _ = catch_unwind(AssertUnwindSafe(move || {
+        let ash::vk::DeviceMemoryReportCallbackDataEXT {
+            s_type: _,
+            p_next: _,
+            flags: _,
+            ty,
+            memory_object_id,
+            size,
+            object_type,
+            object_handle,
+            heap_index,
+        } = *callback_data_vk;
+
+        // Drivers may report event types that vulkano doesn't know about yet.
+        let event_type = match MemoryReportEventType::try_from(ty) {
+            Ok(event_type) => event_type,
+            Err(()) => return,
+        };
+
+        let event = MemoryReportEvent {
+            event_type,
+            memory_object_id,
+            size,
+            object_type,
+            object_handle,
+            heap_index,
+        };
+
+        let user_callback = &*(user_data_vk as *mut CallbackData as *const CallbackData);
+
+        user_callback(&event);
+    }));
+}
+
+/// An event that is reported to a [`DeviceMemoryReportCallback`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct MemoryReportEvent {
+    /// The kind of event.
+    pub event_type: MemoryReportEventType,
+
+    /// A unique identifier of the memory object that the event is about.
+    ///
+    /// The events about the same memory object have the same identifier, from its allocation or
+    /// import until it is freed or unimported.
+    pub memory_object_id: u64,
+
+    /// The size of the memory object in bytes.
+    ///
+    /// This is only meaningful for the `Allocate`, `Import` and `AllocationFailed` events.
+    pub size: DeviceSize,
+
+    /// The type of the Vulkan object that the memory object is associated with.
+    ///
+    /// If the memory was not allocated for a specific Vulkan object, this is
+    /// `ash::vk::ObjectType::UNKNOWN`.
+    pub object_type: ash::vk::ObjectType,
+
+    /// The raw handle of the Vulkan object that the memory object is associated with, or 0 if
+    /// there is no such object or it is not known yet.
+    pub object_handle: u64,
+
+    /// The index of the memory heap that the memory object is in, or that the allocation was
+    /// attempted in.
+    ///
+    /// This is only meaningful for the `Allocate`, `Import` and `AllocationFailed` events.
+    pub heap_index: u32,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The kind of a [`MemoryReportEvent`].
+    MemoryReportEventType = DeviceMemoryReportEventTypeEXT(i32);
+
+    /// The driver allocated memory.
+    Allocate = ALLOCATE,
+
+    /// The driver freed memory.
+    Free = FREE,
+
+    /// Memory was imported from an external handle.
+    Import = IMPORT,
+
+    /// Imported memory was released.
+    Unimport = UNIMPORT,
+
+    /// The driver failed to allocate memory.
+    AllocationFailed = ALLOCATION_FAILED,
+}
@@ -101,11 +101,11 @@
 //!
 //! TODO: write
 
-use self::physical::PhysicalDevice;
 pub(crate) use self::properties::PropertiesFfi;
+use self::{memory_report::DeviceMemoryReportCallback, physical::PhysicalDevice};
 pub use self::{
     properties::Properties,
-    queue::{Queue, QueueFamilyProperties, QueueFlags, QueueGuard},
+    queue::{Queue, QueueFamilyProperties, QueueFlags, QueueGlobalPriority, QueueGuard},
 };
 pub use crate::fns::DeviceFunctions;
 use crate::{
@@ -113,20 +113,24 @@ use crate::{
         AccelerationStructureBuildGeometryInfo, AccelerationStructureBuildSizesInfo,
         AccelerationStructureBuildType, AccelerationStructureGeometries,
     },
+    buffer::{self, BufferCreateInfo},
     descriptor_set::layout::{
         DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo, DescriptorSetLayoutSupport,
     },
+    image::{ImageCreateFlags, ImageCreateInfo, ImageTiling},
     instance::{Instance, InstanceOwned, InstanceOwnedDebugWrapper},
-    macros::{impl_id_counter, vulkan_bitflags},
-    memory::ExternalMemoryHandleType,
-    OomError, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
-    VulkanError, VulkanObject,
+    macros::{impl_id_counter, vulkan_bitflags, vulkan_enum},
+    memory::{ExternalMemoryHandleType, MemoryRequirements},
+    sync::fence::Fence,
+    DeviceSize, OomError, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError,
+    Version, VulkanError, VulkanObject,
 };
 use ash::vk::Handle;
+use bytemuck::cast_slice;
 use parking_lot::Mutex;
 use smallvec::{smallvec, SmallVec};
 use std::{
-    ffi::CString,
+    ffi::{c_char, c_void, CString},
     fmt::{Debug, Error as FmtError, Formatter},
     fs::File,
     mem::MaybeUninit,
@@ -134,11 +138,13 @@ use std::{
     ops::Deref,
     ptr, slice,
     sync::{
-        atomic::{AtomicU32, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc, Weak,
     },
+    time::Duration,
 };
 
+pub mod memory_report;
 pub mod physical;
 pub(crate) mod properties;
 mod queue;
@@ -167,9 +173,13 @@ pub struct Device {
     // This is required for validation in `memory::device_memory`, the count must only be modified
     // in that module.
     pub(crate) allocation_count: AtomicU32,
+    lost: AtomicBool,
     fence_pool: Mutex<Vec<ash::vk::Fence>>,
     semaphore_pool: Mutex<Vec<ash::vk::Semaphore>>,
     event_pool: Mutex<Vec<ash::vk::Event>>,
+    // The queues of the device, for `wait_idle_queues_with_timeout`.
+    queues: Mutex<Vec<Weak<Queue>>>,
+    _memory_report_callbacks: Vec<Arc<DeviceMemoryReportCallback>>,
 }
 
 impl Device {
@@ -198,6 +208,7 @@ impl Device {
             enabled_extensions: _,
             enabled_features: _,
             ref physical_devices,
+            device_memory_report_callbacks: _,
             _ne: _,
         } = create_info;
 
@@ -283,26 +294,47 @@ impl Device {
             ref enabled_extensions,
             ref enabled_features,
             ref physical_devices,
+            ref device_memory_report_callbacks,
             _ne: _,
         } = &create_info;
 
-        let queue_create_infos_vk: SmallVec<[_; 2]> = queue_create_infos
+        let mut queue_global_priority_create_infos_vk: SmallVec<[_; 2]> = queue_create_infos
             .iter()
             .map(|queue_create_info| {
+                queue_create_info.global_priority.map(|global_priority| {
+                    ash::vk::DeviceQueueGlobalPriorityCreateInfoKHR {
+                        global_priority: global_priority.into(),
+                        ..Default::default()
+                    }
+                })
+            })
+            .collect();
+
+        let queue_create_infos_vk: SmallVec<[_; 2]> = queue_create_infos
+            .iter()
+            .zip(queue_global_priority_create_infos_vk.iter_mut())
+            .map(|(queue_create_info, global_priority_create_info_vk)| {
                 let &QueueCreateInfo {
                     flags,
                     queue_family_index,
                     ref queues,
+                    global_priority: _,
                     _ne: _,
                 } = queue_create_info;
 
-                ash::vk::DeviceQueueCreateInfo {
+                let mut queue_create_info_vk = ash::vk::DeviceQueueCreateInfo {
                     flags: flags.into(),
                     queue_family_index,
                     queue_count: queues.len() as u32,
                     p_queue_priorities: queues.as_ptr(),
                     ..Default::default()
+                };
+
+                if let Some(next) = global_priority_create_info_vk {
+                    queue_create_info_vk.p_next = next as *const _ as *const _;
                 }
+
+                queue_create_info_vk
             })
             .collect();
 
@@ -368,6 +400,22 @@ impl Device {
             create_info_vk.p_enabled_features = &features_ffi.head_as_ref().features;
         }
 
+        let mut device_memory_report_create_infos_vk: SmallVec<[_; 2]> =
+            device_memory_report_callbacks
+                .iter()
+                .map(|callback| ash::vk::DeviceDeviceMemoryReportCreateInfoEXT {
+                    flags: ash::vk::DeviceMemoryReportFlagsEXT::empty(),
+                    pfn_user_callback: Some(memory_report::trampoline),
+                    p_user_data: callback.as_ptr() as *const c_void as *mut _,
+                    ..Default::default()
+                })
+                .collect();
+
+        for next in &mut device_memory_report_create_infos_vk {
+            next.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = next as *const _ as *const _;
+        }
+
         let handle = unsafe {
             let fns = physical_device.instance().fns();
             let mut output = MaybeUninit::uninit();
@@ -401,6 +449,7 @@ impl Device {
             enabled_features,
             enabled_extensions,
             physical_devices,
+            device_memory_report_callbacks,
             _ne: _,
         } = create_info;
 
@@ -425,6 +474,7 @@ impl Device {
                 flags,
                 queue_family_index,
                 ref queues,
+                global_priority: _,
                 _ne: _,
             } = queue_create_info;
 
@@ -462,9 +512,12 @@ impl Device {
             active_queue_family_indices,
 
             allocation_count: AtomicU32::new(0),
+            lost: AtomicBool::new(false),
             fence_pool: Mutex::new(Vec::new()),
             semaphore_pool: Mutex::new(Vec::new()),
             event_pool: Mutex::new(Vec::new()),
+            queues: Mutex::new(Vec::new()),
+            _memory_report_callbacks: device_memory_report_callbacks,
         });
 
         let queues_iter = {
@@ -796,6 +849,91 @@ impl Device {
         compatibility_vk == ash::vk::AccelerationStructureCompatibilityKHR::COMPATIBLE
     }
 
+    /// Returns the memory requirements that a buffer created with `create_info` would have,
+    /// without creating the buffer.
+    ///
+    /// The returned requirements are the same as the
+    /// [`memory_requirements`](crate::buffer::sys::RawBuffer::memory_requirements) of a
+    /// `RawBuffer` created with `create_info`.
+    ///
+    /// The device API version must be at least 1.3, or the [`khr_maintenance4`] extension must
+    /// be enabled on the device.
+    ///
+    /// [`khr_maintenance4`]: crate::device::DeviceExtensions::khr_maintenance4
+    #[inline]
+    pub fn buffer_memory_requirements(
+        &self,
+        create_info: &BufferCreateInfo,
+    ) -> Result<MemoryRequirements, Box<ValidationError>> {
+        self.validate_buffer_memory_requirements(create_info)?;
+
+        unsafe { Ok(self.buffer_memory_requirements_unchecked(create_info)) }
+    }
+
+    fn validate_buffer_memory_requirements(
+        &self,
+        create_info: &BufferCreateInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self.api_version() >= Version::V1_3 || self.enabled_extensions().khr_maintenance4) {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                    RequiresAllOf(&[Requires::DeviceExtension("khr_maintenance4")]),
+                ]),
+                ..Default::default()
+            }));
+        }
+
+        // VUID-VkDeviceBufferMemoryRequirements-pCreateInfo-parameter
+        create_info
+            .validate(self)
+            .map_err(|err| err.add_context("create_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn buffer_memory_requirements_unchecked(
+        &self,
+        create_info: &BufferCreateInfo,
+    ) -> MemoryRequirements {
+        let mut memory_requirements2_vk = ash::vk::MemoryRequirements2::default();
+        let mut memory_dedicated_requirements_vk = ash::vk::MemoryDedicatedRequirements::default();
+        memory_requirements2_vk.p_next = &mut memory_dedicated_requirements_vk as *mut _ as *mut _;
+
+        create_info.with_vk(|create_info_vk| {
+            let info_vk = ash::vk::DeviceBufferMemoryRequirements {
+                p_create_info: create_info_vk,
+                ..Default::default()
+            };
+
+            let fns = self.fns();
+
+            if self.api_version() >= Version::V1_3 {
+                (fns.v1_3.get_device_buffer_memory_requirements)(
+                    self.handle,
+                    &info_vk,
+                    &mut memory_requirements2_vk,
+                );
+            } else {
+                (fns.khr_maintenance4
+                    .get_device_buffer_memory_requirements_khr)(
+                    self.handle,
+                    &info_vk,
+                    &mut memory_requirements2_vk,
+                );
+            }
+        });
+
+        let mut memory_requirements = MemoryRequirements::from_vk(
+            &memory_requirements2_vk,
+            Some(&memory_dedicated_requirements_vk),
+        );
+        buffer::sys::align_memory_requirements(self, create_info.usage, &mut memory_requirements);
+
+        memory_requirements
+    }
+
     /// Returns whether a descriptor set layout with the given `create_info` could be created
     /// on the device, and additional supported properties where relevant. `Some` is returned if
     /// the descriptor set layout is supported, `None` if it is not.
@@ -942,6 +1080,121 @@ impl Device {
         })
     }
 
+    /// Returns the memory requirements that an image created with `create_info` would have,
+    /// without creating the image.
+    ///
+    /// If `create_info.flags` contains [`ImageCreateFlags::DISJOINT`], the requirements of each
+    /// plane of the format are returned. Otherwise, a single element is returned. This matches
+    /// the [`memory_requirements`](crate::image::sys::RawImage::memory_requirements) of a
+    /// `RawImage` created with `create_info`.
+    ///
+    /// The device API version must be at least 1.3, or the [`khr_maintenance4`] extension must
+    /// be enabled on the device. `create_info.tiling` must not be
+    /// [`ImageTiling::DrmFormatModifier`].
+    ///
+    /// [`khr_maintenance4`]: crate::device::DeviceExtensions::khr_maintenance4
+    #[inline]
+    pub fn image_memory_requirements(
+        &self,
+        create_info: &ImageCreateInfo,
+    ) -> Result<SmallVec<[MemoryRequirements; 4]>, Box<ValidationError>> {
+        self.validate_image_memory_requirements(create_info)?;
+
+        unsafe { Ok(self.image_memory_requirements_unchecked(create_info)) }
+    }
+
+    fn validate_image_memory_requirements(
+        &self,
+        create_info: &ImageCreateInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self.api_version() >= Version::V1_3 || self.enabled_extensions().khr_maintenance4) {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                    RequiresAllOf(&[Requires::DeviceExtension("khr_maintenance4")]),
+                ]),
+                ..Default::default()
+            }));
+        }
+
+        // VUID-VkDeviceImageMemoryRequirements-pCreateInfo-parameter
+        create_info
+            .validate(self)
+            .map_err(|err| err.add_context("create_info"))?;
+
+        if create_info.tiling == ImageTiling::DrmFormatModifier {
+            return Err(Box::new(ValidationError {
+                context: "create_info.tiling".into(),
+                problem: "is `ImageTiling::DrmFormatModifier`".into(),
+                vuids: &["VUID-VkDeviceImageMemoryRequirements-pCreateInfo-06776"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn image_memory_requirements_unchecked(
+        &self,
+        create_info: &ImageCreateInfo,
+    ) -> SmallVec<[MemoryRequirements; 4]> {
+        // VUID-VkDeviceImageMemoryRequirements-pCreateInfo-06417
+        // VUID-VkDeviceImageMemoryRequirements-pCreateInfo-06419
+        let plane_aspects: SmallVec<[_; 3]> =
+            if create_info.flags.intersects(ImageCreateFlags::DISJOINT) {
+                [
+                    ash::vk::ImageAspectFlags::PLANE_0,
+                    ash::vk::ImageAspectFlags::PLANE_1,
+                    ash::vk::ImageAspectFlags::PLANE_2,
+                ][..create_info.format.planes().len()]
+                    .into()
+            } else {
+                smallvec![ash::vk::ImageAspectFlags::empty()]
+            };
+
+        create_info.with_vk(|create_info_vk| {
+            plane_aspects
+                .into_iter()
+                .map(|plane_aspect| {
+                    let info_vk = ash::vk::DeviceImageMemoryRequirements {
+                        p_create_info: create_info_vk,
+                        plane_aspect,
+                        ..Default::default()
+                    };
+
+                    let mut memory_requirements2_vk = ash::vk::MemoryRequirements2::default();
+                    let mut memory_dedicated_requirements_vk =
+                        ash::vk::MemoryDedicatedRequirements::default();
+                    memory_requirements2_vk.p_next =
+                        &mut memory_dedicated_requirements_vk as *mut _ as *mut _;
+
+                    let fns = self.fns();
+
+                    if self.api_version() >= Version::V1_3 {
+                        (fns.v1_3.get_device_image_memory_requirements)(
+                            self.handle,
+                            &info_vk,
+                            &mut memory_requirements2_vk,
+                        );
+                    } else {
+                        (fns.khr_maintenance4
+                            .get_device_image_memory_requirements_khr)(
+                            self.handle,
+                            &info_vk,
+                            &mut memory_requirements2_vk,
+                        );
+                    }
+
+                    MemoryRequirements::from_vk(
+                        &memory_requirements2_vk,
+                        Some(&memory_dedicated_requirements_vk),
+                    )
+                })
+                .collect()
+        })
+    }
+
     /// Retrieves the properties of an external file descriptor when imported as a given external
     /// handle type.
     ///
@@ -1074,14 +1327,187 @@ impl Device {
     /// of the device (either explicitly or implicitly, for example with a future's destructor)
     /// while this function is waiting.
     #[inline]
-    pub unsafe fn wait_idle(&self) -> Result<(), OomError> {
+    pub unsafe fn wait_idle(&self) -> Result<(), VulkanError> {
         let fns = self.fns();
         (fns.v1_0.device_wait_idle)(self.handle)
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|err| self.check_lost(VulkanError::from(err)))?;
+
+        Ok(())
+    }
+
+    /// Waits until all work that has been submitted to the queues of this device has finished,
+    /// or until `timeout` has elapsed.
+    ///
+    /// Vulkan has no way to wait for the whole device with a timeout, so this is an
+    /// approximation of [`wait_idle`](Self::wait_idle): an empty batch that signals a fence is
+    /// submitted to each queue that is still alive, and then all of the fences are waited on.
+    /// Work that is submitted by other threads while this function is waiting is not waited
+    /// for.
+    ///
+    /// Returns [`VulkanError::Timeout`] if the work did not finish within `timeout`, and
+    /// [`VulkanError::DeviceLost`] if the device is or becomes lost. Unlike `wait_idle`, this
+    /// function is safe to call while other threads submit work to the queues.
+    pub fn wait_idle_queues_with_timeout(&self, timeout: Duration) -> Result<(), VulkanError> {
+        if self.is_lost() {
+            return Err(VulkanError::DeviceLost);
+        }
+
+        let queues: SmallVec<[_; 4]> = {
+            let mut queues = self.queues.lock();
+            queues.retain(|queue| queue.strong_count() > 0);
+            queues.iter().filter_map(Weak::upgrade).collect()
+        };
+
+        let mut fences: SmallVec<[_; 4]> = SmallVec::with_capacity(queues.len());
+
+        for queue in &queues {
+            let fence = Arc::new(Fence::from_pool(queue.device().clone())?);
+
+            // SAFETY: The fence is new, so it is unsignaled and not in use by any other
+            // queue operation.
+            queue.with(|mut q| unsafe { q.submit_unchecked([], Some(fence.clone())) })?;
+            fences.push(fence);
+        }
+
+        unsafe { Fence::multi_wait_unchecked(fences.iter().map(AsRef::as_ref), Some(timeout)) }
+    }
+
+    /// Returns whether the device has been lost.
+    ///
+    /// The device is marked as lost as soon as a Vulkan function returns
+    /// [`VulkanError::DeviceLost`]. After that, submitting work to a queue, presenting and
+    /// acquiring swapchain images fail with `VulkanError::DeviceLost` without calling into
+    /// Vulkan. Waiting on queues, fences and semaphores still returns in finite time, so that
+    /// objects can be dropped. To recover, the device and all objects created from it must be
+    /// recreated.
+    #[inline]
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::Acquire)
+    }
+
+    /// Marks the device as lost if `err` is `VulkanError::DeviceLost`, then returns `err`.
+    pub(crate) fn check_lost(&self, err: VulkanError) -> VulkanError {
+        if err == VulkanError::DeviceLost {
+            self.lost.store(true, Ordering::Release);
+        }
+
+        err
+    }
+
+    /// Retrieves information about the fault that caused the device to be lost.
+    ///
+    /// The [`device_fault`](Features::device_fault) feature must be enabled on the device, and
+    /// the device must be [lost](Self::is_lost). The vendor binary crash dump is only retrieved
+    /// if the [`device_fault_vendor_binary`](Features::device_fault_vendor_binary) feature is
+    /// enabled as well.
+    pub fn get_fault_info(&self) -> Result<DeviceFaultInfo, Validated<VulkanError>> {
+        self.validate_get_fault_info()?;
+
+        unsafe { Ok(self.get_fault_info_unchecked()?) }
+    }
+
+    fn validate_get_fault_info(&self) -> Result<(), Box<ValidationError>> {
+        if !self.enabled_features().device_fault {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "device_fault",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self.is_lost() {
+            return Err(Box::new(ValidationError {
+                problem: "the device has not been lost".into(),
+                vuids: &["VUID-vkGetDeviceFaultInfoEXT-device-07336"],
+                ..Default::default()
+            }));
+        }
 
         Ok(())
     }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn get_fault_info_unchecked(&self) -> Result<DeviceFaultInfo, VulkanError> {
+        let fns = self.fns();
+
+        loop {
+            let mut counts_vk = ash::vk::DeviceFaultCountsEXT::default();
+            (fns.ext_device_fault.get_device_fault_info_ext)(
+                self.handle,
+                &mut counts_vk,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            if !self.enabled_features().device_fault_vendor_binary {
+                counts_vk.vendor_binary_size = 0;
+            }
+
+            let mut address_infos_vk = Vec::with_capacity(counts_vk.address_info_count as usize);
+            let mut vendor_infos_vk = Vec::with_capacity(counts_vk.vendor_info_count as usize);
+            let mut vendor_binary_data: Vec<u8> =
+                Vec::with_capacity(counts_vk.vendor_binary_size as usize);
+
+            let mut info_vk = ash::vk::DeviceFaultInfoEXT {
+                p_address_infos: address_infos_vk.as_mut_ptr(),
+                p_vendor_infos: vendor_infos_vk.as_mut_ptr(),
+                p_vendor_binary_data: if counts_vk.vendor_binary_size != 0 {
+                    vendor_binary_data.as_mut_ptr() as *mut _
+                } else {
+                    ptr::null_mut()
+                },
+                ..Default::default()
+            };
+
+            let result = (fns.ext_device_fault.get_device_fault_info_ext)(
+                self.handle,
+                &mut counts_vk,
+                &mut info_vk,
+            );
+
+            match result {
+                ash::vk::Result::INCOMPLETE => (),
+                ash::vk::Result::SUCCESS => {
+                    address_infos_vk.set_len(counts_vk.address_info_count as usize);
+                    vendor_infos_vk.set_len(counts_vk.vendor_info_count as usize);
+                    vendor_binary_data.set_len(counts_vk.vendor_binary_size as usize);
+
+                    return Ok(DeviceFaultInfo {
+                        description: string_from_array(&info_vk.description),
+                        address_infos: address_infos_vk
+                            .into_iter()
+                            .map(|address_info_vk| DeviceFaultAddressInfo {
+                                address_type: address_info_vk.address_type.try_into().unwrap(),
+                                reported_address: address_info_vk.reported_address,
+                                address_precision: address_info_vk.address_precision,
+                            })
+                            .collect(),
+                        vendor_infos: vendor_infos_vk
+                            .into_iter()
+                            .map(|vendor_info_vk| DeviceFaultVendorInfo {
+                                description: string_from_array(&vendor_info_vk.description),
+                                vendor_fault_code: vendor_info_vk.vendor_fault_code,
+                                vendor_fault_data: vendor_info_vk.vendor_fault_data,
+                            })
+                            .collect(),
+                        vendor_binary_data,
+                    });
+                }
+                err => return Err(VulkanError::from(err)),
+            }
+        }
+    }
+}
+
+/// Converts a fixed-size string array that is returned by the driver into a `String`, stopping
+/// at the first nul byte, or at the end of the array if there is none.
+pub(crate) fn string_from_array(array: &[c_char]) -> String {
+    let bytes = cast_slice(array);
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[0..end]).into()
 }
 
 impl Debug for Device {
@@ -1100,9 +1526,12 @@ impl Debug for Device {
             active_queue_family_indices,
 
             allocation_count,
+            lost,
             fence_pool: _,
             semaphore_pool: _,
             event_pool: _,
+            queues: _,
+            _memory_report_callbacks: _,
         } = self;
 
         f.debug_struct("Device")
@@ -1116,6 +1545,7 @@ impl Debug for Device {
             .field("fns", fns)
             .field("active_queue_family_indices", active_queue_family_indices)
             .field("allocation_count", allocation_count)
+            .field("lost", lost)
             .finish_non_exhaustive()
     }
 }
@@ -1214,6 +1644,15 @@ pub struct DeviceCreateInfo {
     /// [`khr_device_group`]: crate::device::DeviceExtensions::khr_device_group
     pub physical_devices: SmallVec<[Arc<PhysicalDevice>; 2]>,
 
+    /// Callbacks that the driver calls when it allocates, frees, imports or unimports memory for
+    /// the device. See the [`memory_report`] module for more information.
+    ///
+    /// If this is not empty, the [`device_memory_report`](Features::device_memory_report)
+    /// feature must be enabled in `enabled_features`.
+    ///
+    /// The default value is empty.
+    pub device_memory_report_callbacks: Vec<Arc<DeviceMemoryReportCallback>>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1225,6 +1664,7 @@ impl Default for DeviceCreateInfo {
             enabled_extensions: DeviceExtensions::empty(),
             enabled_features: Features::empty(),
             physical_devices: SmallVec::new(),
+            device_memory_report_callbacks: Vec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1240,6 +1680,7 @@ impl DeviceCreateInfo {
             ref enabled_extensions,
             ref enabled_features,
             ref physical_devices,
+            ref device_memory_report_callbacks,
             _ne: _,
         } = self;
 
@@ -1261,6 +1702,7 @@ impl DeviceCreateInfo {
                 flags: _,
                 queue_family_index,
                 queues: _,
+                global_priority: _,
                 _ne: _,
             } = queue_create_info;
 
@@ -1529,6 +1971,17 @@ impl DeviceCreateInfo {
             }
         }
 
+        if !device_memory_report_callbacks.is_empty() && !enabled_features.device_memory_report {
+            return Err(Box::new(ValidationError {
+                context: "device_memory_report_callbacks".into(),
+                problem: "is not empty".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "device_memory_report",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 }
@@ -1556,6 +2009,26 @@ pub struct QueueCreateInfo {
     /// The default value is a single queue with a priority of 0.5.
     pub queues: Vec<f32>,
 
+    /// The priority of the queues relative to queues in other processes and devices on the
+    /// system. Unlike the priorities in `queues`, this affects how the driver schedules work
+    /// system-wide.
+    ///
+    /// If set to `Some`, the [`khr_global_priority`] or [`ext_global_priority`] extension must be
+    /// enabled on the device. If the [`global_priority_query`] feature is enabled, then the
+    /// priority must be one of the [`supported_global_priorities`] of the queue family.
+    ///
+    /// Priorities above [`QueueGlobalPriority::Medium`] may require the process to have
+    /// additional privileges. If the caller doesn't have them, then [`Device::new`] returns
+    /// [`VulkanError::NotPermitted`], and you may want to retry with a lower priority.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`khr_global_priority`]: crate::device::DeviceExtensions::khr_global_priority
+    /// [`ext_global_priority`]: crate::device::DeviceExtensions::ext_global_priority
+    /// [`global_priority_query`]: crate::device::Features::global_priority_query
+    /// [`supported_global_priorities`]: QueueFamilyProperties::supported_global_priorities
+    pub global_priority: Option<QueueGlobalPriority>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1566,6 +2039,7 @@ impl Default for QueueCreateInfo {
             flags: QueueCreateFlags::empty(),
             queue_family_index: 0,
             queues: vec![0.5],
+            global_priority: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1582,6 +2056,7 @@ impl QueueCreateInfo {
             flags,
             queue_family_index,
             ref queues,
+            global_priority,
             _ne: _,
         } = self;
 
@@ -1639,6 +2114,49 @@ impl QueueCreateInfo {
             }
         }
 
+        if let Some(global_priority) = global_priority {
+            if !(device_extensions.khr_global_priority || device_extensions.ext_global_priority) {
+                return Err(Box::new(ValidationError {
+                    context: "global_priority".into(),
+                    problem: "is `Some`".into(),
+                    requires_one_of: RequiresOneOf(&[
+                        RequiresAllOf(&[Requires::DeviceExtension("khr_global_priority")]),
+                        RequiresAllOf(&[Requires::DeviceExtension("ext_global_priority")]),
+                    ]),
+                    ..Default::default()
+                }));
+            }
+
+            global_priority
+                .validate_device_raw(
+                    physical_device.api_version(),
+                    device_features,
+                    device_extensions,
+                    physical_device.instance().enabled_extensions(),
+                )
+                .map_err(|err| ValidationError {
+                    context: "global_priority".into(),
+                    vuids: &[
+                        "VUID-VkDeviceQueueGlobalPriorityCreateInfoKHR-globalPriority-parameter",
+                    ],
+                    ..ValidationError::from_requirement(err)
+                })?;
+
+            if device_features.global_priority_query
+                && !queue_family_properties
+                    .supported_global_priorities
+                    .contains(&global_priority)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the `global_priority_query` feature is enabled, but \
+                        `global_priority` is not one of the supported global priorities of the \
+                        queue family indicated by `queue_family_index`"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
         Ok(())
     }
 }
@@ -1728,6 +2246,85 @@ impl<T> Deref for DeviceOwnedDebugWrapper<T> {
     }
 }
 
+/// Information about the fault that caused a device to be lost.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DeviceFaultInfo {
+    /// A human-readable description of the fault.
+    pub description: String,
+
+    /// The device addresses that are related to the fault.
+    pub address_infos: Vec<DeviceFaultAddressInfo>,
+
+    /// Vendor-specific information about the fault.
+    pub vendor_infos: Vec<DeviceFaultVendorInfo>,
+
+    /// A vendor-specific binary crash dump, which can be processed by vendor tools.
+    ///
+    /// This is empty if the
+    /// [`device_fault_vendor_binary`](Features::device_fault_vendor_binary) feature is not
+    /// enabled.
+    pub vendor_binary_data: Vec<u8>,
+}
+
+/// A device address that is related to a device fault.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct DeviceFaultAddressInfo {
+    /// The kind of fault that happened at the address.
+    pub address_type: DeviceFaultAddressType,
+
+    /// The address that was reported by the device.
+    pub reported_address: DeviceSize,
+
+    /// The precision of `reported_address`, which is a power of two. The faulting address lies
+    /// somewhere in the range of `address_precision` bytes that contains `reported_address`,
+    /// aligned to `address_precision`.
+    pub address_precision: DeviceSize,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The kind of fault that happened at a device address.
+    DeviceFaultAddressType = DeviceFaultAddressTypeEXT(i32);
+
+    /// No address information is available.
+    None = NONE,
+
+    /// The device tried to read from an invalid address.
+    ReadInvalid = READ_INVALID,
+
+    /// The device tried to write to an invalid address.
+    WriteInvalid = WRITE_INVALID,
+
+    /// The device tried to execute instructions at an invalid address.
+    ExecuteInvalid = EXECUTE_INVALID,
+
+    /// The address is an instruction pointer, which may not be related to the fault.
+    InstructionPointerUnknown = INSTRUCTION_POINTER_UNKNOWN,
+
+    /// The address is the instruction pointer of an instruction that caused an invalid access.
+    InstructionPointerInvalid = INSTRUCTION_POINTER_INVALID,
+
+    /// The address is the instruction pointer of an instruction that caused a fault.
+    InstructionPointerFault = INSTRUCTION_POINTER_FAULT,
+}
+
+/// Vendor-specific information about a device fault.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DeviceFaultVendorInfo {
+    /// A human-readable description of the fault.
+    pub description: String,
+
+    /// The vendor-specific fault code.
+    pub vendor_fault_code: u64,
+
+    /// Vendor-specific data that is associated with the fault.
+    pub vendor_fault_data: u64,
+}
+
 /// The properties of a Unix file descriptor when it is imported.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -1738,8 +2335,15 @@ pub struct MemoryFdProperties {
 
 #[cfg(test)]
 mod tests {
-    use crate::device::{Device, DeviceCreateInfo, DeviceExtensions, Features, QueueCreateInfo};
-    use std::{ffi::CString, sync::Arc};
+    use crate::{
+        buffer::{sys::RawBuffer, BufferCreateInfo, BufferUsage},
+        device::{
+            memory_report::DeviceMemoryReportCallback, Device, DeviceCreateInfo, DeviceExtensions,
+            Features, QueueCreateInfo,
+        },
+        Version,
+    };
+    use std::{ffi::CString, sync::Arc, time::Duration};
 
     #[test]
     fn empty_extensions() {
@@ -1783,6 +2387,13 @@ mod tests {
         assert!(Arc::get_mut(&mut device).is_some());
     }
 
+    #[test]
+    fn not_lost() {
+        let (device, _) = gfx_dev_and_queue!();
+        assert!(!device.is_lost());
+        assert!(device.get_fault_info().is_err());
+    }
+
     #[test]
     fn too_many_queues() {
         let instance = instance!();
@@ -1815,6 +2426,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn buffer_memory_requirements() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let create_info = BufferCreateInfo {
+            size: 1024,
+            usage: BufferUsage::UNIFORM_BUFFER,
+            ..Default::default()
+        };
+
+        if !(device.api_version() >= Version::V1_3 || device.enabled_extensions().khr_maintenance4)
+        {
+            assert!(device.buffer_memory_requirements(&create_info).is_err());
+            return;
+        }
+
+        let memory_requirements = device.buffer_memory_requirements(&create_info).unwrap();
+        let buffer = RawBuffer::new(device, create_info).unwrap();
+        assert_eq!(
+            memory_requirements.layout,
+            buffer.memory_requirements().layout,
+        );
+        assert_eq!(
+            memory_requirements.memory_type_bits,
+            buffer.memory_requirements().memory_type_bits,
+        );
+    }
+
+    #[test]
+    fn memory_report_requires_feature() {
+        let instance = instance!();
+        let physical_device = match instance.enumerate_physical_devices().unwrap().next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let callback = unsafe { DeviceMemoryReportCallback::new(|_| ()) };
+
+        assert!(Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo {
+                    queue_family_index: 0,
+                    ..Default::default()
+                }],
+                device_memory_report_callbacks: vec![callback],
+                ..Default::default()
+            },
+        )
+        .is_err());
+    }
+
     #[test]
     fn unsupported_features() {
         let instance = instance!();
@@ -1886,4 +2549,19 @@ mod tests {
             panic!();
         }
     }
+
+    #[test]
+    fn wait_idle_queues_with_timeout() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        device
+            .wait_idle_queues_with_timeout(Duration::from_secs(5))
+            .unwrap();
+
+        // Dropped queues are not waited on.
+        drop(queue);
+        device
+            .wait_idle_queues_with_timeout(Duration::from_secs(5))
+            .unwrap();
+    }
 }
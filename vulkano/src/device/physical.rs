@@ -12,10 +12,10 @@ use crate::{
     buffer::{ExternalBufferInfo, ExternalBufferProperties},
     cache::OnceCache,
     device::{properties::Properties, DeviceExtensions, Features, FeaturesFfi, PropertiesFfi},
-    format::{DrmFormatModifierProperties, Format, FormatProperties},
+    format::{DrmFormatModifierProperties, Format, FormatFeatures, FormatProperties},
     image::{
         ImageAspects, ImageDrmFormatModifierInfo, ImageFormatInfo, ImageFormatProperties,
-        ImageUsage, SparseImageFormatInfo, SparseImageFormatProperties,
+        ImageTiling, ImageUsage, SparseImageFormatInfo, SparseImageFormatProperties,
     },
     instance::{Instance, InstanceOwned},
     macros::{impl_id_counter, vulkan_bitflags, vulkan_enum},
@@ -120,7 +120,8 @@ impl PhysicalDevice {
             properties =
                 Self::get_properties2(handle, &instance, api_version, &supported_extensions);
             memory_properties = Self::get_memory_properties2(handle, &instance);
-            queue_family_properties = Self::get_queue_family_properties2(handle, &instance);
+            queue_family_properties =
+                Self::get_queue_family_properties2(handle, &instance, &supported_extensions);
         } else {
             supported_features = Self::get_features(handle, &instance);
             properties =
@@ -324,6 +325,7 @@ impl PhysicalDevice {
     unsafe fn get_queue_family_properties2(
         handle: ash::vk::PhysicalDevice,
         instance: &Instance,
+        supported_extensions: &DeviceExtensions,
     ) -> Vec<QueueFamilyProperties> {
         let mut num = 0;
         let fns = instance.fns();
@@ -344,6 +346,19 @@ impl PhysicalDevice {
         }
 
         let mut output = vec![ash::vk::QueueFamilyProperties2::default(); num as usize];
+        let mut global_priority_properties_vk = (supported_extensions.khr_global_priority
+            || supported_extensions.ext_global_priority_query)
+            .then(|| {
+                vec![ash::vk::QueueFamilyGlobalPriorityPropertiesKHR::default(); num as usize]
+            });
+
+        if let Some(global_priority_properties_vk) = &mut global_priority_properties_vk {
+            for (family, global_priority_properties) in
+                output.iter_mut().zip(global_priority_properties_vk)
+            {
+                family.p_next = global_priority_properties as *mut _ as *mut _;
+            }
+        }
 
         if instance.api_version() >= Version::V1_1 {
             (fns.v1_1.get_physical_device_queue_family_properties2)(
@@ -362,7 +377,21 @@ impl PhysicalDevice {
 
         output
             .into_iter()
-            .map(|family| family.queue_family_properties.into())
+            .enumerate()
+            .map(|(index, family)| {
+                let mut properties: QueueFamilyProperties = family.queue_family_properties.into();
+
+                if let Some(global_priority_properties_vk) = &global_priority_properties_vk {
+                    let global_priority_properties = &global_priority_properties_vk[index];
+                    properties.supported_global_priorities = global_priority_properties.priorities
+                        [..global_priority_properties.priority_count as usize]
+                        .iter()
+                        .filter_map(|&priority| priority.try_into().ok())
+                        .collect();
+                }
+
+                properties
+            })
             .collect()
     }
 
@@ -982,6 +1011,65 @@ impl PhysicalDevice {
         })
     }
 
+    /// Returns the first format in `candidates` that supports all of `features` with the given
+    /// `tiling`, or `None` if there is no such format.
+    ///
+    /// This is mostly useful to pick a depth or depth/stencil format, as no single one of them is
+    /// supported by every device. [`Format::DEPTH_FORMATS`] and
+    /// [`Format::DEPTH_STENCIL_FORMATS`] can be used as `candidates`. Candidates that are not
+    /// supported by the physical device at all are skipped.
+    ///
+    /// For [`ImageTiling::DrmFormatModifier`], a format is returned if at least one of its DRM
+    /// format modifiers supports all of `features`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use vulkano::device::physical::PhysicalDevice;
+    /// use vulkano::{
+    ///     format::{Format, FormatFeatures},
+    ///     image::ImageTiling,
+    /// };
+    ///
+    /// # let physical_device: std::sync::Arc<PhysicalDevice> = return;
+    /// let depth_format = physical_device
+    ///     .find_supported_depth_format(
+    ///         Format::DEPTH_FORMATS,
+    ///         ImageTiling::Optimal,
+    ///         FormatFeatures::DEPTH_STENCIL_ATTACHMENT,
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn find_supported_depth_format(
+        &self,
+        candidates: &[Format],
+        tiling: ImageTiling,
+        features: FormatFeatures,
+    ) -> Option<Format> {
+        candidates.iter().copied().find(|&format| {
+            if self.validate_format_properties(format).is_err() {
+                return false;
+            }
+
+            let format_properties = unsafe { self.format_properties_unchecked(format) };
+
+            match tiling {
+                ImageTiling::Optimal => {
+                    format_properties.optimal_tiling_features.contains(features)
+                }
+                ImageTiling::Linear => format_properties.linear_tiling_features.contains(features),
+                ImageTiling::DrmFormatModifier => format_properties
+                    .drm_format_modifier_properties
+                    .iter()
+                    .any(|properties| {
+                        properties
+                            .drm_format_modifier_tiling_features
+                            .contains(features)
+                    }),
+            }
+        })
+    }
+
     /// Returns the properties supported for images with a given image configuration.
     ///
     /// `Some` is returned if the configuration is supported, `None` if it is not.
@@ -3069,3 +3157,50 @@ vulkan_enum! {
     // TODO: document
     Reorder = REORDER,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        format::{Format, FormatFeatures},
+        image::ImageTiling,
+    };
+
+    #[test]
+    fn find_supported_depth_format() {
+        let (device, _) = gfx_dev_and_queue!();
+        let physical_device = device.physical_device();
+        let features = FormatFeatures::DEPTH_STENCIL_ATTACHMENT;
+
+        // Every device supports at least one of the depth formats as a depth attachment, and the
+        // first supported candidate is returned.
+        let format = physical_device
+            .find_supported_depth_format(Format::DEPTH_FORMATS, ImageTiling::Optimal, features)
+            .unwrap();
+        let index = Format::DEPTH_FORMATS
+            .iter()
+            .position(|&candidate| candidate == format)
+            .unwrap();
+
+        for &candidate in &Format::DEPTH_FORMATS[..index] {
+            assert!(!physical_device
+                .format_properties(candidate)
+                .unwrap()
+                .optimal_tiling_features
+                .contains(features));
+        }
+
+        // Color formats never support depth/stencil attachments.
+        assert_eq!(
+            physical_device.find_supported_depth_format(
+                &[Format::R8G8B8A8_UNORM],
+                ImageTiling::Optimal,
+                features,
+            ),
+            None,
+        );
+        assert_eq!(
+            physical_device.find_supported_depth_format(&[], ImageTiling::Optimal, features),
+            None,
+        );
+    }
+}
@@ -16,7 +16,7 @@ use crate::{
     },
     image::ImageState,
     instance::{debug::DebugUtilsLabel, InstanceOwnedDebugWrapper},
-    macros::vulkan_bitflags,
+    macros::{vulkan_bitflags, vulkan_enum},
     memory::{
         BindSparseInfo, SparseBufferMemoryBind, SparseImageMemoryBind, SparseImageOpaqueMemoryBind,
     },
@@ -24,10 +24,10 @@ use crate::{
     sync::{
         fence::{Fence, FenceState},
         future::{AccessCheckError, GpuFuture},
-        semaphore::SemaphoreState,
+        semaphore::{SemaphoreState, SemaphoreType},
     },
-    OomError, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
-    VulkanError, VulkanObject,
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version, VulkanError,
+    VulkanObject,
 };
 use ahash::HashMap;
 use parking_lot::{Mutex, MutexGuard};
@@ -86,14 +86,17 @@ impl Queue {
         queue_family_index: u32,
         id: u32,
     ) -> Arc<Self> {
-        Arc::new(Queue {
+        let queue = Arc::new(Queue {
             handle,
             device: InstanceOwnedDebugWrapper(device),
             flags,
             queue_family_index,
             id,
             state: Mutex::new(Default::default()),
-        })
+        });
+        queue.device.queues.lock().push(Arc::downgrade(&queue));
+
+        queue
     }
 
     /// Returns the device that this queue belongs to.
@@ -192,8 +195,11 @@ impl<'a> QueueGuard<'a> {
     ///
     /// Just like [`Device::wait_idle`], you shouldn't have to call this function in a typical
     /// program.
+    ///
+    /// If the device has been lost, this releases the resources as well, and returns
+    /// [`VulkanError::DeviceLost`].
     #[inline]
-    pub fn wait_idle(&mut self) -> Result<(), OomError> {
+    pub fn wait_idle(&mut self) -> Result<(), VulkanError> {
         self.state.wait_idle(&self.queue.device, self.queue.handle)
     }
 
@@ -222,6 +228,10 @@ impl<'a> QueueGuard<'a> {
         fence: Option<(&Arc<Fence>, MutexGuard<'_, FenceState>)>,
         states: &mut States<'_>,
     ) -> Result<(), VulkanError> {
+        if self.queue.device.is_lost() {
+            return Err(VulkanError::DeviceLost);
+        }
+
         struct PerBindSparseInfo {
             wait_semaphores_vk: SmallVec<[ash::vk::Semaphore; 4]>,
             buffer_bind_infos_vk: SmallVec<[ash::vk::SparseBufferMemoryBindInfo; 4]>,
@@ -478,7 +488,7 @@ impl<'a> QueueGuard<'a> {
                 .map_or_else(Default::default, |(fence, _)| fence.handle()),
         )
         .result()
-        .map_err(VulkanError::from)?;
+        .map_err(|err| self.queue.device.check_lost(VulkanError::from(err)))?;
 
         for bind_info in bind_infos {
             let BindSparseInfo {
@@ -528,6 +538,10 @@ impl<'a> QueueGuard<'a> {
         present_info: &PresentInfo,
         states: &mut States<'_>,
     ) -> Result<impl ExactSizeIterator<Item = Result<bool, VulkanError>>, VulkanError> {
+        if self.queue.device.is_lost() {
+            return Err(VulkanError::DeviceLost);
+        }
+
         let PresentInfo {
             wait_semaphores,
             swapchain_infos,
@@ -657,7 +671,7 @@ impl<'a> QueueGuard<'a> {
                 | ash::vk::Result::ERROR_SURFACE_LOST_KHR
                 | ash::vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT,
         ) {
-            return Err(VulkanError::from(result));
+            return Err(self.queue.device.check_lost(VulkanError::from(result)));
         }
 
         for semaphore in wait_semaphores {
@@ -877,6 +891,10 @@ impl<'a> QueueGuard<'a> {
         fence: Option<(&Arc<Fence>, MutexGuard<'_, FenceState>)>,
         states: &mut States<'_>,
     ) -> Result<(), VulkanError> {
+        if self.queue.device.is_lost() {
+            return Err(VulkanError::DeviceLost);
+        }
+
         if self.queue.device.enabled_features().synchronization2 {
             struct PerSubmitInfo {
                 wait_semaphore_infos_vk: SmallVec<[ash::vk::SemaphoreSubmitInfo; 4]>,
@@ -900,13 +918,14 @@ impl<'a> QueueGuard<'a> {
                             .map(|semaphore_submit_info| {
                                 let &SemaphoreSubmitInfo {
                                     ref semaphore,
+                                    value,
                                     stages,
                                     _ne: _,
                                 } = semaphore_submit_info;
 
                                 ash::vk::SemaphoreSubmitInfo {
                                     semaphore: semaphore.handle(),
-                                    value: 0, // TODO:
+                                    value,
                                     stage_mask: stages.into(),
                                     device_index: 0, // TODO:
                                     ..Default::default()
@@ -928,13 +947,14 @@ impl<'a> QueueGuard<'a> {
                             .map(|semaphore_submit_info| {
                                 let &SemaphoreSubmitInfo {
                                     ref semaphore,
+                                    value,
                                     stages,
                                     _ne: _,
                                 } = semaphore_submit_info;
 
                                 ash::vk::SemaphoreSubmitInfo {
                                     semaphore: semaphore.handle(),
-                                    value: 0, // TODO:
+                                    value,
                                     stage_mask: stages.into(),
                                     device_index: 0, // TODO:
                                     ..Default::default()
@@ -1005,16 +1025,19 @@ impl<'a> QueueGuard<'a> {
                 )
             }
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|err| self.queue.device.check_lost(VulkanError::from(err)))?;
         } else {
             struct PerSubmitInfo {
                 wait_semaphores_vk: SmallVec<[ash::vk::Semaphore; 4]>,
+                wait_semaphore_values_vk: SmallVec<[u64; 4]>,
                 wait_dst_stage_mask_vk: SmallVec<[ash::vk::PipelineStageFlags; 4]>,
                 command_buffers_vk: SmallVec<[ash::vk::CommandBuffer; 4]>,
                 signal_semaphores_vk: SmallVec<[ash::vk::Semaphore; 4]>,
+                signal_semaphore_values_vk: SmallVec<[u64; 4]>,
+                timeline_semaphore_submit_info_vk: Option<ash::vk::TimelineSemaphoreSubmitInfo>,
             }
 
-            let (mut submit_info_vk, per_submit_vk): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) =
+            let (mut submit_info_vk, mut per_submit_vk): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) =
                 submit_infos
                     .iter()
                     .map(|submit_info| {
@@ -1025,34 +1048,50 @@ impl<'a> QueueGuard<'a> {
                             _ne: _,
                         } = submit_info;
 
-                        let (wait_semaphores_vk, wait_dst_stage_mask_vk) = wait_semaphores
-                            .iter()
-                            .map(|semaphore_submit_info| {
-                                let &SemaphoreSubmitInfo {
-                                    ref semaphore,
-                                    stages,
-                                    _ne: _,
-                                } = semaphore_submit_info;
-
-                                (semaphore.handle(), stages.into())
-                            })
-                            .unzip();
+                        let mut wait_semaphores_vk = SmallVec::new();
+                        let mut wait_semaphore_values_vk = SmallVec::new();
+                        let mut wait_dst_stage_mask_vk = SmallVec::new();
+
+                        for semaphore_submit_info in wait_semaphores {
+                            let &SemaphoreSubmitInfo {
+                                ref semaphore,
+                                value,
+                                stages,
+                                _ne: _,
+                            } = semaphore_submit_info;
+
+                            wait_semaphores_vk.push(semaphore.handle());
+                            wait_semaphore_values_vk.push(value);
+                            wait_dst_stage_mask_vk.push(stages.into());
+                        }
 
                         let command_buffers_vk =
                             command_buffers.iter().map(|cb| cb.handle()).collect();
 
-                        let signal_semaphores_vk = signal_semaphores
-                            .iter()
-                            .map(|semaphore_submit_info| {
-                                let &SemaphoreSubmitInfo {
-                                    ref semaphore,
-                                    stages: _,
-                                    _ne: _,
-                                } = semaphore_submit_info;
+                        let mut signal_semaphores_vk = SmallVec::new();
+                        let mut signal_semaphore_values_vk = SmallVec::new();
+
+                        for semaphore_submit_info in signal_semaphores {
+                            let &SemaphoreSubmitInfo {
+                                ref semaphore,
+                                value,
+                                stages: _,
+                                _ne: _,
+                            } = semaphore_submit_info;
 
-                                semaphore.handle()
+                            signal_semaphores_vk.push(semaphore.handle());
+                            signal_semaphore_values_vk.push(value);
+                        }
+
+                        // Values are only needed if a timeline semaphore is involved.
+                        let timeline_semaphore_submit_info_vk = wait_semaphores
+                            .iter()
+                            .chain(signal_semaphores)
+                            .any(|semaphore_submit_info| {
+                                semaphore_submit_info.semaphore.semaphore_type()
+                                    == SemaphoreType::Timeline
                             })
-                            .collect();
+                            .then(Default::default);
 
                         (
                             ash::vk::SubmitInfo {
@@ -1067,9 +1106,12 @@ impl<'a> QueueGuard<'a> {
                             },
                             PerSubmitInfo {
                                 wait_semaphores_vk,
+                                wait_semaphore_values_vk,
                                 wait_dst_stage_mask_vk,
                                 command_buffers_vk,
                                 signal_semaphores_vk,
+                                signal_semaphore_values_vk,
+                                timeline_semaphore_submit_info_vk,
                             },
                         )
                     })
@@ -1079,11 +1121,14 @@ impl<'a> QueueGuard<'a> {
                 submit_info_vk,
                 PerSubmitInfo {
                     wait_semaphores_vk,
+                    wait_semaphore_values_vk,
                     wait_dst_stage_mask_vk,
                     command_buffers_vk,
                     signal_semaphores_vk,
+                    signal_semaphore_values_vk,
+                    timeline_semaphore_submit_info_vk,
                 },
-            ) in (submit_info_vk.iter_mut()).zip(per_submit_vk.iter())
+            ) in (submit_info_vk.iter_mut()).zip(per_submit_vk.iter_mut())
             {
                 *submit_info_vk = ash::vk::SubmitInfo {
                     wait_semaphore_count: wait_semaphores_vk.len() as u32,
@@ -1095,6 +1140,19 @@ impl<'a> QueueGuard<'a> {
                     p_signal_semaphores: signal_semaphores_vk.as_ptr(),
                     ..*submit_info_vk
                 };
+
+                if let Some(info) = timeline_semaphore_submit_info_vk {
+                    *info = ash::vk::TimelineSemaphoreSubmitInfo {
+                        wait_semaphore_value_count: wait_semaphore_values_vk.len() as u32,
+                        p_wait_semaphore_values: wait_semaphore_values_vk.as_ptr(),
+                        signal_semaphore_value_count: signal_semaphore_values_vk.len() as u32,
+                        p_signal_semaphore_values: signal_semaphore_values_vk.as_ptr(),
+                        ..Default::default()
+                    };
+
+                    info.p_next = submit_info_vk.p_next;
+                    submit_info_vk.p_next = info as *const _ as *const _;
+                }
             }
 
             let fns = self.queue.device.fns();
@@ -1107,7 +1165,7 @@ impl<'a> QueueGuard<'a> {
                     .map_or_else(Default::default, |(fence, _)| fence.handle()),
             )
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|err| self.queue.device.check_lost(VulkanError::from(err)))?;
         }
 
         for submit_info in submit_infos {
@@ -1358,20 +1416,22 @@ struct QueueState {
 }
 
 impl QueueState {
-    fn wait_idle(&mut self, device: &Device, handle: ash::vk::Queue) -> Result<(), OomError> {
+    fn wait_idle(&mut self, device: &Device, handle: ash::vk::Queue) -> Result<(), VulkanError> {
         unsafe {
             let fns = device.fns();
-            (fns.v1_0.queue_wait_idle)(handle)
+            let result = (fns.v1_0.queue_wait_idle)(handle)
                 .result()
-                .map_err(VulkanError::from)?;
+                .map_err(|err| device.check_lost(VulkanError::from(err)));
 
-            // Since we now know that the queue is finished with all work,
-            // we can safely release all resources.
-            for (operation, _) in take(&mut self.operations) {
-                operation.set_finished();
+            // Since we now know that the queue is finished with all work, or will never execute
+            // anything again because the device was lost, we can safely release all resources.
+            if matches!(result, Ok(()) | Err(VulkanError::DeviceLost)) {
+                for (operation, _) in take(&mut self.operations) {
+                    operation.set_finished();
+                }
             }
 
-            Ok(())
+            result
         }
     }
 
@@ -1673,6 +1733,14 @@ pub struct QueueFamilyProperties {
 
     /// The minimum granularity supported for image transfers, in terms of `[width, height, depth]`.
     pub min_image_transfer_granularity: [u32; 3],
+
+    /// The global priorities that queues of this family can be created with.
+    ///
+    /// This is only queried if the physical device supports the
+    /// [`khr_global_priority`](crate::device::DeviceExtensions::khr_global_priority) or
+    /// [`ext_global_priority_query`](crate::device::DeviceExtensions::ext_global_priority_query)
+    /// extension. Otherwise it is empty.
+    pub supported_global_priorities: Vec<QueueGlobalPriority>,
 }
 
 impl From<ash::vk::QueueFamilyProperties> for QueueFamilyProperties {
@@ -1688,6 +1756,7 @@ impl From<ash::vk::QueueFamilyProperties> for QueueFamilyProperties {
                 val.min_image_transfer_granularity.height,
                 val.min_image_transfer_granularity.depth,
             ],
+            supported_global_priorities: Vec::new(),
         }
     }
 }
@@ -1735,9 +1804,43 @@ vulkan_bitflags! {
     ]),
 }
 
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The priority of a queue relative to queues in other processes and devices on the system.
+    QueueGlobalPriority = QueueGlobalPriorityKHR(i32);
+
+    /// The queue has a low priority. This may be used for background tasks.
+    Low = LOW,
+
+    /// The queue has the system's default priority.
+    ///
+    /// This is the priority that queues have if no global priority is specified.
+    Medium = MEDIUM,
+
+    /// The queue has a high priority, prioritized over the default.
+    High = HIGH,
+
+    /// The queue has the highest possible priority. This is intended for realtime workloads, and
+    /// usually requires the process to have special privileges.
+    Realtime = REALTIME,
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::sync::fence::Fence;
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+        },
+        device::{Device, DeviceCreateInfo, Features, QueueCreateInfo},
+        sync::{
+            fence::Fence,
+            future::wait_timeline_semaphore,
+            now,
+            semaphore::{Semaphore, SemaphoreCreateInfo, SemaphoreType},
+            GpuFuture,
+        },
+    };
     use std::{sync::Arc, time::Duration};
 
     #[test]
@@ -1765,4 +1868,92 @@ mod tests {
             assert!(fence.is_signaled().unwrap());
         }
     }
+
+    #[test]
+    fn timeline_wait_before_signal() {
+        let instance = instance!();
+
+        let enabled_features = Features {
+            timeline_semaphore: true,
+            ..Features::empty()
+        };
+
+        // Two queues are needed, so that the waiting queue can't also be the signaling queue.
+        let select = match instance.enumerate_physical_devices() {
+            Ok(x) => x,
+            Err(_) => return,
+        }
+        .filter(|p| p.supported_features().contains(&enabled_features))
+        .find_map(|p| {
+            p.queue_family_properties()
+                .iter()
+                .position(|q| q.queue_count >= 2)
+                .map(|i| (p, i as u32))
+        });
+
+        let (physical_device, queue_family_index) = match select {
+            Some(x) => x,
+            None => return,
+        };
+
+        let (device, mut queues) = match Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo {
+                    queue_family_index,
+                    queues: vec![0.5; 2],
+                    ..Default::default()
+                }],
+                enabled_features,
+                ..Default::default()
+            },
+        ) {
+            Ok(x) => x,
+            Err(_) => return,
+        };
+        let waiting_queue = queues.next().unwrap();
+        let signaling_queue = queues.next().unwrap();
+
+        let semaphore = Arc::new(
+            Semaphore::new(
+                device.clone(),
+                SemaphoreCreateInfo {
+                    semaphore_type: SemaphoreType::Timeline,
+                    ..Default::default()
+                },
+            )
+            .unwrap(),
+        );
+
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let command_buffer = || {
+            AutoCommandBufferBuilder::primary(
+                &allocator,
+                queue_family_index,
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap()
+            .build()
+            .unwrap()
+        };
+
+        // Submit the wait first. The signal operation hasn't been submitted yet.
+        let waiter = wait_timeline_semaphore(semaphore.clone(), 1)
+            .then_execute(waiting_queue, command_buffer())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        assert_eq!(semaphore.counter_value().unwrap(), 0);
+
+        // Then submit the signal on the other queue, which unblocks the wait.
+        let signaler = now(device)
+            .then_execute(signaling_queue, command_buffer())
+            .unwrap()
+            .then_signal_timeline_semaphore_and_flush(semaphore.clone(), 1)
+            .unwrap();
+
+        waiter.wait(Some(Duration::from_secs(5))).unwrap();
+        assert_eq!(semaphore.counter_value().unwrap(), 1);
+        drop(signaler);
+    }
 }
@@ -98,13 +98,37 @@ use crate::{
     image::{ImageAspects, ImageTiling},
     macros::vulkan_bitflags,
     shader::spirv::ImageFormat,
-    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version,
 };
 
 // Generated by build.rs
 include!(concat!(env!("OUT_DIR"), "/formats.rs"));
 
 impl Format {
+    /// Formats with a depth component, in a commonly preferred order. This can be passed to
+    /// [`PhysicalDevice::find_supported_depth_format`].
+    ///
+    /// `D16_UNORM` comes last, as it is the only format that every device must support as a
+    /// depth attachment.
+    pub const DEPTH_FORMATS: &[Format] = &[
+        Format::D32_SFLOAT,
+        Format::D32_SFLOAT_S8_UINT,
+        Format::D24_UNORM_S8_UINT,
+        Format::X8_D24_UNORM_PACK32,
+        Format::D16_UNORM,
+    ];
+
+    /// Formats with both a depth and a stencil component, in a commonly preferred order. This can
+    /// be passed to [`PhysicalDevice::find_supported_depth_format`].
+    ///
+    /// At least one of `D24_UNORM_S8_UINT` and `D32_SFLOAT_S8_UINT` is supported as a
+    /// depth/stencil attachment by every device.
+    pub const DEPTH_STENCIL_FORMATS: &[Format] = &[
+        Format::D24_UNORM_S8_UINT,
+        Format::D32_SFLOAT_S8_UINT,
+        Format::D16_UNORM_S8_UINT,
+    ];
+
     /// Retrieves the properties of a format when used by a certain device.
     #[deprecated(
         since = "0.28.0",
@@ -166,6 +190,39 @@ impl Format {
                 | Format::R8_UINT
         )
     }
+
+    /// Checks that the device features that are needed to use the format are enabled.
+    ///
+    /// This is in addition to `validate_device`, which only checks the API version and the
+    /// extensions.
+    pub(crate) fn validate_features(self, device: &Device) -> Result<(), Box<ValidationError>> {
+        // The features of `ext_4444_formats` were not promoted to Vulkan 1.3, only the formats.
+        if device.api_version() < Version::V1_3 {
+            if self == Format::A4R4G4B4_UNORM_PACK16 && !device.enabled_features().format_a4r4g4b4 {
+                return Err(Box::new(ValidationError {
+                    problem: "is `Format::A4R4G4B4_UNORM_PACK16`".into(),
+                    requires_one_of: RequiresOneOf(&[
+                        RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                        RequiresAllOf(&[Requires::Feature("format_a4r4g4b4")]),
+                    ]),
+                    ..Default::default()
+                }));
+            }
+
+            if self == Format::A4B4G4R4_UNORM_PACK16 && !device.enabled_features().format_a4b4g4r4 {
+                return Err(Box::new(ValidationError {
+                    problem: "is `Format::A4B4G4R4_UNORM_PACK16`".into(),
+                    requires_one_of: RequiresOneOf(&[
+                        RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                        RequiresAllOf(&[Requires::Feature("format_a4b4g4r4")]),
+                    ]),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl From<Format> for ash::vk::Format {
@@ -1199,3 +1256,75 @@ impl From<ash::vk::FormatFeatureFlags> for FormatFeatures {
         Self::from(ash::vk::FormatFeatureFlags2::from_raw(val.as_raw() as u64))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CompressionType, Format};
+    use crate::image::ImageAspects;
+
+    #[test]
+    fn uncompressed_color() {
+        let format = Format::R8G8B8A8_UNORM;
+        assert_eq!(format.block_extent(), [1, 1, 1]);
+        assert_eq!(format.block_size(), 4);
+        assert_eq!(format.components(), [8, 8, 8, 8]);
+        assert!(format.planes().is_empty());
+        assert_eq!(format.aspects(), ImageAspects::COLOR);
+        assert_eq!(format.compression(), None);
+
+        let format = Format::R16G16_SFLOAT;
+        assert_eq!(format.block_size(), 4);
+        assert_eq!(format.components(), [16, 16, 0, 0]);
+    }
+
+    #[test]
+    fn depth_stencil() {
+        let format = Format::D24_UNORM_S8_UINT;
+        assert_eq!(format.block_size(), 4);
+        assert_eq!(format.components(), [24, 8, 0, 0]);
+        assert_eq!(
+            format.aspects(),
+            ImageAspects::DEPTH | ImageAspects::STENCIL
+        );
+        assert_eq!(format.compression(), None);
+    }
+
+    #[test]
+    fn compressed() {
+        let format = Format::BC1_RGBA_UNORM_BLOCK;
+        assert_eq!(format.block_extent(), [4, 4, 1]);
+        assert_eq!(format.block_size(), 8);
+        assert_eq!(format.compression(), Some(CompressionType::BC));
+
+        let format = Format::BC7_SRGB_BLOCK;
+        assert_eq!(format.block_size(), 16);
+        assert_eq!(format.compression(), Some(CompressionType::BC));
+
+        let format = Format::ETC2_R8G8B8_UNORM_BLOCK;
+        assert_eq!(format.block_extent(), [4, 4, 1]);
+        assert_eq!(format.block_size(), 8);
+        assert_eq!(format.compression(), Some(CompressionType::ETC2));
+
+        let format = Format::ASTC_8x6_UNORM_BLOCK;
+        assert_eq!(format.block_extent(), [8, 6, 1]);
+        assert_eq!(format.block_size(), 16);
+        assert_eq!(format.compression(), Some(CompressionType::ASTC_LDR));
+    }
+
+    #[test]
+    fn multi_planar() {
+        let format = Format::G8_B8R8_2PLANE_420_UNORM;
+        assert_eq!(
+            format.planes(),
+            [Format::R8_UNORM, Format::R8G8_UNORM].as_slice(),
+        );
+        assert_eq!(
+            format.aspects(),
+            ImageAspects::COLOR | ImageAspects::PLANE_0 | ImageAspects::PLANE_1,
+        );
+
+        let format = Format::G8_B8_R8_3PLANE_444_UNORM;
+        assert_eq!(format.planes().len(), 3);
+        assert!(format.aspects().contains(ImageAspects::PLANE_2));
+    }
+}
@@ -7,7 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use super::ImageAspect;
+use super::{ImageAspect, ImageUsage};
 use crate::macros::vulkan_enum;
 
 vulkan_enum! {
@@ -175,12 +175,12 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(khr_shared_presentable_image)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// A layout optimized for being read by the implementation as a fragment density map
+    /// attachment of a render pass.
     FragmentDensityMapOptimal = FRAGMENT_DENSITY_MAP_OPTIMAL_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_fragment_density_map)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -210,12 +210,28 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(khr_video_encode_queue)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// A layout for an image that is used as a color or depth/stencil attachment, and at the same
+    /// time is read in a shader, either as a sampled image or as an input attachment, within the
+    /// same render pass. This allows reading from and writing to the same image without having to
+    /// copy it first.
+    ///
+    /// The image must have been created with the [`ImageUsage::ATTACHMENT_FEEDBACK_LOOP`] usage,
+    /// at least one of the [`ImageUsage::COLOR_ATTACHMENT`] and
+    /// [`ImageUsage::DEPTH_STENCIL_ATTACHMENT`] usages, and at least one of the
+    /// [`ImageUsage::SAMPLED`] and [`ImageUsage::INPUT_ATTACHMENT`] usages.
+    ///
+    /// The [`attachment_feedback_loop_layout`] feature must be enabled on the device.
+    ///
+    /// [`ImageUsage::ATTACHMENT_FEEDBACK_LOOP`]: crate::image::ImageUsage::ATTACHMENT_FEEDBACK_LOOP
+    /// [`ImageUsage::COLOR_ATTACHMENT`]: crate::image::ImageUsage::COLOR_ATTACHMENT
+    /// [`ImageUsage::DEPTH_STENCIL_ATTACHMENT`]: crate::image::ImageUsage::DEPTH_STENCIL_ATTACHMENT
+    /// [`ImageUsage::SAMPLED`]: crate::image::ImageUsage::SAMPLED
+    /// [`ImageUsage::INPUT_ATTACHMENT`]: crate::image::ImageUsage::INPUT_ATTACHMENT
+    /// [`attachment_feedback_loop_layout`]: crate::device::Features::attachment_feedback_loop_layout
     AttachmentFeedbackLoopOptimal = ATTACHMENT_FEEDBACK_LOOP_OPTIMAL_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_attachment_feedback_loop_layout)]),
-    ]),*/
+    ]),
 }
 
 impl Default for ImageLayout {
@@ -226,6 +242,50 @@ impl Default for ImageLayout {
 }
 
 impl ImageLayout {
+    /// Returns the usages that an image must have been created with, in order to be in this
+    /// layout. The usage of the image must contain at least one of the flags of each element of
+    /// the returned slice.
+    pub(crate) fn required_usage(self) -> &'static [ImageUsage] {
+        const ATTACHMENT: ImageUsage =
+            ImageUsage::COLOR_ATTACHMENT.union(ImageUsage::DEPTH_STENCIL_ATTACHMENT);
+        const SHADER_READ: ImageUsage = ImageUsage::SAMPLED.union(ImageUsage::INPUT_ATTACHMENT);
+        const DEPTH_STENCIL_READ: ImageUsage =
+            ImageUsage::DEPTH_STENCIL_ATTACHMENT.union(SHADER_READ);
+
+        match self {
+            ImageLayout::ColorAttachmentOptimal => &[ImageUsage::COLOR_ATTACHMENT],
+            ImageLayout::DepthStencilAttachmentOptimal
+            | ImageLayout::DepthStencilReadOnlyOptimal
+            | ImageLayout::DepthReadOnlyStencilAttachmentOptimal
+            | ImageLayout::DepthAttachmentStencilReadOnlyOptimal
+            | ImageLayout::DepthAttachmentOptimal
+            | ImageLayout::StencilAttachmentOptimal => &[ImageUsage::DEPTH_STENCIL_ATTACHMENT],
+            ImageLayout::DepthReadOnlyOptimal | ImageLayout::StencilReadOnlyOptimal => {
+                &[DEPTH_STENCIL_READ]
+            }
+            ImageLayout::ShaderReadOnlyOptimal => &[SHADER_READ],
+            ImageLayout::TransferSrcOptimal => &[ImageUsage::TRANSFER_SRC],
+            ImageLayout::TransferDstOptimal => &[ImageUsage::TRANSFER_DST],
+            ImageLayout::FragmentDensityMapOptimal => &[ImageUsage::FRAGMENT_DENSITY_MAP],
+            ImageLayout::AttachmentFeedbackLoopOptimal => &[
+                ImageUsage::ATTACHMENT_FEEDBACK_LOOP,
+                ATTACHMENT,
+                SHADER_READ,
+            ],
+            ImageLayout::Undefined
+            | ImageLayout::General
+            | ImageLayout::Preinitialized
+            | ImageLayout::PresentSrc => &[],
+        }
+    }
+
+    /// Returns whether an image created with `usage` can be in this layout.
+    pub(crate) fn is_usage_supported(self, usage: ImageUsage) -> bool {
+        self.required_usage()
+            .iter()
+            .all(|&required_usage| usage.intersects(required_usage))
+    }
+
     /// If the layout can be used for `aspect`, returns whether `aspect` can be written to if an
     /// image is in that layout.
     pub fn is_writable(self, aspect: ImageAspect) -> bool {
@@ -236,7 +296,8 @@ impl ImageLayout {
             | ImageAspect::Plane2 => match self {
                 ImageLayout::General
                 | ImageLayout::ColorAttachmentOptimal
-                | ImageLayout::TransferDstOptimal => true,
+                | ImageLayout::TransferDstOptimal
+                | ImageLayout::AttachmentFeedbackLoopOptimal => true,
                 ImageLayout::Undefined
                 | ImageLayout::DepthStencilAttachmentOptimal
                 | ImageLayout::DepthStencilReadOnlyOptimal
@@ -249,14 +310,16 @@ impl ImageLayout {
                 | ImageLayout::DepthReadOnlyOptimal
                 | ImageLayout::StencilAttachmentOptimal
                 | ImageLayout::StencilReadOnlyOptimal
-                | ImageLayout::PresentSrc => false,
+                | ImageLayout::PresentSrc
+                | ImageLayout::FragmentDensityMapOptimal => false,
             },
             ImageAspect::Depth => match self {
                 ImageLayout::General
                 | ImageLayout::DepthStencilAttachmentOptimal
                 | ImageLayout::TransferDstOptimal
                 | ImageLayout::DepthAttachmentStencilReadOnlyOptimal
-                | ImageLayout::DepthAttachmentOptimal => true,
+                | ImageLayout::DepthAttachmentOptimal
+                | ImageLayout::AttachmentFeedbackLoopOptimal => true,
 
                 ImageLayout::Undefined
                 | ImageLayout::ColorAttachmentOptimal
@@ -268,14 +331,16 @@ impl ImageLayout {
                 | ImageLayout::DepthReadOnlyOptimal
                 | ImageLayout::StencilAttachmentOptimal
                 | ImageLayout::StencilReadOnlyOptimal
-                | ImageLayout::PresentSrc => false,
+                | ImageLayout::PresentSrc
+                | ImageLayout::FragmentDensityMapOptimal => false,
             },
             ImageAspect::Stencil => match self {
                 ImageLayout::General
                 | ImageLayout::DepthStencilAttachmentOptimal
                 | ImageLayout::TransferDstOptimal
                 | ImageLayout::DepthReadOnlyStencilAttachmentOptimal
-                | ImageLayout::StencilAttachmentOptimal => true,
+                | ImageLayout::StencilAttachmentOptimal
+                | ImageLayout::AttachmentFeedbackLoopOptimal => true,
 
                 ImageLayout::Undefined
                 | ImageLayout::ColorAttachmentOptimal
@@ -287,7 +352,8 @@ impl ImageLayout {
                 | ImageLayout::DepthAttachmentOptimal
                 | ImageLayout::DepthReadOnlyOptimal
                 | ImageLayout::StencilReadOnlyOptimal
-                | ImageLayout::PresentSrc => false,
+                | ImageLayout::PresentSrc
+                | ImageLayout::FragmentDensityMapOptimal => false,
             },
             ImageAspect::Metadata
             | ImageAspect::MemoryPlane0
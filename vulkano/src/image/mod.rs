@@ -68,9 +68,12 @@ use crate::{
     format::{Format, FormatFeatures},
     macros::{vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum},
     memory::{
-        allocator::{AllocationCreateInfo, MemoryAlloc, MemoryAllocator, MemoryAllocatorError},
+        allocator::{
+            AllocationCreateInfo, MemoryAlloc, MemoryAllocator, MemoryAllocatorError,
+            MemoryTypeFilter,
+        },
         DedicatedAllocation, ExternalMemoryHandleType, ExternalMemoryHandleTypes,
-        ExternalMemoryProperties, MemoryRequirements,
+        ExternalMemoryProperties, MemoryPropertyFlags, MemoryRequirements,
     },
     range_map::RangeMap,
     swapchain::Swapchain,
@@ -144,34 +147,59 @@ pub enum ImageMemory {
 
 impl Image {
     /// Creates a new uninitialized `Image`.
+    ///
+    /// If `create_info.usage` contains [`ImageUsage::TRANSIENT_ATTACHMENT`], and
+    /// `allocation_info.memory_type_filter` doesn't require host access, then
+    /// [`MemoryTypeFilter::PREFER_LAZILY_ALLOCATED`] is added to the memory type filter, so that
+    /// the image isn't given any real backing memory on implementations that support it.
+    ///
+    /// If `create_info.flags` contains [`ImageCreateFlags::DISJOINT`], then a separate allocation
+    /// is made for each plane of the image, using the same `allocation_info`.
     pub fn new(
         allocator: &(impl MemoryAllocator + ?Sized),
         create_info: ImageCreateInfo,
-        allocation_info: AllocationCreateInfo,
+        mut allocation_info: AllocationCreateInfo,
     ) -> Result<Arc<Self>, Validated<ImageAllocateError>> {
-        // TODO: adjust the code below to make this safe
-        assert!(!create_info.flags.intersects(ImageCreateFlags::DISJOINT));
-
         let allocation_type = create_info.tiling.into();
         let raw_image =
             RawImage::new(allocator.device().clone(), create_info).map_err(|err| match err {
                 Validated::Error(err) => Validated::Error(ImageAllocateError::CreateImage(err)),
                 Validated::ValidationError(err) => err.into(),
             })?;
-        let requirements = raw_image.memory_requirements()[0];
-
-        let allocation = unsafe {
-            allocator
-                .allocate_unchecked(
-                    requirements,
-                    allocation_type,
-                    allocation_info,
-                    Some(DedicatedAllocation::Image(&raw_image)),
-                )
-                .map_err(ImageAllocateError::AllocateMemory)?
-        };
+        // Lazily allocated memory can't be host-visible, so in that case there's no point.
+        if raw_image
+            .usage()
+            .intersects(ImageUsage::TRANSIENT_ATTACHMENT)
+            && !allocation_info
+                .memory_type_filter
+                .required_flags
+                .intersects(MemoryPropertyFlags::HOST_VISIBLE)
+        {
+            allocation_info.memory_type_filter = allocation_info
+                .memory_type_filter
+                .union(MemoryTypeFilter::PREFER_LAZILY_ALLOCATED);
+        }
 
-        let image = raw_image.bind_memory([allocation]).map_err(|(err, _, _)| {
+        // VUID-VkMemoryDedicatedAllocateInfo-image-01797
+        let dedicated_allocation = (!raw_image.flags().intersects(ImageCreateFlags::DISJOINT))
+            .then_some(DedicatedAllocation::Image(&raw_image));
+
+        let allocations = raw_image
+            .memory_requirements()
+            .iter()
+            .map(|&requirements| unsafe {
+                allocator
+                    .allocate_unchecked(
+                        requirements,
+                        allocation_type,
+                        allocation_info.clone(),
+                        dedicated_allocation,
+                    )
+                    .map_err(ImageAllocateError::AllocateMemory)
+            })
+            .collect::<Result<SmallVec<[_; 3]>, _>>()?;
+
+        let image = raw_image.bind_memory(allocations).map_err(|(err, _, _)| {
             err.map(ImageAllocateError::BindMemory)
                 .map_validation(|err| err.add_context("RawImage::bind_memory"))
         })?;
@@ -348,6 +376,13 @@ impl Image {
         self.inner.external_memory_handle_types()
     }
 
+    /// Returns the formats that image views created from this image can have, if they were
+    /// provided when the image was created.
+    #[inline]
+    pub fn view_formats(&self) -> &[Format] {
+        self.inner.view_formats()
+    }
+
     /// Returns an `ImageSubresourceLayers` covering the first mip level of the image. All aspects
     /// of the image are selected, or `plane0` if the image is multi-planar.
     #[inline]
@@ -1115,12 +1150,15 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(ext_sample_locations)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The image may be rendered to with a lower resolution in some regions, as specified by a
+    /// fragment density map. Its contents are then stored in an implementation-dependent format.
+    ///
+    /// The image must be a 2D image with optimal tiling and only one mip level, and must not be
+    /// cube compatible.
     SUBSAMPLED = SUBSAMPLED_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_fragment_density_map)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
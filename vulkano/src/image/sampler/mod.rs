@@ -55,7 +55,7 @@ use crate::{
         ImageAspects,
     },
     instance::InstanceOwnedDebugWrapper,
-    macros::{impl_id_counter, vulkan_enum},
+    macros::{impl_id_counter, vulkan_bitflags, vulkan_enum},
     pipeline::graphics::depth_stencil::CompareOp,
     Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
 };
@@ -100,6 +100,7 @@ pub struct Sampler {
     anisotropy: Option<f32>,
     border_color: Option<BorderColor>,
     compare: Option<CompareOp>,
+    flags: SamplerCreateFlags,
     lod: RangeInclusive<f32>,
     mag_filter: Filter,
     min_filter: Filter,
@@ -139,6 +140,7 @@ impl Sampler {
         create_info: SamplerCreateInfo,
     ) -> Result<Arc<Sampler>, VulkanError> {
         let &SamplerCreateInfo {
+            flags,
             mag_filter,
             min_filter,
             mipmap_mode,
@@ -167,7 +169,7 @@ impl Sampler {
         };
 
         let mut create_info_vk = ash::vk::SamplerCreateInfo {
-            flags: ash::vk::SamplerCreateFlags::empty(),
+            flags: flags.into(),
             mag_filter: mag_filter.into(),
             min_filter: min_filter.into(),
             mipmap_mode: mipmap_mode.into(),
@@ -241,6 +243,7 @@ impl Sampler {
         create_info: SamplerCreateInfo,
     ) -> Arc<Sampler> {
         let SamplerCreateInfo {
+            flags,
             mag_filter,
             min_filter,
             mipmap_mode,
@@ -267,6 +270,7 @@ impl Sampler {
                 .any(|mode| mode == SamplerAddressMode::ClampToBorder)
                 .then_some(border_color),
             compare,
+            flags,
             lod,
             mag_filter,
             min_filter,
@@ -453,17 +457,26 @@ impl Sampler {
             // VkPhysicalDeviceBorderColorSwizzleFeaturesEXT::borderColorSwizzleFromImage
             // feature is not enabled, and
             // VkSamplerBorderColorComponentMappingCreateInfoEXT is not specified.
+            // The values are only defined if the borderColorSwizzle feature is also enabled.
             if matches!(
                 border_color,
                 BorderColor::FloatOpaqueBlack | BorderColor::IntOpaqueBlack
             ) && !image_view.component_mapping().is_identity()
             {
-                return Err(Box::new(ValidationError {
-                    problem: "the sampler has an opaque black border color, and \
-                        the image view is not identity swizzled"
-                        .into(),
-                    ..Default::default()
-                }));
+                let features = self.device.enabled_features();
+
+                if !(features.border_color_swizzle && features.border_color_swizzle_from_image) {
+                    return Err(Box::new(ValidationError {
+                        problem: "the sampler has an opaque black border color, and \
+                            the image view is not identity swizzled"
+                            .into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                            Requires::Feature("border_color_swizzle"),
+                            Requires::Feature("border_color_swizzle_from_image"),
+                        ])]),
+                        ..Default::default()
+                    }));
+                }
             }
         }
 
@@ -528,6 +541,12 @@ impl Sampler {
         self.compare
     }
 
+    /// Returns the flags that the sampler was created with.
+    #[inline]
+    pub fn flags(&self) -> SamplerCreateFlags {
+        self.flags
+    }
+
     /// Returns the LOD range.
     #[inline]
     pub fn lod(&self) -> RangeInclusive<f32> {
@@ -608,6 +627,11 @@ impl_id_counter!(Sampler);
 /// Parameters to create a new `Sampler`.
 #[derive(Clone, Debug)]
 pub struct SamplerCreateInfo {
+    /// Additional properties of the sampler.
+    ///
+    /// The default value is empty.
+    pub flags: SamplerCreateFlags,
+
     /// How the sampled value of a single mipmap should be calculated,
     /// when magnification is applied (LOD <= 0.0).
     ///
@@ -691,6 +715,13 @@ pub struct SamplerCreateInfo {
     /// The border color to use if `address_mode` is set to
     /// [`ClampToBorder`](SamplerAddressMode::ClampToBorder).
     ///
+    /// If the border color is [`FloatOpaqueBlack`](BorderColor::FloatOpaqueBlack) or
+    /// [`IntOpaqueBlack`](BorderColor::IntOpaqueBlack), then the sampler can only be used with
+    /// image views that have a non-identity component mapping if the
+    /// [`border_color_swizzle`](crate::device::Features::border_color_swizzle) and
+    /// [`border_color_swizzle_from_image`](crate::device::Features::border_color_swizzle_from_image)
+    /// features are enabled on the device.
+    ///
     /// The default value is [`FloatTransparentBlack`](BorderColor::FloatTransparentBlack).
     pub border_color: BorderColor,
 
@@ -750,6 +781,7 @@ impl Default for SamplerCreateInfo {
     #[inline]
     fn default() -> Self {
         Self {
+            flags: SamplerCreateFlags::empty(),
             mag_filter: Filter::Nearest,
             min_filter: Filter::Nearest,
             mipmap_mode: SamplerMipmapMode::Nearest,
@@ -797,6 +829,7 @@ impl SamplerCreateInfo {
 
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
+            flags,
             mag_filter,
             min_filter,
             mipmap_mode,
@@ -814,6 +847,27 @@ impl SamplerCreateInfo {
 
         let properties = device.physical_device().properties();
 
+        flags
+            .validate_device(device)
+            .map_err(|err| ValidationError {
+                context: "flags".into(),
+                vuids: &["VUID-VkSamplerCreateInfo-flags-parameter"],
+                ..ValidationError::from_requirement(err)
+            })?;
+
+        if flags.intersects(SamplerCreateFlags::NON_SEAMLESS_CUBE_MAP)
+            && !device.enabled_features().non_seamless_cube_map
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `SamplerCreateFlags::NON_SEAMLESS_CUBE_MAP`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "non_seamless_cube_map",
+                )])]),
+                vuids: &["VUID-VkSamplerCreateInfo-nonSeamlessCubeMap-06788"],
+            }));
+        }
+
         mag_filter
             .validate_device(device)
             .map_err(|err| ValidationError {
@@ -890,7 +944,12 @@ impl SamplerCreateInfo {
         if lod.is_empty() {
             return Err(Box::new(ValidationError {
                 context: "lod".into(),
-                problem: "is empty".into(),
+                problem: format!(
+                    "is empty: the start ({}) is greater than the end ({})",
+                    lod.start(),
+                    lod.end(),
+                )
+                .into(),
                 vuids: &["VUID-VkSamplerCreateInfo-maxLod-01973"],
                 ..Default::default()
             }));
@@ -898,9 +957,13 @@ impl SamplerCreateInfo {
 
         if mip_lod_bias.abs() > properties.max_sampler_lod_bias {
             return Err(Box::new(ValidationError {
-                context: "lod".into(),
-                problem: "the absolute value is greater than the `max_sampler_lod_bias` limit"
-                    .into(),
+                context: "mip_lod_bias".into(),
+                problem: format!(
+                    "the absolute value of {} is greater than the `max_sampler_lod_bias` limit \
+                    ({})",
+                    mip_lod_bias, properties.max_sampler_lod_bias,
+                )
+                .into(),
                 vuids: &["VUID-VkSamplerCreateInfo-mipLodBias-01069"],
                 ..Default::default()
             }));
@@ -937,7 +1000,7 @@ impl SamplerCreateInfo {
             if max_anisotropy < 1.0 {
                 return Err(Box::new(ValidationError {
                     context: "anisotropy".into(),
-                    problem: "is less than 1.0".into(),
+                    problem: format!("is {}, which is less than 1.0", max_anisotropy).into(),
                     vuids: &["VUID-VkSamplerCreateInfo-anisotropyEnable-01071"],
                     ..Default::default()
                 }));
@@ -946,7 +1009,11 @@ impl SamplerCreateInfo {
             if max_anisotropy > properties.max_sampler_anisotropy {
                 return Err(Box::new(ValidationError {
                     context: "anisotropy".into(),
-                    problem: "is greater than the `max_sampler_anisotropy` limit".into(),
+                    problem: format!(
+                        "is {}, which is greater than the `max_sampler_anisotropy` limit ({})",
+                        max_anisotropy, properties.max_sampler_anisotropy,
+                    )
+                    .into(),
                     vuids: &["VUID-VkSamplerCreateInfo-anisotropyEnable-01071"],
                     ..Default::default()
                 }));
@@ -1144,6 +1211,52 @@ impl SamplerCreateInfo {
     }
 }
 
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags specifying additional properties of a sampler.
+    SamplerCreateFlags = SamplerCreateFlags(u32);
+
+    /* TODO: enable
+    // TODO: document
+    SUBSAMPLED = SUBSAMPLED_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_fragment_density_map)]),
+    ]), */
+
+    /* TODO: enable
+    // TODO: document
+    SUBSAMPLED_COARSE_RECONSTRUCTION = SUBSAMPLED_COARSE_RECONSTRUCTION_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_fragment_density_map)]),
+    ]), */
+
+    /* TODO: enable
+    // TODO: document
+    DESCRIPTOR_BUFFER_CAPTURE_REPLAY = DESCRIPTOR_BUFFER_CAPTURE_REPLAY_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
+    ]), */
+
+    /// Cube map images that are sampled with the sampler are not filtered across the edges of
+    /// their faces. Instead, each face is treated like a separate 2D image, with the address mode
+    /// of the sampler applied at its edges.
+    ///
+    /// The [`non_seamless_cube_map`](crate::device::Features::non_seamless_cube_map) feature must
+    /// be enabled on the device.
+    NON_SEAMLESS_CUBE_MAP = NON_SEAMLESS_CUBE_MAP_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_non_seamless_cube_map)]),
+    ]),
+
+    /* TODO: enable
+    // TODO: document
+    IMAGE_PROCESSING = IMAGE_PROCESSING_QCOM
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(qcom_image_processing)]),
+    ]), */
+}
+
 /// A special value to indicate that the maximum LOD should not be clamped.
 pub const LOD_CLAMP_NONE: f32 = ash::vk::LOD_CLAMP_NONE;
 
@@ -1487,9 +1600,16 @@ vulkan_enum! {
 #[cfg(test)]
 mod tests {
     use crate::{
-        image::sampler::{
-            Filter, Sampler, SamplerAddressMode, SamplerCreateInfo, SamplerReductionMode,
+        format::Format,
+        image::{
+            sampler::{
+                BorderColor, ComponentMapping, ComponentSwizzle, Filter, Sampler,
+                SamplerAddressMode, SamplerCreateFlags, SamplerCreateInfo, SamplerReductionMode,
+            },
+            view::{ImageView, ImageViewCreateInfo},
+            Image, ImageCreateInfo, ImageType, ImageUsage,
         },
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
         pipeline::graphics::depth_stencil::CompareOp,
         Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError,
     };
@@ -1739,4 +1859,87 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn non_seamless_cube_map_extension() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let r = Sampler::new(
+            device,
+            SamplerCreateInfo {
+                flags: SamplerCreateFlags::NON_SEAMLESS_CUBE_MAP,
+                ..Default::default()
+            },
+        );
+
+        match r {
+            Err(Validated::ValidationError(err))
+                if matches!(
+                    *err,
+                    ValidationError {
+                        requires_one_of: RequiresOneOf([RequiresAllOf([
+                            Requires::DeviceExtension("ext_non_seamless_cube_map")
+                        ])],),
+                        ..
+                    }
+                ) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn border_color_swizzle_feature() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [1, 1, 1],
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let image_view = ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                component_mapping: ComponentMapping {
+                    r: ComponentSwizzle::Blue,
+                    b: ComponentSwizzle::Red,
+                    ..Default::default()
+                },
+                ..ImageViewCreateInfo::from_image(&image)
+            },
+        )
+        .unwrap();
+
+        let sampler = Sampler::new(
+            device,
+            SamplerCreateInfo {
+                address_mode: [SamplerAddressMode::ClampToBorder; 3],
+                border_color: BorderColor::FloatOpaqueBlack,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        match sampler.check_can_sample(&image_view) {
+            Err(err)
+                if matches!(
+                    *err,
+                    ValidationError {
+                        requires_one_of: RequiresOneOf([RequiresAllOf([
+                            Requires::Feature("border_color_swizzle"),
+                            Requires::Feature("border_color_swizzle_from_image"),
+                        ])],),
+                        ..
+                    }
+                ) => {}
+            _ => panic!(),
+        }
+    }
 }
@@ -33,7 +33,7 @@ use crate::{
     instance::InstanceOwnedDebugWrapper,
     macros::impl_id_counter,
     memory::{
-        allocator::{AllocationType, DeviceLayout, MemoryAlloc},
+        allocator::{AllocationType, MemoryAlloc},
         is_aligned, DedicatedTo, ExternalMemoryHandleTypes, MemoryPropertyFlags,
         MemoryRequirements,
     },
@@ -74,6 +74,7 @@ pub struct RawImage {
     initial_layout: ImageLayout,
     drm_format_modifier: Option<(u64, u32)>,
     external_memory_handle_types: ExternalMemoryHandleTypes,
+    view_formats: Vec<Format>,
 
     memory_requirements: SmallVec<[MemoryRequirements; 4]>,
     needs_destruction: bool, // `vkDestroyImage` is called only if true.
@@ -109,143 +110,18 @@ impl RawImage {
         device: Arc<Device>,
         create_info: ImageCreateInfo,
     ) -> Result<Self, VulkanError> {
-        let &ImageCreateInfo {
-            flags,
-            image_type,
-            format,
-            extent,
-            array_layers,
-            mip_levels,
-            samples,
-            tiling,
-            usage,
-            mut stencil_usage,
-            ref sharing,
-            initial_layout,
-            external_memory_handle_types,
-            _ne: _,
-            ref drm_format_modifiers,
-            ref drm_format_modifier_plane_layouts,
-        } = &create_info;
-
-        let aspects = format.aspects();
-
-        let has_separate_stencil_usage = if stencil_usage.is_empty()
-            || !aspects.contains(ImageAspects::DEPTH | ImageAspects::STENCIL)
-        {
-            stencil_usage = usage;
-            false
-        } else {
-            stencil_usage == usage
-        };
-
-        let (sharing_mode, queue_family_index_count, p_queue_family_indices) = match sharing {
-            Sharing::Exclusive => (ash::vk::SharingMode::EXCLUSIVE, 0, &[] as _),
-            Sharing::Concurrent(queue_family_indices) => (
-                ash::vk::SharingMode::CONCURRENT,
-                queue_family_indices.len() as u32,
-                queue_family_indices.as_ptr(),
-            ),
-        };
-
-        let mut info_vk = ash::vk::ImageCreateInfo {
-            flags: flags.into(),
-            image_type: image_type.into(),
-            format: format.into(),
-            extent: ash::vk::Extent3D {
-                width: extent[0],
-                height: extent[1],
-                depth: extent[2],
-            },
-            mip_levels,
-            array_layers,
-            samples: samples.into(),
-            tiling: tiling.into(),
-            usage: usage.into(),
-            sharing_mode,
-            queue_family_index_count,
-            p_queue_family_indices,
-            initial_layout: initial_layout.into(),
-            ..Default::default()
-        };
-        let mut drm_format_modifier_explicit_info_vk = None;
-        let drm_format_modifier_plane_layouts_vk: SmallVec<[_; 4]>;
-        let mut drm_format_modifier_list_info_vk = None;
-        let mut external_memory_info_vk = None;
-        let mut stencil_usage_info_vk = None;
-
-        #[allow(clippy::comparison_chain)]
-        if drm_format_modifiers.len() == 1 {
-            drm_format_modifier_plane_layouts_vk = drm_format_modifier_plane_layouts
-                .iter()
-                .map(|subresource_layout| {
-                    let &SubresourceLayout {
-                        offset,
-                        size,
-                        row_pitch,
-                        array_pitch,
-                        depth_pitch,
-                    } = subresource_layout;
-
-                    ash::vk::SubresourceLayout {
-                        offset,
-                        size,
-                        row_pitch,
-                        array_pitch: array_pitch.unwrap_or(0),
-                        depth_pitch: depth_pitch.unwrap_or(0),
-                    }
-                })
-                .collect();
-
-            let next = drm_format_modifier_explicit_info_vk.insert(
-                ash::vk::ImageDrmFormatModifierExplicitCreateInfoEXT {
-                    drm_format_modifier: drm_format_modifiers[0],
-                    drm_format_modifier_plane_count: drm_format_modifier_plane_layouts_vk.len()
-                        as u32,
-                    p_plane_layouts: drm_format_modifier_plane_layouts_vk.as_ptr(),
-                    ..Default::default()
-                },
-            );
-
-            next.p_next = info_vk.p_next;
-            info_vk.p_next = next as *const _ as *const _;
-        } else if drm_format_modifiers.len() > 1 {
-            let next = drm_format_modifier_list_info_vk.insert(
-                ash::vk::ImageDrmFormatModifierListCreateInfoEXT {
-                    drm_format_modifier_count: drm_format_modifiers.len() as u32,
-                    p_drm_format_modifiers: drm_format_modifiers.as_ptr(),
-                    ..Default::default()
-                },
-            );
-
-            next.p_next = info_vk.p_next;
-            info_vk.p_next = next as *const _ as *const _;
-        }
-
-        if !external_memory_handle_types.is_empty() {
-            let next = external_memory_info_vk.insert(ash::vk::ExternalMemoryImageCreateInfo {
-                handle_types: external_memory_handle_types.into(),
-                ..Default::default()
-            });
-
-            next.p_next = info_vk.p_next;
-            info_vk.p_next = next as *const _ as *const _;
-        }
-
-        if has_separate_stencil_usage {
-            let next = stencil_usage_info_vk.insert(ash::vk::ImageStencilUsageCreateInfo {
-                stencil_usage: stencil_usage.into(),
-                ..Default::default()
-            });
-
-            next.p_next = info_vk.p_next;
-            info_vk.p_next = next as *const _ as *const _;
-        }
-
         let handle = {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
-            (fns.v1_0.create_image)(device.handle(), &info_vk, ptr::null(), output.as_mut_ptr())
+            create_info
+                .with_vk(|info_vk| {
+                    (fns.v1_0.create_image)(
+                        device.handle(),
+                        info_vk,
+                        ptr::null(),
+                        output.as_mut_ptr(),
+                    )
+                })
                 .result()
                 .map_err(VulkanError::from)?;
             output.assume_init()
@@ -290,6 +166,7 @@ impl RawImage {
             sharing,
             initial_layout,
             external_memory_handle_types,
+            view_formats,
             _ne: _,
             drm_format_modifiers: _,
             drm_format_modifier_plane_layouts: _,
@@ -367,6 +244,7 @@ impl RawImage {
             sharing,
             drm_format_modifier,
             external_memory_handle_types,
+            view_formats,
 
             memory_requirements,
             needs_destruction,
@@ -474,18 +352,10 @@ impl RawImage {
             }
         }
 
-        MemoryRequirements {
-            layout: DeviceLayout::from_size_alignment(
-                memory_requirements2_vk.memory_requirements.size,
-                memory_requirements2_vk.memory_requirements.alignment,
-            )
-            .unwrap(),
-            memory_type_bits: memory_requirements2_vk.memory_requirements.memory_type_bits,
-            prefers_dedicated_allocation: memory_dedicated_requirements_vk
-                .map_or(false, |dreqs| dreqs.prefers_dedicated_allocation != 0),
-            requires_dedicated_allocation: memory_dedicated_requirements_vk
-                .map_or(false, |dreqs| dreqs.requires_dedicated_allocation != 0),
-        }
+        MemoryRequirements::from_vk(
+            &memory_requirements2_vk,
+            memory_dedicated_requirements_vk.as_ref(),
+        )
     }
 
     #[allow(dead_code)] // Remove when sparse memory is implemented
@@ -1284,6 +1154,13 @@ impl RawImage {
         self.external_memory_handle_types
     }
 
+    /// Returns the formats that image views created from this image can have, if they were
+    /// provided when the image was created.
+    #[inline]
+    pub fn view_formats(&self) -> &[Format] {
+        &self.view_formats
+    }
+
     /// Returns an `ImageSubresourceLayers` covering the first mip level of the image. All aspects
     /// of the image are selected, or `plane0` if the image is multi-planar.
     #[inline]
@@ -1657,6 +1534,10 @@ pub struct ImageCreateInfo {
 
     /// The number of mip levels to create the image with.
     ///
+    /// This must not be greater than the number of mip levels in a full mipmap chain for
+    /// `extent`. To create the image with a full mipmap chain, set this to the value returned
+    /// by [`max_mip_levels`](Self::max_mip_levels).
+    ///
     /// The default value is `1`.
     pub mip_levels: u32,
 
@@ -1713,6 +1594,24 @@ pub struct ImageCreateInfo {
     /// The default value is [`ExternalMemoryHandleTypes::empty()`].
     pub external_memory_handle_types: ExternalMemoryHandleTypes,
 
+    /// The formats that image views created from this image can have.
+    ///
+    /// If this is not empty, then any image view that is created from this image must have a
+    /// format that is in this list. All of the formats must be compatible with `format`. If
+    /// `flags` does not contain [`ImageCreateFlags::MUTABLE_FORMAT`], then this can contain at
+    /// most one element.
+    ///
+    /// Providing this list allows the implementation to optimize the image for the formats that
+    /// are actually going to be used, so it is recommended to provide it if `flags` contains
+    /// `ImageCreateFlags::MUTABLE_FORMAT`.
+    ///
+    /// If this is not empty, then the device API version must be at least 1.2, or the
+    /// [`khr_image_format_list`](crate::device::DeviceExtensions::khr_image_format_list)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is empty.
+    pub view_formats: Vec<Format>,
+
     /// The Linux DRM format modifiers that the image should be created with.
     ///
     /// If this is not empty, then the
@@ -1757,6 +1656,7 @@ impl Default for ImageCreateInfo {
             sharing: Sharing::Exclusive,
             initial_layout: ImageLayout::Undefined,
             external_memory_handle_types: ExternalMemoryHandleTypes::empty(),
+            view_formats: Vec::new(),
             drm_format_modifiers: SmallVec::new(),
             drm_format_modifier_plane_layouts: SmallVec::new(),
             _ne: crate::NonExhaustive(()),
@@ -1765,6 +1665,204 @@ impl Default for ImageCreateInfo {
 }
 
 impl ImageCreateInfo {
+    /// Returns the number of mip levels in a full mipmap chain for the current `image_type` and
+    /// `extent`, which is the greatest value that `mip_levels` can have.
+    ///
+    /// Only the dimensions of `extent` that are used by `image_type` are taken into account.
+    /// If the extent is zero in any of them, 1 is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vulkano::{
+    ///     format::Format,
+    ///     image::{ImageCreateInfo, ImageType},
+    /// };
+    ///
+    /// let mut create_info = ImageCreateInfo {
+    ///     image_type: ImageType::Dim3d,
+    ///     format: Format::R8G8B8A8_UNORM,
+    ///     extent: [64, 16, 128],
+    ///     ..Default::default()
+    /// };
+    /// create_info.mip_levels = create_info.max_mip_levels();
+    ///
+    /// assert_eq!(create_info.mip_levels, 8);
+    /// ```
+    #[inline]
+    pub fn max_mip_levels(&self) -> u32 {
+        let [width, height, depth] = self.extent;
+        let extent = match self.image_type {
+            ImageType::Dim1d => [width, 1, 1],
+            ImageType::Dim2d => [width, height, 1],
+            ImageType::Dim3d => [width, height, depth],
+        };
+
+        if extent.contains(&0) {
+            return 1;
+        }
+
+        max_mip_levels(extent)
+    }
+
+    /// Builds the Vulkan create info, including its `p_next` chain, and passes it to `f`.
+    pub(crate) fn with_vk<R>(&self, f: impl FnOnce(&ash::vk::ImageCreateInfo) -> R) -> R {
+        let &Self {
+            flags,
+            image_type,
+            format,
+            extent,
+            array_layers,
+            mip_levels,
+            samples,
+            tiling,
+            usage,
+            mut stencil_usage,
+            ref sharing,
+            initial_layout,
+            external_memory_handle_types,
+            ref view_formats,
+            _ne: _,
+            ref drm_format_modifiers,
+            ref drm_format_modifier_plane_layouts,
+        } = self;
+
+        let aspects = format.aspects();
+
+        let has_separate_stencil_usage = if stencil_usage.is_empty()
+            || !aspects.contains(ImageAspects::DEPTH | ImageAspects::STENCIL)
+        {
+            stencil_usage = usage;
+            false
+        } else {
+            stencil_usage == usage
+        };
+
+        let (sharing_mode, queue_family_index_count, p_queue_family_indices) = match sharing {
+            Sharing::Exclusive => (ash::vk::SharingMode::EXCLUSIVE, 0, &[] as _),
+            Sharing::Concurrent(queue_family_indices) => (
+                ash::vk::SharingMode::CONCURRENT,
+                queue_family_indices.len() as u32,
+                queue_family_indices.as_ptr(),
+            ),
+        };
+
+        let mut info_vk = ash::vk::ImageCreateInfo {
+            flags: flags.into(),
+            image_type: image_type.into(),
+            format: format.into(),
+            extent: ash::vk::Extent3D {
+                width: extent[0],
+                height: extent[1],
+                depth: extent[2],
+            },
+            mip_levels,
+            array_layers,
+            samples: samples.into(),
+            tiling: tiling.into(),
+            usage: usage.into(),
+            sharing_mode,
+            queue_family_index_count,
+            p_queue_family_indices,
+            initial_layout: initial_layout.into(),
+            ..Default::default()
+        };
+        let mut drm_format_modifier_explicit_info_vk = None;
+        let drm_format_modifier_plane_layouts_vk: SmallVec<[_; 4]>;
+        let mut drm_format_modifier_list_info_vk = None;
+        let mut external_memory_info_vk = None;
+        let mut format_list_info_vk = None;
+        let view_formats_vk: Vec<_>;
+        let mut stencil_usage_info_vk = None;
+
+        #[allow(clippy::comparison_chain)]
+        if drm_format_modifiers.len() == 1 {
+            drm_format_modifier_plane_layouts_vk = drm_format_modifier_plane_layouts
+                .iter()
+                .map(|subresource_layout| {
+                    let &SubresourceLayout {
+                        offset,
+                        size,
+                        row_pitch,
+                        array_pitch,
+                        depth_pitch,
+                    } = subresource_layout;
+
+                    ash::vk::SubresourceLayout {
+                        offset,
+                        size,
+                        row_pitch,
+                        array_pitch: array_pitch.unwrap_or(0),
+                        depth_pitch: depth_pitch.unwrap_or(0),
+                    }
+                })
+                .collect();
+
+            let next = drm_format_modifier_explicit_info_vk.insert(
+                ash::vk::ImageDrmFormatModifierExplicitCreateInfoEXT {
+                    drm_format_modifier: drm_format_modifiers[0],
+                    drm_format_modifier_plane_count: drm_format_modifier_plane_layouts_vk.len()
+                        as u32,
+                    p_plane_layouts: drm_format_modifier_plane_layouts_vk.as_ptr(),
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = info_vk.p_next;
+            info_vk.p_next = next as *const _ as *const _;
+        } else if drm_format_modifiers.len() > 1 {
+            let next = drm_format_modifier_list_info_vk.insert(
+                ash::vk::ImageDrmFormatModifierListCreateInfoEXT {
+                    drm_format_modifier_count: drm_format_modifiers.len() as u32,
+                    p_drm_format_modifiers: drm_format_modifiers.as_ptr(),
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = info_vk.p_next;
+            info_vk.p_next = next as *const _ as *const _;
+        }
+
+        if !external_memory_handle_types.is_empty() {
+            let next = external_memory_info_vk.insert(ash::vk::ExternalMemoryImageCreateInfo {
+                handle_types: external_memory_handle_types.into(),
+                ..Default::default()
+            });
+
+            next.p_next = info_vk.p_next;
+            info_vk.p_next = next as *const _ as *const _;
+        }
+
+        if !view_formats.is_empty() {
+            view_formats_vk = view_formats
+                .iter()
+                .copied()
+                .map(ash::vk::Format::from)
+                .collect();
+
+            let next = format_list_info_vk.insert(ash::vk::ImageFormatListCreateInfo {
+                view_format_count: view_formats_vk.len() as u32,
+                p_view_formats: view_formats_vk.as_ptr(),
+                ..Default::default()
+            });
+
+            next.p_next = info_vk.p_next;
+            info_vk.p_next = next as *const _ as *const _;
+        }
+
+        if has_separate_stencil_usage {
+            let next = stencil_usage_info_vk.insert(ash::vk::ImageStencilUsageCreateInfo {
+                stencil_usage: stencil_usage.into(),
+                ..Default::default()
+            });
+
+            next.p_next = info_vk.p_next;
+            info_vk.p_next = next as *const _ as *const _;
+        }
+
+        f(&info_vk)
+    }
+
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             flags,
@@ -1780,6 +1878,7 @@ impl ImageCreateInfo {
             ref sharing,
             initial_layout,
             external_memory_handle_types,
+            ref view_formats,
             _ne: _,
             ref drm_format_modifiers,
             ref drm_format_modifier_plane_layouts,
@@ -1804,6 +1903,10 @@ impl ImageCreateInfo {
                 ..ValidationError::from_requirement(err)
             })?;
 
+        format
+            .validate_features(device)
+            .map_err(|err| err.add_context("format"))?;
+
         samples
             .validate_device(device)
             .map_err(|err| ValidationError {
@@ -2030,9 +2133,12 @@ impl ImageCreateInfo {
 
         if mip_levels > max_mip_levels {
             return Err(Box::new(ValidationError {
-                problem: "`mip_levels` is greater than the maximum allowed number of mip levels \
-                    for `dimensions`"
-                    .into(),
+                problem: format!(
+                    "`mip_levels` ({}) is greater than the number of mip levels in a full \
+                    mipmap chain for `extent` ({})",
+                    mip_levels, max_mip_levels,
+                )
+                .into(),
                 vuids: &["VUID-VkImageCreateInfo-mipLevels-00958"],
                 ..Default::default()
             }));
@@ -2276,6 +2382,40 @@ impl ImageCreateInfo {
             }
         }
 
+        if usage.intersects(ImageUsage::FRAGMENT_DENSITY_MAP)
+            && !device.enabled_features().fragment_density_map_offset
+        {
+            let texel_size = device_properties
+                .min_fragment_density_texel_size
+                .unwrap_or([1, 1]);
+            let max_extent = [
+                device_properties
+                    .max_framebuffer_width
+                    .div_ceil(texel_size[0]),
+                device_properties
+                    .max_framebuffer_height
+                    .div_ceil(texel_size[1]),
+            ];
+
+            if extent[0] > max_extent[0] || extent[1] > max_extent[1] {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`usage` contains `ImageUsage::FRAGMENT_DENSITY_MAP`, but `extent` \
+                        exceeds the `max_framebuffer_width` and `max_framebuffer_height` limits \
+                        divided by the `min_fragment_density_texel_size` property, rounded up \
+                        ({:?})",
+                        max_extent,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkImageCreateInfo-fragmentDensityMapOffset-06514",
+                        "VUID-VkImageCreateInfo-fragmentDensityMapOffset-06515",
+                    ],
+                    ..Default::default()
+                }));
+            }
+        }
+
         if has_separate_stencil_usage {
             if usage.intersects(ImageUsage::DEPTH_STENCIL_ATTACHMENT)
                 && !stencil_usage.intersects(ImageUsage::DEPTH_STENCIL_ATTACHMENT)
@@ -2464,6 +2604,49 @@ impl ImageCreateInfo {
             }
         }
 
+        if flags.intersects(ImageCreateFlags::SUBSAMPLED) {
+            if tiling != ImageTiling::Optimal {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` contains `ImageCreateFlags::SUBSAMPLED`, but \
+                        `tiling` is not `ImageTiling::Optimal`"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-flags-02565"],
+                    ..Default::default()
+                }));
+            }
+
+            if image_type != ImageType::Dim2d {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` contains `ImageCreateFlags::SUBSAMPLED`, but \
+                        `image_type` is not `ImageType::Dim2d`"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-flags-02566"],
+                    ..Default::default()
+                }));
+            }
+
+            if flags.intersects(ImageCreateFlags::CUBE_COMPATIBLE) {
+                return Err(Box::new(ValidationError {
+                    context: "flags".into(),
+                    problem: "contains both `ImageCreateFlags::SUBSAMPLED` and \
+                        `ImageCreateFlags::CUBE_COMPATIBLE`"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-flags-02567"],
+                    ..Default::default()
+                }));
+            }
+
+            if mip_levels != 1 {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` contains `ImageCreateFlags::SUBSAMPLED`, but \
+                        `mip_levels` is not 1"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-flags-02568"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         /* Check sharing mode and queue families */
 
         match sharing {
@@ -2545,6 +2728,65 @@ impl ImageCreateInfo {
             }
         }
 
+        /* View formats */
+
+        if !view_formats.is_empty() {
+            if !(device.api_version() >= Version::V1_2
+                || device.enabled_extensions().khr_image_format_list)
+            {
+                return Err(Box::new(ValidationError {
+                    context: "view_formats".into(),
+                    problem: "is not empty".into(),
+                    requires_one_of: RequiresOneOf(&[
+                        RequiresAllOf(&[Requires::APIVersion(Version::V1_2)]),
+                        RequiresAllOf(&[Requires::DeviceExtension("khr_image_format_list")]),
+                    ]),
+                    ..Default::default()
+                }));
+            }
+
+            if !flags.intersects(ImageCreateFlags::MUTABLE_FORMAT) && view_formats.len() > 1 {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` does not contain `ImageCreateFlags::MUTABLE_FORMAT`, but \
+                        `view_formats` contains more than one element"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-flags-04738"],
+                    ..Default::default()
+                }));
+            }
+
+            for (index, &view_format) in view_formats.iter().enumerate() {
+                view_format
+                    .validate_device(device)
+                    .map_err(|err| ValidationError {
+                        context: format!("view_formats[{}]", index).into(),
+                        vuids: &["VUID-VkImageFormatListCreateInfo-pViewFormats-parameter"],
+                        ..ValidationError::from_requirement(err)
+                    })?;
+
+                let is_compatible = view_format.compatibility() == format.compatibility()
+                    || (flags.intersects(ImageCreateFlags::BLOCK_TEXEL_VIEW_COMPATIBLE)
+                        && view_format.compression().is_none()
+                        && view_format.block_size() == format.block_size())
+                    || format
+                        .planes()
+                        .iter()
+                        .any(|plane| view_format.compatibility() == plane.compatibility());
+
+                if !is_compatible {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`view_formats[{}]` is not compatible with `format`",
+                            index
+                        )
+                        .into(),
+                        vuids: &["VUID-VkImageCreateInfo-pNext-06722"],
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
         if !drm_format_modifiers.is_empty() {
             // This implicitly checks for the enabled extension too,
             // so no need to check that separately.
@@ -2836,10 +3078,37 @@ mod tests {
             ImageAspect, ImageAspects, ImageCreateFlags, ImageSubresourceRange, ImageType,
             SampleCount, SubresourceRangeIterator,
         },
-        DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError,
+        DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
     };
     use smallvec::SmallVec;
 
+    #[test]
+    fn max_mip_levels() {
+        let create_info = |image_type, extent| ImageCreateInfo {
+            image_type,
+            extent,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            create_info(ImageType::Dim1d, [300, 1, 1]).max_mip_levels(),
+            9
+        );
+        assert_eq!(
+            create_info(ImageType::Dim2d, [64, 256, 1]).max_mip_levels(),
+            9
+        );
+        assert_eq!(
+            create_info(ImageType::Dim2d, [64, 64, 512]).max_mip_levels(),
+            7
+        );
+        assert_eq!(
+            create_info(ImageType::Dim3d, [64, 64, 512]).max_mip_levels(),
+            10
+        );
+        assert_eq!(create_info(ImageType::Dim2d, [0, 0, 1]).max_mip_levels(), 1);
+    }
+
     #[test]
     fn create_sampled() {
         let (device, _) = gfx_dev_and_queue!();
@@ -2916,6 +3185,28 @@ mod tests {
         };
     }
 
+    #[test]
+    fn view_formats_without_mutable_format() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [32, 32, 1],
+                usage: ImageUsage::SAMPLED,
+                view_formats: vec![Format::R8G8B8A8_UNORM, Format::R8G8B8A8_SRGB],
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(Validated::ValidationError(_)) => (),
+            _ => panic!(),
+        };
+    }
+
     #[test]
     fn shader_storage_image_multisample() {
         let (device, _) = gfx_dev_and_queue!();
@@ -2948,6 +3239,28 @@ mod tests {
         };
     }
 
+    #[test]
+    fn format_4444_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.api_version() >= Version::V1_3 || device.enabled_features().format_a4r4g4b4 {
+            return; // test ignored
+        }
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::A4R4G4B4_UNORM_PACK16,
+                extent: [32, 32, 1],
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(res, Err(Validated::ValidationError(_))));
+    }
+
     #[test]
     fn compressed_not_color_attachment() {
         let (device, _) = gfx_dev_and_queue!();
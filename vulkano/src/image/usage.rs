@@ -70,12 +70,11 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_video_decode_queue)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used as a fragment density map attachment in a render pass/framebuffer.
     FRAGMENT_DENSITY_MAP = FRAGMENT_DENSITY_MAP_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_fragment_density_map)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -105,12 +104,13 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_video_encode_queue)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used in the
+    /// [`AttachmentFeedbackLoopOptimal`](crate::image::ImageLayout::AttachmentFeedbackLoopOptimal)
+    /// layout.
     ATTACHMENT_FEEDBACK_LOOP = ATTACHMENT_FEEDBACK_LOOP_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_attachment_feedback_loop_layout)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -112,9 +112,13 @@ impl ImageView {
 
         if subresource_range.mip_levels.end > image.mip_levels() {
             return Err(Box::new(ValidationError {
-                problem: "`create_info.subresource_range.mip_levels.end` is greater than \
-                    `image.mip_levels()`"
-                    .into(),
+                problem: format!(
+                    "`create_info.subresource_range.mip_levels.end` ({}) is greater than \
+                    `image.mip_levels()` ({})",
+                    subresource_range.mip_levels.end,
+                    image.mip_levels(),
+                )
+                .into(),
                 vuids: &["VUID-VkImageViewCreateInfo-subresourceRange-01718"],
                 ..Default::default()
             }));
@@ -193,13 +197,16 @@ impl ImageView {
 
             if subresource_range.array_layers.end > mip_level_extent[2] {
                 return Err(Box::new(ValidationError {
-                    problem: "`create_info.view_type` is `ImageViewType::Dim2d` or \
+                    problem: format!(
+                        "`create_info.view_type` is `ImageViewType::Dim2d` or \
                         `ImageViewType::Dim2dArray`, and \
                         `image.image_type()` is `ImageType::Dim3d`, but \
-                        `create_info.subresource_range.array_layers.end` is greater than \
+                        `create_info.subresource_range.array_layers.end` ({}) is greater than \
                         the depth of the mip level \
-                        `create_info.subresource_range.mip_levels` of `image`"
-                        .into(),
+                        `create_info.subresource_range.mip_levels.start` of `image` ({})",
+                        subresource_range.array_layers.end, mip_level_extent[2],
+                    )
+                    .into(),
                     vuids: &[
                         "VUID-VkImageViewCreateInfo-image-02724",
                         "VUID-VkImageViewCreateInfo-subresourceRange-02725",
@@ -210,9 +217,13 @@ impl ImageView {
         } else {
             if subresource_range.array_layers.end > image.array_layers() {
                 return Err(Box::new(ValidationError {
-                    problem: "`create_info.subresource_range.array_layers.end` is greater than \
-                        `image.array_layers()`"
-                        .into(),
+                    problem: format!(
+                        "`create_info.subresource_range.array_layers.end` ({}) is greater than \
+                        `image.array_layers()` ({})",
+                        subresource_range.array_layers.end,
+                        image.array_layers(),
+                    )
+                    .into(),
                     vuids: &[
                         "VUID-VkImageViewCreateInfo-image-06724",
                         "VUID-VkImageViewCreateInfo-subresourceRange-06725",
@@ -380,6 +391,16 @@ impl ImageView {
 
         /* Check flags requirements */
 
+        if !image.view_formats().is_empty() && !image.view_formats().contains(&format) {
+            return Err(Box::new(ValidationError {
+                problem: "`image.view_formats()` is not empty, but it does not contain \
+                    `create_info.format`"
+                    .into(),
+                vuids: &["VUID-VkImageViewCreateInfo-pNext-01585"],
+                ..Default::default()
+            }));
+        }
+
         if format != image.format() {
             if !image.format().planes().is_empty()
                 && subresource_range.aspects.intersects(ImageAspects::COLOR)
@@ -947,6 +968,10 @@ impl ImageViewCreateInfo {
                 ..ValidationError::from_requirement(err)
             })?;
 
+        format
+            .validate_features(device)
+            .map_err(|err| err.add_context("format"))?;
+
         component_mapping
             .validate(device)
             .map_err(|err| err.add_context("component_mapping"))?;
@@ -979,9 +1004,12 @@ impl ImageViewCreateInfo {
             ImageViewType::Cube => {
                 if subresource_range.array_layers.len() != 6 {
                     return Err(Box::new(ValidationError {
-                        problem: "`view_type` is `ImageViewType::Cube`, but \
-                            the length of `subresource_range.array_layers` is not 6"
-                            .into(),
+                        problem: format!(
+                            "`view_type` is `ImageViewType::Cube`, but \
+                            the length of `subresource_range.array_layers` ({}) is not 6",
+                            subresource_range.array_layers.len(),
+                        )
+                        .into(),
                         vuids: &["VUID-VkImageViewCreateInfo-viewType-02960"],
                         ..Default::default()
                     }));
@@ -1001,10 +1029,13 @@ impl ImageViewCreateInfo {
 
                 if subresource_range.array_layers.len() % 6 != 0 {
                     return Err(Box::new(ValidationError {
-                        problem: "`view_type` is `ImageViewType::CubeArray`, but \
-                            the length of `subresource_range.array_layers` is not \
-                            a multiple of 6"
-                            .into(),
+                        problem: format!(
+                            "`view_type` is `ImageViewType::CubeArray`, but \
+                            the length of `subresource_range.array_layers` ({}) is not \
+                            a multiple of 6",
+                            subresource_range.array_layers.len(),
+                        )
+                        .into(),
                         vuids: &["VUID-VkImageViewCreateInfo-viewType-02961"],
                         ..Default::default()
                     }));
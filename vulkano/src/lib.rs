@@ -365,6 +365,10 @@ impl Display for VulkanError {
                 "the requested DRM format modifier plane layout is invalid"
             }
             VulkanError::InvalidShader => "one or more shaders failed to compile or link",
+            VulkanError::PipelineCompileRequired => {
+                "a pipeline needed to be compiled, but the application requested that this not be \
+                done"
+            }
             VulkanError::ImageUsageNotSupported => "the requested `ImageUsage` are not supported",
             VulkanError::VideoPictureLayoutNotSupported => {
                 "the requested video picture layout is not supported"
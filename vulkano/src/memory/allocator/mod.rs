@@ -626,6 +626,32 @@ impl MemoryTypeFilter {
         not_preferred_flags: MemoryPropertyFlags::empty(),
     };
 
+    /// Prefers picking a memory type with the [`LAZILY_ALLOCATED`] flag.
+    ///
+    /// Lazily allocated memory is only committed by the implementation when it is actually
+    /// needed, which on tile-based implementations may be never, if the contents of an image can
+    /// live entirely in tile memory during a render pass. This filter is therefore best suited for
+    /// images with the [`TRANSIENT_ATTACHMENT`] usage, which [`Image::new`] adds it for
+    /// automatically. Only such images are permitted to be bound to lazily allocated memory.
+    ///
+    /// Many implementations, such as most desktop GPUs, don't have any lazily allocated memory
+    /// types. Since this is only a preference, another memory type is picked in that case.
+    ///
+    /// Lazily allocated memory is never host-visible, so this can't be combined with
+    /// [`HOST_SEQUENTIAL_WRITE`] or [`HOST_RANDOM_ACCESS`]. Memory of a lazily-allocated type is
+    /// always given its own [`DeviceMemory`] block instead of being suballocated.
+    ///
+    /// [`LAZILY_ALLOCATED`]: MemoryPropertyFlags::LAZILY_ALLOCATED
+    /// [`TRANSIENT_ATTACHMENT`]: crate::image::ImageUsage::TRANSIENT_ATTACHMENT
+    /// [`Image::new`]: crate::image::Image::new
+    /// [`HOST_SEQUENTIAL_WRITE`]: Self::HOST_SEQUENTIAL_WRITE
+    /// [`HOST_RANDOM_ACCESS`]: Self::HOST_RANDOM_ACCESS
+    pub const PREFER_LAZILY_ALLOCATED: Self = Self {
+        required_flags: MemoryPropertyFlags::empty(),
+        preferred_flags: MemoryPropertyFlags::LAZILY_ALLOCATED,
+        not_preferred_flags: MemoryPropertyFlags::empty(),
+    };
+
     /// Returns a `MemoryTypeFilter` with none of the flags set.
     #[inline]
     pub const fn empty() -> Self {
@@ -672,6 +698,43 @@ pub struct AllocationCreateInfo {
     /// The default value is [`MemoryAllocatePreference::Unknown`].
     pub allocate_preference: MemoryAllocatePreference,
 
+    /// The [priority] of the allocation, between `0.0` and `1.0` inclusive.
+    ///
+    /// The priority belongs to a [`DeviceMemory`] block as a whole, so it is only applied to
+    /// dedicated allocations, where the allocator creates a block of `DeviceMemory` for this
+    /// allocation alone. It is **not** passed through to allocations from the allocator's pools:
+    /// those blocks are shared between allocations of any priority and always get the default
+    /// priority of `0.5`. With [`MemoryAllocatePreference::Unknown`], most small allocations are
+    /// suballocated from a pool, so use [`MemoryAllocatePreference::AlwaysAllocate`] if the
+    /// priority must take effect.
+    ///
+    /// This option is silently ignored (treated as `0.5`) if the [`memory_priority`] feature is
+    /// not enabled on the device.
+    ///
+    /// If the [`pageable_device_local_memory`] feature is enabled on the device as well, the
+    /// implementation is allowed to move device-local blocks to system memory when device memory
+    /// is oversubscribed, starting with the blocks with the lowest priority, instead of failing
+    /// the allocation. The priority of a block can be changed afterwards with
+    /// [`DeviceMemory::set_priority`].
+    ///
+    /// The default value is `0.5`.
+    ///
+    /// [priority]: MemoryAllocateInfo::priority
+    /// [`memory_priority`]: crate::device::Features::memory_priority
+    /// [`pageable_device_local_memory`]: crate::device::Features::pageable_device_local_memory
+    pub priority: f32,
+
+    /// The minimum alignment of the allocation, in addition to the alignment required by the
+    /// memory requirements.
+    ///
+    /// The offset of the allocation will be a multiple of both this alignment and the required
+    /// alignment. This is useful when sharing memory with other APIs that have coarser alignment
+    /// requirements than what Vulkan reports. Since alignments are powers of two, this is the
+    /// greater of the two.
+    ///
+    /// The default value is `None`.
+    pub alignment: Option<DeviceAlignment>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -681,6 +744,8 @@ impl Default for AllocationCreateInfo {
         AllocationCreateInfo {
             memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
             allocate_preference: MemoryAllocatePreference::Unknown,
+            priority: 0.5,
+            alignment: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1078,11 +1143,19 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
     fn validate_allocate(
         &self,
         requirements: MemoryRequirements,
+        create_info: &AllocationCreateInfo,
         dedicated_allocation: Option<DedicatedAllocation<'_>>,
     ) {
         assert!(requirements.memory_type_bits != 0);
         assert!(requirements.memory_type_bits < 1 << self.pools.len());
 
+        // VUID-VkMemoryPriorityAllocateInfoEXT-priority-02602
+        assert!((0.0..=1.0).contains(&create_info.priority));
+
+        if let Some(alignment) = create_info.alignment {
+            assert!(requirements.layout.align_to(alignment).is_some());
+        }
+
         if let Some(dedicated_allocation) = dedicated_allocation {
             match dedicated_allocation {
                 DedicatedAllocation::Buffer(buffer) => {
@@ -1359,6 +1432,9 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
     ///   where *n* is the number of available memory types.
     /// - Panics if `create_info.dedicated_allocation` is `Some` and
     ///   `create_info.requirements.size` doesn't match the memory requirements of the resource.
+    /// - Panics if `create_info.priority` is not between `0.0` and `1.0` inclusive.
+    /// - Panics if `create_info.alignment` is `Some` and `create_info.requirements.size` would
+    ///   overflow [`DeviceLayout::MAX_SIZE`] when rounded up to a multiple of it.
     /// - Panics if finding a suitable memory type failed. This only happens if the
     ///   `create_info.requirements` correspond to those of an optimal image but
     ///   `create_info.memory_type_filter` requires host access.
@@ -1392,7 +1468,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
         create_info: AllocationCreateInfo,
         dedicated_allocation: Option<DedicatedAllocation<'_>>,
     ) -> Result<MemoryAlloc, MemoryAllocatorError> {
-        self.validate_allocate(requirements, dedicated_allocation);
+        self.validate_allocate(requirements, &create_info, dedicated_allocation);
 
         unsafe {
             self.allocate_unchecked(
@@ -1412,7 +1488,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
         mut dedicated_allocation: Option<DedicatedAllocation<'_>>,
     ) -> Result<MemoryAlloc, MemoryAllocatorError> {
         let MemoryRequirements {
-            layout,
+            mut layout,
             mut memory_type_bits,
             mut prefers_dedicated_allocation,
             requires_dedicated_allocation,
@@ -1421,9 +1497,19 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
         let AllocationCreateInfo {
             memory_type_filter,
             allocate_preference,
+            mut priority,
+            alignment,
             _ne: _,
         } = create_info;
 
+        if !self.device.enabled_features().memory_priority {
+            priority = 0.5;
+        }
+
+        if let Some(alignment) = alignment {
+            layout = layout.align_to(alignment).unwrap();
+        }
+
         let create_info = SuballocationCreateInfo {
             layout,
             allocation_type,
@@ -1455,12 +1541,19 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                 MemoryAllocatePreference::Unknown => {
                     // VUID-vkBindBufferMemory-buffer-01444
                     // VUID-vkBindImageMemory-image-01445
-                    if requires_dedicated_allocation {
-                        self.allocate_dedicated_unchecked(
+                    // Lazily allocated memory is also given its own block, as the commitment is
+                    // tracked per `DeviceMemory` and sharing a block would defeat the purpose.
+                    if requires_dedicated_allocation
+                        || memory_type
+                            .property_flags
+                            .contains(ash::vk::MemoryPropertyFlags::LAZILY_ALLOCATED)
+                    {
+                        self.allocate_dedicated(
                             memory_type_index,
                             size,
                             dedicated_allocation,
                             export_handle_types,
+                            priority,
                         )
                     } else {
                         if size > block_size / 2 {
@@ -1473,11 +1566,12 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                         }
 
                         if prefers_dedicated_allocation {
-                            self.allocate_dedicated_unchecked(
+                            self.allocate_dedicated(
                                 memory_type_index,
                                 size,
                                 dedicated_allocation,
                                 export_handle_types,
+                                priority,
                             )
                             // Fall back to suballocation.
                             .or_else(|err| {
@@ -1501,11 +1595,12 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                             // Fall back to dedicated allocation. It is possible that the 1/8 block
                             // size tried was greater than the allocation size, so there's hope.
                             .or_else(|_| {
-                                self.allocate_dedicated_unchecked(
+                                self.allocate_dedicated(
                                     memory_type_index,
                                     size,
                                     dedicated_allocation,
                                     export_handle_types,
+                                    priority,
                                 )
                             })
                         }
@@ -1518,11 +1613,12 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
 
                     self.allocate_from_type_unchecked(memory_type_index, create_info.clone(), true)
                 }
-                MemoryAllocatePreference::AlwaysAllocate => self.allocate_dedicated_unchecked(
+                MemoryAllocatePreference::AlwaysAllocate => self.allocate_dedicated(
                     memory_type_index,
                     size,
                     dedicated_allocation,
                     export_handle_types,
+                    priority,
                 ),
             };
 
@@ -1549,6 +1645,25 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
         allocation_size: DeviceSize,
         dedicated_allocation: Option<DedicatedAllocation<'_>>,
         export_handle_types: ExternalMemoryHandleTypes,
+    ) -> Result<MemoryAlloc, MemoryAllocatorError> {
+        self.allocate_dedicated(
+            memory_type_index,
+            allocation_size,
+            dedicated_allocation,
+            export_handle_types,
+            0.5,
+        )
+    }
+}
+
+impl<S: Suballocator> GenericMemoryAllocator<S> {
+    unsafe fn allocate_dedicated(
+        &self,
+        memory_type_index: u32,
+        allocation_size: DeviceSize,
+        dedicated_allocation: Option<DedicatedAllocation<'_>>,
+        export_handle_types: ExternalMemoryHandleTypes,
+        priority: f32,
     ) -> Result<MemoryAlloc, MemoryAllocatorError> {
         let allocate_info = MemoryAllocateInfo {
             allocation_size,
@@ -1556,6 +1671,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
             dedicated_allocation,
             export_handle_types,
             flags: self.flags,
+            priority,
             ..Default::default()
         };
         let mut allocation = MemoryAlloc::new(DeviceMemory::allocate_unchecked(
@@ -1805,3 +1921,55 @@ mod array_vec {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AllocationCreateInfo, AllocationType, DeviceLayout, MemoryAllocator, MemoryRequirements,
+        StandardMemoryAllocator,
+    };
+    use crate::memory::DeviceAlignment;
+
+    #[test]
+    fn allocation_alignment() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type_count = device
+            .physical_device()
+            .memory_properties()
+            .memory_types
+            .len();
+        let allocator = StandardMemoryAllocator::new_default(device);
+
+        let requirements = MemoryRequirements {
+            layout: DeviceLayout::from_size_alignment(100, 4).unwrap(),
+            memory_type_bits: u32::MAX >> (32 - memory_type_count),
+            prefers_dedicated_allocation: false,
+            requires_dedicated_allocation: false,
+        };
+        let alignment = DeviceAlignment::new(4096).unwrap();
+
+        // Occupy the start of the block, so that the next allocation isn't trivially aligned.
+        let _first = allocator
+            .allocate(
+                requirements,
+                AllocationType::Linear,
+                AllocationCreateInfo::default(),
+                None,
+            )
+            .unwrap();
+        let second = allocator
+            .allocate(
+                requirements,
+                AllocationType::Linear,
+                AllocationCreateInfo {
+                    alignment: Some(alignment),
+                    ..Default::default()
+                },
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(second.offset() % alignment.as_devicesize(), 0);
+        assert!(second.size() >= 100);
+    }
+}
@@ -12,7 +12,10 @@ use crate::{
     device::{Device, DeviceOwned},
     instance::InstanceOwnedDebugWrapper,
     macros::{impl_id_counter, vulkan_bitflags, vulkan_bitflags_enum},
-    memory::{is_aligned, MemoryPropertyFlags},
+    memory::{
+        allocator::{align_down, align_up},
+        is_aligned, MemoryPropertyFlags,
+    },
     DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
     VulkanError, VulkanObject,
 };
@@ -23,7 +26,10 @@ use std::{
     num::NonZeroU64,
     ops::Range,
     ptr, slice,
-    sync::{atomic::Ordering, Arc},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
 };
 
 /// Represents memory that has been allocated from the device.
@@ -61,6 +67,7 @@ pub struct DeviceMemory {
     export_handle_types: ExternalMemoryHandleTypes,
     imported_handle_type: Option<ExternalMemoryHandleType>,
     flags: MemoryAllocateFlags,
+    priority: AtomicU32,
 }
 
 impl DeviceMemory {
@@ -163,6 +170,7 @@ impl DeviceMemory {
             dedicated_allocation,
             export_handle_types,
             flags,
+            priority,
             _ne: _,
         } = allocate_info;
 
@@ -251,6 +259,15 @@ impl DeviceMemory {
             allocate_info = allocate_info.push_next(&mut flags_info);
         }
 
+        let mut priority_info = ash::vk::MemoryPriorityAllocateInfoEXT {
+            priority,
+            ..Default::default()
+        };
+
+        if priority != 0.5 {
+            allocate_info = allocate_info.push_next(&mut priority_info);
+        }
+
         // VUID-vkAllocateMemory-maxMemoryAllocationCount-04101
         let max_allocations = device
             .physical_device()
@@ -291,6 +308,7 @@ impl DeviceMemory {
             export_handle_types,
             imported_handle_type,
             flags,
+            priority: AtomicU32::new(priority.to_bits()),
         })
     }
 
@@ -312,6 +330,7 @@ impl DeviceMemory {
             dedicated_allocation,
             export_handle_types,
             flags,
+            priority,
             _ne: _,
         } = allocate_info;
 
@@ -325,6 +344,7 @@ impl DeviceMemory {
             export_handle_types,
             imported_handle_type: None,
             flags,
+            priority: AtomicU32::new(priority.to_bits()),
         }
     }
 
@@ -370,6 +390,69 @@ impl DeviceMemory {
         self.flags
     }
 
+    /// Returns the current priority of the memory.
+    ///
+    /// This is the priority that the memory was allocated with, unless it was changed with
+    /// [`set_priority`](Self::set_priority).
+    #[inline]
+    pub fn priority(&self) -> f32 {
+        f32::from_bits(self.priority.load(Ordering::Relaxed))
+    }
+
+    /// Changes the priority of the memory, after it has been allocated.
+    ///
+    /// With the [`pageable_device_local_memory`] feature, the implementation can move device-local
+    /// memory to system memory when device memory is oversubscribed, instead of failing new
+    /// allocations. The priority is used as a hint to decide which memory to move first, with
+    /// lower priorities being moved before higher ones. See [`MemoryAllocateInfo::priority`] for
+    /// more information.
+    ///
+    /// The [`pageable_device_local_memory`] feature must be enabled on the device.
+    ///
+    /// [`pageable_device_local_memory`]: crate::device::Features::pageable_device_local_memory
+    #[inline]
+    pub fn set_priority(&self, priority: f32) -> Result<(), Box<ValidationError>> {
+        self.validate_set_priority(priority)?;
+
+        unsafe {
+            self.set_priority_unchecked(priority);
+        }
+
+        Ok(())
+    }
+
+    fn validate_set_priority(&self, priority: f32) -> Result<(), Box<ValidationError>> {
+        if !self.device.enabled_features().pageable_device_local_memory {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "pageable_device_local_memory",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !(0.0..=1.0).contains(&priority) {
+            return Err(Box::new(ValidationError {
+                context: "priority".into(),
+                problem: "is not between 0.0 and 1.0 inclusive".into(),
+                vuids: &["VUID-vkSetDeviceMemoryPriorityEXT-priority-06258"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn set_priority_unchecked(&self, priority: f32) {
+        let fns = self.device.fns();
+        (fns.ext_pageable_device_local_memory
+            .set_device_memory_priority_ext)(self.device.handle(), self.handle, priority);
+
+        self.priority.store(priority.to_bits(), Ordering::Relaxed);
+    }
+
     /// Retrieves the amount of lazily-allocated memory that is currently commited to this
     /// memory object.
     ///
@@ -576,6 +659,22 @@ pub struct MemoryAllocateInfo<'d> {
     /// The default value is [`MemoryAllocateFlags::empty()`].
     pub flags: MemoryAllocateFlags,
 
+    /// The priority of the memory allocation relative to other allocations, used as a hint by
+    /// the implementation when deciding which memory to evict to system memory when under memory
+    /// pressure. Allocations with a lower priority are evicted first.
+    ///
+    /// The value must be between `0.0` and `1.0` inclusive. If it is not `0.5`, the
+    /// [`memory_priority`](crate::device::Features::memory_priority) feature must be enabled on
+    /// the device.
+    ///
+    /// If the [`pageable_device_local_memory`] feature is enabled on the device, the priority
+    /// can be changed after allocation with [`DeviceMemory::set_priority`].
+    ///
+    /// The default value is `0.5`.
+    ///
+    /// [`pageable_device_local_memory`]: crate::device::Features::pageable_device_local_memory
+    pub priority: f32,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -588,6 +687,7 @@ impl Default for MemoryAllocateInfo<'static> {
             dedicated_allocation: None,
             export_handle_types: ExternalMemoryHandleTypes::empty(),
             flags: MemoryAllocateFlags::empty(),
+            priority: 0.5,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -603,6 +703,7 @@ impl<'d> MemoryAllocateInfo<'d> {
             dedicated_allocation: Some(dedicated_allocation),
             export_handle_types: ExternalMemoryHandleTypes::empty(),
             flags: MemoryAllocateFlags::empty(),
+            priority: 0.5,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -614,6 +715,7 @@ impl<'d> MemoryAllocateInfo<'d> {
             ref dedicated_allocation,
             export_handle_types,
             flags,
+            priority,
             _ne: _,
         } = self;
 
@@ -786,6 +888,26 @@ impl<'d> MemoryAllocateInfo<'d> {
             }
         }
 
+        if !(0.0..=1.0).contains(&priority) {
+            return Err(Box::new(ValidationError {
+                context: "priority".into(),
+                problem: "is not between 0.0 and 1.0 inclusive".into(),
+                vuids: &["VUID-VkMemoryPriorityAllocateInfoEXT-priority-02602"],
+                ..Default::default()
+            }));
+        }
+
+        if priority != 0.5 && !device.enabled_features().memory_priority {
+            return Err(Box::new(ValidationError {
+                context: "priority".into(),
+                problem: "is not 0.5".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "memory_priority",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 }
@@ -1271,14 +1393,18 @@ impl MappedDeviceMemory {
     ///
     /// `range` is specified in bytes relative to the start of the memory allocation, and must fall
     /// within the range of the memory mapping given to `new`. If the memory was not allocated
-    /// from host-coherent memory, then the start and end of `range` must be a multiple of the
-    /// [`non_coherent_atom_size`](crate::device::Properties::non_coherent_atom_size) device
-    /// property, but `range.end` can also equal the memory's `allocation_size`.
+    /// from host-coherent memory, then `range` is expanded outward to the nearest multiples of
+    /// the [`non_coherent_atom_size`](crate::device::Properties::non_coherent_atom_size) device
+    /// property, clamped to the mapped range, so only the atoms that `range` touches are
+    /// invalidated.
     ///
     /// # Safety
     ///
     /// - If there are memory writes by the GPU that have not been propagated into the CPU cache,
-    ///   then there must not be any references in Rust code to the specified `range` of the memory.
+    ///   then there must not be any references in Rust code to the specified `range` of the memory,
+    ///   after it is expanded to the atom size.
+    /// - If the memory is not host-coherent, then any host writes within the expanded range that
+    ///   have not been flushed yet will be lost.
     ///
     /// # Panics
     ///
@@ -1288,7 +1414,7 @@ impl MappedDeviceMemory {
         &self,
         range: Range<DeviceSize>,
     ) -> Result<(), Validated<VulkanError>> {
-        self.validate_range(range.clone())?;
+        self.validate_mapped_range(range.clone())?;
 
         Ok(self.invalidate_range_unchecked(range)?)
     }
@@ -1302,12 +1428,7 @@ impl MappedDeviceMemory {
             return Ok(());
         }
 
-        let range = ash::vk::MappedMemoryRange {
-            memory: self.memory.handle(),
-            offset: range.start,
-            size: range.end - range.start,
-            ..Default::default()
-        };
+        let range = self.create_memory_range(range);
 
         let fns = self.memory.device().fns();
         (fns.v1_0.invalidate_mapped_memory_ranges)(self.memory.device().handle(), 1, &range)
@@ -1324,15 +1445,16 @@ impl MappedDeviceMemory {
     /// mapped memory is host-coherent.
     ///
     /// `range` is specified in bytes relative to the start of the memory allocation, and must fall
-    /// within the range of the memory mapping given to `map`. If the memory was not allocated
-    /// from host-coherent memory, then the start and end of `range` must be a multiple of the
-    /// [`non_coherent_atom_size`](crate::device::Properties::non_coherent_atom_size) device
-    /// property, but `range.end` can also equal the memory's `allocation_size`.
+    /// within the range of the memory mapping given to `new`. If the memory was not allocated
+    /// from host-coherent memory, then `range` is expanded outward to the nearest multiples of
+    /// the [`non_coherent_atom_size`](crate::device::Properties::non_coherent_atom_size) device
+    /// property, clamped to the mapped range, so only the atoms that `range` touches are
+    /// flushed.
     ///
     /// # Safety
     ///
     /// - There must be no operations pending or executing in a GPU queue, that access the specified
-    ///   `range` of the memory.
+    ///   `range` of the memory, after it is expanded to the atom size.
     ///
     /// # Panics
     ///
@@ -1342,7 +1464,7 @@ impl MappedDeviceMemory {
         &self,
         range: Range<DeviceSize>,
     ) -> Result<(), Validated<VulkanError>> {
-        self.validate_range(range.clone())?;
+        self.validate_mapped_range(range.clone())?;
 
         Ok(self.flush_range_unchecked(range)?)
     }
@@ -1356,12 +1478,7 @@ impl MappedDeviceMemory {
             return Ok(());
         }
 
-        let range = ash::vk::MappedMemoryRange {
-            memory: self.memory.handle(),
-            offset: range.start,
-            size: range.end - range.start,
-            ..Default::default()
-        };
+        let range = self.create_memory_range(range);
 
         let fns = self.device().fns();
         (fns.v1_0.flush_mapped_memory_ranges)(self.memory.device().handle(), 1, &range)
@@ -1443,27 +1560,26 @@ impl MappedDeviceMemory {
         )
     }
 
-    #[inline]
-    fn validate_range(&self, range: Range<DeviceSize>) -> Result<(), Box<ValidationError>> {
-        // VUID-VkMappedMemoryRange-memory-00684
-        // Guaranteed because `self` owns the memory and it's mapped during our lifetime.
+    fn create_memory_range(&self, range: Range<DeviceSize>) -> ash::vk::MappedMemoryRange {
+        // VUID-VkMappedMemoryRange-offset-00687
+        // VUID-VkMappedMemoryRange-size-01390
+        // The start of the mapped range is aligned to the atom size, and the end is either
+        // aligned as well or equal to the allocation size, so rounding outward and clamping to
+        // the mapped range gives a range that is valid and still within the mapping.
+        let start = align_down(range.start, self.atom_size);
+        let end = align_up(range.end, self.atom_size).min(self.range.end);
 
-        if range.is_empty() {
-            return Err(Box::new(ValidationError {
-                context: "range".into(),
-                problem: "is empty".into(),
-                ..Default::default()
-            }));
+        ash::vk::MappedMemoryRange {
+            memory: self.memory.handle(),
+            offset: start,
+            size: end - start,
+            ..Default::default()
         }
+    }
 
-        if range.start < self.range.start || range.end > self.range.end {
-            return Err(Box::new(ValidationError {
-                context: "range".into(),
-                problem: "is not within the mapped range of this mapped device memory".into(),
-                vuids: &["VUID-VkMappedMemoryRange-size-00685"],
-                ..Default::default()
-            }));
-        }
+    #[inline]
+    fn validate_range(&self, range: Range<DeviceSize>) -> Result<(), Box<ValidationError>> {
+        self.validate_mapped_range(range.clone())?;
 
         if !self.is_coherent {
             if !is_aligned(range.start, self.atom_size)
@@ -1487,6 +1603,31 @@ impl MappedDeviceMemory {
 
         Ok(())
     }
+
+    #[inline]
+    fn validate_mapped_range(&self, range: Range<DeviceSize>) -> Result<(), Box<ValidationError>> {
+        // VUID-VkMappedMemoryRange-memory-00684
+        // Guaranteed because `self` owns the memory and it's mapped during our lifetime.
+
+        if range.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "range".into(),
+                problem: "is empty".into(),
+                ..Default::default()
+            }));
+        }
+
+        if range.start < self.range.start || range.end > self.range.end {
+            return Err(Box::new(ValidationError {
+                context: "range".into(),
+                problem: "is not within the mapped range of this mapped device memory".into(),
+                vuids: &["VUID-VkMappedMemoryRange-size-00685"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
 }
 
 impl AsRef<DeviceMemory> for MappedDeviceMemory {
@@ -1516,7 +1657,7 @@ unsafe impl Sync for MappedDeviceMemory {}
 #[cfg(test)]
 mod tests {
     use super::MemoryAllocateInfo;
-    use crate::memory::{DeviceMemory, MemoryPropertyFlags};
+    use crate::memory::{DeviceMemory, MappedDeviceMemory, MemoryPropertyFlags};
 
     #[test]
     fn create() {
@@ -1532,6 +1673,28 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn set_priority_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.enabled_features().pageable_device_local_memory {
+            return; // test ignored
+        }
+
+        let memory = DeviceMemory::allocate(
+            device,
+            MemoryAllocateInfo {
+                allocation_size: 256,
+                memory_type_index: 0,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(memory.set_priority(0.2).is_err());
+        assert_eq!(memory.priority(), 0.5);
+    }
+
     #[test]
     fn zero_size() {
         let (device, _) = gfx_dev_and_queue!();
@@ -1645,4 +1808,36 @@ mod tests {
         }
         assert_eq!(device.allocation_count(), 1);
     }
+
+    #[test]
+    fn flush_invalidate_unaligned_range() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type_index = device
+            .physical_device()
+            .memory_properties()
+            .memory_types
+            .iter()
+            .position(|m| {
+                m.property_flags
+                    .intersects(MemoryPropertyFlags::HOST_VISIBLE)
+            })
+            .unwrap() as u32;
+        let memory = DeviceMemory::allocate(
+            device,
+            MemoryAllocateInfo {
+                allocation_size: 4096,
+                memory_type_index,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let mapped_memory = MappedDeviceMemory::new(memory, 0..4096).unwrap();
+
+        unsafe {
+            mapped_memory.flush_range(3..17).unwrap();
+            mapped_memory.invalidate_range(1000..1001).unwrap();
+            assert!(mapped_memory.flush_range(4000..5000).is_err());
+            assert!(mapped_memory.invalidate_range(16..16).is_err());
+        }
+    }
 }
@@ -329,6 +329,26 @@ pub struct MemoryRequirements {
     pub requires_dedicated_allocation: bool,
 }
 
+impl MemoryRequirements {
+    pub(crate) fn from_vk(
+        memory_requirements2_vk: &ash::vk::MemoryRequirements2,
+        memory_dedicated_requirements_vk: Option<&ash::vk::MemoryDedicatedRequirements>,
+    ) -> Self {
+        MemoryRequirements {
+            layout: DeviceLayout::from_size_alignment(
+                memory_requirements2_vk.memory_requirements.size,
+                memory_requirements2_vk.memory_requirements.alignment,
+            )
+            .unwrap(),
+            memory_type_bits: memory_requirements2_vk.memory_requirements.memory_type_bits,
+            prefers_dedicated_allocation: memory_dedicated_requirements_vk
+                .is_some_and(|dreqs| dreqs.prefers_dedicated_allocation != 0),
+            requires_dedicated_allocation: memory_dedicated_requirements_vk
+                .is_some_and(|dreqs| dreqs.requires_dedicated_allocation != 0),
+        }
+    }
+}
+
 /// Indicates a specific resource to allocate memory for.
 ///
 /// Using dedicated allocations can yield better performance, but requires the
@@ -27,9 +27,17 @@ use crate::{
     device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
     instance::InstanceOwnedDebugWrapper,
     macros::impl_id_counter,
-    pipeline::{cache::PipelineCache, layout::PipelineLayout, Pipeline, PipelineBindPoint},
+    pipeline::{
+        cache::PipelineCache,
+        executable::{
+            self, PipelineExecutableInternalRepresentation, PipelineExecutableProperties,
+            PipelineExecutableStatistic,
+        },
+        layout::PipelineLayout,
+        Pipeline, PipelineBindPoint,
+    },
     shader::{DescriptorBindingRequirements, ShaderExecution, ShaderStage},
-    Validated, ValidationError, VulkanError, VulkanObject,
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
 use std::{ffi::CString, fmt::Debug, mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
@@ -47,6 +55,7 @@ pub struct ComputePipeline {
     handle: ash::vk::Pipeline,
     device: InstanceOwnedDebugWrapper<Arc<Device>>,
     id: NonZeroU64,
+    flags: PipelineCreateFlags,
     layout: DeviceOwnedDebugWrapper<Arc<PipelineLayout>>,
     descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
     num_used_descriptor_sets: u32,
@@ -100,12 +109,14 @@ impl ComputePipeline {
         let specialization_info_vk;
         let specialization_map_entries_vk: Vec<_>;
         let mut specialization_data_vk: Vec<u8>;
+        let module_identifier_vk;
 
         {
             let &PipelineShaderStageCreateInfo {
                 flags,
                 ref entry_point,
                 ref specialization_info,
+                ref module_identifier,
                 _ne: _,
             } = stage;
 
@@ -135,10 +146,26 @@ impl ComputePipeline {
                 data_size: specialization_data_vk.len(),
                 p_data: specialization_data_vk.as_ptr() as *const _,
             };
+            module_identifier_vk = module_identifier.as_ref().map(|module_identifier| {
+                let bytes = module_identifier.as_bytes();
+
+                ash::vk::PipelineShaderStageModuleIdentifierCreateInfoEXT {
+                    identifier_size: bytes.len() as u32,
+                    p_identifier: bytes.as_ptr(),
+                    ..Default::default()
+                }
+            });
             stage_vk = ash::vk::PipelineShaderStageCreateInfo {
+                p_next: module_identifier_vk
+                    .as_ref()
+                    .map_or(ptr::null(), |info| info as *const _ as *const _),
                 flags: flags.into(),
                 stage: ShaderStage::from(&entry_point_info.execution).into(),
-                module: entry_point.module().handle(),
+                module: if module_identifier.is_some() {
+                    ash::vk::ShaderModule::null()
+                } else {
+                    entry_point.module().handle()
+                },
                 p_name: name_vk.as_ptr(),
                 p_specialization_info: if specialization_info_vk.data_size == 0 {
                     ptr::null()
@@ -190,7 +217,7 @@ impl ComputePipeline {
         create_info: ComputePipelineCreateInfo,
     ) -> Arc<ComputePipeline> {
         let ComputePipelineCreateInfo {
-            flags: _,
+            flags,
             stage,
             layout,
             _ne: _,
@@ -214,6 +241,7 @@ impl ComputePipeline {
             handle,
             device: InstanceOwnedDebugWrapper(device),
             id: Self::next_id(),
+            flags,
             layout: DeviceOwnedDebugWrapper(layout),
             descriptor_binding_requirements,
             num_used_descriptor_sets,
@@ -225,6 +253,100 @@ impl ComputePipeline {
     pub fn device(&self) -> &Arc<Device> {
         &self.device
     }
+
+    /// Returns the flags that the pipeline was created with.
+    #[inline]
+    pub fn flags(&self) -> PipelineCreateFlags {
+        self.flags
+    }
+
+    /// Returns the properties of the executables that the pipeline was compiled into.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, Validated<VulkanError>> {
+        executable::validate_executable_properties(&self.device)?;
+
+        unsafe { Ok(self.executable_properties_unchecked()?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_properties_unchecked(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, VulkanError> {
+        executable::executable_properties_unchecked(&self.device, self.handle)
+    }
+
+    /// Returns statistics about the executable with index `executable_index`, which is an index
+    /// into the list returned by [`executable_properties`](Self::executable_properties).
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_STATISTICS`].
+    ///
+    /// Statistics that are reported in a format that Vulkano does not know are not included.
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, Validated<VulkanError>> {
+        executable::validate_executable_statistics(
+            &self.device,
+            self.handle,
+            self.flags,
+            executable_index,
+        )?;
+
+        unsafe { Ok(self.executable_statistics_unchecked(executable_index)?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_statistics_unchecked(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, VulkanError> {
+        executable::executable_statistics_unchecked(&self.device, self.handle, executable_index)
+    }
+
+    /// Returns the internal representations of the executable with index `executable_index`,
+    /// which is an index into the list returned by
+    /// [`executable_properties`](Self::executable_properties).
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`].
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, Validated<VulkanError>> {
+        executable::validate_executable_internal_representations(
+            &self.device,
+            self.handle,
+            self.flags,
+            executable_index,
+        )?;
+
+        unsafe { Ok(self.executable_internal_representations_unchecked(executable_index)?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_internal_representations_unchecked(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, VulkanError> {
+        executable::executable_internal_representations_unchecked(
+            &self.device,
+            self.handle,
+            executable_index,
+        )
+    }
 }
 
 impl Pipeline for ComputePipeline {
@@ -328,6 +450,37 @@ impl ComputePipelineCreateInfo {
                 ..ValidationError::from_requirement(err)
             })?;
 
+        if flags.intersects(PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED)
+            && !device.enabled_features().pipeline_creation_cache_control
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "pipeline_creation_cache_control",
+                )])]),
+                vuids: &["VUID-VkComputePipelineCreateInfo-pipelineCreationCacheControl-02875"],
+            }));
+        }
+
+        if flags.intersects(
+            PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP
+                | PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP,
+        ) {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP` or \
+                    `PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP`, which are only \
+                    allowed for graphics pipelines"
+                    .into(),
+                vuids: &[
+                    "VUID-VkComputePipelineCreateInfo-flags-07367",
+                    "VUID-VkComputePipelineCreateInfo-flags-07996",
+                ],
+                ..Default::default()
+            }));
+        }
+
         stage
             .validate(device)
             .map_err(|err| err.add_context("stage"))?;
@@ -336,12 +489,25 @@ impl ComputePipelineCreateInfo {
             flags: _,
             ref entry_point,
             specialization_info: _,
+            ref module_identifier,
             _ne: _,
         } = &stage;
 
+        if module_identifier.is_some()
+            && !flags.intersects(PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`stage.module_identifier` is `Some`, but `flags` does not contain \
+                    `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED`"
+                    .into(),
+                vuids: &["VUID-VkPipelineShaderStageModuleIdentifierCreateInfoEXT-pNext-06851"],
+                ..Default::default()
+            }));
+        }
+
         let entry_point_info = entry_point.info();
 
-        if !matches!(entry_point_info.execution, ShaderExecution::Compute) {
+        if !matches!(entry_point_info.execution, ShaderExecution::Compute(_)) {
             return Err(Box::new(ValidationError {
                 context: "stage.entry_point".into(),
                 problem: "is not a `ShaderStage::Compute` entry point".into(),
@@ -388,19 +554,46 @@ mod tests {
         descriptor_set::{
             allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
         },
+        device::Device,
         memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
         pipeline::{
             compute::ComputePipelineCreateInfo, layout::PipelineDescriptorSetLayoutCreateInfo,
             ComputePipeline, Pipeline, PipelineBindPoint, PipelineLayout,
             PipelineShaderStageCreateInfo,
         },
-        shader::{ShaderModule, ShaderModuleCreateInfo},
+        shader::{
+            reflect, spirv::Spirv, EntryPoint, ShaderModule, ShaderModuleCreateInfo,
+            ShaderModuleIdentifier, WorkgroupMemorySize, WorkgroupMemorySizeTerm,
+        },
         sync::{now, GpuFuture},
+        Requires, RequiresAllOf, RequiresOneOf, Validated,
     };
+    use std::sync::Arc;
 
     // TODO: test for basic creation
     // TODO: test for pipeline layout error
 
+    /// Returns the entry point of a compute shader that does nothing.
+    fn empty_compute_shader(device: &Arc<Device>) -> EntryPoint {
+        unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1) in;
+
+            void main() {}
+            */
+            const MODULE: [u32; 35] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+                393232, 1, 17, 1, 1, 1, 131091, 2, 196641, 3, 2, 327734, 2, 1, 0, 3, 131320, 4,
+                65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        }
+    }
+
     #[test]
     fn specialization_constants() {
         // This test checks whether specialization constants work.
@@ -476,7 +669,7 @@ mod tests {
         )
         .unwrap();
 
-        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone());
+        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
         let set = PersistentDescriptorSet::new(
             &ds_allocator,
             pipeline.layout().set_layouts().get(0).unwrap().clone(),
@@ -515,4 +708,277 @@ mod tests {
         let data_buffer_content = data_buffer.read().unwrap();
         assert_eq!(*data_buffer_content, 0x12345678);
     }
+
+    #[test]
+    fn local_size_spec_constant_too_large() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x_id = 0) in;
+
+            void main() {}
+            */
+            const MODULE: [u32; 65] = [
+                119734787, 65536, 0, 10, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+                393232, 1, 17, 1, 1, 1, 262215, 2, 1, 0, 262215, 3, 11, 25, 131091, 4, 196641, 5,
+                4, 262165, 6, 32, 0, 262167, 7, 6, 3, 262194, 6, 2, 1, 262187, 6, 8, 1, 393267, 7,
+                3, 2, 8, 8, 327734, 4, 1, 0, 5, 131320, 9, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let max_size = device
+            .physical_device()
+            .properties()
+            .max_compute_work_group_size[0];
+
+        let stage = PipelineShaderStageCreateInfo {
+            specialization_info: [(0, (max_size + 1).into())].into_iter().collect(),
+            ..PipelineShaderStageCreateInfo::new(cs.clone())
+        };
+        assert!(ComputePipeline::new(
+            device.clone(),
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout.clone()),
+        )
+        .is_err());
+
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        assert!(ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn workgroup_memory_size_too_large() {
+        /*
+        #version 450
+
+        layout(local_size_x = 1) in;
+        layout(constant_id = 0) const uint N = 64;
+
+        shared float data[N];
+        shared uvec4 other[4];
+
+        void main() {}
+        */
+        const MODULE: [u32; 82] = [
+            119734787, 65536, 0, 16, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+            393232, 1, 17, 1, 1, 1, 262215, 6, 1, 0, 131091, 2, 196641, 3, 2, 196630, 4, 32,
+            262165, 5, 32, 0, 262194, 5, 6, 64, 262172, 7, 4, 6, 262176, 8, 4, 7, 262203, 8, 9, 4,
+            262167, 10, 5, 4, 262187, 5, 11, 4, 262172, 12, 10, 11, 262176, 13, 4, 12, 262203, 13,
+            14, 4, 327734, 2, 1, 0, 3, 131320, 15, 65789, 65592,
+        ];
+
+        let spirv = Spirv::new(&MODULE).unwrap();
+        let entry_point_info = reflect::entry_points(&spirv).next().unwrap();
+        assert_eq!(
+            entry_point_info.workgroup_memory_size,
+            Some(WorkgroupMemorySize {
+                terms: vec![
+                    WorkgroupMemorySizeTerm {
+                        size: 4,
+                        spec_ids: vec![0],
+                    },
+                    WorkgroupMemorySizeTerm {
+                        size: 64,
+                        spec_ids: vec![],
+                    },
+                ],
+            }),
+        );
+
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let cs = unsafe {
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let max_size = device
+            .physical_device()
+            .properties()
+            .max_compute_shared_memory_size;
+
+        let stage = PipelineShaderStageCreateInfo {
+            specialization_info: [(0, (max_size / 4).into())].into_iter().collect(),
+            ..PipelineShaderStageCreateInfo::new(cs.clone())
+        };
+        assert!(ComputePipeline::new(
+            device.clone(),
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout.clone()),
+        )
+        .is_err());
+
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        assert!(ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn module_identifier_requires_feature() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let cs = empty_compute_shader(&device);
+
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let stage = PipelineShaderStageCreateInfo {
+            module_identifier: Some(ShaderModuleIdentifier::from_bytes(&[1, 2, 3, 4])),
+            ..PipelineShaderStageCreateInfo::new(cs)
+        };
+        assert!(ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn executable_info_requires_feature() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let cs = empty_compute_shader(&device);
+
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let pipeline = ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(PipelineShaderStageCreateInfo::new(cs), layout),
+        )
+        .unwrap();
+
+        assert!(pipeline.executable_properties().is_err());
+        assert!(pipeline.executable_statistics(0).is_err());
+        assert!(pipeline.executable_internal_representations(0).is_err());
+    }
+
+    #[test]
+    fn specialization_constants_validation() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(constant_id = 83) const int VALUE = 0xdeadbeef;
+
+            layout(set = 0, binding = 0) buffer Output {
+                int write;
+            } write;
+
+            void main() {
+                write.write = VALUE;
+            }
+            */
+            const MODULE: [u32; 120] = [
+                119734787, 65536, 524289, 14, 0, 131089, 1, 393227, 1, 1280527431, 1685353262,
+                808793134, 0, 196622, 0, 1, 327695, 5, 4, 1852399981, 0, 393232, 4, 17, 1, 1, 1,
+                196611, 2, 450, 262149, 4, 1852399981, 0, 262149, 7, 1886680399, 29813, 327686, 7,
+                0, 1953067639, 101, 262149, 9, 1953067639, 101, 262149, 11, 1431060822, 69, 327752,
+                7, 0, 35, 0, 196679, 7, 3, 262215, 9, 34, 0, 262215, 9, 33, 0, 262215, 11, 1, 83,
+                131091, 2, 196641, 3, 2, 262165, 6, 32, 1, 196638, 7, 6, 262176, 8, 2, 7, 262203,
+                8, 9, 2, 262187, 6, 10, 0, 262194, 6, 11, 3735928559, 262176, 12, 2, 6, 327734, 2,
+                4, 0, 3, 131320, 5, 327745, 12, 13, 9, 10, 196670, 13, 11, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages([
+                &PipelineShaderStageCreateInfo::new(cs.clone()),
+            ])
+            .into_pipeline_layout_create_info(device.clone())
+            .unwrap(),
+        )
+        .unwrap();
+
+        // The shader has no constant with this ID.
+        let stage = PipelineShaderStageCreateInfo {
+            specialization_info: [(84, 0x12345678i32.into())].into_iter().collect(),
+            ..PipelineShaderStageCreateInfo::new(cs.clone())
+        };
+        assert!(ComputePipeline::new(
+            device.clone(),
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout.clone()),
+        )
+        .is_err());
+
+        // The constant is an `int`, not a `uint`.
+        let stage = PipelineShaderStageCreateInfo {
+            specialization_info: [(83, 0x12345678u32.into())].into_iter().collect(),
+            ..PipelineShaderStageCreateInfo::new(cs)
+        };
+        assert!(ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn float_atomics_require_feature() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        /*
+        #version 450
+        #extension GL_EXT_shader_atomic_float : require
+
+        layout(set = 0, binding = 0) buffer Data {
+            float value;
+        };
+
+        void main() {
+            atomicAdd(value, 1.0);
+        }
+        */
+        const MODULE: [u32; 116] = [
+            119734787, 65536, 0, 18, 0, 131089, 1, 131089, 6033, 589834, 1599492179, 1599363141,
+            1684105331, 1633645157, 1768779636, 1818648419, 1601462639, 6579297, 196622, 0, 1,
+            327695, 5, 1, 1852399981, 0, 393232, 1, 17, 1, 1, 1, 196679, 5, 3, 327752, 5, 0, 35, 0,
+            262215, 7, 34, 0, 262215, 7, 33, 0, 131091, 3, 196641, 2, 3, 196630, 4, 32, 196638, 5,
+            4, 262176, 6, 2, 5, 262203, 6, 7, 2, 262176, 8, 2, 4, 262165, 9, 32, 1, 262165, 10, 32,
+            0, 262187, 9, 11, 0, 262187, 10, 12, 1, 262187, 10, 13, 0, 262187, 4, 14, 1065353216,
+            327734, 3, 1, 0, 2, 131320, 15, 327745, 8, 16, 7, 11, 464787, 4, 17, 16, 12, 13, 14,
+            65789, 65592,
+        ];
+
+        let err = unsafe {
+            ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap_err()
+        };
+
+        match err {
+            Validated::ValidationError(err) => assert_eq!(
+                err.requires_one_of,
+                RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "shader_buffer_float32_atomic_add"
+                )])]),
+            ),
+            _ => panic!(),
+        }
+    }
 }
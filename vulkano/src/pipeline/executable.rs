@@ -0,0 +1,432 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Information about the executables that a pipeline was compiled into.
+//!
+//! When a pipeline is created, the implementation compiles its shaders into one or more
+//! *executables*. With the [`pipeline_executable_info`] feature enabled, you can query the
+//! properties of these executables, and, if the pipeline was created with the appropriate
+//! [`PipelineCreateFlags`], statistics about them and their internal representations. This is
+//! mostly useful for debugging and performance tuning tools.
+//!
+//! [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+
+use super::PipelineCreateFlags;
+use crate::{
+    device::{string_from_array, Device},
+    shader::ShaderStages,
+    Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanError, VulkanObject,
+};
+use std::ptr;
+
+/// The properties of an executable of a pipeline.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableProperties {
+    /// The shader stages that were compiled into this executable.
+    pub stages: ShaderStages,
+
+    /// A short, human-readable name of the executable.
+    pub name: String,
+
+    /// A human-readable description of the executable.
+    pub description: String,
+
+    /// The subgroup size that the executable was compiled with, or 0 if the executable does not
+    /// use subgroups.
+    pub subgroup_size: u32,
+}
+
+/// A statistic about an executable of a pipeline.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableStatistic {
+    /// A short, human-readable name of the statistic.
+    pub name: String,
+
+    /// A human-readable description of the statistic.
+    pub description: String,
+
+    /// The value of the statistic.
+    pub value: PipelineExecutableStatisticValue,
+}
+
+/// The value of a [`PipelineExecutableStatistic`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PipelineExecutableStatisticValue {
+    /// A boolean value.
+    Bool(bool),
+
+    /// A signed integer value.
+    Int(i64),
+
+    /// An unsigned integer value.
+    Uint(u64),
+
+    /// A floating-point value.
+    Float(f64),
+}
+
+/// An internal representation of an executable of a pipeline, such as an intermediate
+/// representation or the final machine code.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableInternalRepresentation {
+    /// A short, human-readable name of the internal representation.
+    pub name: String,
+
+    /// A human-readable description of the internal representation.
+    pub description: String,
+
+    /// Whether `data` contains text. If `true`, `data` is UTF-8 text, without a null terminator.
+    /// Otherwise, the format of `data` is implementation-specific.
+    pub is_text: bool,
+
+    /// The data of the internal representation.
+    pub data: Vec<u8>,
+}
+
+impl PipelineExecutableInternalRepresentation {
+    /// If `is_text` is `true`, returns `data` as a string.
+    #[inline]
+    pub fn text(&self) -> Option<&str> {
+        self.is_text
+            .then(|| std::str::from_utf8(&self.data).ok())
+            .flatten()
+    }
+}
+
+pub(crate) fn validate_executable_properties(device: &Device) -> Result<(), Box<ValidationError>> {
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(Box::new(ValidationError {
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "pipeline_executable_info",
+            )])]),
+            vuids: &["VUID-vkGetPipelineExecutablePropertiesKHR-pipelineExecutableInfo-03270"],
+            ..Default::default()
+        }));
+    }
+
+    Ok(())
+}
+
+pub(crate) unsafe fn executable_properties_unchecked(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+) -> Result<Vec<PipelineExecutableProperties>, VulkanError> {
+    let pipeline_info_vk = ash::vk::PipelineInfoKHR {
+        pipeline,
+        ..Default::default()
+    };
+
+    let fns = device.fns();
+
+    let properties_vk = loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &pipeline_info_vk,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        let mut properties_vk =
+            vec![ash::vk::PipelineExecutablePropertiesKHR::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &pipeline_info_vk,
+            &mut count,
+            properties_vk.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                properties_vk.truncate(count as usize);
+                break properties_vk;
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    };
+
+    Ok(properties_vk
+        .iter()
+        .map(|properties_vk| PipelineExecutableProperties {
+            stages: properties_vk.stages.into(),
+            name: string_from_array(&properties_vk.name),
+            description: string_from_array(&properties_vk.description),
+            subgroup_size: properties_vk.subgroup_size,
+        })
+        .collect())
+}
+
+pub(crate) fn validate_executable_statistics(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    flags: PipelineCreateFlags,
+    executable_index: u32,
+) -> Result<(), Box<ValidationError>> {
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(Box::new(ValidationError {
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "pipeline_executable_info",
+            )])]),
+            vuids: &["VUID-vkGetPipelineExecutableStatisticsKHR-pipelineExecutableInfo-03272"],
+            ..Default::default()
+        }));
+    }
+
+    if !flags.intersects(PipelineCreateFlags::CAPTURE_STATISTICS) {
+        return Err(Box::new(ValidationError {
+            context: "self.flags()".into(),
+            problem: "does not contain `PipelineCreateFlags::CAPTURE_STATISTICS`".into(),
+            vuids: &["VUID-vkGetPipelineExecutableStatisticsKHR-pipeline-03274"],
+            ..Default::default()
+        }));
+    }
+
+    validate_executable_index(device, pipeline, executable_index)?;
+
+    Ok(())
+}
+
+pub(crate) unsafe fn executable_statistics_unchecked(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableStatistic>, VulkanError> {
+    let executable_info_vk = ash::vk::PipelineExecutableInfoKHR {
+        pipeline,
+        executable_index,
+        ..Default::default()
+    };
+
+    let fns = device.fns();
+
+    let statistics_vk = loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_statistics_khr)(
+            device.handle(),
+            &executable_info_vk,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        let mut statistics_vk =
+            vec![ash::vk::PipelineExecutableStatisticKHR::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_statistics_khr)(
+            device.handle(),
+            &executable_info_vk,
+            &mut count,
+            statistics_vk.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                statistics_vk.truncate(count as usize);
+                break statistics_vk;
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    };
+
+    // Statistics with a format that is not known to Vulkano are skipped.
+    Ok(statistics_vk
+        .iter()
+        .filter_map(|statistic_vk| {
+            let value = match statistic_vk.format {
+                ash::vk::PipelineExecutableStatisticFormatKHR::BOOL32 => {
+                    PipelineExecutableStatisticValue::Bool(statistic_vk.value.b32 != ash::vk::FALSE)
+                }
+                ash::vk::PipelineExecutableStatisticFormatKHR::INT64 => {
+                    PipelineExecutableStatisticValue::Int(statistic_vk.value.i64)
+                }
+                ash::vk::PipelineExecutableStatisticFormatKHR::UINT64 => {
+                    PipelineExecutableStatisticValue::Uint(statistic_vk.value.u64)
+                }
+                ash::vk::PipelineExecutableStatisticFormatKHR::FLOAT64 => {
+                    PipelineExecutableStatisticValue::Float(statistic_vk.value.f64)
+                }
+                _ => return None,
+            };
+
+            Some(PipelineExecutableStatistic {
+                name: string_from_array(&statistic_vk.name),
+                description: string_from_array(&statistic_vk.description),
+                value,
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn validate_executable_internal_representations(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    flags: PipelineCreateFlags,
+    executable_index: u32,
+) -> Result<(), Box<ValidationError>> {
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(Box::new(ValidationError {
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "pipeline_executable_info",
+            )])]),
+            vuids: &[
+                "VUID-vkGetPipelineExecutableInternalRepresentationsKHR-pipelineExecutableInfo-03276",
+            ],
+            ..Default::default()
+        }));
+    }
+
+    if !flags.intersects(PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS) {
+        return Err(Box::new(ValidationError {
+            context: "self.flags()".into(),
+            problem: "does not contain `PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`"
+                .into(),
+            vuids: &["VUID-vkGetPipelineExecutableInternalRepresentationsKHR-pipeline-03278"],
+            ..Default::default()
+        }));
+    }
+
+    validate_executable_index(device, pipeline, executable_index)?;
+
+    Ok(())
+}
+
+pub(crate) unsafe fn executable_internal_representations_unchecked(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableInternalRepresentation>, VulkanError> {
+    let executable_info_vk = ash::vk::PipelineExecutableInfoKHR {
+        pipeline,
+        executable_index,
+        ..Default::default()
+    };
+
+    let fns = device.fns();
+
+    loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_internal_representations_khr)(
+            device.handle(),
+            &executable_info_vk,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        // The first call with a null `p_data` retrieves the size of each representation.
+        let mut representations_vk =
+            vec![ash::vk::PipelineExecutableInternalRepresentationKHR::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_internal_representations_khr)(
+            device.handle(),
+            &executable_info_vk,
+            &mut count,
+            representations_vk.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => representations_vk.truncate(count as usize),
+            ash::vk::Result::INCOMPLETE => continue,
+            err => return Err(VulkanError::from(err)),
+        }
+
+        // The second call fills in the data.
+        let mut data: Vec<Vec<u8>> = representations_vk
+            .iter()
+            .map(|representation_vk| vec![0; representation_vk.data_size])
+            .collect();
+
+        for (representation_vk, data) in representations_vk.iter_mut().zip(&mut data) {
+            representation_vk.p_data = data.as_mut_ptr() as *mut _;
+        }
+
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_internal_representations_khr)(
+            device.handle(),
+            &executable_info_vk,
+            &mut count,
+            representations_vk.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                break Ok(representations_vk
+                    .iter()
+                    .zip(data)
+                    .map(|(representation_vk, mut data)| {
+                        data.truncate(representation_vk.data_size);
+
+                        PipelineExecutableInternalRepresentation {
+                            name: string_from_array(&representation_vk.name),
+                            description: string_from_array(&representation_vk.description),
+                            is_text: representation_vk.is_text != ash::vk::FALSE,
+                            data,
+                        }
+                    })
+                    .collect());
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    }
+}
+
+fn validate_executable_index(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    executable_index: u32,
+) -> Result<(), Box<ValidationError>> {
+    let pipeline_info_vk = ash::vk::PipelineInfoKHR {
+        pipeline,
+        ..Default::default()
+    };
+
+    let executable_count = unsafe {
+        let fns = device.fns();
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &pipeline_info_vk,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(|err| Box::new(ValidationError::from_error(VulkanError::from(err))))?;
+        count
+    };
+
+    if executable_index >= executable_count {
+        return Err(Box::new(ValidationError {
+            context: "executable_index".into(),
+            problem: "is not less than the number of executables of the pipeline".into(),
+            vuids: &["VUID-VkPipelineExecutableInfoKHR-executableIndex-03275"],
+            ..Default::default()
+        }));
+    }
+
+    Ok(())
+}
@@ -162,6 +162,22 @@ impl ColorBlendState {
                 ..ValidationError::from_requirement(err)
             })?;
 
+        if flags.intersects(ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_color_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_color_attachment_access",
+                )])]),
+                vuids: &["VUID-VkPipelineColorBlendStateCreateInfo-rasterizationOrderColorAttachmentAccess-06465"],
+            }));
+        }
+
         if let Some(logic_op) = logic_op {
             if !device.enabled_features().logic_op {
                 return Err(Box::new(ValidationError {
@@ -248,13 +264,20 @@ vulkan_bitflags! {
     /// Flags specifying additional properties of the color blend state.
     ColorBlendStateFlags = PipelineColorBlendStateCreateFlags(u32);
 
-    /* TODO: enable
-    // TODO: document
+    /// The fragment shader may read the color attachments of the subpass as input attachments in
+    /// rasterization order, without needing a pipeline barrier.
+    ///
+    /// The [`rasterization_order_color_attachment_access`] feature must be enabled on the device,
+    /// and the subpass must have been created with
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS`].
+    ///
+    /// [`rasterization_order_color_attachment_access`]: crate::device::Features::rasterization_order_color_attachment_access
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS`]: crate::render_pass::SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS
     RASTERIZATION_ORDER_ATTACHMENT_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_ACCESS_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
         RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
-    ]), */
+    ]),
 }
 
 vulkan_enum! {
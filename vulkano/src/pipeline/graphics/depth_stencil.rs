@@ -46,6 +46,9 @@ pub struct DepthStencilState {
     /// The state of the depth bounds test.
     ///
     /// If set to `None`, the depth bounds test is disabled, all fragments will pass.
+    ///
+    /// If set to `Some`, the [`depth_bounds`](crate::device::Features::depth_bounds) feature must
+    /// be enabled on the device.
     pub depth_bounds: Option<DepthBoundsState>,
 
     /// The state of the stencil test.
@@ -104,6 +107,40 @@ impl DepthStencilState {
                 ..ValidationError::from_requirement(err)
             })?;
 
+        if flags.intersects(DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_depth_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains \
+                    `DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_depth_attachment_access",
+                )])]),
+                vuids: &["VUID-VkPipelineDepthStencilStateCreateInfo-rasterizationOrderDepthAttachmentAccess-06463"],
+            }));
+        }
+
+        if flags.intersects(DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_stencil_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains \
+                    `DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_stencil_attachment_access",
+                )])]),
+                vuids: &["VUID-VkPipelineDepthStencilStateCreateInfo-rasterizationOrderStencilAttachmentAccess-06464"],
+            }));
+        }
+
         if let Some(depth_state) = depth {
             depth_state
                 .validate(device)
@@ -153,21 +190,35 @@ vulkan_bitflags! {
     /// Flags specifying additional properties of the depth/stencil state.
     DepthStencilStateFlags = PipelineDepthStencilStateCreateFlags(u32);
 
-    /* TODO: enable
-    // TODO: document
+    /// The fragment shader may read the depth aspect of the depth/stencil attachment of the
+    /// subpass as an input attachment in rasterization order, without needing a pipeline barrier.
+    ///
+    /// The [`rasterization_order_depth_attachment_access`] feature must be enabled on the device,
+    /// and the subpass must have been created with
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS`].
+    ///
+    /// [`rasterization_order_depth_attachment_access`]: crate::device::Features::rasterization_order_depth_attachment_access
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS`]: crate::render_pass::SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS
     RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
         RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The fragment shader may read the stencil aspect of the depth/stencil attachment of the
+    /// subpass as an input attachment in rasterization order, without needing a pipeline barrier.
+    ///
+    /// The [`rasterization_order_stencil_attachment_access`] feature must be enabled on the
+    /// device, and the subpass must have been created with
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS`].
+    ///
+    /// [`rasterization_order_stencil_attachment_access`]: crate::device::Features::rasterization_order_stencil_attachment_access
+    /// [`SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS`]: crate::render_pass::SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS
     RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
         RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
-    ]), */
+    ]),
 }
 
 /// The state in a graphics pipeline describing how the depth test should behave when enabled.
@@ -303,9 +354,13 @@ pub struct DepthBoundsState {
     /// The minimum and maximum depth values to use for the test. Fragments with values outside this
     /// range are discarded.
     ///
-    /// If set to `Dynamic`, the device API version must be at least 1.3, or the
-    /// [`extended_dynamic_state`](crate::device::Features::extended_dynamic_state) feature must be
-    /// enabled on the device.
+    /// If set to `Fixed`, then unless the
+    /// [`ext_depth_range_unrestricted`](crate::device::DeviceExtensions::ext_depth_range_unrestricted)
+    /// extension is enabled on the device, both bounds must be between 0.0 and 1.0 inclusive.
+    ///
+    /// If set to `Dynamic`, the bounds must be set with
+    /// [`set_depth_bounds`](crate::command_buffer::AutoCommandBufferBuilder::set_depth_bounds)
+    /// before drawing.
     pub bounds: StateMode<RangeInclusive<f32>>,
 }
 
@@ -334,7 +389,7 @@ impl DepthBoundsState {
 
         if let StateMode::Fixed(bounds) = bounds {
             if !device.enabled_extensions().ext_depth_range_unrestricted {
-                if !(0.0..1.0).contains(bounds.start()) {
+                if !(0.0..=1.0).contains(bounds.start()) {
                     return Err(Box::new(ValidationError {
                         context: "bounds.start".into(),
                         problem: "is not between 0.0 and 1.0 inclusive".into(),
@@ -345,7 +400,7 @@ impl DepthBoundsState {
                     }));
                 }
 
-                if !(0.0..1.0).contains(bounds.end()) {
+                if !(0.0..=1.0).contains(bounds.end()) {
                     return Err(Box::new(ValidationError {
                         context: "bounds.end".into(),
                         problem: "is not between 0.0 and 1.0 inclusive".into(),
@@ -31,6 +31,8 @@ pub struct InputAssemblyState {
     /// If true, then when drawing with an index buffer, the special index value consisting of the
     /// maximum unsigned value (`0xff`, `0xffff` or `0xffffffff`) will tell the GPU that it is the
     /// end of the current primitive. A new primitive will restart at the next index.
+    /// The value depends on the index type of the bound index buffer, and is returned by
+    /// [`IndexType::primitive_restart_index`](crate::buffer::IndexType::primitive_restart_index).
     ///
     /// Primitive restart is mostly useful in combination with "strip" and "fan" topologies. "List"
     /// topologies require a feature to be enabled on the device when combined with primitive
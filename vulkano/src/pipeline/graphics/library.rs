@@ -0,0 +1,589 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Graphics pipelines that are built from separately compiled parts.
+//!
+//! The state of a graphics pipeline is divided into four subsets:
+//! - The *vertex input interface*: the vertex input and input assembly state.
+//! - The *pre-rasterization shaders*: the vertex, tessellation and geometry shaders, and the
+//!   tessellation, viewport, rasterization and discard rectangle state.
+//! - The *fragment shader*: the fragment shader, and the depth/stencil state.
+//! - The *fragment output interface*: the color blend and multisample state.
+//!
+//! A [`GraphicsPipelineLibrary`] contains one or more of these subsets, and can be created ahead
+//! of time. Libraries that together contain all the subsets can then be linked into a complete
+//! [`GraphicsPipeline`] with [`GraphicsPipeline::link`], which is much faster than creating the
+//! whole pipeline at once. This lets you, for example, combine one set of pre-rasterization
+//! shaders with many interchangeable fragment shaders.
+//!
+//! Using pipeline libraries requires the [`graphics_pipeline_library`] feature to be enabled on
+//! the device.
+//!
+//! [`graphics_pipeline_library`]: crate::device::Features::graphics_pipeline_library
+
+use super::{GraphicsPipeline, GraphicsPipelineCreateInfo};
+use crate::{
+    device::{Device, DeviceOwned},
+    instance::InstanceOwnedDebugWrapper,
+    macros::{impl_id_counter, vulkan_bitflags},
+    pipeline::{cache::PipelineCache, PipelineCreateFlags, PipelineShaderStageCreateInfo},
+    shader::ShaderStage,
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
+};
+use std::{num::NonZeroU64, ptr, sync::Arc};
+
+/// A graphics pipeline that contains only some of the state subsets of a complete pipeline, and
+/// that can be linked into a [`GraphicsPipeline`].
+#[derive(Debug)]
+pub struct GraphicsPipelineLibrary {
+    handle: ash::vk::Pipeline,
+    device: InstanceOwnedDebugWrapper<Arc<Device>>,
+    id: NonZeroU64,
+
+    library_flags: GraphicsPipelineLibraryFlags,
+    create_info: GraphicsPipelineCreateInfo,
+}
+
+impl GraphicsPipelineLibrary {
+    /// Creates a new `GraphicsPipelineLibrary` that contains the state subsets in
+    /// `library_flags`.
+    ///
+    /// `create_info` must contain exactly the state that belongs to those subsets. For example,
+    /// a library with only [`GraphicsPipelineLibraryFlags::FRAGMENT_SHADER`] must have a
+    /// fragment shader stage, but must not have a `vertex_input_state`.
+    #[inline]
+    pub fn new(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        library_flags: GraphicsPipelineLibraryFlags,
+        create_info: GraphicsPipelineCreateInfo,
+    ) -> Result<Arc<Self>, Validated<VulkanError>> {
+        Self::validate_new(
+            &device,
+            cache.as_ref().map(AsRef::as_ref),
+            library_flags,
+            &create_info,
+        )?;
+
+        unsafe {
+            Ok(Self::new_unchecked(
+                device,
+                cache,
+                library_flags,
+                create_info,
+            )?)
+        }
+    }
+
+    fn validate_new(
+        device: &Device,
+        _cache: Option<&PipelineCache>,
+        library_flags: GraphicsPipelineLibraryFlags,
+        create_info: &GraphicsPipelineCreateInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        if !device.enabled_features().graphics_pipeline_library {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "graphics_pipeline_library",
+                )])]),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-graphicsPipelineLibrary-06606"],
+                ..Default::default()
+            }));
+        }
+
+        library_flags
+            .validate_device(device)
+            .map_err(|err| ValidationError {
+                context: "library_flags".into(),
+                vuids: &["VUID-VkGraphicsPipelineLibraryCreateInfoEXT-flags-parameter"],
+                ..ValidationError::from_requirement(err)
+            })?;
+
+        if library_flags.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "library_flags".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-VkGraphicsPipelineLibraryCreateInfoEXT-flags-requiredbitmask"],
+                ..Default::default()
+            }));
+        }
+
+        create_info
+            .validate_library(device, Some(library_flags))
+            .map_err(|err| err.add_context("create_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        library_flags: GraphicsPipelineLibraryFlags,
+        create_info: GraphicsPipelineCreateInfo,
+    ) -> Result<Arc<Self>, VulkanError> {
+        let handle = GraphicsPipeline::create_handle(
+            &device,
+            cache.as_deref(),
+            &create_info,
+            Some(library_flags),
+        )?;
+
+        Ok(Self::from_handle(
+            device,
+            handle,
+            library_flags,
+            create_info,
+        ))
+    }
+
+    /// Creates a new `GraphicsPipelineLibrary` from a raw object handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid Vulkan object handle created from `device`.
+    /// - `handle` must be a graphics pipeline library created with `library_flags`.
+    /// - `create_info` must match the info used to create the object.
+    #[inline]
+    pub unsafe fn from_handle(
+        device: Arc<Device>,
+        handle: ash::vk::Pipeline,
+        library_flags: GraphicsPipelineLibraryFlags,
+        create_info: GraphicsPipelineCreateInfo,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            handle,
+            device: InstanceOwnedDebugWrapper(device),
+            id: Self::next_id(),
+
+            library_flags,
+            create_info,
+        })
+    }
+
+    /// Returns the state subsets that are contained in the library.
+    #[inline]
+    pub fn library_flags(&self) -> GraphicsPipelineLibraryFlags {
+        self.library_flags
+    }
+
+    /// Returns the info that the library was created with.
+    #[inline]
+    pub fn create_info(&self) -> &GraphicsPipelineCreateInfo {
+        &self.create_info
+    }
+}
+
+impl Drop for GraphicsPipelineLibrary {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.v1_0.destroy_pipeline)(self.device.handle(), self.handle, ptr::null());
+        }
+    }
+}
+
+unsafe impl VulkanObject for GraphicsPipelineLibrary {
+    type Handle = ash::vk::Pipeline;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for GraphicsPipelineLibrary {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl_id_counter!(GraphicsPipelineLibrary);
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// The state subsets of a graphics pipeline that are contained in a pipeline library.
+    GraphicsPipelineLibraryFlags = GraphicsPipelineLibraryFlagsEXT(u32);
+
+    /// The vertex input and input assembly state.
+    VERTEX_INPUT_INTERFACE = VERTEX_INPUT_INTERFACE,
+
+    /// The vertex, tessellation and geometry shader stages, and the tessellation, viewport,
+    /// rasterization and discard rectangle state.
+    PRE_RASTERIZATION_SHADERS = PRE_RASTERIZATION_SHADERS,
+
+    /// The fragment shader stage and the depth/stencil state.
+    FRAGMENT_SHADER = FRAGMENT_SHADER,
+
+    /// The color blend and multisample state.
+    FRAGMENT_OUTPUT_INTERFACE = FRAGMENT_OUTPUT_INTERFACE,
+}
+
+/// Parameters to link graphics pipeline libraries into a [`GraphicsPipeline`].
+#[derive(Clone, Debug)]
+pub struct GraphicsPipelineLinkInfo {
+    /// Additional properties of the pipeline.
+    ///
+    /// The default value is empty.
+    pub flags: PipelineCreateFlags,
+
+    /// The libraries to link.
+    ///
+    /// Together, the libraries must contain all the state subsets that are needed for a complete
+    /// pipeline, and each subset must be contained in only one library.
+    ///
+    /// There is no default value.
+    pub libraries: Vec<Arc<GraphicsPipelineLibrary>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl GraphicsPipelineLinkInfo {
+    /// Returns a `GraphicsPipelineLinkInfo` with the specified `libraries`.
+    #[inline]
+    pub fn libraries(libraries: impl IntoIterator<Item = Arc<GraphicsPipelineLibrary>>) -> Self {
+        Self {
+            flags: PipelineCreateFlags::empty(),
+            libraries: libraries.into_iter().collect(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            flags,
+            ref libraries,
+            _ne: _,
+        } = self;
+
+        if !device.enabled_features().graphics_pipeline_library {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "graphics_pipeline_library",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        flags
+            .validate_device(device)
+            .map_err(|err| ValidationError {
+                context: "flags".into(),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-flags-parameter"],
+                ..ValidationError::from_requirement(err)
+            })?;
+
+        if flags.intersects(PipelineCreateFlags::RETAIN_LINK_TIME_OPTIMIZATION_INFO) {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::RETAIN_LINK_TIME_OPTIMIZATION_INFO`, \
+                    but the linked pipeline is not a pipeline library"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        if libraries.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "libraries".into(),
+                problem: "is empty".into(),
+                ..Default::default()
+            }));
+        }
+
+        let mut library_flags_present = GraphicsPipelineLibraryFlags::empty();
+
+        for (library_index, library) in libraries.iter().enumerate() {
+            assert_eq!(device, library.device().as_ref());
+
+            if library_flags_present.intersects(library.library_flags()) {
+                return Err(Box::new(ValidationError {
+                    context: format!("libraries[{}].library_flags()", library_index).into(),
+                    problem: "contains a state subset that is also contained in a previous \
+                        element of `libraries`"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+
+            library_flags_present |= library.library_flags();
+        }
+
+        if let (Some(pre_rasterization), Some(fragment)) = (
+            self.library(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS),
+            self.library(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER),
+        ) {
+            let pre_rasterization_layout = &pre_rasterization.create_info().layout;
+            let fragment_layout = &fragment.create_info().layout;
+            let num_sets = pre_rasterization_layout.set_layouts().len();
+
+            if fragment_layout.set_layouts().len() != num_sets
+                || !pre_rasterization_layout.is_compatible_with(fragment_layout, num_sets as u32)
+            {
+                return Err(Box::new(ValidationError {
+                    context: "libraries".into(),
+                    problem: "the pipeline layouts of the pre-rasterization shaders library and \
+                        the fragment shader library are not compatible"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        self.to_create_info()
+            .validate(device)
+            .map_err(|err| err.add_context("libraries"))?;
+
+        Ok(())
+    }
+
+    /// Returns the library in `self.libraries` that contains `subset`, if any.
+    fn library(&self, subset: GraphicsPipelineLibraryFlags) -> Option<&GraphicsPipelineLibrary> {
+        self.libraries
+            .iter()
+            .find(|library| library.library_flags().intersects(subset))
+            .map(AsRef::as_ref)
+    }
+
+    /// Combines the state of all the libraries into the create info of a complete pipeline.
+    pub(crate) fn to_create_info(&self) -> GraphicsPipelineCreateInfo {
+        let layout = self
+            .library(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS)
+            .or_else(|| self.library(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER))
+            .unwrap_or(&self.libraries[0])
+            .create_info()
+            .layout
+            .clone();
+        let mut create_info = GraphicsPipelineCreateInfo::layout(layout);
+
+        let stages_in = |library: &GraphicsPipelineLibrary, fragment: bool| {
+            library
+                .create_info()
+                .stages
+                .iter()
+                .filter(move |stage: &&PipelineShaderStageCreateInfo| {
+                    (ShaderStage::from(&stage.entry_point.info().execution)
+                        == ShaderStage::Fragment)
+                        == fragment
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+
+        if let Some(library) = self.library(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE) {
+            let library_create_info = library.create_info();
+            create_info.vertex_input_state = library_create_info.vertex_input_state.clone();
+            create_info.input_assembly_state = library_create_info.input_assembly_state;
+        }
+
+        if let Some(library) = self.library(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS)
+        {
+            let library_create_info = library.create_info();
+            create_info.stages.extend(stages_in(library, false));
+            create_info.tessellation_state = library_create_info.tessellation_state;
+            create_info.viewport_state = library_create_info.viewport_state.clone();
+            create_info.rasterization_state = library_create_info.rasterization_state.clone();
+            create_info.discard_rectangle_state =
+                library_create_info.discard_rectangle_state.clone();
+            create_info.subpass = library_create_info.subpass.clone();
+        }
+
+        if let Some(library) = self.library(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER) {
+            let library_create_info = library.create_info();
+            create_info.stages.extend(stages_in(library, true));
+            create_info.depth_stencil_state = library_create_info.depth_stencil_state.clone();
+            create_info.multisample_state = library_create_info.multisample_state;
+
+            if create_info.subpass.is_none() {
+                create_info.subpass = library_create_info.subpass.clone();
+            }
+        }
+
+        if let Some(library) = self.library(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE)
+        {
+            let library_create_info = library.create_info();
+            create_info.color_blend_state = library_create_info.color_blend_state.clone();
+
+            if library_create_info.multisample_state.is_some() {
+                create_info.multisample_state = library_create_info.multisample_state;
+            }
+
+            if create_info.subpass.is_none() {
+                create_info.subpass = library_create_info.subpass.clone();
+            }
+        }
+
+        create_info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GraphicsPipelineLibrary, GraphicsPipelineLibraryFlags, GraphicsPipelineLinkInfo};
+    use crate::{
+        device::{Device, DeviceCreateInfo, DeviceExtensions, Features, QueueCreateInfo},
+        pipeline::{
+            graphics::{
+                input_assembly::InputAssemblyState, vertex_input::VertexInputState,
+                GraphicsPipelineCreateInfo,
+            },
+            GraphicsPipeline, PipelineLayout,
+        },
+        Requires, RequiresAllOf, RequiresOneOf, Validated,
+    };
+    use std::sync::Arc;
+
+    /// Creates a device with the `graphics_pipeline_library` feature enabled, or returns if there
+    /// is none that supports it.
+    macro_rules! library_device {
+        () => {{
+            let instance = instance!();
+            let enabled_extensions = DeviceExtensions {
+                khr_pipeline_library: true,
+                ext_graphics_pipeline_library: true,
+                ..DeviceExtensions::empty()
+            };
+            let enabled_features = Features {
+                graphics_pipeline_library: true,
+                ..Features::empty()
+            };
+
+            let select = match instance.enumerate_physical_devices() {
+                Ok(x) => x,
+                Err(_) => return,
+            }
+            .filter(|p| {
+                p.supported_extensions().contains(&enabled_extensions)
+                    && p.supported_features().contains(&enabled_features)
+            })
+            .find_map(|p| {
+                p.queue_family_properties()
+                    .iter()
+                    .position(|q| {
+                        q.queue_flags
+                            .intersects(crate::device::QueueFlags::GRAPHICS)
+                    })
+                    .map(|i| (p, i as u32))
+            });
+
+            let (physical_device, queue_family_index) = match select {
+                Some(x) => x,
+                None => return,
+            };
+
+            match Device::new(
+                physical_device,
+                DeviceCreateInfo {
+                    queue_create_infos: vec![QueueCreateInfo {
+                        queue_family_index,
+                        ..Default::default()
+                    }],
+                    enabled_extensions,
+                    enabled_features,
+                    ..Default::default()
+                },
+            ) {
+                Ok((device, _)) => device,
+                Err(_) => return,
+            }
+        }};
+    }
+
+    // Creates a library that contains only the vertex input interface.
+    fn vertex_input_library(device: Arc<Device>) -> Arc<GraphicsPipelineLibrary> {
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        GraphicsPipelineLibrary::new(
+            device,
+            None,
+            GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE,
+            GraphicsPipelineCreateInfo {
+                vertex_input_state: Some(VertexInputState::new()),
+                input_assembly_state: Some(InputAssemblyState::new()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn requires_feature() {
+        let (device, _queue) = gfx_dev_and_queue!();
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        match GraphicsPipelineLibrary::new(
+            device.clone(),
+            None,
+            GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE,
+            GraphicsPipelineCreateInfo {
+                vertex_input_state: Some(VertexInputState::new()),
+                input_assembly_state: Some(InputAssemblyState::new()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        ) {
+            Err(Validated::ValidationError(err)) => assert_eq!(
+                err.requires_one_of,
+                RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "graphics_pipeline_library"
+                )])]),
+            ),
+            _ => panic!(),
+        }
+
+        match GraphicsPipeline::link(device, None, GraphicsPipelineLinkInfo::libraries([])) {
+            Err(Validated::ValidationError(err)) => assert_eq!(
+                err.requires_one_of,
+                RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "graphics_pipeline_library"
+                )])]),
+            ),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn link_empty() {
+        let device = library_device!();
+
+        assert!(
+            GraphicsPipeline::link(device, None, GraphicsPipelineLinkInfo::libraries([])).is_err()
+        );
+    }
+
+    #[test]
+    fn link_duplicate_subsets() {
+        let device = library_device!();
+        let library = vertex_input_library(device.clone());
+
+        assert!(GraphicsPipeline::link(
+            device.clone(),
+            None,
+            GraphicsPipelineLinkInfo::libraries([library, vertex_input_library(device)]),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn link_incomplete() {
+        let device = library_device!();
+        let library = vertex_input_library(device.clone());
+
+        // The pre-rasterization shaders, fragment shader and fragment output interface are
+        // missing.
+        assert!(GraphicsPipeline::link(
+            device,
+            None,
+            GraphicsPipelineLinkInfo::libraries([library]),
+        )
+        .is_err());
+    }
+}
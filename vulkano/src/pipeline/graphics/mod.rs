@@ -57,10 +57,11 @@
 //! command.
 
 use self::{
-    color_blend::ColorBlendState,
-    depth_stencil::{DepthBoundsState, DepthState, DepthStencilState},
+    color_blend::{ColorBlendState, ColorBlendStateFlags},
+    depth_stencil::{DepthBoundsState, DepthState, DepthStencilState, DepthStencilStateFlags},
     discard_rectangle::DiscardRectangleState,
     input_assembly::{InputAssemblyState, PrimitiveTopology, PrimitiveTopologyClass},
+    library::{GraphicsPipelineLibraryFlags, GraphicsPipelineLinkInfo},
     multisample::MultisampleState,
     rasterization::RasterizationState,
     subpass::PipelineSubpassType,
@@ -69,10 +70,16 @@ use self::{
     viewport::ViewportState,
 };
 use super::{
-    cache::PipelineCache, DynamicState, Pipeline, PipelineBindPoint, PipelineCreateFlags,
-    PipelineLayout, PipelineShaderStageCreateInfo, StateMode,
+    cache::PipelineCache,
+    executable::{
+        self, PipelineExecutableInternalRepresentation, PipelineExecutableProperties,
+        PipelineExecutableStatistic,
+    },
+    DynamicState, Pipeline, PipelineBindPoint, PipelineCreateFlags, PipelineLayout,
+    PipelineShaderStageCreateInfo, StateMode,
 };
 use crate::{
+    descriptor_set::layout::DescriptorType,
     device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
     format::FormatFeatures,
     image::{ImageAspect, ImageAspects},
@@ -89,9 +96,11 @@ use crate::{
         },
         PartialStateMode,
     },
+    render_pass::SubpassDescriptionFlags,
     shader::{
         DescriptorBindingRequirements, FragmentShaderExecution, FragmentTestsStages,
-        ShaderExecution, ShaderStage, ShaderStages,
+        GeometryShaderInput, ShaderExecution, ShaderStage, ShaderStages,
+        TessellationShaderSubdivision,
     },
     Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
 };
@@ -106,6 +115,7 @@ pub mod color_blend;
 pub mod depth_stencil;
 pub mod discard_rectangle;
 pub mod input_assembly;
+pub mod library;
 pub mod multisample;
 pub mod rasterization;
 pub mod subpass;
@@ -124,6 +134,7 @@ pub struct GraphicsPipeline {
     handle: ash::vk::Pipeline,
     device: InstanceOwnedDebugWrapper<Arc<Device>>,
     id: NonZeroU64,
+    flags: PipelineCreateFlags,
 
     // TODO: replace () with an object that describes the shaders in some way.
     shaders: HashMap<ShaderStage, ()>,
@@ -177,6 +188,108 @@ impl GraphicsPipeline {
         cache: Option<Arc<PipelineCache>>,
         create_info: GraphicsPipelineCreateInfo,
     ) -> Result<Arc<Self>, VulkanError> {
+        let handle = Self::create_handle(&device, cache.as_deref(), &create_info, None)?;
+
+        Ok(Self::from_handle(device, handle, create_info))
+    }
+
+    /// Creates a new `GraphicsPipeline` by linking graphics pipeline libraries.
+    ///
+    /// The [`graphics_pipeline_library`](crate::device::Features::graphics_pipeline_library)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn link(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        link_info: GraphicsPipelineLinkInfo,
+    ) -> Result<Arc<Self>, Validated<VulkanError>> {
+        Self::validate_link(&device, cache.as_ref().map(AsRef::as_ref), &link_info)?;
+
+        unsafe { Ok(Self::link_unchecked(device, cache, link_info)?) }
+    }
+
+    fn validate_link(
+        device: &Device,
+        _cache: Option<&PipelineCache>,
+        link_info: &GraphicsPipelineLinkInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        link_info
+            .validate(device)
+            .map_err(|err| err.add_context("link_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn link_unchecked(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        link_info: GraphicsPipelineLinkInfo,
+    ) -> Result<Arc<Self>, VulkanError> {
+        let create_info = GraphicsPipelineCreateInfo {
+            flags: link_info.flags,
+            ..link_info.to_create_info()
+        };
+        let GraphicsPipelineLinkInfo {
+            flags,
+            ref libraries,
+            _ne: _,
+        } = link_info;
+
+        let libraries_vk: SmallVec<[_; 4]> =
+            libraries.iter().map(|library| library.handle()).collect();
+        let library_info_vk = ash::vk::PipelineLibraryCreateInfoKHR {
+            library_count: libraries_vk.len() as u32,
+            p_libraries: libraries_vk.as_ptr(),
+            ..Default::default()
+        };
+
+        let create_info_vk = ash::vk::GraphicsPipelineCreateInfo {
+            p_next: &library_info_vk as *const _ as *const _,
+            flags: flags.into(),
+            layout: create_info.layout.handle(),
+            base_pipeline_handle: ash::vk::Pipeline::null(),
+            base_pipeline_index: -1,
+            ..Default::default()
+        };
+
+        let cache_handle = match cache.as_ref() {
+            Some(cache) => cache.handle(),
+            None => ash::vk::PipelineCache::null(),
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.v1_0.create_graphics_pipelines)(
+                device.handle(),
+                cache_handle,
+                1,
+                &create_info_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            output.assume_init()
+        };
+
+        if handle == ash::vk::Pipeline::null() {
+            panic!("vkCreateGraphicsPipelines provided a NULL handle");
+        }
+
+        Ok(Self::from_handle(device, handle, create_info))
+    }
+
+    /// Creates a Vulkan pipeline object from `create_info`. If `library_flags` is `Some`, a
+    /// pipeline library containing the given state subsets is created.
+    pub(crate) unsafe fn create_handle(
+        device: &Device,
+        cache: Option<&PipelineCache>,
+        create_info: &GraphicsPipelineCreateInfo,
+        library_flags: Option<GraphicsPipelineLibraryFlags>,
+    ) -> Result<ash::vk::Pipeline, VulkanError> {
         let &GraphicsPipelineCreateInfo {
             flags,
             ref stages,
@@ -195,7 +308,7 @@ impl GraphicsPipeline {
 
             ref discard_rectangle_state,
             _ne: _,
-        } = &create_info;
+        } = create_info;
 
         let mut dynamic_state: HashMap<DynamicState, bool> = HashMap::default();
 
@@ -204,6 +317,7 @@ impl GraphicsPipeline {
             specialization_info_vk: ash::vk::SpecializationInfo,
             specialization_map_entries_vk: Vec<ash::vk::SpecializationMapEntry>,
             specialization_data_vk: Vec<u8>,
+            module_identifier_vk: Option<ash::vk::PipelineShaderStageModuleIdentifierCreateInfoEXT>,
         }
 
         let (mut stages_vk, mut per_stage_vk): (SmallVec<[_; 5]>, SmallVec<[_; 5]>) = stages
@@ -213,12 +327,23 @@ impl GraphicsPipeline {
                     flags,
                     ref entry_point,
                     ref specialization_info,
+                    ref module_identifier,
                     _ne: _,
                 } = stage;
 
                 let entry_point_info = entry_point.info();
                 let stage = ShaderStage::from(&entry_point_info.execution);
 
+                let module_identifier_vk = module_identifier.as_ref().map(|module_identifier| {
+                    let bytes = module_identifier.as_bytes();
+
+                    ash::vk::PipelineShaderStageModuleIdentifierCreateInfoEXT {
+                        identifier_size: bytes.len() as u32,
+                        p_identifier: bytes.as_ptr(),
+                        ..Default::default()
+                    }
+                });
+
                 let mut specialization_data_vk: Vec<u8> = Vec::new();
                 let specialization_map_entries_vk: Vec<_> = specialization_info
                     .iter()
@@ -240,7 +365,11 @@ impl GraphicsPipeline {
                     ash::vk::PipelineShaderStageCreateInfo {
                         flags: flags.into(),
                         stage: stage.into(),
-                        module: entry_point.module().handle(),
+                        module: if module_identifier.is_some() {
+                            ash::vk::ShaderModule::null()
+                        } else {
+                            entry_point.module().handle()
+                        },
                         p_name: ptr::null(),
                         p_specialization_info: ptr::null(),
                         ..Default::default()
@@ -255,6 +384,7 @@ impl GraphicsPipeline {
                         },
                         specialization_map_entries_vk,
                         specialization_data_vk,
+                        module_identifier_vk,
                     },
                 )
             })
@@ -267,6 +397,7 @@ impl GraphicsPipeline {
                 specialization_info_vk,
                 specialization_map_entries_vk,
                 specialization_data_vk,
+                module_identifier_vk,
             },
         ) in (stages_vk.iter_mut()).zip(per_stage_vk.iter_mut())
         {
@@ -276,6 +407,11 @@ impl GraphicsPipeline {
                 ..*stage_vk
             };
 
+            if let Some(info) = module_identifier_vk.as_mut() {
+                info.p_next = stage_vk.p_next;
+                stage_vk.p_next = info as *const _ as *const _;
+            }
+
             *specialization_info_vk = ash::vk::SpecializationInfo {
                 p_map_entries: specialization_map_entries_vk.as_ptr(),
                 p_data: specialization_data_vk.as_ptr() as _,
@@ -438,13 +574,16 @@ impl GraphicsPipeline {
         }
 
         let mut viewport_state_vk = None;
+        let mut viewport_swizzle_state_vk = None;
         let mut viewports_vk: SmallVec<[_; 2]> = SmallVec::new();
         let mut scissors_vk: SmallVec<[_; 2]> = SmallVec::new();
+        let mut swizzles_vk: SmallVec<[_; 2]> = SmallVec::new();
 
         if let Some(viewport_state) = viewport_state {
             let ViewportState {
                 viewports,
                 scissors,
+                swizzles,
                 _ne: _,
             } = viewport_state;
 
@@ -486,22 +625,39 @@ impl GraphicsPipeline {
                 }
             };
 
-            let _ = viewport_state_vk.insert(ash::vk::PipelineViewportStateCreateInfo {
-                flags: ash::vk::PipelineViewportStateCreateFlags::empty(),
-                viewport_count,
-                p_viewports: if viewports_vk.is_empty() {
-                    ptr::null()
-                } else {
-                    viewports_vk.as_ptr()
-                },
-                scissor_count,
-                p_scissors: if scissors_vk.is_empty() {
-                    ptr::null()
-                } else {
-                    scissors_vk.as_ptr()
-                },
-                ..Default::default()
-            });
+            let viewport_state_vk =
+                viewport_state_vk.insert(ash::vk::PipelineViewportStateCreateInfo {
+                    flags: ash::vk::PipelineViewportStateCreateFlags::empty(),
+                    viewport_count,
+                    p_viewports: if viewports_vk.is_empty() {
+                        ptr::null()
+                    } else {
+                        viewports_vk.as_ptr()
+                    },
+                    scissor_count,
+                    p_scissors: if scissors_vk.is_empty() {
+                        ptr::null()
+                    } else {
+                        scissors_vk.as_ptr()
+                    },
+                    ..Default::default()
+                });
+
+            if !swizzles.is_empty() {
+                swizzles_vk.extend(swizzles.iter().map(Into::into));
+
+                let viewport_swizzle_state_vk = viewport_swizzle_state_vk.insert(
+                    ash::vk::PipelineViewportSwizzleStateCreateInfoNV {
+                        flags: ash::vk::PipelineViewportSwizzleStateCreateFlagsNV::empty(),
+                        viewport_count: swizzles_vk.len() as u32,
+                        p_viewport_swizzles: swizzles_vk.as_ptr(),
+                        ..Default::default()
+                    },
+                );
+
+                viewport_swizzle_state_vk.p_next = viewport_state_vk.p_next;
+                viewport_state_vk.p_next = viewport_swizzle_state_vk as *const _ as *const _;
+            }
         }
 
         let mut rasterization_state_vk = None;
@@ -987,18 +1143,18 @@ impl GraphicsPipeline {
             }
         }
 
-        let render_pass = render_pass.as_ref().unwrap();
         let mut render_pass_vk = ash::vk::RenderPass::null();
         let mut subpass_vk = 0;
         let mut color_attachment_formats_vk: SmallVec<[_; 4]> = SmallVec::new();
         let mut rendering_create_info_vk = None;
 
         match render_pass {
-            PipelineSubpassType::BeginRenderPass(subpass) => {
+            None => (),
+            Some(PipelineSubpassType::BeginRenderPass(subpass)) => {
                 render_pass_vk = subpass.render_pass().handle();
                 subpass_vk = subpass.index();
             }
-            PipelineSubpassType::BeginRendering(rendering_info) => {
+            Some(PipelineSubpassType::BeginRendering(rendering_info)) => {
                 let &PipelineRenderingCreateInfo {
                     view_mask,
                     ref color_attachment_formats,
@@ -1065,8 +1221,19 @@ impl GraphicsPipeline {
             Create
         */
 
+        let mut flags_vk: ash::vk::PipelineCreateFlags = flags.into();
+        let mut library_create_info_vk = None;
+
+        if let Some(library_flags) = library_flags {
+            flags_vk |= ash::vk::PipelineCreateFlags::LIBRARY_KHR;
+            let _ = library_create_info_vk.insert(ash::vk::GraphicsPipelineLibraryCreateInfoEXT {
+                flags: library_flags.into(),
+                ..Default::default()
+            });
+        }
+
         let mut create_info_vk = ash::vk::GraphicsPipelineCreateInfo {
-            flags: flags.into(),
+            flags: flags_vk,
             stage_count: stages_vk.len() as u32,
             p_stages: stages_vk.as_ptr(),
             p_vertex_input_state: vertex_input_state_vk
@@ -1123,7 +1290,12 @@ impl GraphicsPipeline {
             create_info_vk.p_next = info as *const _ as *const _;
         }
 
-        let cache_handle = match cache.as_ref() {
+        if let Some(info) = library_create_info_vk.as_mut() {
+            info.p_next = create_info_vk.p_next as *mut _;
+            create_info_vk.p_next = info as *const _ as *const _;
+        }
+
+        let cache_handle = match cache {
             Some(cache) => cache.handle(),
             None => ash::vk::PipelineCache::null(),
         };
@@ -1152,7 +1324,7 @@ impl GraphicsPipeline {
             panic!("vkCreateGraphicsPipelines provided a NULL handle");
         }
 
-        Ok(Self::from_handle(device, handle, create_info))
+        Ok(handle)
     }
 
     /// Creates a new `GraphicsPipeline` from a raw object handle.
@@ -1168,7 +1340,7 @@ impl GraphicsPipeline {
         create_info: GraphicsPipelineCreateInfo,
     ) -> Arc<Self> {
         let GraphicsPipelineCreateInfo {
-            flags: _,
+            flags,
             stages,
 
             vertex_input_state,
@@ -1284,6 +1456,7 @@ impl GraphicsPipeline {
             let ViewportState {
                 viewports,
                 scissors,
+                swizzles: _,
                 _ne: _,
             } = viewport_state;
 
@@ -1580,6 +1753,7 @@ impl GraphicsPipeline {
             handle,
             device: InstanceOwnedDebugWrapper(device),
             id: Self::next_id(),
+            flags,
 
             shaders,
             descriptor_binding_requirements,
@@ -1608,6 +1782,100 @@ impl GraphicsPipeline {
         &self.device
     }
 
+    /// Returns the flags that the pipeline was created with.
+    #[inline]
+    pub fn flags(&self) -> PipelineCreateFlags {
+        self.flags
+    }
+
+    /// Returns the properties of the executables that the pipeline was compiled into.
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device.
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, Validated<VulkanError>> {
+        executable::validate_executable_properties(&self.device)?;
+
+        unsafe { Ok(self.executable_properties_unchecked()?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_properties_unchecked(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, VulkanError> {
+        executable::executable_properties_unchecked(&self.device, self.handle)
+    }
+
+    /// Returns statistics about the executable with index `executable_index`, which is an index
+    /// into the list returned by [`executable_properties`](Self::executable_properties).
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_STATISTICS`].
+    ///
+    /// Statistics that are reported in a format that Vulkano does not know are not included.
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, Validated<VulkanError>> {
+        executable::validate_executable_statistics(
+            &self.device,
+            self.handle,
+            self.flags,
+            executable_index,
+        )?;
+
+        unsafe { Ok(self.executable_statistics_unchecked(executable_index)?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_statistics_unchecked(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, VulkanError> {
+        executable::executable_statistics_unchecked(&self.device, self.handle, executable_index)
+    }
+
+    /// Returns the internal representations of the executable with index `executable_index`,
+    /// which is an index into the list returned by
+    /// [`executable_properties`](Self::executable_properties).
+    ///
+    /// The [`pipeline_executable_info`](crate::device::Features::pipeline_executable_info)
+    /// feature must be enabled on the device, and the pipeline must have been created with
+    /// [`PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS`].
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, Validated<VulkanError>> {
+        executable::validate_executable_internal_representations(
+            &self.device,
+            self.handle,
+            self.flags,
+            executable_index,
+        )?;
+
+        unsafe { Ok(self.executable_internal_representations_unchecked(executable_index)?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_internal_representations_unchecked(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, VulkanError> {
+        executable::executable_internal_representations_unchecked(
+            &self.device,
+            self.handle,
+            executable_index,
+        )
+    }
+
     /// Returns information about a particular shader.
     ///
     /// `None` is returned if the pipeline does not contain this shader.
@@ -1880,6 +2148,16 @@ impl GraphicsPipelineCreateInfo {
     }
 
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        self.validate_library(device, None)
+    }
+
+    /// Validates `self` for the creation of a pipeline library with the state subsets in
+    /// `library_flags`, or a complete pipeline if it is `None`.
+    pub(crate) fn validate_library(
+        &self,
+        device: &Device,
+        library_flags: Option<GraphicsPipelineLibraryFlags>,
+    ) -> Result<(), Box<ValidationError>> {
         let &Self {
             flags,
             ref stages,
@@ -1908,6 +2186,48 @@ impl GraphicsPipelineCreateInfo {
                 ..ValidationError::from_requirement(err)
             })?;
 
+        if library_flags.is_none()
+            && flags.intersects(PipelineCreateFlags::RETAIN_LINK_TIME_OPTIMIZATION_INFO)
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::RETAIN_LINK_TIME_OPTIMIZATION_INFO`, \
+                    but the pipeline is not a pipeline library"
+                    .into(),
+                vuids: &[
+                    "VUID-VkGraphicsPipelineCreateInfo-flags-06609",
+                    "VUID-VkGraphicsPipelineCreateInfo-flags-06610",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        if flags.intersects(PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED)
+            && !device.enabled_features().pipeline_creation_cache_control
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "pipeline_creation_cache_control",
+                )])]),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-pipelineCreationCacheControl-02878"],
+            }));
+        }
+
+        if flags.intersects(PipelineCreateFlags::INDIRECT_BINDABLE)
+            && !device.enabled_features().device_generated_commands
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::INDIRECT_BINDABLE`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "device_generated_commands",
+                )])]),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-flags-02877"],
+            }));
+        }
+
         /*
             Gather shader stages
         */
@@ -1924,6 +2244,22 @@ impl GraphicsPipelineCreateInfo {
             let stage_enum = ShaderStage::from(&entry_point_info.execution);
             let stage_flag = ShaderStages::from(stage_enum);
 
+            if stage.module_identifier.is_some()
+                && !flags.intersects(PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`stages[{}].module_identifier` is `Some`, but \
+                        `flags` does not contain \
+                        `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED`",
+                        stage_index
+                    )
+                    .into(),
+                    vuids: &["VUID-VkPipelineShaderStageModuleIdentifierCreateInfoEXT-pNext-06851"],
+                    ..Default::default()
+                }));
+            }
+
             if stages_present.intersects(stage_flag) {
                 return Err(Box::new(ValidationError {
                     context: "stages".into(),
@@ -1940,7 +2276,7 @@ impl GraphicsPipelineCreateInfo {
 
             const PRIMITIVE_SHADING_STAGES: ShaderStages = ShaderStages::VERTEX
                 .union(ShaderStages::TESSELLATION_CONTROL)
-                .union(ShaderStages::TESSELLATION_CONTROL)
+                .union(ShaderStages::TESSELLATION_EVALUATION)
                 .union(ShaderStages::GEOMETRY);
             const MESH_SHADING_STAGES: ShaderStages = ShaderStages::MESH.union(ShaderStages::TASK);
 
@@ -1982,7 +2318,15 @@ impl GraphicsPipelineCreateInfo {
             Validate needed/unused state
         */
 
-        let need_pre_rasterization_shader_state = true;
+        // A complete pipeline includes all state subsets.
+        let library_flags = library_flags.unwrap_or(
+            GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE
+                | GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS
+                | GraphicsPipelineLibraryFlags::FRAGMENT_SHADER
+                | GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE,
+        );
+        let need_pre_rasterization_shader_state =
+            library_flags.intersects(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS);
 
         // Check this first because everything else depends on it.
         match (
@@ -2012,22 +2356,27 @@ impl GraphicsPipelineCreateInfo {
             _ => (),
         }
 
-        let need_vertex_input_state = need_pre_rasterization_shader_state
-            && stages
-                .iter()
-                .any(|stage| matches!(stage.entry_point.info().execution, ShaderExecution::Vertex));
-        let need_fragment_shader_state = need_pre_rasterization_shader_state
-            && rasterization_state
-                .as_ref()
-                .unwrap()
-                .rasterizer_discard_enable
-                != StateMode::Fixed(true);
-        let need_fragment_output_state = need_pre_rasterization_shader_state
-            && rasterization_state
-                .as_ref()
-                .unwrap()
-                .rasterizer_discard_enable
-                != StateMode::Fixed(true);
+        // If the pipeline does not include pre-rasterization shader state, it can't be known yet
+        // whether the other states will be used, so they are needed if they are included.
+        let rasterizer_discard_enable = matches!(
+            rasterization_state,
+            Some(RasterizationState {
+                rasterizer_discard_enable: StateMode::Fixed(true),
+                ..
+            })
+        );
+        let need_vertex_input_state = library_flags
+            .intersects(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE)
+            && (!need_pre_rasterization_shader_state
+                || stages.iter().any(|stage| {
+                    matches!(stage.entry_point.info().execution, ShaderExecution::Vertex)
+                }));
+        let need_fragment_shader_state = library_flags
+            .intersects(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER)
+            && !rasterizer_discard_enable;
+        let need_fragment_output_state = library_flags
+            .intersects(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE)
+            && !rasterizer_discard_enable;
 
         match (vertex_stage.is_some(), need_pre_rasterization_shader_state) {
             (true, false) => {
@@ -2259,11 +2608,14 @@ impl GraphicsPipelineCreateInfo {
             _ => (),
         }
 
-        match (multisample_state.is_some(), need_fragment_output_state) {
+        match (
+            multisample_state.is_some(),
+            need_fragment_shader_state || need_fragment_output_state,
+        ) {
             (true, false) => {
                 return Err(Box::new(ValidationError {
                     problem: "the pipeline is not being created with \
-                        fragment output state, but \
+                        fragment shader or fragment output state, but \
                         `multisample_state` is `Some`"
                         .into(),
                     ..Default::default()
@@ -2272,7 +2624,7 @@ impl GraphicsPipelineCreateInfo {
             (false, true) => {
                 return Err(Box::new(ValidationError {
                     problem: "the pipeline is being created with \
-                        fragment output state, but \
+                        fragment shader or fragment output state, but \
                         `multisample_state` is `None`"
                         .into(),
                     vuids: &["VUID-VkGraphicsPipelineCreateInfo-rasterizerDiscardEnable-00751"],
@@ -2284,22 +2636,23 @@ impl GraphicsPipelineCreateInfo {
 
         match (
             depth_stencil_state.is_some(),
-            !need_fragment_output_state
-                || match subpass {
-                    Some(PipelineSubpassType::BeginRenderPass(subpass)) => {
-                        subpass.subpass_desc().depth_stencil_attachment.is_some()
-                    }
-                    Some(PipelineSubpassType::BeginRendering(rendering_info)) => {
-                        rendering_info.depth_attachment_format.is_some()
-                            || rendering_info.stencil_attachment_format.is_some()
-                    }
-                    None => false,
-                },
+            (need_pre_rasterization_shader_state && rasterizer_discard_enable)
+                || (need_fragment_shader_state
+                    && match subpass {
+                        Some(PipelineSubpassType::BeginRenderPass(subpass)) => {
+                            subpass.subpass_desc().depth_stencil_attachment.is_some()
+                        }
+                        Some(PipelineSubpassType::BeginRendering(rendering_info)) => {
+                            rendering_info.depth_attachment_format.is_some()
+                                || rendering_info.stencil_attachment_format.is_some()
+                        }
+                        None => false,
+                    }),
         ) {
             (true, false) => {
                 return Err(Box::new(ValidationError {
-                    problem: "the pipeline is being created with \
-                        fragment output state, and \
+                    problem: "the pipeline is not being created with \
+                        fragment shader state, or \
                         `subpass` does not have a depth/stencil attachment, but \
                         `depth_stencil_state` is `Some`"
                         .into(),
@@ -2308,9 +2661,10 @@ impl GraphicsPipelineCreateInfo {
             }
             (false, true) => {
                 return Err(Box::new(ValidationError {
-                    problem: "the pipeline is not being created with \
-                        fragment output state, or \
-                        `subpass` has a depth/stencil attachment, but \
+                    problem: "the pipeline is being created with \
+                        fragment shader state and \
+                        `subpass` has a depth/stencil attachment, or \
+                        rasterizer discarding is enabled, but \
                         `depth_stencil_state` is `None`"
                         .into(),
                     vuids: &[
@@ -2423,6 +2777,7 @@ impl GraphicsPipelineCreateInfo {
                 flags: _,
                 ref entry_point,
                 specialization_info: _,
+                module_identifier: _,
                 _ne: _,
             } = stage;
 
@@ -2454,8 +2809,6 @@ impl GraphicsPipelineCreateInfo {
         .flatten()
         .collect();
 
-        // TODO: this check is too strict; the output only has to be a superset, any variables
-        // not used in the input of the next shader are just ignored.
         for (output, input) in ordered_stages.iter().zip(ordered_stages.iter().skip(1)) {
             if let Err(err) = (input.entry_point.info().input_interface)
                 .matches(&output.entry_point.info().output_interface)
@@ -2726,19 +3079,129 @@ impl GraphicsPipelineCreateInfo {
             }
         }
 
-        if let (Some(_), Some(_)) = (tessellation_control_stage, tessellation_evaluation_stage) {
-            // FIXME: must check that the control shader and evaluation shader are compatible
+        if let (Some(tessellation_control_stage), Some(tessellation_evaluation_stage)) =
+            (tessellation_control_stage, tessellation_evaluation_stage)
+        {
+            let control = match tessellation_control_stage.entry_point.info().execution {
+                ShaderExecution::TessellationControl(execution) => execution,
+                _ => unreachable!(),
+            };
+            let evaluation = match tessellation_evaluation_stage.entry_point.info().execution {
+                ShaderExecution::TessellationEvaluation(execution) => execution,
+                _ => unreachable!(),
+            };
 
-            // TODO:
-            // VUID-VkGraphicsPipelineCreateInfo-pStages-00732
-            // VUID-VkGraphicsPipelineCreateInfo-pStages-00733
-            // VUID-VkGraphicsPipelineCreateInfo-pStages-00734
-            // VUID-VkGraphicsPipelineCreateInfo-pStages-00735
-        }
+            let subdivision = match (control.subdivision, evaluation.subdivision) {
+                (None, None) => {
+                    return Err(Box::new(ValidationError {
+                        problem: "neither the tessellation control shader nor the tessellation \
+                            evaluation shader specifies a type of subdivision"
+                            .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-00732"],
+                        ..Default::default()
+                    }));
+                }
+                (Some(first), Some(second)) if first != second => {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "the tessellation control shader specifies the subdivision type \
+                            `{:?}`, but the tessellation evaluation shader specifies `{:?}`",
+                            first, second,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-00733"],
+                        ..Default::default()
+                    }));
+                }
+                (first, second) => first.or(second).unwrap(),
+            };
 
-        if let (Some(_), Some(_)) = (tessellation_evaluation_stage, geometry_stage) {
-            // TODO:
-            // VUID-VkGraphicsPipelineCreateInfo-pStages-00739
+            match (control.output_vertices, evaluation.output_vertices) {
+                (None, None) => {
+                    return Err(Box::new(ValidationError {
+                        problem: "neither the tessellation control shader nor the tessellation \
+                            evaluation shader specifies the output patch size"
+                            .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-00734"],
+                        ..Default::default()
+                    }));
+                }
+                (Some(first), Some(second)) if first != second => {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "the tessellation control shader specifies an output patch size of \
+                            {}, but the tessellation evaluation shader specifies {}",
+                            first, second,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-00735"],
+                        ..Default::default()
+                    }));
+                }
+                _ => (),
+            }
+
+            // No VUID, but the specification requires the spacing to be identical if both shaders
+            // specify it.
+            if let (Some(first), Some(second)) = (control.spacing, evaluation.spacing) {
+                if first != second {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "the tessellation control shader specifies the spacing `{:?}`, but \
+                            the tessellation evaluation shader specifies `{:?}`",
+                            first, second,
+                        )
+                        .into(),
+                        ..Default::default()
+                    }));
+                }
+            }
+
+            // No VUID, but the specification requires the vertex order to be identical if both
+            // shaders specify it.
+            if let (Some(first), Some(second)) = (control.vertex_order, evaluation.vertex_order) {
+                if first != second {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "the tessellation control shader specifies the vertex order \
+                            `{:?}`, but the tessellation evaluation shader specifies `{:?}`",
+                            first, second,
+                        )
+                        .into(),
+                        ..Default::default()
+                    }));
+                }
+            }
+
+            if let Some(geometry_stage) = geometry_stage {
+                let input = match geometry_stage.entry_point.info().execution {
+                    ShaderExecution::Geometry(execution) => execution.input,
+                    _ => unreachable!(),
+                };
+
+                let tessellation_output = if control.point_mode || evaluation.point_mode {
+                    GeometryShaderInput::Points
+                } else {
+                    match subdivision {
+                        TessellationShaderSubdivision::Isolines => GeometryShaderInput::Lines,
+                        TessellationShaderSubdivision::Triangles
+                        | TessellationShaderSubdivision::Quads => GeometryShaderInput::Triangles,
+                    }
+                };
+
+                if input != tessellation_output {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "the tessellation shaders output `{:?}` primitives, but the input \
+                            primitive type of the geometry shader is `{:?}`",
+                            tessellation_output, input,
+                        )
+                        .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-pStages-00739"],
+                        ..Default::default()
+                    }));
+                }
+            }
         }
 
         if let (None, Some(geometry_stage), Some(input_assembly_state)) = (
@@ -2785,11 +3248,30 @@ impl GraphicsPipelineCreateInfo {
                 }
             }
 
+            // Reading input attachments with dynamic rendering requires
+            // VK_KHR_dynamic_rendering_local_read, which is not supported yet.
+            if matches!(subpass, PipelineSubpassType::BeginRendering(_))
+                && entry_point_info
+                    .descriptor_binding_requirements
+                    .values()
+                    .any(|reqs| {
+                        reqs.descriptor_types
+                            .contains(&DescriptorType::InputAttachment)
+                    })
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`subpass` is `PipelineSubpassType::BeginRendering`, but \
+                        the fragment shader reads from input attachments"
+                        .into(),
+                    vuids: &["VUID-VkGraphicsPipelineCreateInfo-renderPass-06061"],
+                    ..Default::default()
+                }));
+            }
+
             // TODO:
             // VUID-VkGraphicsPipelineCreateInfo-pStages-01565
             // VUID-VkGraphicsPipelineCreateInfo-renderPass-06038
             // VUID-VkGraphicsPipelineCreateInfo-renderPass-06056
-            // VUID-VkGraphicsPipelineCreateInfo-renderPass-06061
         }
 
         if let (Some(input_assembly_state), Some(_)) = (input_assembly_state, tessellation_state) {
@@ -2933,38 +3415,67 @@ impl GraphicsPipelineCreateInfo {
                     }
                 }
             }
+        }
 
-            if depth_stencil_state.stencil.is_some() {
-                let has_stencil_attachment = match subpass {
-                    PipelineSubpassType::BeginRenderPass(subpass) => subpass
-                        .subpass_desc()
-                        .depth_stencil_attachment
-                        .as_ref()
-                        .map_or(false, |depth_stencil_attachment| {
-                            subpass.render_pass().attachments()
-                                [depth_stencil_attachment.attachment as usize]
-                                .format
-                                .aspects()
-                                .intersects(ImageAspects::STENCIL)
-                        }),
-                    PipelineSubpassType::BeginRendering(rendering_info) => {
-                        rendering_info.stencil_attachment_format.is_some()
+        if let (Some(depth_stencil_state), Some(subpass)) = (depth_stencil_state, subpass) {
+            for (flag, subpass_flag, flag_name, subpass_flag_name, vuids) in [
+                (
+                    DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS,
+                    SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS,
+                    "DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS",
+                    "SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS",
+                    &["VUID-VkGraphicsPipelineCreateInfo-renderPass-06483"],
+                ),
+                (
+                    DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS,
+                    SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS,
+                    "DepthStencilStateFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS",
+                    "SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS",
+                    &["VUID-VkGraphicsPipelineCreateInfo-renderPass-06484"],
+                ),
+            ] {
+                if !depth_stencil_state.flags.intersects(flag) {
+                    continue;
+                }
+
+                if let PipelineSubpassType::BeginRenderPass(subpass) = subpass {
+                    if !subpass.subpass_desc().flags.intersects(subpass_flag) {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "`depth_stencil_state.flags` contains `{}`, but \
+                                `subpass.subpass_desc().flags` does not contain `{}`",
+                                flag_name, subpass_flag_name,
+                            )
+                            .into(),
+                            vuids,
+                            ..Default::default()
+                        }));
                     }
-                };
+                }
+            }
+        }
 
-                if !has_stencil_attachment {
+        if let (Some(color_blend_state), Some(subpass)) = (color_blend_state, subpass) {
+            if let PipelineSubpassType::BeginRenderPass(subpass) = subpass {
+                if color_blend_state
+                    .flags
+                    .intersects(ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS)
+                    && !subpass.subpass_desc().flags.intersects(
+                        SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS,
+                    )
+                {
                     return Err(Box::new(ValidationError {
-                        problem: "`depth_stencil_state.stencil` is `Some`, but `subpass` does not \
-                            have a stencil attachment"
+                        problem: "`color_blend_state.flags` contains \
+                            `ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS`, \
+                            but `subpass.subpass_desc().flags` does not contain \
+                            `SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS`"
                             .into(),
-                        // vuids?
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-renderPass-06482"],
                         ..Default::default()
                     }));
                 }
             }
-        }
 
-        if let (Some(color_blend_state), Some(subpass)) = (color_blend_state, subpass) {
             let color_attachment_count = match subpass {
                 PipelineSubpassType::BeginRenderPass(subpass) => {
                     subpass.subpass_desc().color_attachments.len()
@@ -3034,3 +3545,222 @@ impl GraphicsPipelineCreateInfo {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        color_blend::{ColorBlendState, ColorBlendStateFlags},
+        input_assembly::{InputAssemblyState, PrimitiveTopology},
+        library::GraphicsPipelineLibraryFlags,
+        multisample::MultisampleState,
+        rasterization::RasterizationState,
+        subpass::{PipelineRenderingCreateInfo, PipelineSubpassType},
+        tessellation::TessellationState,
+        vertex_input::VertexInputState,
+        viewport::{Viewport, ViewportState, ViewportSwizzle},
+        GraphicsPipelineCreateInfo,
+    };
+    use crate::{
+        format::Format,
+        image::ImageLayout,
+        pipeline::{PipelineLayout, PipelineShaderStageCreateInfo},
+        render_pass::{
+            AttachmentDescription, AttachmentLoadOp, AttachmentReference, AttachmentStoreOp,
+            RenderPass, RenderPassCreateInfo, Subpass, SubpassDescription, SubpassDescriptionFlags,
+        },
+        shader::{ShaderModule, ShaderModuleCreateInfo},
+    };
+    use smallvec::smallvec;
+
+    #[test]
+    fn rasterization_order_color_attachment_access() {
+        let (device, _queue) = gfx_dev_and_queue!(
+            extensions: [ext_rasterization_order_attachment_access, khr_dynamic_rendering],
+            features: [rasterization_order_color_attachment_access, dynamic_rendering],
+        );
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let subpass = |flags| {
+            let render_pass = RenderPass::new(
+                device.clone(),
+                RenderPassCreateInfo {
+                    attachments: vec![AttachmentDescription {
+                        format: Format::R8G8B8A8_UNORM,
+                        load_op: AttachmentLoadOp::Clear,
+                        store_op: AttachmentStoreOp::Store,
+                        initial_layout: ImageLayout::ColorAttachmentOptimal,
+                        final_layout: ImageLayout::ColorAttachmentOptimal,
+                        ..Default::default()
+                    }],
+                    subpasses: vec![SubpassDescription {
+                        flags,
+                        color_attachments: vec![Some(AttachmentReference {
+                            attachment: 0,
+                            layout: ImageLayout::ColorAttachmentOptimal,
+                            ..Default::default()
+                        })],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            Subpass::from(render_pass, 0).unwrap()
+        };
+
+        // Only the fragment output interface is validated, so that no shaders are needed.
+        let validate = |subpass: PipelineSubpassType| {
+            GraphicsPipelineCreateInfo {
+                multisample_state: Some(MultisampleState::default()),
+                color_blend_state: Some(ColorBlendState {
+                    flags: ColorBlendStateFlags::RASTERIZATION_ORDER_ATTACHMENT_ACCESS,
+                    ..ColorBlendState::new(1)
+                }),
+                subpass: Some(subpass),
+                ..GraphicsPipelineCreateInfo::layout(layout.clone())
+            }
+            .validate_library(
+                &device,
+                Some(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE),
+            )
+        };
+
+        let err = validate(subpass(SubpassDescriptionFlags::empty()).into()).unwrap_err();
+        assert_eq!(
+            err.vuids,
+            &["VUID-VkGraphicsPipelineCreateInfo-renderPass-06482"],
+        );
+
+        validate(
+            subpass(SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS).into(),
+        )
+        .unwrap();
+
+        // With dynamic rendering, there are no subpass flags to match.
+        let rendering_info =
+            PipelineRenderingCreateInfo::from_subpass(&subpass(SubpassDescriptionFlags::empty()));
+        validate(rendering_info.into()).unwrap();
+    }
+
+    #[test]
+    fn tessellation_spacing_mismatch() {
+        let (device, _queue) = gfx_dev_and_queue!(tessellation_shader);
+
+        let entry_point = |words: &[u32]| unsafe {
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(words)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        /*
+        #version 450
+
+        void main() {}
+        */
+        const VS: [u32; 29] = [
+            119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 1, 1852399981, 0,
+            131091, 2, 196641, 3, 2, 327734, 2, 1, 0, 3, 131320, 4, 65789, 65592,
+        ];
+
+        /*
+        #version 450
+
+        layout(vertices = 3) out;
+
+        void main() {}
+        */
+        // With `OpExecutionMode %main SpacingEqual` added.
+        const TCS: [u32; 36] = [
+            119734787, 65536, 0, 5, 0, 131089, 3, 196622, 0, 1, 327695, 1, 1, 1852399981, 0,
+            262160, 1, 26, 3, 196624, 1, 1, 131091, 2, 196641, 3, 2, 327734, 2, 1, 0, 3, 131320, 4,
+            65789, 65592,
+        ];
+
+        /*
+        #version 450
+
+        layout(triangles, fractional_odd_spacing) in;
+
+        void main() {}
+        */
+        const TES: [u32; 35] = [
+            119734787, 65536, 0, 5, 0, 131089, 3, 196622, 0, 1, 327695, 2, 1, 1852399981, 0,
+            196624, 1, 22, 196624, 1, 3, 131091, 2, 196641, 3, 2, 327734, 2, 1, 0, 3, 131320, 4,
+            65789, 65592,
+        ];
+
+        let render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: Store,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let err = GraphicsPipelineCreateInfo {
+            stages: [VS.as_slice(), &TCS, &TES]
+                .into_iter()
+                .map(|words| PipelineShaderStageCreateInfo::new(entry_point(words)))
+                .collect(),
+            vertex_input_state: Some(VertexInputState::new()),
+            input_assembly_state: Some(
+                InputAssemblyState::new().topology(PrimitiveTopology::PatchList),
+            ),
+            tessellation_state: Some(TessellationState::new()),
+            viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+            rasterization_state: Some(RasterizationState::new()),
+            subpass: Some(Subpass::from(render_pass, 0).unwrap().into()),
+            ..GraphicsPipelineCreateInfo::layout(layout)
+        }
+        .validate_library(
+            &device,
+            Some(
+                GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE
+                    | GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS,
+            ),
+        )
+        .unwrap_err();
+        assert!(err.problem.contains("spacing"));
+    }
+
+    #[test]
+    fn viewport_swizzle_count() {
+        let (device, _queue) = gfx_dev_and_queue!(
+            extensions: [nv_viewport_swizzle],
+            features: [multi_viewport],
+        );
+
+        let viewport = Viewport {
+            offset: [0.0, 0.0],
+            extent: [64.0, 64.0],
+            depth_range: 0.0..=1.0,
+        };
+
+        // Swizzles beyond the number of viewports are allowed.
+        let viewport_state = ViewportState {
+            swizzles: smallvec![ViewportSwizzle::default(); 2],
+            ..ViewportState::viewport_fixed_scissor_irrelevant([viewport.clone()])
+        };
+        assert!(viewport_state.validate(&device).is_ok());
+
+        let viewport_state = ViewportState {
+            swizzles: smallvec![ViewportSwizzle::default(); 1],
+            ..ViewportState::viewport_fixed_scissor_irrelevant([viewport.clone(), viewport])
+        };
+        assert_eq!(
+            viewport_state.validate(&device).unwrap_err().vuids,
+            &["VUID-VkPipelineViewportSwizzleStateCreateInfoNV-viewportCount-01215"],
+        );
+    }
+}
@@ -130,7 +130,7 @@ impl TessellationState {
                 context: "domain_origin".into(),
                 problem: "is not `TessellationDomainOrigin::UpperLeft`".into(),
                 requires_one_of: RequiresOneOf(&[
-                    RequiresAllOf(&[Requires::APIVersion(Version::V1_2)]),
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_1)]),
                     RequiresAllOf(&[Requires::DeviceExtension("khr_maintenance2")]),
                 ]),
                 ..Default::default()
@@ -16,6 +16,14 @@ use crate::{
 };
 
 /// Trait for types that can create a [`VertexInputState`] from a [`ShaderInterface`].
+///
+/// For [`VertexBufferDescription`]s, such as those returned by [`Vertex::per_vertex`] and
+/// [`Vertex::per_instance`], each input variable of the shader is matched by name to a member of
+/// one of the descriptions. The attribute formats are checked against the shader's input types
+/// when the graphics pipeline is created.
+///
+/// [`Vertex::per_vertex`]: super::Vertex::per_vertex
+/// [`Vertex::per_instance`]: super::Vertex::per_instance
 pub unsafe trait VertexDefinition {
     /// Builds the `VertexInputState` for the provided `interface`.
     fn definition(
@@ -42,7 +50,17 @@ unsafe impl VertexDefinition for &[VertexBufferDescription] {
         let mut attributes: Vec<(u32, VertexInputAttributeDescription)> = Vec::new();
 
         for element in interface.elements() {
-            let name = element.name.as_ref().unwrap();
+            let name = element.name.as_ref().ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: format!(
+                        "the shader interface contains a variable at location {} that has no \
+                        name, so it cannot be matched to an attribute in the vertex definition",
+                        element.location,
+                    )
+                    .into(),
+                    ..Default::default()
+                })
+            })?;
 
             let (infos, binding) = self
                 .iter()
@@ -42,8 +42,14 @@ pub unsafe trait Vertex: BufferContents + Sized {
     /// Returns the information about this Vertex type.
     fn per_vertex() -> VertexBufferDescription;
 
+    /// Returns the information about this Vertex type, with an input rate of one element per
+    /// instance.
     fn per_instance() -> VertexBufferDescription;
 
+    /// Returns the information about this Vertex type, with an input rate of one element per
+    /// `divisor` instances.
+    ///
+    /// See [`VertexInputRate::Instance`] for the requirements on `divisor`.
     fn per_instance_with_divisor(divisor: u32) -> VertexBufferDescription;
 }
 
@@ -59,6 +65,7 @@ pub struct VertexBufferDescription {
 }
 
 impl VertexBufferDescription {
+    /// Returns a copy of `self` with an input rate of one element per vertex.
     #[inline]
     pub fn per_vertex(self) -> VertexBufferDescription {
         let VertexBufferDescription {
@@ -71,11 +78,15 @@ impl VertexBufferDescription {
         }
     }
 
+    /// Returns a copy of `self` with an input rate of one element per instance.
     #[inline]
     pub fn per_instance(self) -> VertexBufferDescription {
         self.per_instance_with_divisor(1)
     }
 
+    /// Returns a copy of `self` with an input rate of one element per `divisor` instances.
+    ///
+    /// See [`VertexInputRate::Instance`] for the requirements on `divisor`.
     #[inline]
     pub fn per_instance_with_divisor(self, divisor: u32) -> VertexBufferDescription {
         let VertexBufferDescription {
@@ -115,7 +126,7 @@ impl VertexMemberInfo {
 #[cfg(test)]
 mod tests {
     use crate::format::Format;
-    use crate::pipeline::graphics::vertex_input::Vertex;
+    use crate::pipeline::graphics::vertex_input::{Vertex, VertexInputRate};
 
     use bytemuck::{Pod, Zeroable};
 
@@ -152,4 +163,25 @@ mod tests {
         assert_eq!(unorm.format, Format::R8_UNORM);
         assert_eq!(unorm.num_elements, 1);
     }
+
+    #[test]
+    fn derive_vertex_divisor() {
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, Default, Zeroable, Pod, Vertex)]
+        struct TestVertex {
+            #[format(R32G32_SFLOAT)]
+            offset: [f32; 2],
+        }
+
+        let info = TestVertex::per_instance_with_divisor(4);
+        assert_eq!(info.stride, 8);
+        assert!(matches!(
+            info.input_rate,
+            VertexInputRate::Instance { divisor: 4 }
+        ));
+        assert!(matches!(
+            info.per_vertex().input_rate,
+            VertexInputRate::Vertex
+        ));
+    }
 }
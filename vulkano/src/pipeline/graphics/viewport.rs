@@ -50,6 +50,7 @@
 
 use crate::{
     device::Device,
+    macros::vulkan_enum,
     pipeline::{PartialStateMode, StateMode},
     Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version,
 };
@@ -93,6 +94,20 @@ pub struct ViewportState {
     /// The default value is `PartialStateMode::Fixed` with no scissors.
     pub scissors: PartialStateMode<SmallVec<[Scissor; 1]>, StateMode<u32>>,
 
+    /// Specifies how the coordinates of primitives are swizzled for each viewport, before
+    /// clipping and the viewport transform are applied.
+    ///
+    /// If not empty, the [`nv_viewport_swizzle`](crate::device::DeviceExtensions::nv_viewport_swizzle)
+    /// extension must be enabled on the device, and there must be at least one element for each
+    /// viewport.
+    /// If the number of viewports is dynamic, the number set with
+    /// [`set_viewport_with_count`] must not exceed the length.
+    ///
+    /// The default value is empty.
+    ///
+    /// [`set_viewport_with_count`]: crate::command_buffer::AutoCommandBufferBuilder::set_viewport_with_count
+    pub swizzles: SmallVec<[ViewportSwizzle; 1]>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -103,6 +118,7 @@ impl ViewportState {
         Self {
             viewports: PartialStateMode::Fixed(SmallVec::new()),
             scissors: PartialStateMode::Fixed(SmallVec::new()),
+            swizzles: SmallVec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -115,6 +131,7 @@ impl ViewportState {
         Self {
             viewports: PartialStateMode::Fixed(viewports),
             scissors: PartialStateMode::Fixed(scissors),
+            swizzles: SmallVec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -127,6 +144,7 @@ impl ViewportState {
         Self {
             viewports: PartialStateMode::Fixed(viewports),
             scissors: PartialStateMode::Fixed(scissors),
+            swizzles: SmallVec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -138,6 +156,7 @@ impl ViewportState {
         Self {
             viewports: PartialStateMode::Dynamic(StateMode::Fixed(1)),
             scissors: PartialStateMode::Fixed(smallvec![Scissor::irrelevant(); 1]),
+            swizzles: SmallVec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -148,6 +167,7 @@ impl ViewportState {
         Self {
             viewports: PartialStateMode::Dynamic(StateMode::Fixed(count)),
             scissors: PartialStateMode::Dynamic(StateMode::Fixed(count)),
+            swizzles: SmallVec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -158,6 +178,7 @@ impl ViewportState {
         Self {
             viewports: PartialStateMode::Dynamic(StateMode::Dynamic),
             scissors: PartialStateMode::Dynamic(StateMode::Dynamic),
+            swizzles: SmallVec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -186,6 +207,7 @@ impl ViewportState {
         let Self {
             viewports,
             scissors,
+            swizzles,
             _ne: _,
         } = self;
 
@@ -341,6 +363,35 @@ impl ViewportState {
             }));
         }
 
+        if !swizzles.is_empty() {
+            if !device.enabled_extensions().nv_viewport_swizzle {
+                return Err(Box::new(ValidationError {
+                    context: "swizzles".into(),
+                    problem: "is not empty".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                        "nv_viewport_swizzle",
+                    )])]),
+                    ..Default::default()
+                }));
+            }
+
+            if viewport_count != 0 && (swizzles.len() as u32) < viewport_count {
+                return Err(Box::new(ValidationError {
+                    problem: "`swizzles` is not empty, and its length is less than the \
+                        length of `viewports`"
+                        .into(),
+                    vuids: &["VUID-VkPipelineViewportSwizzleStateCreateInfoNV-viewportCount-01215"],
+                    ..Default::default()
+                }));
+            }
+
+            for (index, swizzle) in swizzles.iter().enumerate() {
+                swizzle
+                    .validate(device)
+                    .map_err(|err| err.add_context(format!("swizzles[{}]", index)))?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -354,12 +405,31 @@ impl Default for ViewportState {
 }
 
 /// State of a single viewport.
+///
+/// # Flipping the Y axis
+///
+/// In Vulkan, the Y axis of the framebuffer points downwards, unlike in OpenGL. To render with an
+/// upwards pointing Y axis without changing the projection matrix, the viewport can be given a
+/// negative height, by setting `offset[1]` to the bottom edge of the area to render to, and
+/// `extent[1]` to the negated height. For example, for a framebuffer that is 600 pixels high,
+/// `offset: [0.0, 600.0]` and `extent: [800.0, -600.0]` covers the whole framebuffer, with the
+/// Y axis flipped.
+///
+/// A negative height requires the device API version to be at least 1.1, or the
+/// [`khr_maintenance1`](crate::device::DeviceExtensions::khr_maintenance1) extension to be
+/// enabled on the device.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Viewport {
     /// Coordinates in pixels of the top-left hand corner of the viewport.
+    ///
+    /// If `extent[1]` is negative, then `offset[1]` is the coordinate of the bottom edge of the
+    /// viewport instead.
     pub offset: [f32; 2],
 
     /// Dimensions in pixels of the viewport.
+    ///
+    /// `extent[0]` must be greater than zero. `extent[1]` can be negative to flip the Y axis,
+    /// in which case the viewport extends upwards from `offset[1]`.
     pub extent: [f32; 2],
 
     /// Minimum and maximum values of the depth.
@@ -575,3 +645,108 @@ impl From<ash::vk::Rect2D> for Scissor {
         }
     }
 }
+
+/// Specifies how the coordinates of primitives are swizzled for a single viewport.
+///
+/// The default value swizzles each coordinate to itself, making no changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ViewportSwizzle {
+    /// The coordinate that becomes the new X coordinate.
+    pub x: ViewportCoordinateSwizzle,
+
+    /// The coordinate that becomes the new Y coordinate.
+    pub y: ViewportCoordinateSwizzle,
+
+    /// The coordinate that becomes the new Z coordinate.
+    pub z: ViewportCoordinateSwizzle,
+
+    /// The coordinate that becomes the new W coordinate.
+    pub w: ViewportCoordinateSwizzle,
+}
+
+impl ViewportSwizzle {
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self { x, y, z, w } = self;
+
+        x.validate_device(device).map_err(|err| ValidationError {
+            context: "x".into(),
+            vuids: &["VUID-VkViewportSwizzleNV-x-parameter"],
+            ..ValidationError::from_requirement(err)
+        })?;
+
+        y.validate_device(device).map_err(|err| ValidationError {
+            context: "y".into(),
+            vuids: &["VUID-VkViewportSwizzleNV-y-parameter"],
+            ..ValidationError::from_requirement(err)
+        })?;
+
+        z.validate_device(device).map_err(|err| ValidationError {
+            context: "z".into(),
+            vuids: &["VUID-VkViewportSwizzleNV-z-parameter"],
+            ..ValidationError::from_requirement(err)
+        })?;
+
+        w.validate_device(device).map_err(|err| ValidationError {
+            context: "w".into(),
+            vuids: &["VUID-VkViewportSwizzleNV-w-parameter"],
+            ..ValidationError::from_requirement(err)
+        })?;
+
+        Ok(())
+    }
+}
+
+impl Default for ViewportSwizzle {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            x: ViewportCoordinateSwizzle::PositiveX,
+            y: ViewportCoordinateSwizzle::PositiveY,
+            z: ViewportCoordinateSwizzle::PositiveZ,
+            w: ViewportCoordinateSwizzle::PositiveW,
+        }
+    }
+}
+
+impl From<&ViewportSwizzle> for ash::vk::ViewportSwizzleNV {
+    #[inline]
+    fn from(val: &ViewportSwizzle) -> Self {
+        ash::vk::ViewportSwizzleNV {
+            x: val.x.into(),
+            y: val.y.into(),
+            z: val.z.into(),
+            w: val.w.into(),
+        }
+    }
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// A coordinate that is used as the source of a swizzled coordinate, optionally negated.
+    ViewportCoordinateSwizzle = ViewportCoordinateSwizzleNV(i32);
+
+    /// The X coordinate.
+    PositiveX = POSITIVE_X,
+
+    /// The X coordinate, negated.
+    NegativeX = NEGATIVE_X,
+
+    /// The Y coordinate.
+    PositiveY = POSITIVE_Y,
+
+    /// The Y coordinate, negated.
+    NegativeY = NEGATIVE_Y,
+
+    /// The Z coordinate.
+    PositiveZ = POSITIVE_Z,
+
+    /// The Z coordinate, negated.
+    NegativeZ = NEGATIVE_Z,
+
+    /// The W coordinate.
+    PositiveW = POSITIVE_W,
+
+    /// The W coordinate, negated.
+    NegativeW = NEGATIVE_W,
+}
@@ -292,10 +292,17 @@ impl PipelineLayout {
     }
 
     /// Returns whether `self` is compatible with `other` for the given number of sets.
+    ///
+    /// This is the case if both have the same push constant ranges, and the first `num_sets`
+    /// descriptor set layouts of both are compatible.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `num_sets` is greater than the number of descriptor set layouts in `self`.
     #[inline]
     pub fn is_compatible_with(&self, other: &PipelineLayout, num_sets: u32) -> bool {
         let num_sets = num_sets as usize;
-        assert!(num_sets >= self.set_layouts.len());
+        assert!(num_sets <= self.set_layouts.len());
 
         if self == other {
             return true;
@@ -310,12 +317,11 @@ impl PipelineLayout {
             None => return false,
         };
 
-        self.set_layouts
-            .iter()
-            .zip(other_sets)
-            .all(|(self_set_layout, other_set_layout)| {
+        self.set_layouts[..num_sets].iter().zip(other_sets).all(
+            |(self_set_layout, other_set_layout)| {
                 self_set_layout.is_compatible_with(other_set_layout)
-            })
+            },
+        )
     }
 
     /// Makes sure that `self` is a superset of the provided descriptor set layouts and push
@@ -902,6 +908,17 @@ pub struct PipelineDescriptorSetLayoutCreateInfo {
 impl PipelineDescriptorSetLayoutCreateInfo {
     /// Creates a new `PipelineDescriptorSetLayoutCreateInfo` from the union of the requirements of
     /// each shader stage in `stages`.
+    ///
+    /// If a binding is declared with a different array size in different stages, the largest
+    /// size is used. Bindings that are declared as runtime-sized arrays in any stage get a
+    /// `descriptor_count` of 0, which must be set to the desired number of descriptors before
+    /// creating the layouts.
+    ///
+    /// The push constant ranges of the stages are merged into as few ranges as possible: ranges
+    /// that overlap are combined into a single range that covers both, with the stages of both.
+    /// The ranges are checked against the
+    /// [`max_push_constants_size`](crate::device::Properties::max_push_constants_size) limit
+    /// when the pipeline layout is created.
     pub fn from_stages<'a>(
         stages: impl IntoIterator<Item = &'a PipelineShaderStageCreateInfo>,
     ) -> Self {
@@ -938,20 +955,12 @@ impl PipelineDescriptorSetLayoutCreateInfo {
             }
 
             if let Some(range) = &entry_point_info.push_constant_requirements {
-                if let Some(existing_range) =
-                    push_constant_ranges.iter_mut().find(|existing_range| {
-                        existing_range.offset == range.offset && existing_range.size == range.size
-                    })
-                {
-                    // If this range was already used before, add our stage to it.
-                    existing_range.stages |= range.stages;
-                } else {
-                    // If this range is new, insert it.
-                    push_constant_ranges.push(*range)
-                }
+                push_constant_ranges.push(*range);
             }
         }
 
+        let push_constant_ranges = merge_push_constant_ranges(push_constant_ranges);
+
         // Convert the descriptor binding requirements.
         let mut set_layouts =
             vec![DescriptorSetLayoutCreateInfo::default(); max_set_num as usize + 1];
@@ -1003,6 +1012,29 @@ impl PipelineDescriptorSetLayoutCreateInfo {
     }
 }
 
+/// Combines the ranges that overlap into a single range with the stages of both, so that every
+/// byte is covered by at most one range. The returned ranges are sorted by offset.
+fn merge_push_constant_ranges(mut ranges: Vec<PushConstantRange>) -> Vec<PushConstantRange> {
+    ranges.sort_unstable_by_key(|range| range.offset);
+    let mut merged_ranges: Vec<PushConstantRange> = Vec::with_capacity(ranges.len());
+
+    for range in ranges {
+        if let Some(last_range) = merged_ranges.last_mut() {
+            let last_end = last_range.offset + last_range.size;
+
+            if range.offset < last_end {
+                last_range.size = max(last_end, range.offset + range.size) - last_range.offset;
+                last_range.stages |= range.stages;
+                continue;
+            }
+        }
+
+        merged_ranges.push(range);
+    }
+
+    merged_ranges
+}
+
 #[derive(Clone, Debug)]
 pub struct IntoPipelineLayoutCreateInfoError {
     pub set_num: u32,
@@ -1028,11 +1060,16 @@ impl Error for IntoPipelineLayoutCreateInfoError {
 #[cfg(test)]
 mod tests {
 
-    use super::PipelineLayout;
+    use super::{merge_push_constant_ranges, PipelineLayout};
     use crate::{
+        descriptor_set::layout::{
+            DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
+            DescriptorType,
+        },
         pipeline::layout::{PipelineLayoutCreateInfo, PushConstantRange},
         shader::ShaderStages,
     };
+    use std::collections::BTreeMap;
 
     #[test]
     fn push_constant_ranges_disjoint() {
@@ -1199,6 +1236,100 @@ mod tests {
             assert_eq!(layout.push_constant_ranges_disjoint.as_slice(), expected);
         }
     }
+
+    #[test]
+    fn merge_push_constant_ranges_overlapping() {
+        let range = |stages, offset, size| PushConstantRange {
+            stages,
+            offset,
+            size,
+        };
+
+        // Identical ranges get the stages of both.
+        assert_eq!(
+            merge_push_constant_ranges(vec![
+                range(ShaderStages::VERTEX, 0, 16),
+                range(ShaderStages::FRAGMENT, 0, 16),
+            ]),
+            [range(ShaderStages::VERTEX | ShaderStages::FRAGMENT, 0, 16)],
+        );
+
+        // Overlapping ranges are merged transitively, disjoint ranges are kept apart.
+        assert_eq!(
+            merge_push_constant_ranges(vec![
+                range(ShaderStages::COMPUTE, 64, 8),
+                range(ShaderStages::FRAGMENT, 12, 20),
+                range(ShaderStages::VERTEX, 0, 16),
+                range(ShaderStages::GEOMETRY, 28, 4),
+            ]),
+            [
+                range(
+                    ShaderStages::VERTEX | ShaderStages::FRAGMENT | ShaderStages::GEOMETRY,
+                    0,
+                    32,
+                ),
+                range(ShaderStages::COMPUTE, 64, 8),
+            ],
+        );
+
+        // Adjacent ranges do not overlap.
+        assert_eq!(
+            merge_push_constant_ranges(vec![
+                range(ShaderStages::FRAGMENT, 16, 16),
+                range(ShaderStages::VERTEX, 0, 16),
+            ]),
+            [
+                range(ShaderStages::VERTEX, 0, 16),
+                range(ShaderStages::FRAGMENT, 16, 16),
+            ],
+        );
+    }
+
+    #[test]
+    fn is_compatible_with() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let set_layout = |descriptor_type| {
+            DescriptorSetLayout::new(
+                device.clone(),
+                DescriptorSetLayoutCreateInfo {
+                    bindings: BTreeMap::from([(
+                        0,
+                        DescriptorSetLayoutBinding {
+                            stages: ShaderStages::all_graphics(),
+                            ..DescriptorSetLayoutBinding::descriptor_type(descriptor_type)
+                        },
+                    )]),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+        let pipeline_layout = |set_layouts| {
+            PipelineLayout::new(
+                device.clone(),
+                PipelineLayoutCreateInfo {
+                    set_layouts,
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        let layout_a = pipeline_layout(vec![
+            set_layout(DescriptorType::UniformBuffer),
+            set_layout(DescriptorType::StorageBuffer),
+        ]);
+        let layout_b = pipeline_layout(vec![
+            set_layout(DescriptorType::UniformBuffer),
+            set_layout(DescriptorType::SampledImage),
+        ]);
+
+        // Set 0 is identically defined, set 1 is not.
+        assert!(layout_a.is_compatible_with(&layout_b, 0));
+        assert!(layout_a.is_compatible_with(&layout_b, 1));
+        assert!(!layout_a.is_compatible_with(&layout_b, 2));
+    }
 }
 
 /* TODO: restore
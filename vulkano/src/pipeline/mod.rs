@@ -19,9 +19,15 @@
 
 pub use self::{compute::ComputePipeline, graphics::GraphicsPipeline, layout::PipelineLayout};
 use crate::{
-    device::{Device, DeviceOwned},
+    device::{
+        physical::{ShaderFloatControlsIndependence, SubgroupFeatures},
+        Device, DeviceOwned,
+    },
     macros::{vulkan_bitflags, vulkan_enum},
-    shader::{DescriptorBindingRequirements, EntryPoint, ShaderStage, SpecializationConstant},
+    shader::{
+        DescriptorBindingRequirements, EntryPoint, FloatControls, LocalSize, ShaderExecution,
+        ShaderModuleIdentifier, ShaderStage, SpecializationConstant,
+    },
     Requires, RequiresAllOf, RequiresOneOf, ValidationError,
 };
 use ahash::HashMap;
@@ -29,6 +35,7 @@ use std::sync::Arc;
 
 pub mod cache;
 pub mod compute;
+pub mod executable;
 pub mod graphics;
 pub mod layout;
 
@@ -116,13 +123,20 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_device_group)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// If the pipeline would need to be compiled, because it was not found in the pipeline
+    /// cache, then pipeline creation fails with [`VulkanError::PipelineCompileRequired`] instead
+    /// of compiling the pipeline. You can then choose to create the pipeline again without this
+    /// flag, for example on a background thread.
+    ///
+    /// The [`pipeline_creation_cache_control`] feature must be enabled on the device.
+    ///
+    /// [`VulkanError::PipelineCompileRequired`]: crate::VulkanError::PipelineCompileRequired
+    /// [`pipeline_creation_cache_control`]: crate::device::Features::pipeline_creation_cache_control
     FAIL_ON_PIPELINE_COMPILE_REQUIRED = FAIL_ON_PIPELINE_COMPILE_REQUIRED
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_3)]),
         RequiresAllOf([DeviceExtension(ext_pipeline_creation_cache_control)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -201,24 +215,35 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(nv_ray_tracing)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The implementation will capture statistics about the executables of the pipeline, which
+    /// can then be retrieved with the `executable_statistics` method of the pipeline.
+    ///
+    /// Capturing statistics may reduce the performance of pipeline creation.
     CAPTURE_STATISTICS = CAPTURE_STATISTICS_KHR
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(khr_pipeline_executable_properties)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    CAPTURE_INTERNAL_REPRESENTATIONS = CAPTURE_INTERNAL_REPRESENTATIONS_KHR{
-        device_extensions: [khr_pipeline_executable_properties],
-    },*/
+    /// The implementation will capture the internal representations of the executables of the
+    /// pipeline, which can then be retrieved with the `executable_internal_representations`
+    /// method of the pipeline.
+    ///
+    /// Capturing internal representations may reduce the performance of pipeline creation.
+    CAPTURE_INTERNAL_REPRESENTATIONS = CAPTURE_INTERNAL_REPRESENTATIONS_KHR
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(khr_pipeline_executable_properties)]),
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    INDIRECT_BINDABLE = INDIRECT_BINDABLE_NV{
-        device_extensions: [nv_device_generated_commands],
-    },*/
+    /// The pipeline can be used in generated commands, by passing it to
+    /// [`IndirectCommandsLayout::generated_commands_memory_requirements`] and
+    /// [`execute_generated_commands`].
+    ///
+    /// [`IndirectCommandsLayout::generated_commands_memory_requirements`]: crate::command_buffer::generated_commands::IndirectCommandsLayout::generated_commands_memory_requirements
+    /// [`execute_generated_commands`]: crate::command_buffer::AutoCommandBufferBuilder::execute_generated_commands
+    INDIRECT_BINDABLE = INDIRECT_BINDABLE_NV
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(nv_device_generated_commands)]),
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -234,19 +259,22 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// For a graphics pipeline library, the information needed to perform link time
+    /// optimization will be kept, so that pipelines that are linked from the library can use
+    /// [`LINK_TIME_OPTIMIZATION`](Self::LINK_TIME_OPTIMIZATION).
     RETAIN_LINK_TIME_OPTIMIZATION_INFO = RETAIN_LINK_TIME_OPTIMIZATION_INFO_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_graphics_pipeline_library)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// When linking graphics pipeline libraries, optimizations will be performed on the linked
+    /// pipeline as a whole. This makes linking slower, but the resulting pipeline may perform
+    /// better. It is most effective if the libraries were created with
+    /// [`RETAIN_LINK_TIME_OPTIMIZATION_INFO`](Self::RETAIN_LINK_TIME_OPTIMIZATION_INFO).
     LINK_TIME_OPTIMIZATION = LINK_TIME_OPTIMIZATION_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_graphics_pipeline_library)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -255,19 +283,27 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(nv_ray_tracing_motion_blur)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline may be used with color attachments that are in the
+    /// [`AttachmentFeedbackLoopOptimal`] layout, and read from in the same render pass.
+    ///
+    /// This flag is only allowed for graphics pipelines.
+    ///
+    /// [`AttachmentFeedbackLoopOptimal`]: crate::image::ImageLayout::AttachmentFeedbackLoopOptimal
     COLOR_ATTACHMENT_FEEDBACK_LOOP = COLOR_ATTACHMENT_FEEDBACK_LOOP_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_attachment_feedback_loop_layout)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline may be used with a depth/stencil attachment that is in the
+    /// [`AttachmentFeedbackLoopOptimal`] layout, and read from in the same render pass.
+    ///
+    /// This flag is only allowed for graphics pipelines.
+    ///
+    /// [`AttachmentFeedbackLoopOptimal`]: crate::image::ImageLayout::AttachmentFeedbackLoopOptimal
     DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP = DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_attachment_feedback_loop_layout)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -308,19 +344,51 @@ pub struct PipelineShaderStageCreateInfo {
 
     /// The shader entry point for the stage.
     ///
+    /// The stage is not specified separately, but is determined by the execution model of the
+    /// entry point, which is available as [`EntryPointInfo::execution`]. Pipeline creation
+    /// returns an error if the stage is not valid in the kind of pipeline being created, for
+    /// example if a fragment shader is provided to a compute pipeline.
+    ///
     /// There is no default value.
+    ///
+    /// [`EntryPointInfo::execution`]: crate::shader::EntryPointInfo::execution
     pub entry_point: EntryPoint,
 
     /// Values for the specialization constants in the shader, indexed by their `constant_id`.
     ///
     /// Specialization constants are constants whose value can be overridden when you create
-    /// a pipeline. When provided, they must have the same type as defined in the shader.
-    /// Constants that are not given a value here will have the default value that was specified
-    /// for them in the shader code.
+    /// a pipeline. When provided, they must be declared in the shader, and have the same type as
+    /// defined there. The declared constants and their types can be found in
+    /// [`EntryPointInfo::specialization_constants`]. Constants that are not given a value here
+    /// will have the default value that was specified for them in the shader code.
+    ///
+    /// The map entries and data that Vulkan expects are generated from the values automatically.
     ///
     /// The default value is empty.
+    ///
+    /// [`EntryPointInfo::specialization_constants`]: crate::shader::EntryPointInfo::specialization_constants
     pub specialization_info: HashMap<u32, SpecializationConstant>,
 
+    /// If `Some`, the pipeline is created using the identifier of a shader module, instead of
+    /// the shader module of `entry_point`. The module of `entry_point` is then only used to
+    /// provide reflection information about the shader, and must be the module that the
+    /// identifier was retrieved from.
+    ///
+    /// The implementation can only create the pipeline this way if it is found in the pipeline
+    /// cache. The pipeline must therefore be created with the
+    /// [`PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED`] flag, and creation returns
+    /// [`VulkanError::PipelineCompileRequired`] if the pipeline is not in the cache. In that
+    /// case, create the pipeline again with `module_identifier` set to `None`.
+    ///
+    /// If this is `Some`, then the [`shader_module_identifier`] feature must be enabled on the
+    /// device.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`VulkanError::PipelineCompileRequired`]: crate::VulkanError::PipelineCompileRequired
+    /// [`shader_module_identifier`]: crate::device::Features::shader_module_identifier
+    pub module_identifier: Option<ShaderModuleIdentifier>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -332,6 +400,7 @@ impl PipelineShaderStageCreateInfo {
             flags: PipelineShaderStageCreateFlags::empty(),
             entry_point,
             specialization_info: HashMap::default(),
+            module_identifier: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -341,6 +410,7 @@ impl PipelineShaderStageCreateInfo {
             flags,
             ref entry_point,
             ref specialization_info,
+            ref module_identifier,
             _ne: _,
         } = self;
 
@@ -352,6 +422,30 @@ impl PipelineShaderStageCreateInfo {
                 ..ValidationError::from_requirement(err)
             })?;
 
+        if let Some(module_identifier) = module_identifier {
+            if !device.enabled_features().shader_module_identifier {
+                return Err(Box::new(ValidationError {
+                    context: "module_identifier".into(),
+                    problem: "is `Some`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "shader_module_identifier",
+                    )])]),
+                    vuids: &["VUID-VkPipelineShaderStageModuleIdentifierCreateInfoEXT-pNext-06850"],
+                }));
+            }
+
+            if module_identifier.as_bytes().is_empty() {
+                return Err(Box::new(ValidationError {
+                    context: "module_identifier".into(),
+                    problem: "is empty".into(),
+                    vuids: &[
+                        "VUID-VkPipelineShaderStageModuleIdentifierCreateInfoEXT-identifierSize-06719",
+                    ],
+                    ..Default::default()
+                }));
+            }
+        }
+
         let entry_point_info = entry_point.info();
         let stage_enum = ShaderStage::from(&entry_point_info.execution);
 
@@ -452,23 +546,405 @@ impl PipelineShaderStageCreateInfo {
             ShaderStage::SubpassShading => (),
         }
 
+        if !entry_point_info.subgroup_operations.is_empty() {
+            let properties = device.physical_device().properties();
+
+            if let Some(subgroup_supported_stages) = properties.subgroup_supported_stages {
+                if !subgroup_supported_stages.contains_enum(stage_enum) {
+                    return Err(Box::new(ValidationError {
+                        context: "entry_point".into(),
+                        problem: format!(
+                            "uses subgroup operations, but the `subgroup_supported_stages` \
+                            device property does not contain `ShaderStages::{:?}`",
+                            stage_enum,
+                        )
+                        .into(),
+                        vuids: &["VUID-RuntimeSpirv-None-06343"],
+                        ..Default::default()
+                    }));
+                }
+            }
+
+            if entry_point_info
+                .subgroup_operations
+                .intersects(SubgroupFeatures::QUAD)
+                && !matches!(stage_enum, ShaderStage::Fragment | ShaderStage::Compute)
+                && properties.subgroup_quad_operations_in_all_stages == Some(false)
+            {
+                return Err(Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    problem: "uses quad subgroup operations in a stage other than \
+                        `ShaderStage::Fragment` or `ShaderStage::Compute`, but the \
+                        `subgroup_quad_operations_in_all_stages` device property is `false`"
+                        .into(),
+                    vuids: &["VUID-RuntimeSpirv-None-06342"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if !entry_point_info.float_controls.is_empty() {
+            let properties = device.physical_device().properties();
+
+            for (&width, float_controls) in &entry_point_info.float_controls {
+                let index = match width {
+                    16 => 0,
+                    32 => 1,
+                    64 => 2,
+                    _ => continue,
+                };
+
+                let modes = [
+                    (
+                        float_controls.denorm_preserve,
+                        "DenormPreserve",
+                        "shader_denorm_preserve_float",
+                        [
+                            properties.shader_denorm_preserve_float16,
+                            properties.shader_denorm_preserve_float32,
+                            properties.shader_denorm_preserve_float64,
+                        ],
+                        [
+                            &["VUID-RuntimeSpirv-shaderDenormPreserveFloat16-06296"],
+                            &["VUID-RuntimeSpirv-shaderDenormPreserveFloat32-06297"],
+                            &["VUID-RuntimeSpirv-shaderDenormPreserveFloat64-06298"],
+                        ],
+                    ),
+                    (
+                        float_controls.denorm_flush_to_zero,
+                        "DenormFlushToZero",
+                        "shader_denorm_flush_to_zero_float",
+                        [
+                            properties.shader_denorm_flush_to_zero_float16,
+                            properties.shader_denorm_flush_to_zero_float32,
+                            properties.shader_denorm_flush_to_zero_float64,
+                        ],
+                        [
+                            &["VUID-RuntimeSpirv-shaderDenormFlushToZeroFloat16-06299"],
+                            &["VUID-RuntimeSpirv-shaderDenormFlushToZeroFloat32-06300"],
+                            &["VUID-RuntimeSpirv-shaderDenormFlushToZeroFloat64-06301"],
+                        ],
+                    ),
+                    (
+                        float_controls.signed_zero_inf_nan_preserve,
+                        "SignedZeroInfNanPreserve",
+                        "shader_signed_zero_inf_nan_preserve_float",
+                        [
+                            properties.shader_signed_zero_inf_nan_preserve_float16,
+                            properties.shader_signed_zero_inf_nan_preserve_float32,
+                            properties.shader_signed_zero_inf_nan_preserve_float64,
+                        ],
+                        [
+                            &["VUID-RuntimeSpirv-shaderSignedZeroInfNanPreserveFloat16-06293"],
+                            &["VUID-RuntimeSpirv-shaderSignedZeroInfNanPreserveFloat32-06294"],
+                            &["VUID-RuntimeSpirv-shaderSignedZeroInfNanPreserveFloat64-06295"],
+                        ],
+                    ),
+                    (
+                        float_controls.rounding_mode_rte,
+                        "RoundingModeRTE",
+                        "shader_rounding_mode_rte_float",
+                        [
+                            properties.shader_rounding_mode_rte_float16,
+                            properties.shader_rounding_mode_rte_float32,
+                            properties.shader_rounding_mode_rte_float64,
+                        ],
+                        [
+                            &["VUID-RuntimeSpirv-shaderRoundingModeRTEFloat16-06302"],
+                            &["VUID-RuntimeSpirv-shaderRoundingModeRTEFloat32-06303"],
+                            &["VUID-RuntimeSpirv-shaderRoundingModeRTEFloat64-06304"],
+                        ],
+                    ),
+                    (
+                        float_controls.rounding_mode_rtz,
+                        "RoundingModeRTZ",
+                        "shader_rounding_mode_rtz_float",
+                        [
+                            properties.shader_rounding_mode_rtz_float16,
+                            properties.shader_rounding_mode_rtz_float32,
+                            properties.shader_rounding_mode_rtz_float64,
+                        ],
+                        [
+                            &["VUID-RuntimeSpirv-shaderRoundingModeRTZFloat16-06305"],
+                            &["VUID-RuntimeSpirv-shaderRoundingModeRTZFloat32-06306"],
+                            &["VUID-RuntimeSpirv-shaderRoundingModeRTZFloat64-06307"],
+                        ],
+                    ),
+                ];
+
+                for (declared, mode_name, property_name, supported, vuids) in modes {
+                    if declared && !supported[index].unwrap_or(false) {
+                        return Err(Box::new(ValidationError {
+                            context: "entry_point".into(),
+                            problem: format!(
+                                "declares the `{}` execution mode for {}-bit floating-point \
+                                types, but the `{}{}` device property is not `true`",
+                                mode_name, width, property_name, width,
+                            )
+                            .into(),
+                            vuids: vuids[index],
+                            ..Default::default()
+                        }));
+                    }
+                }
+            }
+
+            let mode_of = |width: u32, modes: [(&'static str, fn(&FloatControls) -> bool); 2]| {
+                let float_controls = entry_point_info.float_controls.get(&width)?;
+                modes
+                    .into_iter()
+                    .find_map(|(name, declared)| declared(float_controls).then_some(name))
+            };
+            let describe = |mode: Option<&str>| match mode {
+                Some(name) => format!("`{}`", name),
+                None => "no mode".to_owned(),
+            };
+
+            let denorm_modes: [(&'static str, fn(&FloatControls) -> bool); 2] = [
+                ("DenormPreserve", |c| c.denorm_preserve),
+                ("DenormFlushToZero", |c| c.denorm_flush_to_zero),
+            ];
+            let rounding_modes: [(&'static str, fn(&FloatControls) -> bool); 2] = [
+                ("RoundingModeRTE", |c| c.rounding_mode_rte),
+                ("RoundingModeRTZ", |c| c.rounding_mode_rtz),
+            ];
+
+            for (kind, modes, independence, property_name, vuids) in [
+                (
+                    "denormal",
+                    denorm_modes,
+                    properties.denorm_behavior_independence,
+                    "denorm_behavior_independence",
+                    [
+                        &["VUID-RuntimeSpirv-denormBehaviorIndependence-06289"],
+                        &["VUID-RuntimeSpirv-denormBehaviorIndependence-06290"],
+                    ],
+                ),
+                (
+                    "rounding",
+                    rounding_modes,
+                    properties.rounding_mode_independence,
+                    "rounding_mode_independence",
+                    [
+                        &["VUID-RuntimeSpirv-roundingModeIndependence-06291"],
+                        &["VUID-RuntimeSpirv-roundingModeIndependence-06292"],
+                    ],
+                ),
+            ] {
+                let [mode16, mode32, mode64] = [16, 32, 64].map(|width| mode_of(width, modes));
+
+                match independence {
+                    Some(ShaderFloatControlsIndependence::Float32Only) if mode16 != mode64 => {
+                        return Err(Box::new(ValidationError {
+                            context: "entry_point".into(),
+                            problem: format!(
+                                "declares {} for 16-bit and {} for 64-bit floating-point types \
+                                as the {} execution mode, but the `{}` device property is \
+                                `ShaderFloatControlsIndependence::Float32Only`, so they cannot \
+                                be controlled independently",
+                                describe(mode16),
+                                describe(mode64),
+                                kind,
+                                property_name,
+                            )
+                            .into(),
+                            vuids: vuids[0],
+                            ..Default::default()
+                        }));
+                    }
+                    Some(ShaderFloatControlsIndependence::None)
+                        if mode16 != mode32 || mode32 != mode64 =>
+                    {
+                        return Err(Box::new(ValidationError {
+                            context: "entry_point".into(),
+                            problem: format!(
+                                "declares {} for 16-bit, {} for 32-bit and {} for 64-bit \
+                                floating-point types as the {} execution mode, but the `{}` \
+                                device property is `ShaderFloatControlsIndependence::None`, so \
+                                they cannot be controlled independently",
+                                describe(mode16),
+                                describe(mode32),
+                                describe(mode64),
+                                kind,
+                                property_name,
+                            )
+                            .into(),
+                            vuids: vuids[1],
+                            ..Default::default()
+                        }));
+                    }
+                    _ => (),
+                }
+            }
+        }
+
         for (&constant_id, provided_value) in specialization_info {
             // Per `VkSpecializationMapEntry` spec:
             // "If a constantID value is not a specialization constant ID used in the shader,
             // that map entry does not affect the behavior of the pipeline."
-            // We *may* want to be stricter than this for the sake of catching user errors?
-            if let Some(default_value) = entry_point_info.specialization_constants.get(&constant_id)
-            {
-                // Check for equal types rather than only equal size.
-                if !provided_value.eq_type(default_value) {
-                    return Err(Box::new(ValidationError {
+            // We are stricter than this for the sake of catching user errors.
+            let default_value = entry_point_info
+                .specialization_constants
+                .get(&constant_id)
+                .ok_or_else(|| {
+                    Box::new(ValidationError {
                         problem: format!(
-                            "`specialization_info[{0}]` does not have the same type as \
-                            `entry_point.info().specialization_constants[{0}]`",
+                            "`specialization_info` contains constant ID {0}, but \
+                            `entry_point.info().specialization_constants` does not",
                             constant_id
                         )
                         .into(),
-                        vuids: &["VUID-VkSpecializationMapEntry-constantID-00776"],
+                        ..Default::default()
+                    })
+                })?;
+
+            // Check for equal types rather than only equal size.
+            if !provided_value.eq_type(default_value) {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`specialization_info[{0}]` does not have the same type as \
+                        `entry_point.info().specialization_constants[{0}]`",
+                        constant_id
+                    )
+                    .into(),
+                    vuids: &["VUID-VkSpecializationMapEntry-constantID-00776"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if let ShaderExecution::Compute(execution) = &entry_point_info.execution {
+            let local_size = execution.local_size.and_then(|local_size| {
+                let mut resolved = [0; 3];
+
+                for (resolved, size) in resolved.iter_mut().zip(local_size) {
+                    *resolved = match size {
+                        LocalSize::Literal(value) => value,
+                        LocalSize::SpecId(constant_id) => {
+                            match specialization_info.get(&constant_id).or_else(|| {
+                                entry_point_info.specialization_constants.get(&constant_id)
+                            })? {
+                                SpecializationConstant::U32(value) => *value,
+                                SpecializationConstant::I32(value) => *value as u32,
+                                _ => return None,
+                            }
+                        }
+                    };
+                }
+
+                Some(resolved)
+            });
+
+            if let Some(local_size) = local_size {
+                let properties = device.physical_device().properties();
+
+                let max_size = properties.max_compute_work_group_size;
+
+                for (dimension, size, max_size, vuids) in [
+                    (
+                        "X",
+                        local_size[0],
+                        max_size[0],
+                        &["VUID-RuntimeSpirv-x-06429"] as &[_],
+                    ),
+                    (
+                        "Y",
+                        local_size[1],
+                        max_size[1],
+                        &["VUID-RuntimeSpirv-y-06430"],
+                    ),
+                    (
+                        "Z",
+                        local_size[2],
+                        max_size[2],
+                        &["VUID-RuntimeSpirv-z-06431"],
+                    ),
+                ] {
+                    if size > max_size {
+                        return Err(Box::new(ValidationError {
+                            context: "entry_point".into(),
+                            problem: format!(
+                                "the local workgroup size in the {} dimension ({}) is greater \
+                                than the `max_compute_work_group_size` limit ({}) for that \
+                                dimension",
+                                dimension, size, max_size,
+                            )
+                            .into(),
+                            vuids,
+                            ..Default::default()
+                        }));
+                    }
+                }
+
+                let invocations = local_size.into_iter().map(u64::from).product::<u64>();
+
+                if invocations > properties.max_compute_work_group_invocations as u64 {
+                    return Err(Box::new(ValidationError {
+                        context: "entry_point".into(),
+                        problem: format!(
+                            "the product of the local workgroup size in all dimensions \
+                            ({} x {} x {} = {}) is greater than the \
+                            `max_compute_work_group_invocations` limit ({})",
+                            local_size[0],
+                            local_size[1],
+                            local_size[2],
+                            invocations,
+                            properties.max_compute_work_group_invocations,
+                        )
+                        .into(),
+                        vuids: &["VUID-RuntimeSpirv-x-06432"],
+                        ..Default::default()
+                    }));
+                }
+            }
+
+            let workgroup_memory_size =
+                entry_point_info
+                    .workgroup_memory_size
+                    .as_ref()
+                    .and_then(|workgroup_memory_size| {
+                        let mut total: u64 = 0;
+
+                        for term in &workgroup_memory_size.terms {
+                            let mut size = term.size;
+
+                            for constant_id in &term.spec_ids {
+                                let length =
+                                    match specialization_info.get(constant_id).or_else(|| {
+                                        entry_point_info.specialization_constants.get(constant_id)
+                                    })? {
+                                        SpecializationConstant::U32(value) => *value as u64,
+                                        SpecializationConstant::I32(value) => *value as u64,
+                                        SpecializationConstant::U64(value) => *value,
+                                        SpecializationConstant::I64(value) => *value as u64,
+                                        _ => return None,
+                                    };
+                                size = size.saturating_mul(length);
+                            }
+
+                            total = total.saturating_add(size);
+                        }
+
+                        Some(total)
+                    });
+
+            if let Some(workgroup_memory_size) = workgroup_memory_size {
+                let max_compute_shared_memory_size = device
+                    .physical_device()
+                    .properties()
+                    .max_compute_shared_memory_size;
+
+                if workgroup_memory_size > max_compute_shared_memory_size as u64 {
+                    return Err(Box::new(ValidationError {
+                        context: "entry_point".into(),
+                        problem: format!(
+                            "the total size of the variables in `Workgroup` storage \
+                            ({} bytes) is greater than the `max_compute_shared_memory_size` \
+                            limit ({} bytes)",
+                            workgroup_memory_size, max_compute_shared_memory_size,
+                        )
+                        .into(),
+                        vuids: &["VUID-RuntimeSpirv-Workgroup-06530"],
                         ..Default::default()
                     }));
                 }
@@ -945,3 +1421,59 @@ pub enum PartialStateMode<F, D> {
     Fixed(F),
     Dynamic(D),
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        device::physical::SubgroupFeatures,
+        pipeline::PipelineShaderStageCreateInfo,
+        shader::{reflect, spirv::Spirv, ShaderModule, ShaderModuleCreateInfo, ShaderStages},
+    };
+
+    #[test]
+    fn subgroup_operations_unsupported_stage() {
+        /*
+        #version 450
+        #extension GL_KHR_shader_subgroup_basic : require
+
+        void main() {
+            subgroupElect();
+        }
+        */
+        const MODULE: [u32; 45] = [
+            119734787, 66304, 0, 9, 0, 131089, 1, 131089, 61, 196622, 0, 1, 327695, 0, 1,
+            1852399981, 0, 131091, 2, 196641, 3, 2, 131092, 4, 262165, 5, 32, 0, 262187, 5, 6, 3,
+            327734, 2, 1, 0, 3, 131320, 7, 262477, 4, 8, 6, 65789, 65592,
+        ];
+
+        let spirv = Spirv::new(&MODULE).unwrap();
+        let entry_point_info = reflect::entry_points(&spirv).next().unwrap();
+        assert_eq!(
+            entry_point_info.subgroup_operations,
+            SubgroupFeatures::BASIC
+        );
+
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let vs = match unsafe {
+            ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE))
+        } {
+            Ok(module) => module.entry_point("main").unwrap(),
+            Err(_) => return, // Subgroup operations are not supported at all.
+        };
+
+        let supported = device
+            .physical_device()
+            .properties()
+            .subgroup_supported_stages
+            .unwrap_or_default()
+            .intersects(ShaderStages::VERTEX);
+
+        assert_eq!(
+            PipelineShaderStageCreateInfo::new(vs)
+                .validate(&device)
+                .is_ok(),
+            supported,
+        );
+    }
+}
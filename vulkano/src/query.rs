@@ -259,7 +259,8 @@ impl QueryPool {
             | QueryType::AccelerationStructureCompactedSize
             | QueryType::AccelerationStructureSerializationSize
             | QueryType::AccelerationStructureSerializationBottomLevelPointers
-            | QueryType::AccelerationStructureSize => (),
+            | QueryType::AccelerationStructureSize
+            | QueryType::PrimitivesGenerated => (),
         }
 
         Ok(())
@@ -296,7 +297,7 @@ impl QueryPool {
         match result {
             ash::vk::Result::SUCCESS => Ok(true),
             ash::vk::Result::NOT_READY => Ok(false),
-            err => Err(VulkanError::from(err)),
+            err => Err(self.device.check_lost(VulkanError::from(err))),
         }
     }
 }
@@ -392,6 +393,18 @@ impl QueryPoolCreateInfo {
                         ..ValidationError::from_requirement(err)
                     })?;
             }
+            QueryType::PrimitivesGenerated => {
+                if !device.enabled_features().primitives_generated_query {
+                    return Err(Box::new(ValidationError {
+                        context: "query_type".into(),
+                        problem: "is `QueryType::PrimitivesGenerated`".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "primitives_generated_query",
+                        )])]),
+                        ..Default::default()
+                    }));
+                }
+            }
             QueryType::Occlusion
             | QueryType::Timestamp
             | QueryType::AccelerationStructureCompactedSize
@@ -428,6 +441,11 @@ pub enum QueryType {
 
     /// Tracks statistics on pipeline invocations and their input data.
     ///
+    /// Each query produces one result element per flag that is set, ordered from the lowest to
+    /// the highest bit, which is the order in which the flags of [`QueryPipelineStatisticFlags`]
+    /// are listed. [`QueryPipelineStatisticFlags::result_index`] returns the position of a
+    /// particular statistic.
+    ///
     /// Used with the [`begin_query`] and [`end_query`] commands.
     ///
     /// [`begin_query`]: crate::command_buffer::AutoCommandBufferBuilder::begin_query
@@ -479,13 +497,22 @@ pub enum QueryType {
     ///
     /// [`write_acceleration_structures_properties`]: crate::command_buffer::AutoCommandBufferBuilder::write_acceleration_structures_properties
     AccelerationStructureSize = ash::vk::QueryType::ACCELERATION_STRUCTURE_SIZE_KHR.as_raw(),
+
+    /// Tracks the number of primitives that are generated by the last pre-rasterization shader
+    /// stage, or by the primitive assembly stage if there is no such shader.
+    ///
+    /// Used with the [`begin_query`] and [`end_query`] commands.
+    ///
+    /// [`begin_query`]: crate::command_buffer::AutoCommandBufferBuilder::begin_query
+    /// [`end_query`]: crate::command_buffer::AutoCommandBufferBuilder::end_query
+    PrimitivesGenerated = ash::vk::QueryType::PRIMITIVES_GENERATED_EXT.as_raw(),
 }
 
 impl QueryType {
     /// Returns the number of [`QueryResultElement`]s that are needed to hold the result of a
     /// single query of this type.
     ///
-    /// - For [`Occlusion`] and [`Timestamp`] queries, this returns 1.
+    /// - For [`Occlusion`], [`Timestamp`] and [`PrimitivesGenerated`] queries, this returns 1.
     /// - For [`PipelineStatistics`] queries, this returns the number of statistics flags enabled.
     ///
     /// If the results are retrieved with [`WITH_AVAILABILITY`] enabled, then an additional element
@@ -493,6 +520,7 @@ impl QueryType {
     ///
     /// [`Occlusion`]: QueryType::Occlusion
     /// [`Timestamp`]: QueryType::Timestamp
+    /// [`PrimitivesGenerated`]: QueryType::PrimitivesGenerated
     /// [`PipelineStatistics`]: QueryType::PipelineStatistics
     /// [`WITH_AVAILABILITY`]: QueryResultFlags::WITH_AVAILABILITY
     #[inline]
@@ -503,7 +531,8 @@ impl QueryType {
             | Self::AccelerationStructureCompactedSize
             | Self::AccelerationStructureSerializationSize
             | Self::AccelerationStructureSerializationBottomLevelPointers
-            | Self::AccelerationStructureSize => 1,
+            | Self::AccelerationStructureSize
+            | Self::PrimitivesGenerated => 1,
             Self::PipelineStatistics(flags) => flags.count() as DeviceSize,
         }
     }
@@ -554,6 +583,16 @@ impl QueryType {
                     });
                 }
             }
+            QueryType::PrimitivesGenerated => {
+                if !device.enabled_extensions().ext_primitives_generated_query {
+                    return Err(crate::RequirementNotMet {
+                        required_for: "QueryType::PrimitivesGenerated",
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                            Requires::DeviceExtension("ext_primitives_generated_query"),
+                        ])]),
+                    });
+                }
+            }
         }
 
         Ok(())
@@ -579,6 +618,7 @@ impl From<&QueryType> for ash::vk::QueryType {
             QueryType::AccelerationStructureSize => {
                 ash::vk::QueryType::ACCELERATION_STRUCTURE_SIZE_KHR
             }
+            QueryType::PrimitivesGenerated => ash::vk::QueryType::PRIMITIVES_GENERATED_EXT,
         }
     }
 }
@@ -622,6 +662,19 @@ vulkan_bitflags! {
                     .union(QueryPipelineStatisticFlags::TESSELLATION_EVALUATION_SHADER_INVOCATIONS),
             )
         }
+
+        /// Returns the index of the statistic `flag` within the results of a pipeline statistics
+        /// query that was created with `self`.
+        ///
+        /// Returns `None` if `flag` is not exactly one flag, or if `self` does not contain it.
+        #[inline]
+        pub const fn result_index(self, flag: QueryPipelineStatisticFlags) -> Option<usize> {
+            if flag.count() != 1 || !self.contains(flag) {
+                return None;
+            }
+
+            Some(Self(self.0 & (flag.0 - 1)).count() as usize)
+        }
     }
     = QueryPipelineStatisticFlags(u32);
 
@@ -725,7 +778,7 @@ mod tests {
     use super::QueryPoolCreateInfo;
     use crate::{
         query::{QueryPipelineStatisticFlags, QueryPool, QueryType},
-        Validated,
+        Requires, RequiresAllOf, RequiresOneOf, Validated,
     };
 
     #[test]
@@ -743,4 +796,53 @@ mod tests {
             Err(Validated::ValidationError(_)),
         ));
     }
+
+    #[test]
+    fn pipeline_statistics_result_index() {
+        let flags = QueryPipelineStatisticFlags::INPUT_ASSEMBLY_VERTICES
+            | QueryPipelineStatisticFlags::VERTEX_SHADER_INVOCATIONS
+            | QueryPipelineStatisticFlags::FRAGMENT_SHADER_INVOCATIONS;
+
+        assert_eq!(QueryType::PipelineStatistics(flags).result_len(), 3);
+        assert_eq!(
+            flags.result_index(QueryPipelineStatisticFlags::INPUT_ASSEMBLY_VERTICES),
+            Some(0),
+        );
+        assert_eq!(
+            flags.result_index(QueryPipelineStatisticFlags::VERTEX_SHADER_INVOCATIONS),
+            Some(1),
+        );
+        assert_eq!(
+            flags.result_index(QueryPipelineStatisticFlags::FRAGMENT_SHADER_INVOCATIONS),
+            Some(2),
+        );
+        assert_eq!(
+            flags.result_index(QueryPipelineStatisticFlags::CLIPPING_INVOCATIONS),
+            None,
+        );
+        assert_eq!(flags.result_index(flags), None);
+    }
+
+    #[test]
+    fn primitives_generated_feature() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_primitives_generated_query],
+            features: [],
+        );
+        match QueryPool::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count: 1,
+                ..QueryPoolCreateInfo::query_type(QueryType::PrimitivesGenerated)
+            },
+        ) {
+            Err(Validated::ValidationError(err)) => assert_eq!(
+                err.requires_one_of,
+                RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "primitives_generated_query"
+                )])]),
+            ),
+            _ => panic!(),
+        }
+    }
 }
@@ -27,6 +27,7 @@ impl RenderPass {
             ref subpasses,
             ref dependencies,
             ref correlated_view_masks,
+            ref fragment_density_map_attachment,
             _ne: _,
         } = create_info;
 
@@ -477,7 +478,7 @@ impl RenderPass {
             }
         }
 
-        let create_info = ash::vk::RenderPassCreateInfo2 {
+        let mut create_info = ash::vk::RenderPassCreateInfo2 {
             flags: flags.into(),
             attachment_count: attachments_vk.len() as u32,
             p_attachments: if attachments_vk.is_empty() {
@@ -502,6 +503,23 @@ impl RenderPass {
             ..Default::default()
         };
 
+        let mut fragment_density_map_create_info_vk = None;
+
+        if let Some(fragment_density_map_attachment) = fragment_density_map_attachment {
+            let next = fragment_density_map_create_info_vk.insert(
+                ash::vk::RenderPassFragmentDensityMapCreateInfoEXT {
+                    fragment_density_map_attachment: ash::vk::AttachmentReference {
+                        attachment: fragment_density_map_attachment.attachment,
+                        layout: fragment_density_map_attachment.layout.into(),
+                    },
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = create_info.p_next;
+            create_info.p_next = next as *const _ as *const _;
+        }
+
         Ok({
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -538,6 +556,7 @@ impl RenderPass {
             ref subpasses,
             ref dependencies,
             ref correlated_view_masks,
+            ref fragment_density_map_attachment,
             _ne: _,
         } = create_info;
 
@@ -881,6 +900,25 @@ impl RenderPass {
             create_info_vk.p_next = next as *const _ as *const _;
         }
 
+        /* Fragment density map */
+
+        let mut fragment_density_map_create_info_vk = None;
+
+        if let Some(fragment_density_map_attachment) = fragment_density_map_attachment {
+            let next = fragment_density_map_create_info_vk.insert(
+                ash::vk::RenderPassFragmentDensityMapCreateInfoEXT {
+                    fragment_density_map_attachment: ash::vk::AttachmentReference {
+                        attachment: fragment_density_map_attachment.attachment,
+                        layout: fragment_density_map_attachment.layout.into(),
+                    },
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = next as *const _ as *const _;
+        }
+
         Ok({
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -154,6 +154,24 @@ impl Framebuffer {
                 }));
             }
 
+            if attachment_use.fragment_density_map_attachment
+                && !image_view
+                    .usage()
+                    .intersects(ImageUsage::FRAGMENT_DENSITY_MAP)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`render_pass` uses `create_info.attachments[{}]` as \
+                        a fragment density map attachment, but it was not created with the \
+                        `ImageUsage::FRAGMENT_DENSITY_MAP` usage",
+                        index,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkFramebufferCreateInfo-pAttachments-02554"],
+                    ..Default::default()
+                }));
+            }
+
             if image_view.format() != attachment_desc.format {
                 return Err(Box::new(ValidationError {
                     problem: format!(
@@ -235,6 +253,37 @@ impl Framebuffer {
                 }
             }
 
+            if attachment_use.fragment_density_map_attachment {
+                let texel_size = render_pass
+                    .device()
+                    .physical_device()
+                    .properties()
+                    .max_fragment_density_texel_size
+                    .unwrap_or([1, 1]);
+                let min_extent = [
+                    extent[0].div_ceil(texel_size[0]),
+                    extent[1].div_ceil(texel_size[1]),
+                ];
+
+                if image_view_extent[0] < min_extent[0] || image_view_extent[1] < min_extent[1] {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`render_pass` uses `create_info.attachments[{}]` as a fragment \
+                            density map attachment, but its width and height are less than \
+                            `create_info.extent` divided by the \
+                            `max_fragment_density_texel_size` property, rounded up",
+                            index,
+                        )
+                        .into(),
+                        vuids: &[
+                            "VUID-VkFramebufferCreateInfo-pAttachments-02555",
+                            "VUID-VkFramebufferCreateInfo-pAttachments-02556",
+                        ],
+                        ..Default::default()
+                    }));
+                }
+            }
+
             if render_pass.views_used() != 0 && layers != 1 {
                 return Err(Box::new(ValidationError {
                     problem: "`render_pass` has multiview enabled, but \
@@ -494,7 +543,14 @@ impl FramebufferCreateInfo {
                 u32::MAX
             };
 
-            for image_view in attachments.iter() {
+            for (image_view, attachment_use) in attachments.iter().zip(&render_pass.attachment_use)
+            {
+                // A fragment density map covers the framebuffer with one texel per region, so
+                // it is smaller than the other attachments.
+                if attachment_use.fragment_density_map_attachment {
+                    continue;
+                }
+
                 let image_view_extent = image_view.image().extent();
                 let image_view_array_layers =
                     image_view.subresource_range().array_layers.len() as u32;
@@ -660,13 +716,15 @@ vulkan_bitflags! {
 #[cfg(test)]
 mod tests {
     use crate::{
-        format::Format,
-        image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage},
+        format::{Format, FormatFeatures},
+        image::{view::ImageView, Image, ImageCreateInfo, ImageLayout, ImageType, ImageUsage},
         memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
         render_pass::{
+            AttachmentDescription, AttachmentLoadOp, AttachmentReference, AttachmentStoreOp,
             Framebuffer, FramebufferCreateInfo, RenderPass, RenderPassCreateInfo,
             SubpassDescription,
         },
+        Validated,
     };
 
     #[test]
@@ -1129,4 +1187,113 @@ mod tests {
 
         assert!(Framebuffer::new(render_pass, FramebufferCreateInfo::default()).is_err());
     }
+
+    #[test]
+    fn fragment_density_map_attachment() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_fragment_density_map],
+            features: [fragment_density_map],
+        );
+
+        if !device
+            .physical_device()
+            .format_properties(Format::R8G8_UNORM)
+            .unwrap()
+            .potential_format_features()
+            .intersects(FormatFeatures::FRAGMENT_DENSITY_MAP)
+        {
+            return; // test ignored
+        }
+
+        let render_pass = RenderPass::new(
+            device.clone(),
+            RenderPassCreateInfo {
+                attachments: vec![AttachmentDescription {
+                    format: Format::R8G8_UNORM,
+                    load_op: AttachmentLoadOp::Load,
+                    store_op: AttachmentStoreOp::DontCare,
+                    initial_layout: ImageLayout::FragmentDensityMapOptimal,
+                    final_layout: ImageLayout::FragmentDensityMapOptimal,
+                    ..Default::default()
+                }],
+                subpasses: vec![SubpassDescription::default()],
+                fragment_density_map_attachment: Some(AttachmentReference {
+                    attachment: 0,
+                    layout: ImageLayout::FragmentDensityMapOptimal,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let texel_size = device
+            .physical_device()
+            .properties()
+            .max_fragment_density_texel_size
+            .unwrap();
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+        let density_map = |usage| {
+            ImageView::new_default(
+                Image::new(
+                    &memory_allocator,
+                    ImageCreateInfo {
+                        image_type: ImageType::Dim2d,
+                        format: Format::R8G8_UNORM,
+                        extent: [4, 4, 1],
+                        usage,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo::default(),
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        };
+
+        match Framebuffer::new(
+            render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments: vec![density_map(ImageUsage::SAMPLED)],
+                extent: [4 * texel_size[0], 4 * texel_size[1]],
+                ..Default::default()
+            },
+        ) {
+            Err(Validated::ValidationError(err)) => {
+                assert_eq!(
+                    err.vuids,
+                    &["VUID-VkFramebufferCreateInfo-pAttachments-02554"]
+                )
+            }
+            _ => panic!(),
+        }
+
+        match Framebuffer::new(
+            render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments: vec![density_map(ImageUsage::FRAGMENT_DENSITY_MAP)],
+                extent: [4 * texel_size[0] + 1, 4 * texel_size[1]],
+                ..Default::default()
+            },
+        ) {
+            Err(Validated::ValidationError(err)) => assert_eq!(
+                err.vuids,
+                &[
+                    "VUID-VkFramebufferCreateInfo-pAttachments-02555",
+                    "VUID-VkFramebufferCreateInfo-pAttachments-02556",
+                ],
+            ),
+            _ => panic!(),
+        }
+
+        let _ = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![density_map(ImageUsage::FRAGMENT_DENSITY_MAP)],
+                extent: [4 * texel_size[0], 4 * texel_size[1]],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    }
 }
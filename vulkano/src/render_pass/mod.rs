@@ -118,6 +118,7 @@ pub struct RenderPass {
     subpasses: Vec<SubpassDescription>,
     dependencies: Vec<SubpassDependency>,
     correlated_view_masks: Vec<u32>,
+    fragment_density_map_attachment: Option<AttachmentReference>,
 
     attachment_use: Vec<AttachmentUse>,
     granularity: [u32; 2],
@@ -213,6 +214,7 @@ impl RenderPass {
             subpasses,
             dependencies,
             correlated_view_masks,
+            fragment_density_map_attachment,
             _ne: _,
         } = create_info;
 
@@ -261,6 +263,11 @@ impl RenderPass {
             views_used = max(views_used, u32::BITS - view_mask.leading_zeros());
         }
 
+        if let Some(fragment_density_map_attachment) = &fragment_density_map_attachment {
+            attachment_use[fragment_density_map_attachment.attachment as usize]
+                .fragment_density_map_attachment = true;
+        }
+
         Arc::new(RenderPass {
             handle,
             device: InstanceOwnedDebugWrapper(device),
@@ -271,6 +278,7 @@ impl RenderPass {
             subpasses,
             dependencies,
             correlated_view_masks,
+            fragment_density_map_attachment,
 
             attachment_use,
             granularity,
@@ -319,6 +327,12 @@ impl RenderPass {
         &self.correlated_view_masks
     }
 
+    /// Returns the fragment density map attachment of the render pass.
+    #[inline]
+    pub fn fragment_density_map_attachment(&self) -> Option<&AttachmentReference> {
+        self.fragment_density_map_attachment.as_ref()
+    }
+
     /// If the render pass has multiview enabled, returns the number of views used by the render
     /// pass. Returns 0 if multiview is not enabled.
     #[inline]
@@ -362,6 +376,7 @@ impl RenderPass {
             subpasses: subpasses1,
             dependencies: dependencies1,
             correlated_view_masks: correlated_view_masks1,
+            fragment_density_map_attachment: fragment_density_map_attachment1,
 
             attachment_use: _,
             granularity: _,
@@ -377,6 +392,7 @@ impl RenderPass {
             subpasses: subpasses2,
             dependencies: dependencies2,
             correlated_view_masks: correlated_view_masks2,
+            fragment_density_map_attachment: fragment_density_map_attachment2,
 
             attachment_use: _,
             granularity: _,
@@ -598,6 +614,21 @@ impl RenderPass {
             return false;
         }
 
+        match (
+            fragment_density_map_attachment1,
+            fragment_density_map_attachment2,
+        ) {
+            (None, None) => (),
+            (Some(attachment_ref1), Some(attachment_ref2)) => {
+                if attachment_ref1.attachment != attachment_ref2.attachment
+                    || attachment_ref1.layout != attachment_ref2.layout
+                {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+
         true
     }
 
@@ -806,6 +837,22 @@ pub struct RenderPassCreateInfo {
     /// The default value is empty.
     pub correlated_view_masks: Vec<u32>,
 
+    /// An attachment that is read by the implementation to determine the density of fragments
+    /// in each region of the render area, instead of shading every pixel at full rate.
+    ///
+    /// The attachment must not be used by any subpass, and its `layout` must be
+    /// [`ImageLayout::FragmentDensityMapOptimal`] or [`ImageLayout::General`]. Its `format` must
+    /// support [`FormatFeatures::FRAGMENT_DENSITY_MAP`], its `load_op` must be
+    /// [`AttachmentLoadOp::Load`] or [`AttachmentLoadOp::DontCare`], and its `store_op` must be
+    /// [`AttachmentStoreOp::DontCare`].
+    ///
+    /// If this is `Some`, the
+    /// [`fragment_density_map`](crate::device::Features::fragment_density_map) feature must be
+    /// enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub fragment_density_map_attachment: Option<AttachmentReference>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -818,6 +865,7 @@ impl Default for RenderPassCreateInfo {
             subpasses: Vec::new(),
             dependencies: Vec::new(),
             correlated_view_masks: Vec::new(),
+            fragment_density_map_attachment: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -831,6 +879,7 @@ impl RenderPassCreateInfo {
             ref subpasses,
             ref dependencies,
             ref correlated_view_masks,
+            ref fragment_density_map_attachment,
             _ne: _,
         } = self;
 
@@ -1626,6 +1675,129 @@ impl RenderPassCreateInfo {
             })?;
         }
 
+        if let Some(attachment_ref) = fragment_density_map_attachment {
+            // No VUID, but the description of the `fragment_density_map` feature requires this.
+            if !device.enabled_features().fragment_density_map {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_density_map_attachment".into(),
+                    problem: "is `Some`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "fragment_density_map",
+                    )])]),
+                    ..Default::default()
+                }));
+            }
+
+            attachment_ref
+                .validate(device)
+                .map_err(|err| err.add_context("fragment_density_map_attachment"))?;
+
+            let &AttachmentReference {
+                attachment,
+                layout,
+                stencil_layout: _,
+                aspects: _,
+                _ne: _,
+            } = attachment_ref;
+
+            let attachment_desc = attachments.get(attachment as usize).ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "`fragment_density_map_attachment.attachment` is not less than the \
+                        length of `attachments`"
+                        .into(),
+                    vuids: &[
+                        "VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02547",
+                    ],
+                    ..Default::default()
+                })
+            })?;
+
+            if let Some(subpass_index) = subpasses.iter().position(|subpass| {
+                subpass
+                    .input_attachments
+                    .iter()
+                    .chain(&subpass.color_attachments)
+                    .chain(&subpass.color_resolve_attachments)
+                    .chain([
+                        &subpass.depth_stencil_attachment,
+                        &subpass.depth_stencil_resolve_attachment,
+                    ])
+                    .flatten()
+                    .any(|other_ref| other_ref.attachment == attachment)
+                    || subpass.preserve_attachments.contains(&attachment)
+            }) {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`fragment_density_map_attachment.attachment` is also used by \
+                        `subpasses[{}]`",
+                        subpass_index
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02548",
+                    ],
+                    ..Default::default()
+                }));
+            }
+
+            if !matches!(
+                layout,
+                ImageLayout::FragmentDensityMapOptimal | ImageLayout::General
+            ) {
+                return Err(Box::new(ValidationError {
+                    context: "fragment_density_map_attachment.layout".into(),
+                    problem: "is not `ImageLayout::FragmentDensityMapOptimal` or \
+                        `ImageLayout::General`"
+                        .into(),
+                    vuids: &[
+                        "VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02549",
+                    ],
+                    ..Default::default()
+                }));
+            }
+
+            if !matches!(
+                attachment_desc.load_op,
+                AttachmentLoadOp::Load | AttachmentLoadOp::DontCare
+            ) {
+                return Err(Box::new(ValidationError {
+                    problem: "the `load_op` of the attachment referenced by \
+                        `fragment_density_map_attachment` is not `AttachmentLoadOp::Load` or \
+                        `AttachmentLoadOp::DontCare`"
+                        .into(),
+                    vuids: &[
+                        "VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02550",
+                    ],
+                    ..Default::default()
+                }));
+            }
+
+            if attachment_desc.store_op != AttachmentStoreOp::DontCare {
+                return Err(Box::new(ValidationError {
+                    problem: "the `store_op` of the attachment referenced by \
+                        `fragment_density_map_attachment` is not `AttachmentStoreOp::DontCare`"
+                        .into(),
+                    vuids: &[
+                        "VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02551",
+                    ],
+                    ..Default::default()
+                }));
+            }
+
+            if !attachment_potential_format_features[attachment as usize]
+                .intersects(FormatFeatures::FRAGMENT_DENSITY_MAP)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the format of the attachment referenced by \
+                        `fragment_density_map_attachment` does not support the \
+                        `FormatFeatures::FRAGMENT_DENSITY_MAP` format feature"
+                        .into(),
+                    vuids: &["VUID-VkRenderPassCreateInfo2-fragmentDensityMapAttachment-06472"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         Ok(())
     }
 }
@@ -1825,6 +1997,30 @@ impl AttachmentDescription {
             }));
         }
 
+        if !device.enabled_features().attachment_feedback_loop_layout {
+            if initial_layout == ImageLayout::AttachmentFeedbackLoopOptimal {
+                return Err(Box::new(ValidationError {
+                    context: "initial_layout".into(),
+                    problem: "is `ImageLayout::AttachmentFeedbackLoopOptimal`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "attachment_feedback_loop_layout",
+                    )])]),
+                    vuids: &["VUID-VkAttachmentDescription2-attachmentFeedbackLoopLayout-07309"],
+                }));
+            }
+
+            if final_layout == ImageLayout::AttachmentFeedbackLoopOptimal {
+                return Err(Box::new(ValidationError {
+                    context: "final_layout".into(),
+                    problem: "is `ImageLayout::AttachmentFeedbackLoopOptimal`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "attachment_feedback_loop_layout",
+                    )])]),
+                    vuids: &["VUID-VkAttachmentDescription2-attachmentFeedbackLoopLayout-07310"],
+                }));
+            }
+        }
+
         if !device.enabled_features().separate_depth_stencil_layouts {
             if matches!(
                 initial_layout,
@@ -1885,6 +2081,36 @@ impl AttachmentDescription {
                 })?;
         }
 
+        if !(device.api_version() >= Version::V1_3
+            || device.enabled_extensions().ext_load_store_op_none
+            || device.enabled_extensions().qcom_render_pass_store_ops)
+        {
+            // `khr_dynamic_rendering` only allows `AttachmentStoreOp::None` for dynamic rendering.
+            let requires_one_of = RequiresOneOf(&[
+                RequiresAllOf(&[Requires::APIVersion(Version::V1_3)]),
+                RequiresAllOf(&[Requires::DeviceExtension("ext_load_store_op_none")]),
+                RequiresAllOf(&[Requires::DeviceExtension("qcom_render_pass_store_ops")]),
+            ]);
+
+            if store_op == AttachmentStoreOp::None {
+                return Err(Box::new(ValidationError {
+                    context: "store_op".into(),
+                    problem: "is `AttachmentStoreOp::None`".into(),
+                    requires_one_of,
+                    vuids: &["VUID-VkAttachmentDescription2-storeOp-parameter"],
+                }));
+            }
+
+            if stencil_store_op == Some(AttachmentStoreOp::None) {
+                return Err(Box::new(ValidationError {
+                    context: "stencil_store_op".into(),
+                    problem: "is `AttachmentStoreOp::None`".into(),
+                    requires_one_of,
+                    vuids: &["VUID-VkAttachmentDescription2-stencilStoreOp-parameter"],
+                }));
+            }
+        }
+
         if stencil_initial_layout.is_some() != stencil_final_layout.is_some() {
             return Err(Box::new(ValidationError {
                 problem: "`stencil_initial_layout` and `stencil_final_layout` are not either both \
@@ -2383,6 +2609,63 @@ impl SubpassDescription {
                 ..ValidationError::from_requirement(err)
             })?;
 
+        if flags.intersects(SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_color_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains \
+                    `SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_color_attachment_access",
+                )])]),
+                vuids: &[
+                    "VUID-VkSubpassDescription2-rasterizationOrderColorAttachmentAccess-06499",
+                ],
+            }));
+        }
+
+        if flags.intersects(SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_depth_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains \
+                    `SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_depth_attachment_access",
+                )])]),
+                vuids: &[
+                    "VUID-VkSubpassDescription2-rasterizationOrderDepthAttachmentAccess-06500",
+                ],
+            }));
+        }
+
+        if flags.intersects(SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS)
+            && !device
+                .enabled_features()
+                .rasterization_order_stencil_attachment_access
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains \
+                    `SubpassDescriptionFlags::RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "rasterization_order_stencil_attachment_access",
+                )])]),
+                vuids: &[
+                    "VUID-VkSubpassDescription2-rasterizationOrderStencilAttachmentAccess-06501",
+                ],
+            }));
+        }
+
         if color_attachments.len() as u32 > properties.max_color_attachments {
             return Err(Box::new(ValidationError {
                 context: "color_attachments".into(),
@@ -3079,23 +3362,44 @@ vulkan_bitflags! {
         device_extensions: [qcom_render_pass_shader_resolve],
     }, */
 
-    /* TODO: enable
-    // TODO: document
-    RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS_EXT {
-        device_extensions: [ext_rasterization_order_attachment_access, arm_rasterization_order_attachment_access],
-    }, */
+    /// Graphics pipelines used in this subpass may access the color attachments of the subpass
+    /// as input attachments in rasterization order. This means that a fragment shader can read
+    /// the values written to the attachments by fragments earlier in rasterization order, without
+    /// needing a pipeline barrier.
+    ///
+    /// The [`rasterization_order_color_attachment_access`] feature must be enabled on the device.
+    ///
+    /// [`rasterization_order_color_attachment_access`]: crate::device::Features::rasterization_order_color_attachment_access
+    RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_COLOR_ACCESS_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
+        RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS_EXT {
-        device_extensions: [ext_rasterization_order_attachment_access, arm_rasterization_order_attachment_access],
-    }, */
+    /// Graphics pipelines used in this subpass may access the depth aspect of the depth/stencil
+    /// attachment of the subpass as an input attachment in rasterization order.
+    ///
+    /// The [`rasterization_order_depth_attachment_access`] feature must be enabled on the device.
+    ///
+    /// [`rasterization_order_depth_attachment_access`]: crate::device::Features::rasterization_order_depth_attachment_access
+    RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_DEPTH_ACCESS_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
+        RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS_EXT {
-        device_extensions: [ext_rasterization_order_attachment_access, arm_rasterization_order_attachment_access],
-    }, */
+    /// Graphics pipelines used in this subpass may access the stencil aspect of the
+    /// depth/stencil attachment of the subpass as an input attachment in rasterization order.
+    ///
+    /// The [`rasterization_order_stencil_attachment_access`] feature must be enabled on the
+    /// device.
+    ///
+    /// [`rasterization_order_stencil_attachment_access`]: crate::device::Features::rasterization_order_stencil_attachment_access
+    RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS = RASTERIZATION_ORDER_ATTACHMENT_STENCIL_ACCESS_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_rasterization_order_attachment_access)]),
+        RequiresAllOf([DeviceExtension(arm_rasterization_order_attachment_access)]),
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -3198,6 +3502,19 @@ impl AttachmentReference {
             }));
         }
 
+        if layout == ImageLayout::AttachmentFeedbackLoopOptimal
+            && !device.enabled_features().attachment_feedback_loop_layout
+        {
+            return Err(Box::new(ValidationError {
+                context: "layout".into(),
+                problem: "is `ImageLayout::AttachmentFeedbackLoopOptimal`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "attachment_feedback_loop_layout",
+                )])]),
+                vuids: &["VUID-VkAttachmentReference2-attachmentFeedbackLoopLayout-07311"],
+            }));
+        }
+
         if matches!(
             layout,
             ImageLayout::DepthAttachmentOptimal
@@ -3628,12 +3945,15 @@ vulkan_enum! {
     /// instead.
     DontCare = DONT_CARE,
 
-    /* TODO: enable
-    // TODO: document
+    /// The attachment is not accessed at all at the start of the subpass, and its content is
+    /// preserved.
+    ///
+    /// Unlike `Load`, this does not require the attachment to be read, so it can be used for
+    /// attachments that are only read by the subpasses, or not used at all.
     None = NONE_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_load_store_op_none)]),
-    ]),*/
+    ]),
 }
 
 vulkan_enum! {
@@ -3659,12 +3979,24 @@ vulkan_enum! {
     /// image will be undefined.
     DontCare = DONT_CARE,
 
-    /* TODO: enable
-    // TODO: document
-    None = NONE {
-        api_version: V1_3,
-        device_extensions: [khr_dynamic_rendering, ext_load_store_op_none, qcom_render_pass_store_ops],
-    },*/
+    /// The attachment is not accessed at all after the subpasses have completed. If the
+    /// attachment was not written to, its content is preserved, otherwise it becomes undefined.
+    ///
+    /// Unlike `DontCare`, the content is not explicitly discarded, and unlike `Store`, it is not
+    /// written back to memory. This is useful for attachments that are only read by the
+    /// subpasses, such as a read-only depth attachment, as it avoids a needless store on tiled
+    /// implementations.
+    ///
+    /// In a render pass, as opposed to dynamic rendering, the
+    /// [`khr_dynamic_rendering`](crate::device::DeviceExtensions::khr_dynamic_rendering)
+    /// extension alone is not enough to use this value.
+    None = NONE
+    RequiresOneOf([
+        RequiresAllOf([APIVersion(V1_3)]),
+        RequiresAllOf([DeviceExtension(khr_dynamic_rendering)]),
+        RequiresAllOf([DeviceExtension(ext_load_store_op_none)]),
+        RequiresAllOf([DeviceExtension(qcom_render_pass_store_ops)]),
+    ]),
 }
 
 vulkan_bitflags_enum! {
@@ -3705,12 +4037,20 @@ pub(crate) struct AttachmentUse {
     pub(crate) color_attachment: bool,
     pub(crate) depth_stencil_attachment: bool,
     pub(crate) input_attachment: bool,
+    pub(crate) fragment_density_map_attachment: bool,
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{RenderPassCreateInfo, SubpassDescription};
-    use crate::{format::Format, render_pass::RenderPass};
+    use super::{
+        AttachmentDescription, AttachmentReference, RenderPassCreateInfo, SubpassDescription,
+    };
+    use crate::{
+        format::{Format, FormatFeatures},
+        image::ImageLayout,
+        render_pass::{AttachmentLoadOp, AttachmentStoreOp, RenderPass},
+        Validated, Version,
+    };
 
     #[test]
     fn empty() {
@@ -3775,4 +4115,182 @@ mod tests {
         assert_ne!(granularity[0], 0);
         assert_ne!(granularity[1], 0);
     }
+
+    #[test]
+    fn fragment_density_map_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.enabled_features().fragment_density_map {
+            return; // test ignored
+        }
+
+        let create_info = RenderPassCreateInfo {
+            attachments: vec![AttachmentDescription {
+                format: Format::R8G8_UNORM,
+                load_op: AttachmentLoadOp::DontCare,
+                store_op: AttachmentStoreOp::DontCare,
+                initial_layout: ImageLayout::FragmentDensityMapOptimal,
+                final_layout: ImageLayout::FragmentDensityMapOptimal,
+                ..Default::default()
+            }],
+            subpasses: vec![SubpassDescription::default()],
+            fragment_density_map_attachment: Some(AttachmentReference {
+                attachment: 0,
+                layout: ImageLayout::FragmentDensityMapOptimal,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert!(RenderPass::new(device, create_info).is_err());
+    }
+
+    #[test]
+    fn fragment_density_map_attachment() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_fragment_density_map],
+            features: [fragment_density_map],
+        );
+
+        if !device
+            .physical_device()
+            .format_properties(Format::R8G8_UNORM)
+            .unwrap()
+            .potential_format_features()
+            .intersects(FormatFeatures::FRAGMENT_DENSITY_MAP)
+        {
+            return; // test ignored
+        }
+
+        let create_info = |store_op, layout| RenderPassCreateInfo {
+            attachments: vec![AttachmentDescription {
+                format: Format::R8G8_UNORM,
+                load_op: AttachmentLoadOp::Load,
+                store_op,
+                initial_layout: ImageLayout::FragmentDensityMapOptimal,
+                final_layout: ImageLayout::FragmentDensityMapOptimal,
+                ..Default::default()
+            }],
+            subpasses: vec![SubpassDescription::default()],
+            fragment_density_map_attachment: Some(AttachmentReference {
+                attachment: 0,
+                layout,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        match RenderPass::new(
+            device.clone(),
+            create_info(
+                AttachmentStoreOp::DontCare,
+                ImageLayout::ShaderReadOnlyOptimal,
+            ),
+        ) {
+            Err(Validated::ValidationError(err)) => assert_eq!(
+                err.vuids,
+                &["VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02549"],
+            ),
+            _ => panic!(),
+        }
+
+        match RenderPass::new(
+            device.clone(),
+            create_info(
+                AttachmentStoreOp::Store,
+                ImageLayout::FragmentDensityMapOptimal,
+            ),
+        ) {
+            Err(Validated::ValidationError(err)) => assert_eq!(
+                err.vuids,
+                &["VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02551"],
+            ),
+            _ => panic!(),
+        }
+
+        let render_pass = RenderPass::new(
+            device,
+            create_info(
+                AttachmentStoreOp::DontCare,
+                ImageLayout::FragmentDensityMapOptimal,
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            render_pass
+                .fragment_density_map_attachment()
+                .map(|attachment_ref| attachment_ref.attachment),
+            Some(0),
+        );
+    }
+
+    #[test]
+    fn store_op_none_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.api_version() >= Version::V1_3
+            || device.enabled_extensions().ext_load_store_op_none
+            || device.enabled_extensions().qcom_render_pass_store_ops
+        {
+            return; // test ignored
+        }
+
+        let create_info = RenderPassCreateInfo {
+            attachments: vec![AttachmentDescription {
+                format: Format::D16_UNORM,
+                load_op: AttachmentLoadOp::Load,
+                store_op: AttachmentStoreOp::None,
+                initial_layout: ImageLayout::DepthStencilReadOnlyOptimal,
+                final_layout: ImageLayout::DepthStencilReadOnlyOptimal,
+                ..Default::default()
+            }],
+            subpasses: vec![SubpassDescription {
+                depth_stencil_attachment: Some(AttachmentReference {
+                    attachment: 0,
+                    layout: ImageLayout::DepthStencilReadOnlyOptimal,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert!(RenderPass::new(device, create_info).is_err());
+    }
+
+    #[test]
+    fn attachment_feedback_loop_layout_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_attachment_feedback_loop_layout],
+            features: [],
+        );
+
+        let create_info = RenderPassCreateInfo {
+            attachments: vec![AttachmentDescription {
+                format: Format::R8G8B8A8_UNORM,
+                load_op: AttachmentLoadOp::Load,
+                store_op: AttachmentStoreOp::Store,
+                initial_layout: ImageLayout::AttachmentFeedbackLoopOptimal,
+                final_layout: ImageLayout::ColorAttachmentOptimal,
+                ..Default::default()
+            }],
+            subpasses: vec![SubpassDescription {
+                color_attachments: vec![Some(AttachmentReference {
+                    attachment: 0,
+                    layout: ImageLayout::ColorAttachmentOptimal,
+                    ..Default::default()
+                })],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        match RenderPass::new(device, create_info) {
+            Err(Validated::ValidationError(err)) => assert_eq!(
+                err.vuids,
+                &["VUID-VkAttachmentDescription2-attachmentFeedbackLoopLayout-07309"],
+            ),
+            _ => panic!(),
+        }
+    }
 }
@@ -82,6 +82,10 @@
 //! Each alignment type is a subset of the ones above it, so if something adheres to the extended
 //! alignment rules, it also follows the rules for the base and scalar alignments.
 //!
+//! When a shader module is created with [`ShaderModule::new`], vulkano compares the offsets and
+//! strides that are decorated in the SPIR-V code against these rules, and checks that the
+//! features that are needed for the loosest layout in use are enabled on the device.
+//!
 //! In all three of these alignment rules, a primitive/scalar value with a size of N bytes has an
 //! alignment of N, meaning that it must have an offset that is a multiple of its size,
 //! like in C or Rust. For example, a `float` (like a Rust `f32`) has a size of 4 bytes,
@@ -133,16 +137,19 @@
 
 use crate::{
     descriptor_set::layout::DescriptorType,
-    device::{Device, DeviceOwned},
+    device::{physical::SubgroupFeatures, Device, DeviceOwned},
     format::{Format, NumericType},
     image::view::ImageViewType,
     instance::InstanceOwnedDebugWrapper,
     macros::{impl_id_counter, vulkan_bitflags_enum},
     pipeline::{graphics::input_assembly::PrimitiveTopology, layout::PushConstantRange},
-    shader::spirv::{Capability, Spirv},
+    shader::{
+        spirv::{Capability, Spirv},
+        validation_cache::ValidationCache,
+    },
     sync::PipelineStages,
-    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version, VulkanError,
-    VulkanObject,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
+    VulkanError, VulkanObject,
 };
 use ahash::{HashMap, HashSet};
 use bytemuck::bytes_of;
@@ -151,6 +158,7 @@ use spirv::ExecutionModel;
 use std::{
     borrow::Cow,
     collections::hash_map::Entry,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
     mem::{discriminant, size_of_val, MaybeUninit},
     num::NonZeroU64,
     ptr,
@@ -159,6 +167,7 @@ use std::{
 
 pub mod reflect;
 pub mod spirv;
+pub mod validation_cache;
 
 // Generated by build.rs
 include!(concat!(env!("OUT_DIR"), "/spirv_reqs.rs"));
@@ -192,6 +201,30 @@ impl ShaderModule {
             })
         })?;
 
+        validate_block_layouts(&device, reflect::block_layouts(&spirv))
+            .map_err(|err| err.add_context("create_info.code"))?;
+        validate_block_small_scalar_widths(&device, reflect::block_small_scalar_widths(&spirv))
+            .map_err(|err| err.add_context("create_info.code"))?;
+        validate_float_atomics(&device, reflect::float_atomics(&spirv))
+            .map_err(|err| err.add_context("create_info.code"))?;
+
+        if reflect::has_workgroup_variable_initializers(&spirv)
+            && !device
+                .enabled_features()
+                .shader_zero_initialize_workgroup_memory
+        {
+            return Err(Box::new(ValidationError {
+                context: "create_info.code".into(),
+                problem: "contains a variable in `Workgroup` storage that has an initializer"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "shader_zero_initialize_workgroup_memory",
+                )])]),
+                vuids: &["VUID-RuntimeSpirv-shaderZeroInitializeWorkgroupMemory-06372"],
+            })
+            .into());
+        }
+
         Self::new_with_data(
             device,
             create_info,
@@ -256,16 +289,31 @@ impl ShaderModule {
         create_info: ShaderModuleCreateInfo<'_>,
         entry_points: impl IntoIterator<Item = EntryPointInfo>,
     ) -> Result<Arc<ShaderModule>, VulkanError> {
-        let &ShaderModuleCreateInfo { code, _ne: _ } = &create_info;
+        let &ShaderModuleCreateInfo {
+            code,
+            ref validation_cache,
+            _ne: _,
+        } = &create_info;
 
         let handle = {
-            let infos = ash::vk::ShaderModuleCreateInfo {
+            let mut infos = ash::vk::ShaderModuleCreateInfo {
                 flags: ash::vk::ShaderModuleCreateFlags::empty(),
                 code_size: size_of_val(code),
                 p_code: code.as_ptr(),
                 ..Default::default()
             };
 
+            let validation_cache_info_vk = validation_cache.as_ref().map(|validation_cache| {
+                ash::vk::ShaderModuleValidationCacheCreateInfoEXT {
+                    validation_cache: validation_cache.handle(),
+                    ..Default::default()
+                }
+            });
+
+            if let Some(next) = &validation_cache_info_vk {
+                infos.p_next = next as *const _ as *const _;
+            }
+
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
             (fns.v1_0.create_shader_module)(
@@ -309,7 +357,11 @@ impl ShaderModule {
         create_info: ShaderModuleCreateInfo<'_>,
         entry_points: impl IntoIterator<Item = EntryPointInfo>,
     ) -> Arc<ShaderModule> {
-        let ShaderModuleCreateInfo { code: _, _ne: _ } = create_info;
+        let ShaderModuleCreateInfo {
+            code: _,
+            validation_cache: _,
+            _ne: _,
+        } = create_info;
 
         let mut entry_point_map: HashMap<String, HashMap<ExecutionModel, usize>> =
             HashMap::default();
@@ -400,6 +452,57 @@ impl ShaderModule {
             })
         })
     }
+
+    /// Returns the identifier of the shader module.
+    ///
+    /// The identifier can be used to create pipelines with
+    /// [`PipelineShaderStageCreateInfo::module_identifier`], without needing the implementation
+    /// to read the SPIR-V code of the module again. Unlike the handle of the shader module, the
+    /// identifier remains the same when the application is restarted, as long as the
+    /// [`shader_module_identifier_algorithm_uuid`] of the device is the same.
+    ///
+    /// The [`shader_module_identifier`] feature must be enabled on the device.
+    ///
+    /// [`PipelineShaderStageCreateInfo::module_identifier`]: crate::pipeline::PipelineShaderStageCreateInfo::module_identifier
+    /// [`shader_module_identifier_algorithm_uuid`]: crate::device::Properties::shader_module_identifier_algorithm_uuid
+    /// [`shader_module_identifier`]: crate::device::Features::shader_module_identifier
+    #[inline]
+    pub fn identifier(&self) -> Result<ShaderModuleIdentifier, Box<ValidationError>> {
+        self.validate_identifier()?;
+
+        unsafe { Ok(self.identifier_unchecked()) }
+    }
+
+    fn validate_identifier(&self) -> Result<(), Box<ValidationError>> {
+        if !self.device.enabled_features().shader_module_identifier {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "shader_module_identifier",
+                )])]),
+                vuids: &["VUID-vkGetShaderModuleIdentifierEXT-shaderModuleIdentifier-06884"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn identifier_unchecked(&self) -> ShaderModuleIdentifier {
+        let mut identifier_vk = ash::vk::ShaderModuleIdentifierEXT::default();
+
+        let fns = self.device.fns();
+        (fns.ext_shader_module_identifier
+            .get_shader_module_identifier_ext)(
+            self.device.handle(),
+            self.handle,
+            &mut identifier_vk,
+        );
+
+        ShaderModuleIdentifier::from_bytes(
+            &identifier_vk.identifier[..identifier_vk.identifier_size as usize],
+        )
+    }
 }
 
 impl Drop for ShaderModule {
@@ -430,12 +533,68 @@ unsafe impl DeviceOwned for ShaderModule {
 
 impl_id_counter!(ShaderModule);
 
+/// An opaque identifier of a shader module, obtained from [`ShaderModule::identifier`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ShaderModuleIdentifier {
+    data: [u8; ash::vk::MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT],
+    size: u8,
+}
+
+impl ShaderModuleIdentifier {
+    /// Creates a `ShaderModuleIdentifier` from previously stored bytes, as returned by
+    /// [`as_bytes`](Self::as_bytes).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `bytes` is longer than 32 bytes.
+    #[inline]
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        assert!(bytes.len() <= ash::vk::MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT);
+
+        let mut data = [0; ash::vk::MAX_SHADER_MODULE_IDENTIFIER_SIZE_EXT];
+        data[..bytes.len()].copy_from_slice(bytes);
+
+        Self {
+            data,
+            size: bytes.len() as u8,
+        }
+    }
+
+    /// Returns the bytes of the identifier.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data[..self.size as usize]
+    }
+}
+
+impl Debug for ShaderModuleIdentifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "ShaderModuleIdentifier(")?;
+
+        for byte in self.as_bytes() {
+            write!(f, "{:02x}", byte)?;
+        }
+
+        write!(f, ")")
+    }
+}
+
 pub struct ShaderModuleCreateInfo<'a> {
     /// The SPIR-V code, in the form of 32-bit words.
     ///
     /// There is no default value.
     pub code: &'a [u32],
 
+    /// A validation cache that the validation layers can use to skip validating the code, if
+    /// it has been validated before.
+    ///
+    /// If this is `Some`, the
+    /// [`ext_validation_cache`](crate::device::DeviceExtensions::ext_validation_cache) extension
+    /// must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub validation_cache: Option<Arc<ValidationCache>>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -445,6 +604,7 @@ impl<'a> ShaderModuleCreateInfo<'a> {
     pub fn new(code: &'a [u32]) -> Self {
         Self {
             code,
+            validation_cache: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -456,7 +616,11 @@ impl<'a> ShaderModuleCreateInfo<'a> {
         spirv_capabilities: impl IntoIterator<Item = &'b Capability>,
         spirv_extensions: impl IntoIterator<Item = &'b str>,
     ) -> Result<(), Box<ValidationError>> {
-        let &Self { code, _ne: _ } = self;
+        let &Self {
+            code,
+            ref validation_cache,
+            _ne: _,
+        } = self;
 
         if code.is_empty() {
             return Err(Box::new(ValidationError {
@@ -467,6 +631,11 @@ impl<'a> ShaderModuleCreateInfo<'a> {
             }));
         }
 
+        if let Some(validation_cache) = validation_cache {
+            // VUID-VkShaderModuleValidationCacheCreateInfoEXT-validationCache-parameter
+            assert_eq!(device, validation_cache.device().as_ref());
+        }
+
         {
             spirv_version.patch = 0; // Ignore the patch version
 
@@ -537,6 +706,297 @@ impl<'a> ShaderModuleCreateInfo<'a> {
     }
 }
 
+/// The kind of memory block that a shader accesses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlockKind {
+    /// A uniform buffer, which is `Block`-decorated and in the `Uniform` storage class.
+    UniformBuffer,
+
+    /// A storage buffer, or any other block that is backed by buffer memory, such as a physical
+    /// storage buffer or a shader record buffer.
+    StorageBuffer,
+
+    /// A push constant block.
+    PushConstant,
+}
+
+/// A set of rules for the alignment of the members of a block.
+///
+/// See the [module-level documentation](self#alignment-rules) for more details. Each set of rules
+/// is stricter than the ones that compare less than it, so a block that adheres to
+/// `BlockLayout::Extended` also adheres to the other two.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BlockLayout {
+    /// The scalar alignment rules (GLSL qualifier: `layout(scalar)`).
+    Scalar,
+
+    /// The base alignment rules, also known as std430 (GLSL qualifier: `layout(std430)`).
+    Base,
+
+    /// The extended alignment rules, also known as std140 (GLSL qualifier: `layout(std140)`).
+    Extended,
+}
+
+/// The kind of memory that a floating-point atomic operation in a shader accesses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FloatAtomicMemory {
+    /// Memory that is backed by a buffer, in the `StorageBuffer`, `PhysicalStorageBuffer` or
+    /// `Uniform` storage class.
+    Buffer,
+
+    /// Shared memory, in the `Workgroup` storage class.
+    Shared,
+
+    /// A texel of a storage image, accessed through `OpImageTexelPointer`.
+    Image,
+}
+
+/// A kind of floating-point atomic operation in a shader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FloatAtomicOperation {
+    /// `OpAtomicLoad`, `OpAtomicStore` or `OpAtomicExchange`.
+    LoadStoreExchange,
+
+    /// `OpAtomicFAddEXT`.
+    Add,
+
+    /// `OpAtomicFMinEXT` or `OpAtomicFMaxEXT`.
+    MinMax,
+}
+
+/// Checks that the layouts of blocks in a shader are allowed by the enabled features.
+fn validate_block_layouts(
+    device: &Device,
+    block_layouts: impl IntoIterator<Item = (BlockKind, Option<BlockLayout>)>,
+) -> Result<(), Box<ValidationError>> {
+    for (kind, layout) in block_layouts {
+        let block_name = match kind {
+            BlockKind::UniformBuffer => "a uniform buffer block",
+            BlockKind::StorageBuffer => "a storage buffer block",
+            BlockKind::PushConstant => "a push constant block",
+        };
+
+        let layout = match layout {
+            Some(layout) => layout,
+            None => {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "contains {} whose member offsets or strides do not adhere to any of \
+                        the alignment rules",
+                        block_name,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkShaderModuleCreateInfo-pCode-08737"],
+                    ..Default::default()
+                }));
+            }
+        };
+
+        if layout == BlockLayout::Scalar && !device.enabled_features().scalar_block_layout {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "contains {} that is laid out according to the scalar alignment rules",
+                    block_name,
+                )
+                .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "scalar_block_layout",
+                )])]),
+                vuids: &["VUID-VkShaderModuleCreateInfo-pCode-08737"],
+                ..Default::default()
+            }));
+        }
+
+        if kind == BlockKind::UniformBuffer
+            && layout == BlockLayout::Base
+            && !(device.enabled_features().uniform_buffer_standard_layout
+                || device.enabled_features().scalar_block_layout)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "contains a uniform buffer block that is laid out according to the base \
+                    alignment rules"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::Feature("uniform_buffer_standard_layout")]),
+                    RequiresAllOf(&[Requires::Feature("scalar_block_layout")]),
+                ]),
+                vuids: &["VUID-VkShaderModuleCreateInfo-pCode-08737"],
+                ..Default::default()
+            }));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the floating-point atomic operations in a shader are allowed by the enabled
+/// features.
+fn validate_float_atomics(
+    device: &Device,
+    float_atomics: impl IntoIterator<Item = (FloatAtomicMemory, FloatAtomicOperation, u32)>,
+) -> Result<(), Box<ValidationError>> {
+    let features = device.enabled_features();
+
+    macro_rules! float_atomic_features {
+        ($(($memory:ident, $operation:ident, $width:literal) => $feature:ident,)+) => {
+            [$(
+                (
+                    FloatAtomicMemory::$memory,
+                    FloatAtomicOperation::$operation,
+                    $width,
+                    features.$feature,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(stringify!($feature))])]),
+                ),
+            )+]
+        };
+    }
+
+    // The feature that is required for each combination of memory, operation and bit width.
+    // Combinations that are not listed are not checked.
+    let float_atomic_features = float_atomic_features! {
+        (Buffer, LoadStoreExchange, 16) => shader_buffer_float16_atomics,
+        (Buffer, LoadStoreExchange, 32) => shader_buffer_float32_atomics,
+        (Buffer, LoadStoreExchange, 64) => shader_buffer_float64_atomics,
+        (Buffer, Add, 16) => shader_buffer_float16_atomic_add,
+        (Buffer, Add, 32) => shader_buffer_float32_atomic_add,
+        (Buffer, Add, 64) => shader_buffer_float64_atomic_add,
+        (Buffer, MinMax, 16) => shader_buffer_float16_atomic_min_max,
+        (Buffer, MinMax, 32) => shader_buffer_float32_atomic_min_max,
+        (Buffer, MinMax, 64) => shader_buffer_float64_atomic_min_max,
+        (Shared, LoadStoreExchange, 16) => shader_shared_float16_atomics,
+        (Shared, LoadStoreExchange, 32) => shader_shared_float32_atomics,
+        (Shared, LoadStoreExchange, 64) => shader_shared_float64_atomics,
+        (Shared, Add, 16) => shader_shared_float16_atomic_add,
+        (Shared, Add, 32) => shader_shared_float32_atomic_add,
+        (Shared, Add, 64) => shader_shared_float64_atomic_add,
+        (Shared, MinMax, 16) => shader_shared_float16_atomic_min_max,
+        (Shared, MinMax, 32) => shader_shared_float32_atomic_min_max,
+        (Shared, MinMax, 64) => shader_shared_float64_atomic_min_max,
+        (Image, LoadStoreExchange, 32) => shader_image_float32_atomics,
+        (Image, Add, 32) => shader_image_float32_atomic_add,
+        (Image, MinMax, 32) => shader_image_float32_atomic_min_max,
+    };
+
+    for (memory, operation, width) in float_atomics {
+        let (supported, requires_one_of) = match float_atomic_features
+            .iter()
+            .find(|&&(m, o, w, ..)| (m, o, w) == (memory, operation, width))
+        {
+            Some(&(.., supported, requires_one_of)) => (supported, requires_one_of),
+            None => continue,
+        };
+
+        if !supported {
+            let (memory_name, vuids): (_, &'static [_]) = match memory {
+                FloatAtomicMemory::Buffer => ("buffer memory", &["VUID-RuntimeSpirv-None-06284"]),
+                FloatAtomicMemory::Shared => ("shared memory", &["VUID-RuntimeSpirv-None-06285"]),
+                FloatAtomicMemory::Image => ("a storage image", &["VUID-RuntimeSpirv-None-06286"]),
+            };
+            let operation_name = match operation {
+                FloatAtomicOperation::LoadStoreExchange => "an atomic load, store or exchange",
+                FloatAtomicOperation::Add => "an atomic add",
+                FloatAtomicOperation::MinMax => "an atomic min or max",
+            };
+
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "contains {} operation on {}-bit floating-point values in {}",
+                    operation_name, width, memory_name,
+                )
+                .into(),
+                requires_one_of,
+                vuids,
+                ..Default::default()
+            }));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the 8-bit and 16-bit types that are contained in blocks in a shader are allowed by
+/// the enabled features.
+fn validate_block_small_scalar_widths(
+    device: &Device,
+    block_small_scalar_widths: impl IntoIterator<Item = (BlockKind, u32)>,
+) -> Result<(), Box<ValidationError>> {
+    let features = device.enabled_features();
+
+    for (kind, width) in block_small_scalar_widths {
+        let (supported, block_name, requires_one_of, vuids): (_, _, _, &'static [_]) =
+            match (kind, width) {
+                (BlockKind::StorageBuffer, 8) => (
+                    features.storage_buffer8_bit_access
+                        || features.uniform_and_storage_buffer8_bit_access,
+                    "a storage buffer block",
+                    RequiresOneOf(&[
+                        RequiresAllOf(&[Requires::Feature("storage_buffer8_bit_access")]),
+                        RequiresAllOf(&[Requires::Feature(
+                            "uniform_and_storage_buffer8_bit_access",
+                        )]),
+                    ]),
+                    &["VUID-RuntimeSpirv-storageBuffer8BitAccess-06328"],
+                ),
+                (BlockKind::UniformBuffer, 8) => (
+                    features.uniform_and_storage_buffer8_bit_access,
+                    "a uniform buffer block",
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "uniform_and_storage_buffer8_bit_access",
+                    )])]),
+                    &["VUID-RuntimeSpirv-uniformAndStorageBuffer8BitAccess-06329"],
+                ),
+                (BlockKind::PushConstant, 8) => (
+                    features.storage_push_constant8,
+                    "a push constant block",
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "storage_push_constant8",
+                    )])]),
+                    &["VUID-RuntimeSpirv-storagePushConstant8-06330"],
+                ),
+                (BlockKind::StorageBuffer, 16) => (
+                    features.storage_buffer16_bit_access
+                        || features.uniform_and_storage_buffer16_bit_access,
+                    "a storage buffer block",
+                    RequiresOneOf(&[
+                        RequiresAllOf(&[Requires::Feature("storage_buffer16_bit_access")]),
+                        RequiresAllOf(&[Requires::Feature(
+                            "uniform_and_storage_buffer16_bit_access",
+                        )]),
+                    ]),
+                    &["VUID-RuntimeSpirv-storageBuffer16BitAccess-06331"],
+                ),
+                (BlockKind::UniformBuffer, 16) => (
+                    features.uniform_and_storage_buffer16_bit_access,
+                    "a uniform buffer block",
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "uniform_and_storage_buffer16_bit_access",
+                    )])]),
+                    &["VUID-RuntimeSpirv-uniformAndStorageBuffer16BitAccess-06332"],
+                ),
+                (BlockKind::PushConstant, 16) => (
+                    features.storage_push_constant16,
+                    "a push constant block",
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "storage_push_constant16",
+                    )])]),
+                    &["VUID-RuntimeSpirv-storagePushConstant16-06333"],
+                ),
+                _ => continue,
+            };
+
+        if !supported {
+            return Err(Box::new(ValidationError {
+                problem: format!("contains {} with {}-bit members", block_name, width).into(),
+                requires_one_of,
+                vuids,
+                ..Default::default()
+            }));
+        }
+    }
+
+    Ok(())
+}
+
 /// The information associated with a single entry point in a shader.
 #[derive(Clone, Debug)]
 pub struct EntryPointInfo {
@@ -547,6 +1007,18 @@ pub struct EntryPointInfo {
     pub specialization_constants: HashMap<u32, SpecializationConstant>,
     pub input_interface: ShaderInterface,
     pub output_interface: ShaderInterface,
+    /// The categories of subgroup operations that the entry point uses.
+    pub subgroup_operations: SubgroupFeatures,
+    /// The amount of `Workgroup` storage that the entry point declares.
+    ///
+    /// This is `None` if the size could not be determined without evaluating the shader, for
+    /// example if an array length is the result of a specialization constant operation.
+    pub workgroup_memory_size: Option<WorkgroupMemorySize>,
+    /// The floating-point controls that the entry point declares with execution modes, keyed by
+    /// the bit width of the floating-point type that they apply to.
+    ///
+    /// Widths that have no floating-point control execution modes are not included.
+    pub float_controls: HashMap<u32, FloatControls>,
 }
 
 /// Represents a shader entry point in a shader module.
@@ -570,6 +1042,17 @@ impl EntryPoint {
     pub fn info(&self) -> &EntryPointInfo {
         &self.module.entry_point_infos[self.info_index]
     }
+
+    /// Returns the requirements that the entry point imposes on each `(set, binding)` of the
+    /// pipeline layout.
+    ///
+    /// This is a shorthand for [`info().descriptor_binding_requirements`](EntryPointInfo).
+    #[inline]
+    pub fn descriptor_binding_requirements(
+        &self,
+    ) -> &HashMap<(u32, u32), DescriptorBindingRequirements> {
+        &self.info().descriptor_binding_requirements
+    }
 }
 
 /// The mode in which a shader executes. This includes both information about the shader type/stage,
@@ -577,11 +1060,11 @@ impl EntryPoint {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ShaderExecution {
     Vertex,
-    TessellationControl,
-    TessellationEvaluation,
+    TessellationControl(TessellationShaderExecution),
+    TessellationEvaluation(TessellationShaderExecution),
     Geometry(GeometryShaderExecution),
     Fragment(FragmentShaderExecution),
-    Compute,
+    Compute(ComputeShaderExecution),
     RayGeneration,
     AnyHit,
     ClosestHit,
@@ -597,11 +1080,11 @@ impl From<&ShaderExecution> for ExecutionModel {
     fn from(value: &ShaderExecution) -> Self {
         match value {
             ShaderExecution::Vertex => Self::Vertex,
-            ShaderExecution::TessellationControl => Self::TessellationControl,
-            ShaderExecution::TessellationEvaluation => Self::TessellationEvaluation,
+            ShaderExecution::TessellationControl(_) => Self::TessellationControl,
+            ShaderExecution::TessellationEvaluation(_) => Self::TessellationEvaluation,
             ShaderExecution::Geometry(_) => Self::Geometry,
             ShaderExecution::Fragment(_) => Self::Fragment,
-            ShaderExecution::Compute => Self::GLCompute,
+            ShaderExecution::Compute(_) => Self::GLCompute,
             ShaderExecution::RayGeneration => Self::RayGenerationKHR,
             ShaderExecution::AnyHit => Self::AnyHitKHR,
             ShaderExecution::ClosestHit => Self::ClosestHitKHR,
@@ -615,19 +1098,64 @@ impl From<&ShaderExecution> for ExecutionModel {
     }
 }
 
-/*#[derive(Clone, Copy, Debug)]
+/// The mode in which a tessellation control or evaluation shader executes.
+///
+/// Each of the tessellation modes can be declared in the tessellation control shader, the
+/// tessellation evaluation shader, or both. A value of `None` means that the mode is not declared
+/// in this shader.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub struct TessellationShaderExecution {
-    pub num_output_vertices: u32,
+    /// The number of vertices in each output patch of the tessellation control shader.
+    pub output_vertices: Option<u32>,
+
+    /// The type of subdivision that the tessellator performs.
+    pub subdivision: Option<TessellationShaderSubdivision>,
+
+    /// The spacing of the segments that the edges of a patch are subdivided into.
+    pub spacing: Option<TessellationShaderSpacing>,
+
+    /// The order of the vertices of the generated triangles.
+    pub vertex_order: Option<TessellationShaderVertexOrder>,
+
+    /// Whether the tessellator generates points instead of lines or triangles.
     pub point_mode: bool,
-    pub subdivision: TessellationShaderSubdivision,
 }
 
+/// The type of subdivision that is performed by the tessellator.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TessellationShaderSubdivision {
+    /// Patches are subdivided into triangles.
     Triangles,
+
+    /// Patches are subdivided into quads, which are then split into triangles.
     Quads,
+
+    /// Patches are subdivided into lines.
     Isolines,
-}*/
+}
+
+/// The spacing of the segments that are generated by the tessellator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TessellationShaderSpacing {
+    /// The segments have equal lengths.
+    Equal,
+
+    /// The segments are spaced for an even number of fractional segments.
+    FractionalEven,
+
+    /// The segments are spaced for an odd number of fractional segments.
+    FractionalOdd,
+}
+
+/// The order of the vertices of the triangles that are generated by the tessellator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TessellationShaderVertexOrder {
+    /// The vertices are in clockwise order.
+    Clockwise,
+
+    /// The vertices are in counter-clockwise order.
+    CounterClockwise,
+}
 
 /// The mode in which a geometry shader executes.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -699,6 +1227,72 @@ pub enum FragmentTestsStages {
     EarlyAndLate,
 }
 
+/// The mode in which a compute shader executes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComputeShaderExecution {
+    /// The local workgroup size in the X, Y and Z dimensions, as declared in the shader.
+    ///
+    /// This is `None` if the size could not be determined without evaluating the shader, for
+    /// example if it is the result of a specialization constant operation.
+    pub local_size: Option<[LocalSize; 3]>,
+}
+
+/// The size of a compute shader's local workgroup in one dimension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LocalSize {
+    /// The size is a constant value.
+    Literal(u32),
+
+    /// The size is given by the specialization constant with this constant ID. If no value is
+    /// provided for it when creating a pipeline, the default value from
+    /// [`EntryPointInfo::specialization_constants`] is used.
+    SpecId(u32),
+}
+
+/// The amount of `Workgroup` storage that an entry point declares.
+///
+/// The size is the sum of `terms`. It can depend on the values of specialization constants, if
+/// the shader declares arrays whose length is a specialization constant.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WorkgroupMemorySize {
+    /// The terms that make up the size.
+    pub terms: Vec<WorkgroupMemorySizeTerm>,
+}
+
+/// One term of a [`WorkgroupMemorySize`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WorkgroupMemorySizeTerm {
+    /// The size in bytes, before multiplying with the specialization constants.
+    pub size: DeviceSize,
+
+    /// The constant IDs of the specialization constants whose values `size` is multiplied with.
+    /// If no value is provided for one of them when creating a pipeline, the default value from
+    /// [`EntryPointInfo::specialization_constants`] is used.
+    pub spec_ids: Vec<u32>,
+}
+
+/// The floating-point controls that an entry point declares for one bit width.
+///
+/// These correspond to the execution modes of the `SPV_KHR_float_controls` SPIR-V extension.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FloatControls {
+    /// The `DenormPreserve` execution mode: denormalized values are preserved.
+    pub denorm_preserve: bool,
+
+    /// The `DenormFlushToZero` execution mode: denormalized values are flushed to zero.
+    pub denorm_flush_to_zero: bool,
+
+    /// The `SignedZeroInfNanPreserve` execution mode: the sign of zero, and infinity and NaN
+    /// values, are preserved.
+    pub signed_zero_inf_nan_preserve: bool,
+
+    /// The `RoundingModeRTE` execution mode: the default rounding mode is round-to-nearest-even.
+    pub rounding_mode_rte: bool,
+
+    /// The `RoundingModeRTZ` execution mode: the default rounding mode is round-towards-zero.
+    pub rounding_mode_rtz: bool,
+}
+
 /// The requirements imposed by a shader on a binding within a descriptor set layout, and on any
 /// resource that is bound to that binding.
 #[derive(Clone, Debug, Default)]
@@ -843,7 +1437,10 @@ impl DescriptorBindingRequirements {
 
         descriptor_types.retain(|ty| other.descriptor_types.contains(ty));
 
-        *descriptor_count = (*descriptor_count).max(other.descriptor_count);
+        // A runtime-sized array in either shader makes the merged binding runtime-sized.
+        *descriptor_count = descriptor_count
+            .zip(other.descriptor_count)
+            .map(|(first, second)| first.max(second));
         *image_format = image_format.or(other.image_format);
         *image_scalar_type = image_scalar_type.or(other.image_scalar_type);
         *image_view_type = image_view_type.or(other.image_view_type);
@@ -1052,66 +1649,67 @@ impl ShaderInterface {
         self.elements.as_ref()
     }
 
-    /// Checks whether the interface is potentially compatible with another one.
+    /// Checks whether `self`, the input interface of a shader stage, is compatible with `other`,
+    /// the output interface of the previous stage.
+    ///
+    /// Every input must have an output at the same location and component, with the same type.
+    /// Outputs that are not consumed by any input are allowed, and are ignored.
     ///
     /// Returns `Ok` if the two interfaces are compatible.
-    #[inline]
     pub fn matches(&self, other: &ShaderInterface) -> Result<(), Box<ValidationError>> {
-        if self.elements().len() != other.elements().len() {
-            return Err(Box::new(ValidationError {
-                problem: "the number of elements in the shader interfaces are not equal".into(),
-                ..Default::default()
-            }));
-        }
-
-        for a in self.elements() {
-            let location_range = a.location..a.location + a.ty.num_locations();
-            for loc in location_range {
-                let b = match other
-                    .elements()
-                    .iter()
-                    .find(|e| loc >= e.location && loc < e.location + e.ty.num_locations())
-                {
-                    None => {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the second shader is missing an interface element at location {}",
-                                loc
-                            )
-                            .into(),
-                            ..Default::default()
-                        }));
-                    }
-                    Some(b) => b,
-                };
-
-                if a.ty != b.ty {
+        for input in self.elements() {
+            let output = match other.elements().iter().find(|output| {
+                output.location == input.location && output.component == input.component
+            }) {
+                Some(output) => output,
+                None => {
                     return Err(Box::new(ValidationError {
                         problem: format!(
-                            "the interface element at location {} does not have the same type \
-                            in both shaders",
-                            loc
+                            "the input {}at location {}, component {} has no matching output \
+                            in the previous stage",
+                            DisplayName(&input.name),
+                            input.location,
+                            input.component,
                         )
                         .into(),
                         ..Default::default()
                     }));
                 }
+            };
 
-                // TODO: enforce this?
-                /*match (a.name, b.name) {
-                    (Some(ref an), Some(ref bn)) => if an != bn { return false },
-                    _ => ()
-                };*/
+            if input.ty != output.ty {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "the input {}at location {}, component {} has type `{}`, but the \
+                        output {}of the previous stage at the same location has type `{}`",
+                        DisplayName(&input.name),
+                        input.location,
+                        input.component,
+                        input.ty.glsl_name(),
+                        DisplayName(&output.name),
+                        output.ty.glsl_name(),
+                    )
+                    .into(),
+                    ..Default::default()
+                }));
             }
         }
 
-        // NOTE: since we check that the number of elements is the same, we don't need to iterate
-        // over b's elements.
-
         Ok(())
     }
 }
 
+struct DisplayName<'a>(&'a Option<Cow<'static, str>>);
+
+impl Display for DisplayName<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.0 {
+            Some(name) => write!(f, "`{}` ", name),
+            None => Ok(()),
+        }
+    }
+}
+
 /// Entry of a shader interface definition.
 #[derive(Debug, Clone)]
 pub struct ShaderInterfaceEntry {
@@ -1150,6 +1748,37 @@ impl ShaderInterfaceEntryType {
         assert!(!self.is_64bit); // TODO: implement
         self.num_elements
     }
+
+    /// Returns the name of the type as it would be written in GLSL, for error messages.
+    fn glsl_name(&self) -> String {
+        let prefix = match (self.base_type, self.is_64bit) {
+            (NumericType::Float, false) => "",
+            (NumericType::Float, true) => "d",
+            (NumericType::Int, false) => "i",
+            (NumericType::Int, true) => "i64",
+            (NumericType::Uint, false) => "u",
+            (NumericType::Uint, true) => "u64",
+        };
+
+        let mut name = if self.num_components == 1 {
+            match (self.base_type, self.is_64bit) {
+                (NumericType::Float, false) => "float".to_owned(),
+                (NumericType::Float, true) => "double".to_owned(),
+                (NumericType::Int, false) => "int".to_owned(),
+                (NumericType::Int, true) => "int64_t".to_owned(),
+                (NumericType::Uint, false) => "uint".to_owned(),
+                (NumericType::Uint, true) => "uint64_t".to_owned(),
+            }
+        } else {
+            format!("{}vec{}", prefix, self.num_components)
+        };
+
+        if self.num_elements > 1 {
+            name += &format!("[{}]", self.num_elements);
+        }
+
+        name
+    }
 }
 
 vulkan_bitflags_enum! {
@@ -1259,11 +1888,11 @@ impl From<&ShaderExecution> for ShaderStage {
     fn from(value: &ShaderExecution) -> Self {
         match value {
             ShaderExecution::Vertex => Self::Vertex,
-            ShaderExecution::TessellationControl => Self::TessellationControl,
-            ShaderExecution::TessellationEvaluation => Self::TessellationEvaluation,
+            ShaderExecution::TessellationControl(_) => Self::TessellationControl,
+            ShaderExecution::TessellationEvaluation(_) => Self::TessellationEvaluation,
             ShaderExecution::Geometry(_) => Self::Geometry,
             ShaderExecution::Fragment(_) => Self::Fragment,
-            ShaderExecution::Compute => Self::Compute,
+            ShaderExecution::Compute(_) => Self::Compute,
             ShaderExecution::RayGeneration => Self::Raygen,
             ShaderExecution::AnyHit => Self::AnyHit,
             ShaderExecution::ClosestHit => Self::ClosestHit,
@@ -1332,3 +1961,400 @@ impl From<ShaderStages> for PipelineStages {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        reflect, spirv::Spirv, BlockKind, BlockLayout, DescriptorBindingRequirements,
+        FloatControls, ShaderInterface, ShaderInterfaceEntry, ShaderInterfaceEntryType,
+        ShaderModule, ShaderModuleCreateInfo, ShaderStages, WorkgroupMemorySize,
+        WorkgroupMemorySizeTerm,
+    };
+    use crate::{
+        descriptor_set::layout::DescriptorType, format::NumericType, Requires, RequiresAllOf,
+        RequiresOneOf, Validated,
+    };
+
+    fn entry(location: u32, component: u32, num_components: u32) -> ShaderInterfaceEntry {
+        ShaderInterfaceEntry {
+            location,
+            component,
+            name: None,
+            ty: ShaderInterfaceEntryType {
+                base_type: NumericType::Float,
+                num_components,
+                num_elements: 1,
+                is_64bit: false,
+            },
+        }
+    }
+
+    #[test]
+    fn interface_matches() {
+        let outputs = unsafe {
+            ShaderInterface::new_unchecked(vec![entry(0, 0, 4), entry(1, 0, 2), entry(1, 2, 2)])
+        };
+
+        // Unused outputs are allowed.
+        let inputs = unsafe { ShaderInterface::new_unchecked(vec![entry(1, 2, 2)]) };
+        assert!(inputs.matches(&outputs).is_ok());
+        assert!(ShaderInterface::empty().matches(&outputs).is_ok());
+
+        // Missing output.
+        let inputs = unsafe { ShaderInterface::new_unchecked(vec![entry(2, 0, 4)]) };
+        assert!(inputs.matches(&outputs).is_err());
+
+        // Wrong component.
+        let inputs = unsafe { ShaderInterface::new_unchecked(vec![entry(0, 1, 3)]) };
+        assert!(inputs.matches(&outputs).is_err());
+
+        // Type mismatch.
+        let inputs = unsafe { ShaderInterface::new_unchecked(vec![entry(0, 0, 3)]) };
+        let err = inputs.matches(&outputs).unwrap_err();
+        assert!(err.problem.contains("`vec3`") && err.problem.contains("`vec4`"));
+    }
+
+    #[test]
+    fn merge_descriptor_count() {
+        let reqs = |descriptor_count, stages| DescriptorBindingRequirements {
+            descriptor_types: vec![DescriptorType::StorageBuffer],
+            descriptor_count,
+            stages,
+            ..Default::default()
+        };
+
+        let mut merged = reqs(Some(2), ShaderStages::VERTEX);
+        merged
+            .merge(&reqs(Some(4), ShaderStages::FRAGMENT))
+            .unwrap();
+        assert_eq!(merged.descriptor_count, Some(4));
+        assert_eq!(merged.stages, ShaderStages::VERTEX | ShaderStages::FRAGMENT);
+
+        // A runtime-sized array is not shrunk by a fixed-size one.
+        let mut merged = reqs(None, ShaderStages::VERTEX);
+        merged
+            .merge(&reqs(Some(4), ShaderStages::FRAGMENT))
+            .unwrap();
+        assert_eq!(merged.descriptor_count, None);
+
+        let mut merged = reqs(Some(4), ShaderStages::VERTEX);
+        merged.merge(&reqs(None, ShaderStages::FRAGMENT)).unwrap();
+        assert_eq!(merged.descriptor_count, None);
+    }
+
+    #[test]
+    fn workgroup_initializer_requires_feature() {
+        /*
+        #version 450
+        #extension GL_EXT_null_initializer : require
+
+        layout(local_size_x = 1) in;
+
+        shared uint value = {};
+
+        void main() {}
+        */
+        const MODULE: [u32; 51] = [
+            119734787, 65536, 0, 9, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+            393232, 1, 17, 1, 1, 1, 131091, 2, 196641, 3, 2, 262165, 4, 32, 0, 196654, 4, 5,
+            262176, 6, 4, 4, 327739, 6, 7, 4, 5, 327734, 2, 1, 0, 3, 131320, 8, 65789, 65592,
+        ];
+
+        let spirv = Spirv::new(&MODULE).unwrap();
+        assert!(reflect::has_workgroup_variable_initializers(&spirv));
+        assert_eq!(reflect::entry_points(&spirv).count(), 1);
+
+        {
+            let (device, _queue) = gfx_dev_and_queue!();
+
+            assert!(matches!(
+                unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)) },
+                Err(Validated::ValidationError(_)),
+            ));
+        }
+
+        {
+            let (device, _queue) = gfx_dev_and_queue!(shader_zero_initialize_workgroup_memory);
+
+            let module =
+                unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)) }.unwrap();
+            assert!(module.entry_point("main").is_some());
+        }
+    }
+
+    #[test]
+    fn block_layout_std140_requires_no_features() {
+        /*
+        #version 450
+
+        layout(local_size_x = 1) in;
+
+        layout(set = 0, binding = 0, std140) uniform Data {
+            float a;
+            vec4 b;
+            mat2 c;
+        };
+
+        void main() {}
+        */
+        const MODULE: [u32; 98] = [
+            119734787, 65536, 0, 12, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+            393232, 1, 17, 1, 1, 1, 327752, 9, 0, 35, 0, 327752, 9, 1, 35, 16, 262216, 9, 2, 5,
+            327752, 9, 2, 35, 32, 327752, 9, 2, 7, 16, 196679, 9, 2, 262215, 11, 34, 0, 262215, 11,
+            33, 0, 131091, 2, 196641, 3, 2, 196630, 5, 32, 262167, 6, 5, 4, 262167, 7, 5, 2,
+            262168, 8, 7, 2, 327710, 9, 5, 6, 8, 262176, 10, 2, 9, 262203, 10, 11, 2, 327734, 2, 1,
+            0, 3, 131320, 4, 65789, 65592,
+        ];
+
+        let spirv = Spirv::new(&MODULE).unwrap();
+        assert_eq!(
+            reflect::block_layouts(&spirv).collect::<Vec<_>>(),
+            [(BlockKind::UniformBuffer, Some(BlockLayout::Extended))],
+        );
+
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let module =
+            unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)) }.unwrap();
+        assert!(module.entry_point("main").is_some());
+    }
+
+    #[test]
+    fn block_layout_matrix_stride() {
+        /*
+        #version 450
+
+        layout(local_size_x = 1) in;
+
+        layout(set = 0, binding = 0, std430) uniform Data {
+            mat2 a;
+        };
+
+        void main() {}
+        */
+        const MODULE: [u32; 82] = [
+            119734787, 65536, 0, 11, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+            393232, 1, 17, 1, 1, 1, 262216, 8, 0, 5, 327752, 8, 0, 35, 0, 327752, 8, 0, 7, 8,
+            196679, 8, 2, 262215, 10, 34, 0, 262215, 10, 33, 0, 131091, 2, 196641, 3, 2, 196630, 5,
+            32, 262167, 6, 5, 2, 262168, 7, 6, 2, 196638, 8, 7, 262176, 9, 2, 8, 262203, 9, 10, 2,
+            327734, 2, 1, 0, 3, 131320, 4, 65789, 65592,
+        ];
+
+        // A matrix stride of 8 does not meet the extended alignment of a `mat2`.
+        let spirv = Spirv::new(&MODULE).unwrap();
+        assert_eq!(
+            reflect::block_layouts(&spirv).collect::<Vec<_>>(),
+            [(BlockKind::UniformBuffer, Some(BlockLayout::Base))],
+        );
+    }
+
+    #[test]
+    fn block_layout_scalar_requires_feature() {
+        /*
+        #version 450
+        #extension GL_EXT_scalar_block_layout : require
+
+        layout(local_size_x = 1) in;
+
+        layout(set = 0, binding = 0, scalar) buffer Data {
+            float a;
+            vec3 b;
+        };
+
+        void main() {}
+        */
+        const MODULE: [u32; 75] = [
+            119734787, 65536, 0, 10, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+            393232, 1, 17, 1, 1, 1, 327752, 7, 0, 35, 0, 327752, 7, 1, 35, 4, 196679, 7, 3, 262215,
+            9, 34, 0, 262215, 9, 33, 0, 131091, 2, 196641, 3, 2, 196630, 5, 32, 262167, 6, 5, 3,
+            262174, 7, 5, 6, 262176, 8, 2, 7, 262203, 8, 9, 2, 327734, 2, 1, 0, 3, 131320, 4,
+            65789, 65592,
+        ];
+
+        let spirv = Spirv::new(&MODULE).unwrap();
+        assert_eq!(
+            reflect::block_layouts(&spirv).collect::<Vec<_>>(),
+            [(BlockKind::StorageBuffer, Some(BlockLayout::Scalar))],
+        );
+
+        {
+            let (device, _queue) = gfx_dev_and_queue!();
+
+            match unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)) } {
+                Err(Validated::ValidationError(err)) => assert_eq!(
+                    err.requires_one_of,
+                    RequiresOneOf(&[RequiresAllOf(&[Requires::Feature("scalar_block_layout")])]),
+                ),
+                _ => panic!(),
+            }
+        }
+
+        {
+            let (device, _queue) = gfx_dev_and_queue!(scalar_block_layout);
+
+            let module =
+                unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)) }.unwrap();
+            assert!(module.entry_point("main").is_some());
+        }
+    }
+
+    #[test]
+    fn block_layout_std430_uniform_requires_feature() {
+        /*
+        #version 450
+
+        layout(local_size_x = 1) in;
+
+        layout(set = 0, binding = 0, std430) uniform Data {
+            float a[2];
+        };
+
+        void main() {}
+        */
+        const MODULE: [u32; 81] = [
+            119734787, 65536, 0, 12, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+            393232, 1, 17, 1, 1, 1, 262215, 8, 6, 4, 327752, 9, 0, 35, 0, 196679, 9, 2, 262215, 11,
+            34, 0, 262215, 11, 33, 0, 131091, 2, 196641, 3, 2, 196630, 5, 32, 262165, 6, 32, 0,
+            262187, 6, 7, 2, 262172, 8, 5, 7, 196638, 9, 8, 262176, 10, 2, 9, 262203, 10, 11, 2,
+            327734, 2, 1, 0, 3, 131320, 4, 65789, 65592,
+        ];
+
+        let spirv = Spirv::new(&MODULE).unwrap();
+        assert_eq!(
+            reflect::block_layouts(&spirv).collect::<Vec<_>>(),
+            [(BlockKind::UniformBuffer, Some(BlockLayout::Base))],
+        );
+
+        {
+            let (device, _queue) = gfx_dev_and_queue!();
+
+            match unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)) } {
+                Err(Validated::ValidationError(err)) => assert_eq!(
+                    err.requires_one_of,
+                    RequiresOneOf(&[
+                        RequiresAllOf(&[Requires::Feature("uniform_buffer_standard_layout")]),
+                        RequiresAllOf(&[Requires::Feature("scalar_block_layout")]),
+                    ]),
+                ),
+                _ => panic!(),
+            }
+        }
+
+        {
+            let (device, _queue) = gfx_dev_and_queue!(uniform_buffer_standard_layout);
+
+            let module =
+                unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)) }.unwrap();
+            assert!(module.entry_point("main").is_some());
+        }
+    }
+
+    #[test]
+    fn workgroup_memory_size_padding() {
+        /*
+        #version 450
+
+        layout(local_size_x = 1) in;
+
+        struct S {
+            float a;
+            double b;
+        };
+
+        struct T {
+            double a;
+            float b;
+        };
+
+        shared S data;
+        shared T other[2];
+
+        void main() {}
+        */
+        const MODULE: [u32; 79] = [
+            119734787, 65536, 0, 16, 0, 131089, 1, 131089, 10, 196622, 0, 1, 327695, 5, 1,
+            1852399981, 0, 393232, 1, 17, 1, 1, 1, 131091, 2, 196641, 3, 2, 196630, 5, 32, 196630,
+            6, 64, 262174, 7, 5, 6, 262176, 8, 4, 7, 262203, 8, 9, 4, 262174, 10, 6, 5, 262165, 11,
+            32, 0, 262187, 11, 12, 2, 262172, 13, 10, 12, 262176, 14, 4, 13, 262203, 14, 15, 4,
+            327734, 2, 1, 0, 3, 131320, 4, 65789, 65592,
+        ];
+
+        // `S` has 4 bytes of padding before `b`, and `T` has 4 bytes of padding after `b`.
+        let spirv = Spirv::new(&MODULE).unwrap();
+        let entry_point = reflect::entry_points(&spirv).next().unwrap();
+        assert_eq!(
+            entry_point.workgroup_memory_size,
+            Some(WorkgroupMemorySize {
+                terms: vec![WorkgroupMemorySizeTerm {
+                    size: 48,
+                    spec_ids: vec![],
+                }],
+            }),
+        );
+    }
+
+    #[test]
+    fn float_controls_reflection() {
+        /*
+        OpCapability Shader
+        OpCapability DenormPreserve
+        OpCapability RoundingModeRTZ
+        OpExtension "SPV_KHR_float_controls"
+        OpMemoryModel Logical GLSL450
+        OpEntryPoint GLCompute %main "main"
+        OpExecutionMode %main LocalSize 1 1 1
+        OpExecutionMode %main DenormPreserve 32
+        OpExecutionMode %main RoundingModeRTZ 16
+        */
+        const MODULE: [u32; 54] = [
+            119734787, 65536, 0, 5, 0, 131089, 1, 131089, 4464, 131089, 4468, 458762, 1599492179,
+            1599227979, 1634692198, 1868783476, 1869771886, 29548, 196622, 0, 1, 327695, 5, 1,
+            1852399981, 0, 393232, 1, 17, 1, 1, 1, 262160, 1, 4459, 32, 262160, 1, 4463, 16,
+            131091, 2, 196641, 3, 2, 327734, 2, 1, 0, 3, 131320, 4, 65789, 65592,
+        ];
+
+        let spirv = Spirv::new(&MODULE).unwrap();
+        let entry_point = reflect::entry_points(&spirv).next().unwrap();
+
+        assert_eq!(entry_point.float_controls.len(), 2);
+        assert_eq!(
+            entry_point.float_controls[&16],
+            FloatControls {
+                rounding_mode_rtz: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            entry_point.float_controls[&32],
+            FloatControls {
+                denorm_preserve: true,
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn draw_parameters_requires_feature() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        /*
+        #version 460
+
+        void main() {
+            int base_instance = gl_BaseInstance;
+        }
+        */
+        const MODULE: [u32; 61] = [
+            119734787, 65536, 0, 9, 0, 131089, 1, 131089, 4427, 589834, 1599492179, 1599227979,
+            1684105331, 1683976805, 1601659250, 1634886000, 1702126957, 29554, 196622, 0, 1,
+            393231, 0, 1, 1852399981, 0, 6, 262215, 6, 11, 4425, 131091, 3, 196641, 2, 3, 262165,
+            4, 32, 1, 262176, 5, 1, 4, 262203, 5, 6, 1, 327734, 3, 1, 0, 2, 131320, 7, 262205, 4,
+            8, 6, 65789, 65592,
+        ];
+
+        assert!(matches!(
+            unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)) },
+            Err(Validated::ValidationError(_)),
+        ));
+    }
+}
@@ -9,21 +9,28 @@
 
 //! Extraction of information from SPIR-V modules, that is needed by the rest of Vulkano.
 
-use super::{DescriptorBindingRequirements, FragmentShaderExecution, FragmentTestsStages};
+use super::{
+    BlockKind, BlockLayout, ComputeShaderExecution, DescriptorBindingRequirements,
+    FloatAtomicMemory, FloatAtomicOperation, FloatControls, FragmentShaderExecution,
+    FragmentTestsStages, LocalSize, TessellationShaderExecution, TessellationShaderSpacing,
+    TessellationShaderSubdivision, TessellationShaderVertexOrder, WorkgroupMemorySize,
+    WorkgroupMemorySizeTerm,
+};
 use crate::{
     descriptor_set::layout::DescriptorType,
+    device::physical::SubgroupFeatures,
     image::view::ImageViewType,
     pipeline::layout::PushConstantRange,
     shader::{
         spirv::{
-            Capability, Decoration, Dim, ExecutionMode, ExecutionModel, Id, Instruction, Spirv,
-            StorageClass,
+            BuiltIn, Capability, Decoration, Dim, ExecutionMode, ExecutionModel, GroupOperation,
+            Id, Instruction, Spirv, StorageClass,
         },
         DescriptorIdentifier, DescriptorRequirements, EntryPointInfo, GeometryShaderExecution,
         GeometryShaderInput, NumericType, ShaderExecution, ShaderInterface, ShaderInterfaceEntry,
         ShaderInterfaceEntryType, ShaderStage, SpecializationConstant,
     },
-    DeviceSize,
+    DeviceSize, Version,
 };
 use ahash::{HashMap, HashSet};
 use half::f16;
@@ -79,6 +86,9 @@ pub fn entry_points(spirv: &Spirv) -> impl Iterator<Item = EntryPointInfo> + '_
         );
         let push_constant_requirements = push_constant_requirements(spirv, stage);
         let specialization_constants = specialization_constants(spirv);
+        let subgroup_operations = subgroup_operations(spirv, function_id);
+        let workgroup_memory_size = workgroup_memory_size(spirv, interface);
+        let float_controls = float_controls(spirv, function_id);
         let input_interface = shader_interface(
             spirv,
             interface,
@@ -105,6 +115,9 @@ pub fn entry_points(spirv: &Spirv) -> impl Iterator<Item = EntryPointInfo> + '_
             specialization_constants,
             input_interface,
             output_interface,
+            subgroup_operations,
+            workgroup_memory_size,
+            float_controls,
         })
     })
 }
@@ -118,9 +131,13 @@ fn shader_execution(
     match execution_model {
         ExecutionModel::Vertex => ShaderExecution::Vertex,
 
-        ExecutionModel::TessellationControl => ShaderExecution::TessellationControl,
+        ExecutionModel::TessellationControl => {
+            ShaderExecution::TessellationControl(tessellation_shader_execution(spirv, function_id))
+        }
 
-        ExecutionModel::TessellationEvaluation => ShaderExecution::TessellationEvaluation,
+        ExecutionModel::TessellationEvaluation => ShaderExecution::TessellationEvaluation(
+            tessellation_shader_execution(spirv, function_id),
+        ),
 
         ExecutionModel::Geometry => {
             let mut input = None;
@@ -187,7 +204,9 @@ fn shader_execution(
             })
         }
 
-        ExecutionModel::GLCompute => ShaderExecution::Compute,
+        ExecutionModel::GLCompute => ShaderExecution::Compute(ComputeShaderExecution {
+            local_size: local_size(spirv, function_id),
+        }),
 
         ExecutionModel::RayGenerationKHR => ShaderExecution::RayGeneration,
         ExecutionModel::IntersectionKHR => ShaderExecution::Intersection,
@@ -203,6 +222,56 @@ fn shader_execution(
     }
 }
 
+/// Extracts the `TessellationShaderExecution` for the entry point `function_id` from `spirv`.
+fn tessellation_shader_execution(spirv: &Spirv, function_id: Id) -> TessellationShaderExecution {
+    let mut execution = TessellationShaderExecution::default();
+
+    for instruction in spirv.iter_execution_mode() {
+        let mode = match instruction {
+            Instruction::ExecutionMode {
+                entry_point, mode, ..
+            } if *entry_point == function_id => mode,
+            _ => continue,
+        };
+
+        match *mode {
+            ExecutionMode::OutputVertices { vertex_count } => {
+                execution.output_vertices = Some(vertex_count);
+            }
+            ExecutionMode::Triangles => {
+                execution.subdivision = Some(TessellationShaderSubdivision::Triangles);
+            }
+            ExecutionMode::Quads => {
+                execution.subdivision = Some(TessellationShaderSubdivision::Quads);
+            }
+            ExecutionMode::Isolines => {
+                execution.subdivision = Some(TessellationShaderSubdivision::Isolines);
+            }
+            ExecutionMode::SpacingEqual => {
+                execution.spacing = Some(TessellationShaderSpacing::Equal);
+            }
+            ExecutionMode::SpacingFractionalEven => {
+                execution.spacing = Some(TessellationShaderSpacing::FractionalEven);
+            }
+            ExecutionMode::SpacingFractionalOdd => {
+                execution.spacing = Some(TessellationShaderSpacing::FractionalOdd);
+            }
+            ExecutionMode::VertexOrderCw => {
+                execution.vertex_order = Some(TessellationShaderVertexOrder::Clockwise);
+            }
+            ExecutionMode::VertexOrderCcw => {
+                execution.vertex_order = Some(TessellationShaderVertexOrder::CounterClockwise);
+            }
+            ExecutionMode::PointMode => {
+                execution.point_mode = true;
+            }
+            _ => (),
+        }
+    }
+
+    execution
+}
+
 #[derive(Clone, Debug, Default)]
 struct InterfaceVariables {
     descriptor_binding: HashMap<Id, DescriptorBindingVariable>,
@@ -1038,6 +1107,925 @@ fn push_constant_requirements(spirv: &Spirv, stage: ShaderStage) -> Option<PushC
         })
 }
 
+/// Returns an iterator over the blocks in `spirv` that are backed by buffer memory or push
+/// constants, with the kind of block and the strictest set of layout rules that the offsets and
+/// strides of the block adhere to. If the block doesn't even adhere to the scalar layout rules,
+/// then the layout is `None`.
+pub fn block_layouts(spirv: &Spirv) -> impl Iterator<Item = (BlockKind, Option<BlockLayout>)> + '_ {
+    memory_blocks(spirv).into_iter().map(|(id, kind)| {
+        let layout = [
+            BlockLayout::Extended,
+            BlockLayout::Base,
+            BlockLayout::Scalar,
+        ]
+        .into_iter()
+        .find(|&layout| struct_adheres_to_layout(spirv, id, layout));
+
+        (kind, layout)
+    })
+}
+
+/// Returns an iterator over the 8-bit and 16-bit scalar types that are contained in the blocks in
+/// `spirv` that are backed by buffer memory or push constants, as pairs of the kind of block and
+/// the bit width of the scalar type. Each pair is returned only once.
+pub fn block_small_scalar_widths(spirv: &Spirv) -> impl Iterator<Item = (BlockKind, u32)> {
+    let mut widths: Vec<(BlockKind, u32)> = Vec::new();
+
+    for (id, kind) in memory_blocks(spirv) {
+        let mut block_widths = Vec::new();
+        scalar_widths_of_type(spirv, id, &mut block_widths);
+
+        for width in block_widths {
+            if matches!(width, 8 | 16) && !widths.contains(&(kind, width)) {
+                widths.push((kind, width));
+            }
+        }
+    }
+
+    widths.into_iter()
+}
+
+/// Returns whether `spirv` contains a variable in `Workgroup` storage that has an initializer.
+pub fn has_workgroup_variable_initializers(spirv: &Spirv) -> bool {
+    spirv.iter_global().any(|instruction| {
+        matches!(
+            instruction,
+            Instruction::Variable {
+                storage_class: StorageClass::Workgroup,
+                initializer: Some(_),
+                ..
+            }
+        )
+    })
+}
+
+/// Returns an iterator over the floating-point atomic operations in `spirv`, as triples of the
+/// kind of memory that is accessed, the kind of operation and the bit width of the floating-point
+/// type. Each triple is returned only once.
+pub fn float_atomics(
+    spirv: &Spirv,
+) -> impl Iterator<Item = (FloatAtomicMemory, FloatAtomicOperation, u32)> {
+    let mut atomics = Vec::new();
+
+    for instruction in spirv.instructions() {
+        let (operation, pointer) = match *instruction {
+            Instruction::AtomicLoad { pointer, .. }
+            | Instruction::AtomicStore { pointer, .. }
+            | Instruction::AtomicExchange { pointer, .. } => {
+                (FloatAtomicOperation::LoadStoreExchange, pointer)
+            }
+            Instruction::AtomicFAddEXT { pointer, .. } => (FloatAtomicOperation::Add, pointer),
+            Instruction::AtomicFMinEXT { pointer, .. }
+            | Instruction::AtomicFMaxEXT { pointer, .. } => (FloatAtomicOperation::MinMax, pointer),
+            _ => continue,
+        };
+
+        let (storage_class, pointee) = match pointer_type(spirv, pointer) {
+            Some(pointer_type) => pointer_type,
+            None => continue,
+        };
+
+        let memory = match storage_class {
+            StorageClass::StorageBuffer
+            | StorageClass::PhysicalStorageBuffer
+            | StorageClass::Uniform => FloatAtomicMemory::Buffer,
+            StorageClass::Workgroup => FloatAtomicMemory::Shared,
+            StorageClass::Image => FloatAtomicMemory::Image,
+            _ => continue,
+        };
+
+        let width = match *spirv.id(pointee).instruction() {
+            Instruction::TypeFloat { width, .. } => width,
+            _ => continue,
+        };
+
+        if !atomics.contains(&(memory, operation, width)) {
+            atomics.push((memory, operation, width));
+        }
+    }
+
+    atomics.into_iter()
+}
+
+/// Returns the storage class and the pointee type of the pointer `id`, if it is the result of an
+/// instruction that produces pointers.
+fn pointer_type(spirv: &Spirv, id: Id) -> Option<(StorageClass, Id)> {
+    let result_type_id = match *spirv.id(id).instruction() {
+        Instruction::Variable { result_type_id, .. }
+        | Instruction::AccessChain { result_type_id, .. }
+        | Instruction::InBoundsAccessChain { result_type_id, .. }
+        | Instruction::PtrAccessChain { result_type_id, .. }
+        | Instruction::InBoundsPtrAccessChain { result_type_id, .. }
+        | Instruction::ImageTexelPointer { result_type_id, .. }
+        | Instruction::FunctionParameter { result_type_id, .. }
+        | Instruction::CopyObject { result_type_id, .. }
+        | Instruction::Bitcast { result_type_id, .. }
+        | Instruction::ConvertUToPtr { result_type_id, .. }
+        | Instruction::Select { result_type_id, .. }
+        | Instruction::Phi { result_type_id, .. } => result_type_id,
+        _ => return None,
+    };
+
+    match *spirv.id(result_type_id).instruction() {
+        Instruction::TypePointer {
+            storage_class, ty, ..
+        } => Some((storage_class, ty)),
+        _ => None,
+    }
+}
+
+/// Adds the bit widths of the scalar types that type `id` consists of to `widths`.
+fn scalar_widths_of_type(spirv: &Spirv, id: Id, widths: &mut Vec<u32>) {
+    match *spirv.id(id).instruction() {
+        Instruction::TypeInt { width, .. } | Instruction::TypeFloat { width, .. }
+            if !widths.contains(&width) =>
+        {
+            widths.push(width)
+        }
+        Instruction::TypeVector { component_type, .. } => {
+            scalar_widths_of_type(spirv, component_type, widths)
+        }
+        Instruction::TypeMatrix { column_type, .. } => {
+            scalar_widths_of_type(spirv, column_type, widths)
+        }
+        Instruction::TypeArray { element_type, .. }
+        | Instruction::TypeRuntimeArray { element_type, .. } => {
+            scalar_widths_of_type(spirv, element_type, widths)
+        }
+        Instruction::TypeStruct {
+            ref member_types, ..
+        } => {
+            for &member_type in member_types {
+                scalar_widths_of_type(spirv, member_type, widths);
+            }
+        }
+        // Physical pointers point to blocks of their own, which are checked separately.
+        _ => (),
+    }
+}
+
+/// Returns the struct types in `spirv` that are used as blocks backed by buffer memory or push
+/// constants, with the kind of block.
+fn memory_blocks(spirv: &Spirv) -> Vec<(Id, BlockKind)> {
+    let mut blocks: Vec<(Id, BlockKind)> = Vec::new();
+
+    for instruction in spirv.iter_global() {
+        let (ty, kind) = match *instruction {
+            Instruction::TypePointer {
+                ty, storage_class, ..
+            } => match storage_class {
+                StorageClass::Uniform => (ty, BlockKind::UniformBuffer),
+                StorageClass::StorageBuffer
+                | StorageClass::PhysicalStorageBuffer
+                | StorageClass::ShaderRecordBufferKHR => (ty, BlockKind::StorageBuffer),
+                StorageClass::PushConstant => (ty, BlockKind::PushConstant),
+                _ => continue,
+            },
+            _ => continue,
+        };
+
+        // Descriptor arrays contain the block as their element.
+        let mut ty = ty;
+
+        while let Instruction::TypeArray { element_type, .. }
+        | Instruction::TypeRuntimeArray { element_type, .. } = *spirv.id(ty).instruction()
+        {
+            ty = element_type;
+        }
+
+        let id_info = spirv.id(ty);
+
+        if !matches!(id_info.instruction(), Instruction::TypeStruct { .. }) {
+            continue;
+        }
+
+        // Older SPIR-V uses the `Uniform` storage class with `BufferBlock` for storage buffers.
+        let kind = if kind == BlockKind::UniformBuffer
+            && id_info.iter_decoration().any(|instruction| {
+                matches!(
+                    instruction,
+                    Instruction::Decorate {
+                        decoration: Decoration::BufferBlock,
+                        ..
+                    }
+                )
+            }) {
+            BlockKind::StorageBuffer
+        } else {
+            kind
+        };
+
+        if !blocks.contains(&(ty, kind)) {
+            blocks.push((ty, kind));
+        }
+    }
+
+    blocks
+}
+
+/// Returns whether the offsets and strides of the members of the struct `id` adhere to the
+/// `layout` rules.
+///
+/// See also section 15.6.4 of the Vulkan specs: Offset and Stride Assignment
+fn struct_adheres_to_layout(spirv: &Spirv, id: Id, layout: BlockLayout) -> bool {
+    let id_info = spirv.id(id);
+    let member_types = match id_info.instruction() {
+        Instruction::TypeStruct { member_types, .. } => member_types,
+        _ => return true,
+    };
+
+    let mut members = Vec::with_capacity(member_types.len());
+
+    for (&member_type, member_info) in member_types.iter().zip(id_info.iter_members()) {
+        let mut offset = None;
+        let mut matrix_stride = None;
+        let mut row_major = false;
+
+        for instruction in member_info.iter_decoration() {
+            match *instruction {
+                Instruction::MemberDecorate {
+                    decoration: Decoration::Offset { byte_offset },
+                    ..
+                } => offset = Some(byte_offset as DeviceSize),
+                Instruction::MemberDecorate {
+                    decoration:
+                        Decoration::MatrixStride {
+                            matrix_stride: stride,
+                        },
+                    ..
+                } => matrix_stride = Some(stride as DeviceSize),
+                Instruction::MemberDecorate {
+                    decoration: Decoration::RowMajor,
+                    ..
+                } => row_major = true,
+                Instruction::MemberDecorate {
+                    decoration: Decoration::BuiltIn { .. },
+                    ..
+                } => return true,
+                _ => (),
+            }
+        }
+
+        // Structs without `Offset` decorations don't have an explicit layout.
+        let offset = match offset {
+            Some(offset) => offset,
+            None => return true,
+        };
+
+        members.push((offset, member_type, matrix_stride, row_major));
+    }
+
+    members.sort_by_key(|&(offset, _, _, _)| offset);
+
+    // The smallest offset that the next member is allowed to have.
+    let mut min_offset = 0;
+
+    for (offset, member_type, matrix_stride, row_major) in members {
+        let alignment = alignment_of_type(spirv, member_type, layout, row_major);
+
+        if offset % alignment != 0 || offset < min_offset {
+            return false;
+        }
+
+        if !type_adheres_to_layout(spirv, member_type, layout, row_major) {
+            return false;
+        }
+
+        // The matrix stride applies to the matrix, or to the matrices in an array of matrices.
+        if let Some(matrix_stride) = matrix_stride {
+            let mut matrix_type = member_type;
+
+            while let Instruction::TypeArray { element_type, .. }
+            | Instruction::TypeRuntimeArray { element_type, .. } =
+                *spirv.id(matrix_type).instruction()
+            {
+                matrix_type = element_type;
+            }
+
+            if matrix_stride % alignment_of_type(spirv, matrix_type, layout, row_major) != 0 {
+                return false;
+            }
+        }
+
+        let size = size_of_type(spirv, member_type);
+
+        match *spirv.id(member_type).instruction() {
+            // Vectors must not improperly straddle a 16-byte boundary, except with the scalar
+            // layout.
+            Instruction::TypeVector { .. } if layout != BlockLayout::Scalar => {
+                let size = size.unwrap();
+
+                if size <= 16 {
+                    if offset / 16 != (offset + size - 1) / 16 {
+                        return false;
+                    }
+                } else if offset % 16 != 0 {
+                    return false;
+                }
+            }
+            // Members must not be placed between the end of a struct, array or matrix and the
+            // next multiple of its alignment.
+            Instruction::TypeStruct { .. }
+            | Instruction::TypeArray { .. }
+            | Instruction::TypeMatrix { .. } => {
+                if let Some(size) = size {
+                    let end = offset + size;
+                    min_offset = (end + alignment - 1) & !(alignment - 1);
+                    continue;
+                }
+            }
+            _ => (),
+        }
+
+        min_offset = offset + size.unwrap_or(0);
+    }
+
+    true
+}
+
+/// Returns whether the strides of type `id`, and the offsets of any structs that it contains,
+/// adhere to the `layout` rules.
+fn type_adheres_to_layout(spirv: &Spirv, id: Id, layout: BlockLayout, row_major: bool) -> bool {
+    let id_info = spirv.id(id);
+
+    match *id_info.instruction() {
+        Instruction::TypeStruct { .. } => struct_adheres_to_layout(spirv, id, layout),
+        Instruction::TypeArray { element_type, .. }
+        | Instruction::TypeRuntimeArray { element_type, .. } => {
+            let array_stride =
+                id_info
+                    .iter_decoration()
+                    .find_map(|instruction| match *instruction {
+                        Instruction::Decorate {
+                            decoration: Decoration::ArrayStride { array_stride },
+                            ..
+                        } => Some(array_stride as DeviceSize),
+                        _ => None,
+                    });
+
+            if let Some(array_stride) = array_stride {
+                if array_stride % alignment_of_type(spirv, id, layout, row_major) != 0 {
+                    return false;
+                }
+            }
+
+            type_adheres_to_layout(spirv, element_type, layout, row_major)
+        }
+        _ => true,
+    }
+}
+
+/// Returns the alignment of type `id` according to the `layout` rules.
+fn alignment_of_type(spirv: &Spirv, id: Id, layout: BlockLayout, row_major: bool) -> DeviceSize {
+    match *spirv.id(id).instruction() {
+        Instruction::TypeInt { width, .. } | Instruction::TypeFloat { width, .. } => {
+            width as DeviceSize / 8
+        }
+        Instruction::TypePointer { .. } => 8,
+        Instruction::TypeVector {
+            component_type,
+            component_count,
+            ..
+        } => {
+            let component_alignment = alignment_of_type(spirv, component_type, layout, false);
+
+            match layout {
+                BlockLayout::Scalar => component_alignment,
+                BlockLayout::Base | BlockLayout::Extended => {
+                    component_alignment * component_count.next_power_of_two() as DeviceSize
+                }
+            }
+        }
+        Instruction::TypeMatrix {
+            column_type,
+            column_count,
+            ..
+        } => {
+            let alignment = if row_major {
+                let component_type = match *spirv.id(column_type).instruction() {
+                    Instruction::TypeVector { component_type, .. } => component_type,
+                    _ => unreachable!(),
+                };
+                let component_alignment = alignment_of_type(spirv, component_type, layout, false);
+
+                match layout {
+                    BlockLayout::Scalar => component_alignment,
+                    BlockLayout::Base | BlockLayout::Extended => {
+                        component_alignment * column_count.next_power_of_two() as DeviceSize
+                    }
+                }
+            } else {
+                alignment_of_type(spirv, column_type, layout, false)
+            };
+
+            // A matrix has the same alignment as the equivalent array of vectors.
+            match layout {
+                BlockLayout::Scalar | BlockLayout::Base => alignment,
+                BlockLayout::Extended => alignment.max(16),
+            }
+        }
+        Instruction::TypeArray { element_type, .. }
+        | Instruction::TypeRuntimeArray { element_type, .. } => {
+            let alignment = alignment_of_type(spirv, element_type, layout, row_major);
+
+            match layout {
+                BlockLayout::Scalar | BlockLayout::Base => alignment,
+                BlockLayout::Extended => alignment.max(16),
+            }
+        }
+        Instruction::TypeStruct {
+            ref member_types, ..
+        } => {
+            let id_info = spirv.id(id);
+            let alignment = member_types
+                .iter()
+                .zip(id_info.iter_members())
+                .map(|(&member_type, member_info)| {
+                    let row_major = member_info.iter_decoration().any(|instruction| {
+                        matches!(
+                            instruction,
+                            Instruction::MemberDecorate {
+                                decoration: Decoration::RowMajor,
+                                ..
+                            }
+                        )
+                    });
+
+                    alignment_of_type(spirv, member_type, layout, row_major)
+                })
+                .max()
+                .unwrap_or(1);
+
+            match layout {
+                BlockLayout::Scalar | BlockLayout::Base => alignment,
+                BlockLayout::Extended => alignment.max(16),
+            }
+        }
+        _ => 1,
+    }
+}
+
+/// Extracts the local workgroup size of the compute entry point `function_id` from `spirv`.
+fn local_size(spirv: &Spirv, function_id: Id) -> Option<[LocalSize; 3]> {
+    let component = |id: Id| match *spirv.id(id).instruction() {
+        Instruction::Constant { ref value, .. } => Some(LocalSize::Literal(value[0])),
+        Instruction::SpecConstant { ref value, .. } => Some(
+            spirv
+                .id(id)
+                .iter_decoration()
+                .find_map(|instruction| match *instruction {
+                    Instruction::Decorate {
+                        decoration:
+                            Decoration::SpecId {
+                                specialization_constant_id,
+                            },
+                        ..
+                    } => Some(LocalSize::SpecId(specialization_constant_id)),
+                    _ => None,
+                })
+                .unwrap_or(LocalSize::Literal(value[0])),
+        ),
+        _ => None,
+    };
+
+    // A constant decorated with the `WorkgroupSize` built-in overrides any execution mode.
+    let workgroup_size = spirv
+        .iter_global()
+        .find_map(|instruction| match *instruction {
+            Instruction::ConstantComposite {
+                result_id,
+                ref constituents,
+                ..
+            }
+            | Instruction::SpecConstantComposite {
+                result_id,
+                ref constituents,
+                ..
+            } if spirv.id(result_id).iter_decoration().any(|instruction| {
+                matches!(
+                    instruction,
+                    Instruction::Decorate {
+                        decoration: Decoration::BuiltIn {
+                            built_in: BuiltIn::WorkgroupSize,
+                        },
+                        ..
+                    }
+                )
+            }) =>
+            {
+                Some(constituents.clone())
+            }
+            _ => None,
+        });
+
+    if let Some(constituents) = workgroup_size {
+        return Some([
+            component(constituents[0])?,
+            component(constituents[1])?,
+            component(constituents[2])?,
+        ]);
+    }
+
+    for instruction in spirv.iter_execution_mode() {
+        match *instruction {
+            Instruction::ExecutionMode {
+                entry_point,
+                mode:
+                    ExecutionMode::LocalSize {
+                        x_size,
+                        y_size,
+                        z_size,
+                    },
+            } if entry_point == function_id => {
+                return Some([
+                    LocalSize::Literal(x_size),
+                    LocalSize::Literal(y_size),
+                    LocalSize::Literal(z_size),
+                ]);
+            }
+            Instruction::ExecutionModeId {
+                entry_point,
+                mode:
+                    ExecutionMode::LocalSizeId {
+                        x_size,
+                        y_size,
+                        z_size,
+                    },
+            } if entry_point == function_id => {
+                return Some([component(x_size)?, component(y_size)?, component(z_size)?]);
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Returns the amount of `Workgroup` storage that is declared by the entry point with the
+/// interface `interface`, or `None` if it cannot be determined without evaluating the shader.
+fn workgroup_memory_size(spirv: &Spirv, interface: &[Id]) -> Option<WorkgroupMemorySize> {
+    let mut terms = Vec::new();
+    let mut explicit_layout_size = 0;
+
+    for instruction in spirv.iter_global() {
+        let (result_type_id, result_id) = match *instruction {
+            Instruction::Variable {
+                result_type_id,
+                result_id,
+                storage_class: StorageClass::Workgroup,
+                ..
+            } => (result_type_id, result_id),
+            _ => continue,
+        };
+
+        // Before SPIR-V 1.4, the interface of an entry point only lists its input and output
+        // variables, so all variables of the module are counted.
+        if spirv.version() >= Version::V1_4 && !interface.contains(&result_id) {
+            continue;
+        }
+
+        let ty = match *spirv.id(result_type_id).instruction() {
+            Instruction::TypePointer { ty, .. } => ty,
+            _ => return None,
+        };
+
+        // Variables with an explicit layout alias each other, so only the largest one counts.
+        let is_block = spirv.id(ty).iter_decoration().any(|instruction| {
+            matches!(
+                instruction,
+                Instruction::Decorate {
+                    decoration: Decoration::Block,
+                    ..
+                }
+            )
+        });
+
+        if is_block {
+            explicit_layout_size = explicit_layout_size.max(size_of_type(spirv, ty)?);
+        } else {
+            for term in workgroup_size_of_type(spirv, ty)? {
+                add_workgroup_memory_size_term(&mut terms, term);
+            }
+        }
+    }
+
+    if explicit_layout_size != 0 {
+        add_workgroup_memory_size_term(
+            &mut terms,
+            WorkgroupMemorySizeTerm {
+                size: explicit_layout_size,
+                spec_ids: Vec::new(),
+            },
+        );
+    }
+
+    Some(WorkgroupMemorySize { terms })
+}
+
+/// Returns the size of type `id` in `Workgroup` storage without an explicit layout, as the terms
+/// of a `WorkgroupMemorySize`.
+///
+/// The members of structs are padded to their scalar alignment, and the size of a struct is
+/// padded to a multiple of its alignment. The padding is computed from the constant part of the
+/// offset, so it can be underestimated if an earlier member has a specialization constant length.
+fn workgroup_size_of_type(spirv: &Spirv, id: Id) -> Option<Vec<WorkgroupMemorySizeTerm>> {
+    let literal = |size: DeviceSize| {
+        vec![WorkgroupMemorySizeTerm {
+            size,
+            spec_ids: Vec::new(),
+        }]
+    };
+    let scale = |terms: Vec<WorkgroupMemorySizeTerm>, factor: DeviceSize| {
+        terms
+            .into_iter()
+            .map(|term| WorkgroupMemorySizeTerm {
+                size: term.size.saturating_mul(factor),
+                ..term
+            })
+            .collect()
+    };
+
+    match *spirv.id(id).instruction() {
+        // Booleans have no defined size, but implementations store them as 32-bit values.
+        Instruction::TypeBool { .. } => Some(literal(4)),
+        Instruction::TypeInt { width, .. } | Instruction::TypeFloat { width, .. } => {
+            Some(literal(width as DeviceSize / 8))
+        }
+        Instruction::TypePointer { .. } => Some(literal(8)),
+        Instruction::TypeVector {
+            component_type,
+            component_count,
+            ..
+        } => Some(scale(
+            workgroup_size_of_type(spirv, component_type)?,
+            component_count as DeviceSize,
+        )),
+        Instruction::TypeMatrix {
+            column_type,
+            column_count,
+            ..
+        } => Some(scale(
+            workgroup_size_of_type(spirv, column_type)?,
+            column_count as DeviceSize,
+        )),
+        Instruction::TypeArray {
+            element_type,
+            length,
+            ..
+        } => {
+            let terms = workgroup_size_of_type(spirv, element_type)?;
+            let length_info = spirv.id(length);
+            let (value, spec_id) = match *length_info.instruction() {
+                Instruction::Constant { ref value, .. } => (value, None),
+                Instruction::SpecConstant { ref value, .. } => (
+                    value,
+                    length_info
+                        .iter_decoration()
+                        .find_map(|instruction| match *instruction {
+                            Instruction::Decorate {
+                                decoration:
+                                    Decoration::SpecId {
+                                        specialization_constant_id,
+                                    },
+                                ..
+                            } => Some(specialization_constant_id),
+                            _ => None,
+                        }),
+                ),
+                _ => return None,
+            };
+
+            match spec_id {
+                Some(spec_id) => Some(
+                    terms
+                        .into_iter()
+                        .map(|mut term| {
+                            term.spec_ids.push(spec_id);
+                            term.spec_ids.sort_unstable();
+                            term
+                        })
+                        .collect(),
+                ),
+                None => Some(scale(
+                    terms,
+                    value
+                        .iter()
+                        .rev()
+                        .fold(0u64, |a, &b| (a << 32) | b as DeviceSize),
+                )),
+            }
+        }
+        Instruction::TypeStruct {
+            ref member_types, ..
+        } => {
+            let mut terms = Vec::new();
+
+            for &member in member_types {
+                pad_workgroup_memory_size(&mut terms, workgroup_alignment_of_type(spirv, member)?);
+
+                for term in workgroup_size_of_type(spirv, member)? {
+                    add_workgroup_memory_size_term(&mut terms, term);
+                }
+            }
+
+            pad_workgroup_memory_size(&mut terms, workgroup_alignment_of_type(spirv, id)?);
+
+            Some(terms)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the scalar alignment of type `id` in `Workgroup` storage without an explicit layout.
+fn workgroup_alignment_of_type(spirv: &Spirv, id: Id) -> Option<DeviceSize> {
+    match *spirv.id(id).instruction() {
+        Instruction::TypeBool { .. } => Some(4),
+        Instruction::TypeInt { width, .. } | Instruction::TypeFloat { width, .. } => {
+            Some(width as DeviceSize / 8)
+        }
+        Instruction::TypePointer { .. } => Some(8),
+        Instruction::TypeVector { component_type, .. } => {
+            workgroup_alignment_of_type(spirv, component_type)
+        }
+        Instruction::TypeMatrix { column_type, .. } => {
+            workgroup_alignment_of_type(spirv, column_type)
+        }
+        Instruction::TypeArray { element_type, .. } => {
+            workgroup_alignment_of_type(spirv, element_type)
+        }
+        Instruction::TypeStruct {
+            ref member_types, ..
+        } => member_types.iter().try_fold(1, |alignment, &member| {
+            Some(alignment.max(workgroup_alignment_of_type(spirv, member)?))
+        }),
+        _ => None,
+    }
+}
+
+/// Adds padding to `terms` so that their constant part is a multiple of `alignment`.
+fn pad_workgroup_memory_size(terms: &mut Vec<WorkgroupMemorySizeTerm>, alignment: DeviceSize) {
+    let size = terms
+        .iter()
+        .find(|term| term.spec_ids.is_empty())
+        .map_or(0, |term| term.size);
+    let padding = size.next_multiple_of(alignment) - size;
+
+    if padding != 0 {
+        add_workgroup_memory_size_term(
+            terms,
+            WorkgroupMemorySizeTerm {
+                size: padding,
+                spec_ids: Vec::new(),
+            },
+        );
+    }
+}
+
+/// Adds `term` to `terms`, merging it with an existing term that has the same `spec_ids`.
+fn add_workgroup_memory_size_term(
+    terms: &mut Vec<WorkgroupMemorySizeTerm>,
+    term: WorkgroupMemorySizeTerm,
+) {
+    match terms
+        .iter_mut()
+        .find(|existing| existing.spec_ids == term.spec_ids)
+    {
+        Some(existing) => existing.size = existing.size.saturating_add(term.size),
+        None => terms.push(term),
+    }
+}
+
+/// Returns the categories of subgroup operations that are used by the entry point
+/// `function_id`, or by any function that it calls.
+fn subgroup_operations(spirv: &Spirv, function_id: Id) -> SubgroupFeatures {
+    fn arithmetic(operation: GroupOperation) -> SubgroupFeatures {
+        match operation {
+            GroupOperation::Reduce
+            | GroupOperation::InclusiveScan
+            | GroupOperation::ExclusiveScan => SubgroupFeatures::ARITHMETIC,
+            GroupOperation::ClusteredReduce => SubgroupFeatures::CLUSTERED,
+            GroupOperation::PartitionedReduceNV
+            | GroupOperation::PartitionedInclusiveScanNV
+            | GroupOperation::PartitionedExclusiveScanNV => SubgroupFeatures::PARTITIONED,
+        }
+    }
+
+    fn inspect_function(
+        spirv: &Spirv,
+        function: Id,
+        inspected_functions: &mut HashSet<Id>,
+        result: &mut SubgroupFeatures,
+    ) {
+        inspected_functions.insert(function);
+        let mut in_function = false;
+
+        for instruction in spirv.instructions() {
+            if !in_function {
+                match *instruction {
+                    Instruction::Function { result_id, .. } if result_id == function => {
+                        in_function = true;
+                    }
+                    _ => {}
+                }
+            } else {
+                *result |= match *instruction {
+                    Instruction::FunctionEnd => return,
+                    Instruction::FunctionCall { function, .. } => {
+                        if !inspected_functions.contains(&function) {
+                            inspect_function(spirv, function, inspected_functions, result);
+                        }
+
+                        continue;
+                    }
+                    Instruction::GroupNonUniformElect { .. } => SubgroupFeatures::BASIC,
+                    Instruction::GroupNonUniformAll { .. }
+                    | Instruction::GroupNonUniformAny { .. }
+                    | Instruction::GroupNonUniformAllEqual { .. } => SubgroupFeatures::VOTE,
+                    Instruction::GroupNonUniformBroadcast { .. }
+                    | Instruction::GroupNonUniformBroadcastFirst { .. }
+                    | Instruction::GroupNonUniformBallot { .. }
+                    | Instruction::GroupNonUniformInverseBallot { .. }
+                    | Instruction::GroupNonUniformBallotBitExtract { .. }
+                    | Instruction::GroupNonUniformBallotBitCount { .. }
+                    | Instruction::GroupNonUniformBallotFindLSB { .. }
+                    | Instruction::GroupNonUniformBallotFindMSB { .. } => SubgroupFeatures::BALLOT,
+                    Instruction::GroupNonUniformShuffle { .. }
+                    | Instruction::GroupNonUniformShuffleXor { .. } => SubgroupFeatures::SHUFFLE,
+                    Instruction::GroupNonUniformShuffleUp { .. }
+                    | Instruction::GroupNonUniformShuffleDown { .. } => {
+                        SubgroupFeatures::SHUFFLE_RELATIVE
+                    }
+                    Instruction::GroupNonUniformIAdd { operation, .. }
+                    | Instruction::GroupNonUniformFAdd { operation, .. }
+                    | Instruction::GroupNonUniformIMul { operation, .. }
+                    | Instruction::GroupNonUniformFMul { operation, .. }
+                    | Instruction::GroupNonUniformSMin { operation, .. }
+                    | Instruction::GroupNonUniformUMin { operation, .. }
+                    | Instruction::GroupNonUniformFMin { operation, .. }
+                    | Instruction::GroupNonUniformSMax { operation, .. }
+                    | Instruction::GroupNonUniformUMax { operation, .. }
+                    | Instruction::GroupNonUniformFMax { operation, .. }
+                    | Instruction::GroupNonUniformBitwiseAnd { operation, .. }
+                    | Instruction::GroupNonUniformBitwiseOr { operation, .. }
+                    | Instruction::GroupNonUniformBitwiseXor { operation, .. }
+                    | Instruction::GroupNonUniformLogicalAnd { operation, .. }
+                    | Instruction::GroupNonUniformLogicalOr { operation, .. }
+                    | Instruction::GroupNonUniformLogicalXor { operation, .. } => {
+                        arithmetic(operation)
+                    }
+                    Instruction::GroupNonUniformQuadBroadcast { .. }
+                    | Instruction::GroupNonUniformQuadSwap { .. } => SubgroupFeatures::QUAD,
+                    Instruction::GroupNonUniformPartitionNV { .. } => SubgroupFeatures::PARTITIONED,
+                    _ => continue,
+                };
+            }
+        }
+    }
+
+    let mut result = SubgroupFeatures::empty();
+    inspect_function(spirv, function_id, &mut HashSet::default(), &mut result);
+    result
+}
+
+/// Returns the floating-point controls that are declared for the entry point `function_id`,
+/// keyed by bit width.
+fn float_controls(spirv: &Spirv, function_id: Id) -> HashMap<u32, FloatControls> {
+    let mut result: HashMap<u32, FloatControls> = HashMap::default();
+
+    for instruction in spirv.iter_execution_mode() {
+        let mode = match instruction {
+            Instruction::ExecutionMode {
+                entry_point, mode, ..
+            } if *entry_point == function_id => mode,
+            _ => continue,
+        };
+
+        match *mode {
+            ExecutionMode::DenormPreserve { target_width } => {
+                result.entry(target_width).or_default().denorm_preserve = true;
+            }
+            ExecutionMode::DenormFlushToZero { target_width } => {
+                result.entry(target_width).or_default().denorm_flush_to_zero = true;
+            }
+            ExecutionMode::SignedZeroInfNanPreserve { target_width } => {
+                result
+                    .entry(target_width)
+                    .or_default()
+                    .signed_zero_inf_nan_preserve = true;
+            }
+            ExecutionMode::RoundingModeRTE { target_width } => {
+                result.entry(target_width).or_default().rounding_mode_rte = true;
+            }
+            ExecutionMode::RoundingModeRTZ { target_width } => {
+                result.entry(target_width).or_default().rounding_mode_rtz = true;
+            }
+            _ => (),
+        }
+    }
+
+    result
+}
+
 /// Extracts the `SpecializationConstant` map from `spirv`.
 fn specialization_constants(spirv: &Spirv) -> HashMap<u32, SpecializationConstant> {
     let get_constant_id = |result_id| {
@@ -1265,12 +2253,15 @@ fn size_of_type(spirv: &Spirv, id: Id) -> Option<DeviceSize> {
                         .fold(0u64, |a, &b| (a << 32) | b as DeviceSize),
                 ),
                 _ => None,
-            }
-            .unwrap();
+            }?;
 
             Some(stride as DeviceSize * length)
         }
         Instruction::TypeRuntimeArray { .. } => None,
+        Instruction::TypePointer {
+            storage_class: StorageClass::PhysicalStorageBuffer,
+            ..
+        } => Some(8),
         Instruction::TypeStruct {
             ref member_types, ..
         } => {
@@ -0,0 +1,322 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Cache the results of shader validation to disk for faster reloads.
+//!
+//! When validation layers are enabled, they validate the SPIR-V code of every shader module that
+//! is created, which can be slow for large shaders. A validation cache lets the layers store the
+//! results of this validation, so that it can be skipped for shader modules that have already
+//! been validated.
+//!
+//! The cache is passed to shader module creation through
+//! [`ShaderModuleCreateInfo::validation_cache`]. Like a [`PipelineCache`], its data can be
+//! extracted with [`get_data`](ValidationCache::get_data) and stored on disk, and passed back in
+//! [`ValidationCacheCreateInfo::initial_data`] on the next run.
+//!
+//! Using this requires the [`ext_validation_cache`] extension to be enabled on the device. It is
+//! usually provided by the validation layers themselves.
+//!
+//! [`ShaderModuleCreateInfo::validation_cache`]: crate::shader::ShaderModuleCreateInfo::validation_cache
+//! [`PipelineCache`]: crate::pipeline::cache::PipelineCache
+//! [`ext_validation_cache`]: crate::device::DeviceExtensions::ext_validation_cache
+
+use crate::{
+    device::{Device, DeviceOwned},
+    instance::InstanceOwnedDebugWrapper,
+    macros::impl_id_counter,
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
+
+/// Opaque cache that contains the results of shader validation.
+///
+/// See [the documentation of the module](crate::shader::validation_cache) for more info.
+#[derive(Debug)]
+pub struct ValidationCache {
+    device: InstanceOwnedDebugWrapper<Arc<Device>>,
+    handle: ash::vk::ValidationCacheEXT,
+    id: NonZeroU64,
+}
+
+impl ValidationCache {
+    /// Creates a new validation cache.
+    ///
+    /// # Safety
+    ///
+    /// - The data in `create_info.initial_data` must be valid data that was previously retrieved
+    ///   using [`get_data`](ValidationCache::get_data).
+    #[inline]
+    pub unsafe fn new(
+        device: Arc<Device>,
+        create_info: ValidationCacheCreateInfo,
+    ) -> Result<Arc<ValidationCache>, Validated<VulkanError>> {
+        Self::validate_new(&device, &create_info)?;
+
+        Ok(Self::new_unchecked(device, create_info)?)
+    }
+
+    fn validate_new(
+        device: &Device,
+        create_info: &ValidationCacheCreateInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        if !device.enabled_extensions().ext_validation_cache {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_validation_cache",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        create_info
+            .validate(device)
+            .map_err(|err| err.add_context("create_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: Arc<Device>,
+        create_info: ValidationCacheCreateInfo,
+    ) -> Result<Arc<ValidationCache>, VulkanError> {
+        let ValidationCacheCreateInfo {
+            ref initial_data,
+            _ne: _,
+        } = create_info;
+
+        let infos = ash::vk::ValidationCacheCreateInfoEXT {
+            flags: ash::vk::ValidationCacheCreateFlagsEXT::empty(),
+            initial_data_size: initial_data.len(),
+            p_initial_data: if initial_data.is_empty() {
+                ptr::null()
+            } else {
+                initial_data.as_ptr() as _
+            },
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.ext_validation_cache.create_validation_cache_ext)(
+                device.handle(),
+                &infos,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        Ok(Self::from_handle(device, handle, create_info))
+    }
+
+    /// Creates a new `ValidationCache` from a raw object handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid Vulkan object handle created from `device`.
+    /// - `create_info` must match the info used to create the object.
+    pub unsafe fn from_handle(
+        device: Arc<Device>,
+        handle: ash::vk::ValidationCacheEXT,
+        create_info: ValidationCacheCreateInfo,
+    ) -> Arc<ValidationCache> {
+        let ValidationCacheCreateInfo {
+            initial_data: _,
+            _ne: _,
+        } = create_info;
+
+        Arc::new(ValidationCache {
+            device: InstanceOwnedDebugWrapper(device),
+            handle,
+            id: Self::next_id(),
+        })
+    }
+
+    /// Obtains the data from the cache.
+    ///
+    /// This data can be stored and then reloaded and passed to `ValidationCache::new`.
+    #[inline]
+    pub fn get_data(&self) -> Result<Vec<u8>, VulkanError> {
+        let fns = self.device.fns();
+
+        let data = unsafe {
+            loop {
+                let mut count = 0;
+                (fns.ext_validation_cache.get_validation_cache_data_ext)(
+                    self.device.handle(),
+                    self.handle,
+                    &mut count,
+                    ptr::null_mut(),
+                )
+                .result()
+                .map_err(VulkanError::from)?;
+
+                let mut data: Vec<u8> = Vec::with_capacity(count);
+                let result = (fns.ext_validation_cache.get_validation_cache_data_ext)(
+                    self.device.handle(),
+                    self.handle,
+                    &mut count,
+                    data.as_mut_ptr() as *mut _,
+                );
+
+                match result {
+                    ash::vk::Result::SUCCESS => {
+                        data.set_len(count);
+                        break data;
+                    }
+                    ash::vk::Result::INCOMPLETE => (),
+                    err => return Err(VulkanError::from(err)),
+                }
+            }
+        };
+
+        Ok(data)
+    }
+
+    /// Merges other validation caches into this one.
+    ///
+    /// It is `self` that is modified here. The validation caches passed as parameter are
+    /// untouched.
+    pub fn merge<'a>(
+        &self,
+        src_caches: impl IntoIterator<Item = &'a ValidationCache>,
+    ) -> Result<(), Validated<VulkanError>> {
+        let src_caches: SmallVec<[_; 8]> = src_caches.into_iter().collect();
+        self.validate_merge(&src_caches)?;
+
+        unsafe { Ok(self.merge_unchecked(src_caches)?) }
+    }
+
+    fn validate_merge(&self, src_caches: &[&ValidationCache]) -> Result<(), Box<ValidationError>> {
+        for (index, &src_cache) in src_caches.iter().enumerate() {
+            if src_cache == self {
+                return Err(Box::new(ValidationError {
+                    context: format!("src_caches[{}]", index).into(),
+                    problem: "equals `self`".into(),
+                    vuids: &["VUID-vkMergeValidationCachesEXT-dstCache-01536"],
+                    ..Default::default()
+                }));
+            }
+
+            // VUID-vkMergeValidationCachesEXT-pSrcCaches-parent
+            assert_eq!(self.device(), src_cache.device());
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn merge_unchecked<'a>(
+        &self,
+        src_caches: impl IntoIterator<Item = &'a ValidationCache>,
+    ) -> Result<(), VulkanError> {
+        let src_caches_vk: SmallVec<[_; 8]> =
+            src_caches.into_iter().map(VulkanObject::handle).collect();
+
+        let fns = self.device.fns();
+        (fns.ext_validation_cache.merge_validation_caches_ext)(
+            self.device.handle(),
+            self.handle,
+            src_caches_vk.len() as u32,
+            src_caches_vk.as_ptr(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(())
+    }
+}
+
+impl Drop for ValidationCache {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.ext_validation_cache.destroy_validation_cache_ext)(
+                self.device.handle(),
+                self.handle,
+                ptr::null(),
+            );
+        }
+    }
+}
+
+unsafe impl VulkanObject for ValidationCache {
+    type Handle = ash::vk::ValidationCacheEXT;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for ValidationCache {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl_id_counter!(ValidationCache);
+
+/// Parameters to create a new `ValidationCache`.
+#[derive(Clone, Debug)]
+pub struct ValidationCacheCreateInfo {
+    /// The initial data to provide to the cache.
+    ///
+    /// If this is not empty, then the data must have been previously retrieved by calling
+    /// [`ValidationCache::get_data`].
+    ///
+    /// The default value is empty.
+    pub initial_data: Vec<u8>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for ValidationCacheCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            initial_data: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl ValidationCacheCreateInfo {
+    pub(crate) fn validate(&self, _device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            initial_data: _,
+            _ne,
+        } = self;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidationCache;
+
+    #[test]
+    fn requires_extension() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        if device.enabled_extensions().ext_validation_cache {
+            return;
+        }
+
+        assert!(unsafe { ValidationCache::new(device, Default::default()) }.is_err());
+    }
+}
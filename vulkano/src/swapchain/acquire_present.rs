@@ -10,12 +10,13 @@
 use super::{PresentMode, Swapchain};
 use crate::{
     buffer::Buffer,
+    command_buffer::{SemaphoreSubmitInfo, SubmitInfo},
     device::{Device, DeviceOwned, Queue},
     image::{Image, ImageLayout},
     sync::{
         fence::Fence,
         future::{AccessCheckError, AccessError, GpuFuture, SubmitAnyBuilder},
-        semaphore::Semaphore,
+        semaphore::{Semaphore, SemaphoreType},
     },
     DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError,
     VulkanObject,
@@ -46,38 +47,48 @@ use std::{
 /// The second field in the tuple in the Ok result is a bool represent if the acquisition was
 /// suboptimal. In this case the acquired image is still usable, but the swapchain should be
 /// recreated as the Surface's properties no longer match the swapchain.
+///
+/// If no image could be acquired, one of the following errors is returned:
+/// - [`VulkanError::Timeout`] if `timeout` is not zero, and no image became available within
+///   the timeout duration.
+/// - [`VulkanError::NotReady`] if `timeout` is zero, and no image was immediately available.
+/// - [`VulkanError::OutOfDate`] if the swapchain no longer matches the surface, or has been
+///   retired, and must be recreated.
 pub fn acquire_next_image(
     swapchain: Arc<Swapchain>,
     timeout: Option<Duration>,
+) -> Result<(u32, bool, SwapchainAcquireFuture), Validated<VulkanError>> {
+    let fence = Arc::new(Fence::from_pool(swapchain.device.clone())?);
+
+    acquire_next_image_with_fence(swapchain, timeout, fence)
+}
+
+/// Same as [`acquire_next_image`], but signals `fence` when the acquisition is complete.
+///
+/// This makes it possible to block on the acquisition from the CPU, by waiting on `fence` or
+/// calling [`SwapchainAcquireFuture::wait`].
+///
+/// `fence` must not be signaled, and must not be in use by any queue operation. It is kept alive
+/// by the returned future.
+pub fn acquire_next_image_with_fence(
+    swapchain: Arc<Swapchain>,
+    timeout: Option<Duration>,
+    fence: Arc<Fence>,
 ) -> Result<(u32, bool, SwapchainAcquireFuture), Validated<VulkanError>> {
     let semaphore = Arc::new(Semaphore::from_pool(swapchain.device.clone())?);
-    let fence = Fence::from_pool(swapchain.device.clone())?;
 
+    // The semaphore and the fence are kept alive by the returned future, which waits for the
+    // fence when it is dropped.
     let AcquiredImage {
         image_index,
         suboptimal,
-    } = {
-        // Check that this is not an old swapchain. From specs:
-        // > swapchain must not have been replaced by being passed as the
-        // > VkSwapchainCreateInfoKHR::oldSwapchain value to vkCreateSwapchainKHR
-        let retired = swapchain.is_retired.lock();
-        if *retired {
-            return Err(VulkanError::OutOfDate.into());
-        }
-
-        let acquire_result =
-            unsafe { acquire_next_image_raw(&swapchain, timeout, Some(&semaphore), Some(&fence)) };
-
-        if matches!(
-            acquire_result,
-            Err(Validated::Error(VulkanError::FullScreenExclusiveModeLost))
-        ) {
-            swapchain
-                .full_screen_exclusive_held
-                .store(false, Ordering::SeqCst);
-        }
-
-        acquire_result?
+    } = unsafe {
+        swapchain.acquire_next_image(&AcquireNextImageInfo {
+            timeout,
+            semaphore: Some(semaphore.clone()),
+            fence: Some(fence.clone()),
+            ..Default::default()
+        })?
     };
 
     Ok((
@@ -93,6 +104,113 @@ pub fn acquire_next_image(
     ))
 }
 
+/// Parameters to acquire the next image from a swapchain.
+#[derive(Clone, Debug)]
+pub struct AcquireNextImageInfo {
+    /// If no image is currently available, how long to wait for one to become available.
+    ///
+    /// Specify `None` to wait forever. This is only allowed if at least one image of the
+    /// swapchain is not currently acquired.
+    ///
+    /// The default value is `None`.
+    pub timeout: Option<Duration>,
+
+    /// The semaphore to signal when the image is available.
+    ///
+    /// The default value is `None`.
+    pub semaphore: Option<Arc<Semaphore>>,
+
+    /// The fence to signal when the image is available.
+    ///
+    /// The default value is `None`.
+    pub fence: Option<Arc<Fence>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for AcquireNextImageInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            semaphore: None,
+            fence: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl AcquireNextImageInfo {
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            timeout: _,
+            ref semaphore,
+            ref fence,
+            _ne: _,
+        } = self;
+
+        if semaphore.is_none() && fence.is_none() {
+            return Err(Box::new(ValidationError {
+                problem: "`semaphore` and `fence` are both `None`".into(),
+                vuids: &["VUID-vkAcquireNextImageKHR-semaphore-01780"],
+                ..Default::default()
+            }));
+        }
+
+        if let Some(semaphore) = semaphore {
+            // VUID-vkAcquireNextImageKHR-commonparent
+            assert_eq!(device, semaphore.device().as_ref());
+
+            if semaphore.semaphore_type() != SemaphoreType::Binary {
+                return Err(Box::new(ValidationError {
+                    context: "semaphore.semaphore_type()".into(),
+                    problem: "is not `SemaphoreType::Binary`".into(),
+                    vuids: &["VUID-vkAcquireNextImageKHR-semaphore-03265"],
+                    ..Default::default()
+                }));
+            }
+
+            let state = semaphore.state();
+
+            if state.is_signaled().unwrap_or(false) {
+                return Err(Box::new(ValidationError {
+                    context: "semaphore".into(),
+                    problem: "is already signaled".into(),
+                    vuids: &["VUID-vkAcquireNextImageKHR-semaphore-01286"],
+                    ..Default::default()
+                }));
+            }
+
+            if state.is_signal_pending() || state.is_wait_pending() {
+                return Err(Box::new(ValidationError {
+                    context: "semaphore".into(),
+                    problem: "has a pending signal or wait operation".into(),
+                    vuids: &["VUID-vkAcquireNextImageKHR-semaphore-01779"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if let Some(fence) = fence {
+            // VUID-vkAcquireNextImageKHR-commonparent
+            assert_eq!(device, fence.device().as_ref());
+
+            let state = fence.state();
+
+            if state.is_signaled().unwrap_or(false) || state.is_in_queue() {
+                return Err(Box::new(ValidationError {
+                    context: "fence".into(),
+                    problem: "is already signaled, or is in use by a queue operation".into(),
+                    vuids: &["VUID-vkAcquireNextImageKHR-fence-01287"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// Unsafe variant of `acquire_next_image`.
 ///
 /// # Safety
@@ -106,6 +224,10 @@ pub unsafe fn acquire_next_image_raw(
     semaphore: Option<&Semaphore>,
     fence: Option<&Fence>,
 ) -> Result<AcquiredImage, Validated<VulkanError>> {
+    if swapchain.device.is_lost() {
+        return Err(VulkanError::DeviceLost.into());
+    }
+
     let fns = swapchain.device.fns();
 
     let timeout_ns = if let Some(timeout) = timeout {
@@ -134,7 +256,7 @@ pub unsafe fn acquire_next_image_raw(
         ash::vk::Result::SUBOPTIMAL_KHR => true,
         ash::vk::Result::NOT_READY => return Err(VulkanError::NotReady.into()),
         ash::vk::Result::TIMEOUT => return Err(VulkanError::Timeout.into()),
-        err => return Err(VulkanError::from(err).into()),
+        err => return Err(swapchain.device.check_lost(VulkanError::from(err)).into()),
     };
 
     if let Some(semaphore) = semaphore {
@@ -153,8 +275,14 @@ pub unsafe fn acquire_next_image_raw(
     })
 }
 
+/// The result of acquiring an image from a swapchain.
+#[derive(Clone, Copy, Debug)]
 pub struct AcquiredImage {
+    /// The index of the acquired image in the swapchain's images.
     pub image_index: u32,
+
+    /// Whether the swapchain no longer matches the surface exactly. The image can still be used
+    /// and presented, but the swapchain should be recreated.
     pub suboptimal: bool,
 }
 
@@ -168,7 +296,7 @@ pub struct SwapchainAcquireFuture {
     semaphore: Option<Arc<Semaphore>>,
     // Fence that is signalled when the acquire is complete. Empty if the acquire has already
     // happened.
-    fence: Option<Fence>,
+    fence: Option<Arc<Fence>>,
     finished: AtomicBool,
 }
 
@@ -201,7 +329,7 @@ unsafe impl GpuFuture for SwapchainAcquireFuture {
 
     unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, Validated<VulkanError>> {
         if let Some(ref semaphore) = self.semaphore {
-            let sem = smallvec![semaphore.clone()];
+            let sem = smallvec![SemaphoreSubmitInfo::semaphore(semaphore.clone())];
             Ok(SubmitAnyBuilder::SemaphoresWait(sem))
         } else {
             Ok(SubmitAnyBuilder::Empty)
@@ -557,8 +685,41 @@ where
                 ..Default::default()
             }),
             SubmitAnyBuilder::SemaphoresWait(semaphores) => {
+                let mut wait_semaphores = Vec::new();
+                let mut timeline_waits = Vec::new();
+
+                for semaphore_submit_info in semaphores {
+                    match semaphore_submit_info.semaphore.semaphore_type() {
+                        SemaphoreType::Binary => {
+                            wait_semaphores.push(semaphore_submit_info.semaphore)
+                        }
+                        SemaphoreType::Timeline => timeline_waits.push(semaphore_submit_info),
+                    }
+                }
+
+                // Presentation can only wait on binary semaphores, so the timeline semaphore
+                // waits are forwarded to a binary semaphore with an empty queue submission.
+                if !timeline_waits.is_empty() {
+                    let semaphore = Arc::new(Semaphore::from_pool(device.clone())?);
+
+                    self.queue.with(|mut q| {
+                        q.submit_unchecked(
+                            [SubmitInfo {
+                                wait_semaphores: timeline_waits,
+                                signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
+                                    semaphore.clone(),
+                                )],
+                                ..Default::default()
+                            }],
+                            None,
+                        )
+                    })?;
+
+                    wait_semaphores.push(semaphore);
+                }
+
                 SubmitAnyBuilder::QueuePresent(PresentInfo {
-                    wait_semaphores: semaphores.into_iter().collect(),
+                    wait_semaphores,
                     swapchain_infos: vec![self.swapchain_info.clone()],
                     ..Default::default()
                 })
@@ -797,8 +958,12 @@ where
             }
 
             if !*self.finished.get_mut() {
-                // Block until the queue finished.
-                self.queue().unwrap().with(|mut q| q.wait_idle()).unwrap();
+                // Block until the queue finished. If the device was lost, the queue won't
+                // execute anything anymore, and the resources are released anyway.
+                match self.queue().unwrap().with(|mut q| q.wait_idle()) {
+                    Ok(()) | Err(VulkanError::DeviceLost) => (),
+                    Err(err) => panic!("{:?}", err),
+                }
                 self.previous.signal_finished();
             }
         }
@@ -857,7 +1022,7 @@ pub fn wait_for_present(
         ash::vk::Result::SUBOPTIMAL_KHR => Ok(true),
         ash::vk::Result::TIMEOUT => Err(VulkanError::Timeout.into()),
         err => {
-            let err = VulkanError::from(err);
+            let err = swapchain.device.check_lost(VulkanError::from(err));
 
             if matches!(err, VulkanError::FullScreenExclusiveModeLost) {
                 swapchain
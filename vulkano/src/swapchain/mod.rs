@@ -322,6 +322,20 @@
 //!     }
 //! }
 //! ```
+//!
+//! ## Full-screen exclusivity
+//!
+//! With the [`ext_full_screen_exclusive`](crate::device::DeviceExtensions::ext_full_screen_exclusive)
+//! extension, a swapchain can be given exclusive access to the display, which bypasses the
+//! compositor and can be required for variable refresh rate to engage. To control this
+//! yourself, create the swapchain with [`FullScreenExclusive::ApplicationControlled`], then call
+//! [`Swapchain::acquire_full_screen_exclusive_mode`] once the window is full-screen.
+//!
+//! Exclusivity can be lost at any time, for example when the user switches to another window.
+//! This is reported by acquire and present operations returning
+//! [`VulkanError::FullScreenExclusiveModeLost`]. The swapchain remains usable after this error,
+//! but no longer holds exclusivity; you can either acquire it again, or recreate the swapchain
+//! like for [`VulkanError::OutOfDate`].
 
 pub use self::{acquire_present::*, surface::*};
 #[cfg(target_os = "ios")]
@@ -443,6 +457,12 @@ impl Swapchain {
     ///
     /// Use this when a swapchain has become invalidated, such as due to window resizes.
     ///
+    /// `self` is passed to the implementation as the old swapchain, and becomes retired. After
+    /// this, no more images can be acquired from it, but images that were already acquired can
+    /// still be presented. The old swapchain and its images are kept alive for as long as there
+    /// are references to them, including those held by the futures of frames that are still in
+    /// flight, so it is not necessary to wait for those frames before recreating.
+    ///
     /// # Panics
     ///
     /// - Panics if `create_info.usage` is empty.
@@ -1373,12 +1393,93 @@ impl Swapchain {
         self.full_screen_exclusive
     }
 
+    /// Returns whether the swapchain has been retired, by being used to [`recreate`] a new
+    /// swapchain.
+    ///
+    /// [`recreate`]: Self::recreate
+    #[inline]
+    pub fn is_retired(&self) -> bool {
+        *self.is_retired.lock()
+    }
+
+    /// Acquires the next image from the swapchain, signaling the semaphore and/or fence in
+    /// `acquire_info` when the image is available.
+    ///
+    /// On success, returns the index of the image and whether the swapchain is suboptimal.
+    /// If no image could be acquired, one of the following errors is returned:
+    /// - [`VulkanError::Timeout`] if `timeout` is not zero, and no image became available within
+    ///   the timeout duration.
+    /// - [`VulkanError::NotReady`] if `timeout` is zero, and no image was immediately available.
+    /// - [`VulkanError::OutOfDate`] if the swapchain no longer matches the surface, or has been
+    ///   retired, and must be recreated.
+    ///
+    /// For a safe way to acquire an image, see [`acquire_next_image`] and
+    /// [`acquire_next_image_with_fence`].
+    ///
+    /// # Safety
+    ///
+    /// - The semaphore and fence, if provided, must be kept alive until they are signaled.
+    #[inline]
+    pub unsafe fn acquire_next_image(
+        &self,
+        acquire_info: &AcquireNextImageInfo,
+    ) -> Result<AcquiredImage, Validated<VulkanError>> {
+        // Check that this is not an old swapchain. From specs:
+        // > swapchain must not have been replaced by being passed as the
+        // > VkSwapchainCreateInfoKHR::oldSwapchain value to vkCreateSwapchainKHR
+        let is_retired = self.is_retired.lock();
+        if *is_retired {
+            return Err(VulkanError::OutOfDate.into());
+        }
+
+        self.validate_acquire_next_image(acquire_info)?;
+
+        Ok(self.acquire_next_image_unchecked(acquire_info)?)
+    }
+
+    fn validate_acquire_next_image(
+        &self,
+        acquire_info: &AcquireNextImageInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        acquire_info
+            .validate(&self.device)
+            .map_err(|err| err.add_context("acquire_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn acquire_next_image_unchecked(
+        &self,
+        acquire_info: &AcquireNextImageInfo,
+    ) -> Result<AcquiredImage, VulkanError> {
+        let &AcquireNextImageInfo {
+            timeout,
+            ref semaphore,
+            ref fence,
+            _ne: _,
+        } = acquire_info;
+
+        let result = acquire_next_image_raw(self, timeout, semaphore.as_deref(), fence.as_deref())
+            .map_err(Validated::unwrap);
+
+        if let Err(VulkanError::FullScreenExclusiveModeLost) = result {
+            self.full_screen_exclusive_held
+                .store(false, Ordering::SeqCst);
+        }
+
+        result
+    }
+
     /// Acquires full-screen exclusivity.
     ///
     /// The swapchain must have been created with [`FullScreenExclusive::ApplicationControlled`],
     /// and must not already hold full-screen exclusivity. Full-screen exclusivity is held until
-    /// either the `release_full_screen_exclusive` is called, or if any of the the other `Swapchain`
-    /// functions return `FullScreenExclusiveLost`.
+    /// either [`release_full_screen_exclusive_mode`] is called, or any operation on the
+    /// swapchain returns [`VulkanError::FullScreenExclusiveModeLost`].
+    ///
+    /// [`release_full_screen_exclusive_mode`]: Swapchain::release_full_screen_exclusive_mode
     #[inline]
     pub fn acquire_full_screen_exclusive_mode(&self) -> Result<(), Validated<VulkanError>> {
         self.validate_acquire_full_screen_exclusive_mode()?;
@@ -1428,7 +1529,12 @@ impl Swapchain {
         (fns.ext_full_screen_exclusive
             .acquire_full_screen_exclusive_mode_ext)(self.device.handle(), self.handle)
         .result()
-        .map_err(VulkanError::from)?;
+        .map_err(|err| {
+            // Exclusivity was not acquired, so it can be tried again later.
+            self.full_screen_exclusive_held
+                .store(false, Ordering::Relaxed);
+            VulkanError::from(err)
+        })?;
 
         Ok(())
     }
@@ -1479,10 +1585,11 @@ impl Swapchain {
         Ok(())
     }
 
-    /// `FullScreenExclusive::AppControlled` is not the active full-screen exclusivity mode,
-    /// then this function will always return false. If true is returned the swapchain
-    /// is in `FullScreenExclusive::AppControlled` full-screen exclusivity mode and exclusivity
-    /// is currently acquired.
+    /// Returns whether the swapchain currently holds full-screen exclusivity. If
+    /// `FullScreenExclusive::ApplicationControlled` is not the active full-screen exclusivity
+    /// mode, then this function will always return false. If true is returned the swapchain
+    /// is in `FullScreenExclusive::ApplicationControlled` full-screen exclusivity mode and
+    /// exclusivity is currently acquired.
     #[inline]
     pub fn is_full_screen_exclusive(&self) -> bool {
         if self.full_screen_exclusive != FullScreenExclusive::ApplicationControlled {
@@ -1492,6 +1599,89 @@ impl Swapchain {
         }
     }
 
+    /// Sets the HDR metadata of the swapchain, which describes the mastering display and the
+    /// content of the images that will be presented.
+    ///
+    /// The implementation may use this information to adjust the images for the display. The
+    /// metadata applies to the images that are presented after this call.
+    ///
+    /// The metadata is only meaningful if the swapchain was created with an HDR color space,
+    /// such as [`ColorSpace::Hdr10St2084`]. The color spaces that the surface supports, and that
+    /// can be used in [`SwapchainCreateInfo::image_color_space`], can be queried with
+    /// [`PhysicalDevice::surface_formats`].
+    ///
+    /// The [`ext_hdr_metadata`](crate::device::DeviceExtensions::ext_hdr_metadata) extension
+    /// must be enabled on the device.
+    ///
+    /// [`PhysicalDevice::surface_formats`]: crate::device::physical::PhysicalDevice::surface_formats
+    #[inline]
+    pub fn set_hdr_metadata(&self, metadata: &HdrMetadata) -> Result<(), Box<ValidationError>> {
+        self.validate_set_hdr_metadata(metadata)?;
+
+        unsafe {
+            self.set_hdr_metadata_unchecked(metadata);
+        }
+
+        Ok(())
+    }
+
+    fn validate_set_hdr_metadata(
+        &self,
+        metadata: &HdrMetadata,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device.enabled_extensions().ext_hdr_metadata {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_hdr_metadata",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        metadata
+            .validate()
+            .map_err(|err| err.add_context("metadata"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn set_hdr_metadata_unchecked(&self, metadata: &HdrMetadata) {
+        let &HdrMetadata {
+            display_primary_red,
+            display_primary_green,
+            display_primary_blue,
+            white_point,
+            max_luminance,
+            min_luminance,
+            max_content_light_level,
+            max_frame_average_light_level,
+            _ne: _,
+        } = metadata;
+
+        let xy_color_vk = |[x, y]: [f32; 2]| ash::vk::XYColorEXT { x, y };
+        let metadata_vk = ash::vk::HdrMetadataEXT {
+            display_primary_red: xy_color_vk(display_primary_red),
+            display_primary_green: xy_color_vk(display_primary_green),
+            display_primary_blue: xy_color_vk(display_primary_blue),
+            white_point: xy_color_vk(white_point),
+            max_luminance,
+            min_luminance,
+            max_content_light_level,
+            max_frame_average_light_level,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        (fns.ext_hdr_metadata.set_hdr_metadata_ext)(
+            self.device.handle(),
+            1,
+            &self.handle,
+            &metadata_vk,
+        );
+    }
+
     // This method is necessary to allow `SwapchainImage`s to signal when they have been
     // transitioned out of their initial `undefined` image layout.
     //
@@ -1642,9 +1832,22 @@ pub struct SwapchainCreateInfo {
     ///
     /// If this is `Some`, then the
     /// [`ext_swapchain_maintenance1`](crate::device::DeviceExtensions::ext_swapchain_maintenance1)
-    /// extension must be enabled on the device.
+    /// extension must be enabled on the device. The value must be supported by the surface for
+    /// `present_mode` and every present mode in `present_modes`, as reported by
+    /// [`SurfaceCapabilities::supported_present_scaling`] when querying the surface capabilities
+    /// with [`SurfaceInfo::present_mode`] set.
+    ///
+    /// If this is `Some`, then `image_extent` may be anywhere between
+    /// [`SurfaceCapabilities::min_scaled_image_extent`] and
+    /// [`SurfaceCapabilities::max_scaled_image_extent`], instead of between the unscaled minimum and
+    /// maximum extents.
     ///
     /// The default value is `None`.
+    ///
+    /// [`SurfaceCapabilities::supported_present_scaling`]: crate::swapchain::SurfaceCapabilities::supported_present_scaling
+    /// [`SurfaceInfo::present_mode`]: crate::swapchain::SurfaceInfo::present_mode
+    /// [`SurfaceCapabilities::min_scaled_image_extent`]: crate::swapchain::SurfaceCapabilities::min_scaled_image_extent
+    /// [`SurfaceCapabilities::max_scaled_image_extent`]: crate::swapchain::SurfaceCapabilities::max_scaled_image_extent
     pub scaling_behavior: Option<PresentScaling>,
 
     /// The horizontal and vertical alignment to use when the swapchain image, after applying
@@ -1654,9 +1857,13 @@ pub struct SwapchainCreateInfo {
     ///
     /// If this is `Some`, then the
     /// [`ext_swapchain_maintenance1`](crate::device::DeviceExtensions::ext_swapchain_maintenance1)
-    /// extension must be enabled on the device.
+    /// extension must be enabled on the device. The values must be supported by the surface for
+    /// `present_mode` and every present mode in `present_modes`, as reported by
+    /// [`SurfaceCapabilities::supported_present_gravity`].
     ///
     /// The default value is `None`.
+    ///
+    /// [`SurfaceCapabilities::supported_present_gravity`]: crate::swapchain::SurfaceCapabilities::supported_present_gravity
     pub present_gravity: Option<[PresentGravity; 2]>,
 
     /// How full-screen exclusivity is to be handled.
@@ -2102,11 +2309,105 @@ vulkan_enum! {
     Disallowed = DISALLOWED,
 
     /// Indicates the application will manage full-screen exclusive mode by using the
-    /// [`Swapchain::acquire_full_screen_exclusive()`] and
-    /// [`Swapchain::release_full_screen_exclusive()`] functions.
+    /// [`Swapchain::acquire_full_screen_exclusive_mode`] and
+    /// [`Swapchain::release_full_screen_exclusive_mode`] functions.
     ApplicationControlled = APPLICATION_CONTROLLED,
 }
 
+/// HDR metadata for a swapchain, describing the color volume of the display that the content was
+/// mastered on, and the light levels of the content.
+///
+/// Chromaticity coordinates are given as `[x, y]` in the CIE 1931 color space. Luminance values
+/// are given in nits (candela per square meter).
+#[derive(Clone, Debug)]
+pub struct HdrMetadata {
+    /// The chromaticity of the red primary of the mastering display.
+    ///
+    /// The default value is `[0.0; 2]`.
+    pub display_primary_red: [f32; 2],
+
+    /// The chromaticity of the green primary of the mastering display.
+    ///
+    /// The default value is `[0.0; 2]`.
+    pub display_primary_green: [f32; 2],
+
+    /// The chromaticity of the blue primary of the mastering display.
+    ///
+    /// The default value is `[0.0; 2]`.
+    pub display_primary_blue: [f32; 2],
+
+    /// The chromaticity of the white point of the mastering display.
+    ///
+    /// The default value is `[0.0; 2]`.
+    pub white_point: [f32; 2],
+
+    /// The maximum luminance of the mastering display.
+    ///
+    /// The default value is 0.0.
+    pub max_luminance: f32,
+
+    /// The minimum luminance of the mastering display.
+    ///
+    /// The default value is 0.0.
+    pub min_luminance: f32,
+
+    /// The maximum content light level (MaxCLL): the luminance of the brightest pixel in the
+    /// content.
+    ///
+    /// The default value is 0.0.
+    pub max_content_light_level: f32,
+
+    /// The maximum frame-average light level (MaxFALL): the highest average luminance of any
+    /// single frame in the content.
+    ///
+    /// The default value is 0.0.
+    pub max_frame_average_light_level: f32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for HdrMetadata {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            display_primary_red: [0.0; 2],
+            display_primary_green: [0.0; 2],
+            display_primary_blue: [0.0; 2],
+            white_point: [0.0; 2],
+            max_luminance: 0.0,
+            min_luminance: 0.0,
+            max_content_light_level: 0.0,
+            max_frame_average_light_level: 0.0,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl HdrMetadata {
+    pub(crate) fn validate(&self) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            display_primary_red: _,
+            display_primary_green: _,
+            display_primary_blue: _,
+            white_point: _,
+            max_luminance,
+            min_luminance,
+            max_content_light_level: _,
+            max_frame_average_light_level: _,
+            _ne: _,
+        } = self;
+
+        if min_luminance > max_luminance {
+            return Err(Box::new(ValidationError {
+                problem: "`min_luminance` is greater than `max_luminance`".into(),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+}
+
 /// A wrapper around a Win32 monitor handle.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Win32Monitor(pub(crate) ash::vk::HMONITOR);
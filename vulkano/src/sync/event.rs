@@ -202,7 +202,7 @@ impl Event {
             match result {
                 ash::vk::Result::EVENT_SET => Ok(true),
                 ash::vk::Result::EVENT_RESET => Ok(false),
-                err => Err(VulkanError::from(err)),
+                err => Err(self.device.check_lost(VulkanError::from(err))),
             }
         }
     }
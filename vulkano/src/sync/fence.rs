@@ -253,7 +253,7 @@ impl Fence {
             match result {
                 ash::vk::Result::SUCCESS => unsafe { state.set_signaled() },
                 ash::vk::Result::NOT_READY => return Ok(false),
-                err => return Err(VulkanError::from(err)),
+                err => return Err(self.device.check_lost(VulkanError::from(err))),
             }
         };
 
@@ -300,7 +300,7 @@ impl Fence {
 
             match result {
                 ash::vk::Result::SUCCESS => unsafe { state.set_signaled() },
-                err => return Err(VulkanError::from(err)),
+                err => return Err(self.device.check_lost(VulkanError::from(err))),
             }
         };
 
@@ -401,7 +401,7 @@ impl Fence {
                     .zip(&mut states)
                     .filter_map(|(fence, state)| state.set_signaled().map(|state| (state, fence)))
                     .collect(),
-                err => return Err(VulkanError::from(err)),
+                err => return Err(device.check_lost(VulkanError::from(err))),
             }
         };
 
@@ -1559,7 +1559,7 @@ pub(crate) struct FenceState {
 impl FenceState {
     /// If the fence is not in a queue and has no external references, returns the current status.
     #[inline]
-    fn is_signaled(&self) -> Option<bool> {
+    pub(crate) fn is_signaled(&self) -> Option<bool> {
         // If either of these is true, we can't be certain of the status.
         if self.is_in_queue() || self.has_external_reference() {
             None
@@ -1569,7 +1569,7 @@ impl FenceState {
     }
 
     #[inline]
-    fn is_in_queue(&self) -> bool {
+    pub(crate) fn is_in_queue(&self) -> bool {
         self.pending_signal.is_some()
     }
 
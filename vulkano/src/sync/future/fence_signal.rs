@@ -10,14 +10,13 @@
 use super::{AccessCheckError, GpuFuture};
 use crate::{
     buffer::Buffer,
-    command_buffer::{SemaphoreSubmitInfo, SubmitInfo},
+    command_buffer::SubmitInfo,
     device::{Device, DeviceOwned, Queue, QueueFlags},
     image::{Image, ImageLayout},
     swapchain::Swapchain,
     sync::{
         fence::Fence,
         future::{AccessError, SubmitAnyBuilder},
-        PipelineStages,
     },
     DeviceSize, Validated, ValidationError, VulkanError,
 };
@@ -66,7 +65,9 @@ pub enum FenceSignalFutureBehavior {
 /// Represents a fence being signaled after a previous event.
 ///
 /// Contrary to most other future types, it is possible to block the current thread until the event
-/// happens. This is done by calling the `wait()` function.
+/// happens. This is done by calling the `wait()` function. To check whether the event has happened
+/// without blocking, or to block with a timeout while keeping the future usable, call `poll()` or
+/// `wait_timeout()` instead.
 ///
 /// This can also be done through Rust's Async system by simply `.await`ing this object. Note though
 /// that (due to the Vulkan API fence design) this will spin to check the fence, rather than
@@ -159,24 +160,59 @@ where
     /// the specified timeout has elapsed and an error will be returned.
     ///
     /// If the wait is successful, this function also cleans any resource locked by previous
-    /// submissions.
+    /// submissions. If the wait fails, the future remains usable and can be waited on again.
     pub fn wait(&self, timeout: Option<Duration>) -> Result<(), Validated<VulkanError>> {
+        match self.wait_timeout(timeout)? {
+            true => Ok(()),
+            false => Err(VulkanError::Timeout.into()),
+        }
+    }
+
+    /// Blocks the current thread until the fence is signaled by the GPU or `timeout` elapses.
+    /// Performs a flush if necessary. Returns whether the fence was signaled.
+    ///
+    /// If `timeout` is `None`, then the wait is infinite.
+    ///
+    /// If the fence was signaled, this function also cleans any resource locked by previous
+    /// submissions. Otherwise, the resources are kept alive and the future remains usable, so
+    /// that it can be waited on again later.
+    pub fn wait_timeout(&self, timeout: Option<Duration>) -> Result<bool, Validated<VulkanError>> {
         let mut state = self.state.lock();
 
         self.flush_impl(&mut state)?;
 
         match replace(&mut *state, FenceSignalFutureState::Cleaned) {
-            FenceSignalFutureState::Flushed(previous, fence) => {
-                fence.wait(timeout)?;
-                unsafe {
-                    previous.signal_finished();
+            FenceSignalFutureState::Flushed(previous, fence) => match fence.wait(timeout) {
+                Ok(()) => {
+                    unsafe {
+                        previous.signal_finished();
+                    }
+                    Ok(true)
                 }
-                Ok(())
-            }
-            FenceSignalFutureState::Cleaned => Ok(()),
+                Err(err) => {
+                    *state = FenceSignalFutureState::Flushed(previous, fence);
+
+                    match err {
+                        VulkanError::Timeout => Ok(false),
+                        err => Err(err.into()),
+                    }
+                }
+            },
+            FenceSignalFutureState::Cleaned => Ok(true),
             _ => unreachable!(),
         }
     }
+
+    /// Returns whether the fence is signaled by the GPU, without blocking. Performs a flush if
+    /// necessary.
+    ///
+    /// This is equivalent to calling [`wait_timeout`](Self::wait_timeout) with a timeout of
+    /// zero. If the fence is signaled, this function also cleans any resource locked by
+    /// previous submissions.
+    #[inline]
+    pub fn poll(&self) -> Result<bool, Validated<VulkanError>> {
+        self.wait_timeout(Some(Duration::ZERO))
+    }
 }
 
 impl<F> FenceSignalFuture<F>
@@ -256,16 +292,7 @@ where
                         .with(|mut q| {
                             q.submit_unchecked(
                                 [SubmitInfo {
-                                    wait_semaphores: semaphores
-                                        .into_iter()
-                                        .map(|semaphore| {
-                                            SemaphoreSubmitInfo {
-                                                // TODO: correct stages ; hard
-                                                stages: PipelineStages::ALL_COMMANDS,
-                                                ..SemaphoreSubmitInfo::semaphore(semaphore)
-                                            }
-                                        })
-                                        .collect(),
+                                    wait_semaphores: semaphores.into_vec(),
                                     ..Default::default()
                                 }],
                                 None,
@@ -558,8 +585,12 @@ where
         match replace(&mut *state, FenceSignalFutureState::Cleaned) {
             FenceSignalFutureState::Flushed(previous, fence) => {
                 // This is a normal situation. Submitting worked.
-                // TODO: handle errors?
-                fence.wait(None).unwrap();
+                // If the device was lost, the fence may never be signaled, but the device won't
+                // execute anything anymore either.
+                match fence.wait(None) {
+                    Ok(()) | Err(VulkanError::DeviceLost) => (),
+                    Err(err) => panic!("{:?}", err),
+                }
                 unsafe {
                     previous.signal_finished();
                 }
@@ -640,3 +671,90 @@ where
         (**self).check_swapchain_image_acquired(swapchain, image_index, before)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+        },
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+        sync::{
+            future::wait_timeline_semaphore,
+            semaphore::{Semaphore, SemaphoreCreateInfo, SemaphoreType},
+            GpuFuture,
+        },
+        VulkanObject,
+    };
+    use std::{sync::Arc, time::Duration};
+
+    #[test]
+    fn wait_timeout_keeps_future_usable() {
+        let (device, queue) = gfx_dev_and_queue!(timeline_semaphore);
+
+        let timeline = Arc::new(
+            Semaphore::new(
+                device.clone(),
+                SemaphoreCreateInfo {
+                    semaphore_type: SemaphoreType::Timeline,
+                    ..Default::default()
+                },
+            )
+            .unwrap(),
+        );
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let buffer = Buffer::from_iter(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            [0_u32; 4],
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder.fill_buffer(buffer.clone(), 7).unwrap();
+        let command_buffer = builder.build().unwrap();
+
+        // The submission can't finish before the host signals the timeline semaphore.
+        let future = wait_timeline_semaphore(timeline.clone(), 1)
+            .then_execute(queue, command_buffer)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+
+        assert!(!future.poll().unwrap());
+        assert!(!future
+            .wait_timeout(Some(Duration::from_millis(10)))
+            .unwrap());
+
+        unsafe {
+            let signal_info = ash::vk::SemaphoreSignalInfo {
+                semaphore: timeline.handle(),
+                value: 1,
+                ..Default::default()
+            };
+            (device.fns().v1_2.signal_semaphore)(device.handle(), &signal_info)
+                .result()
+                .unwrap();
+        }
+
+        assert!(future.wait_timeout(None).unwrap());
+        assert!(future.poll().unwrap());
+        assert_eq!(*buffer.read().unwrap(), [7_u32; 4]);
+    }
+}
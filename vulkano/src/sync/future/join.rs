@@ -10,9 +10,11 @@
 use super::{AccessCheckError, GpuFuture, SubmitAnyBuilder};
 use crate::{
     buffer::Buffer,
+    command_buffer::SemaphoreSubmitInfo,
     device::{Device, DeviceOwned, Queue},
     image::{Image, ImageLayout},
     swapchain::Swapchain,
+    sync::semaphore::SemaphoreType,
     DeviceSize, Validated, VulkanError, VulkanObject,
 };
 use std::{ops::Range, sync::Arc};
@@ -33,6 +35,32 @@ where
     JoinFuture { first, second }
 }
 
+/// Appends the semaphore waits in `b` to `a`.
+///
+/// A timeline semaphore only needs to be waited on once, for the highest of the values, since
+/// signal operations on a timeline semaphore must be in increasing order. So if `a` already
+/// contains a wait on the same timeline semaphore, the two waits are combined instead.
+fn merge_semaphore_waits<W>(a: &mut W, b: impl IntoIterator<Item = SemaphoreSubmitInfo>)
+where
+    W: AsMut<[SemaphoreSubmitInfo]> + Extend<SemaphoreSubmitInfo>,
+{
+    for wait_b in b {
+        if wait_b.semaphore.semaphore_type() == SemaphoreType::Timeline {
+            if let Some(wait_a) = a
+                .as_mut()
+                .iter_mut()
+                .find(|wait_a| Arc::ptr_eq(&wait_a.semaphore, &wait_b.semaphore))
+            {
+                wait_a.value = wait_a.value.max(wait_b.value);
+                wait_a.stages |= wait_b.stages;
+                continue;
+            }
+        }
+
+        a.extend([wait_b]);
+    }
+}
+
 /// Two futures joined into one.
 #[must_use]
 pub struct JoinFuture<A, B> {
@@ -82,7 +110,7 @@ where
             (SubmitAnyBuilder::Empty, b) => b,
             (a, SubmitAnyBuilder::Empty) => a,
             (SubmitAnyBuilder::SemaphoresWait(mut a), SubmitAnyBuilder::SemaphoresWait(b)) => {
-                a.extend(b);
+                merge_semaphore_waits(&mut a, b);
                 SubmitAnyBuilder::SemaphoresWait(a)
             }
             (SubmitAnyBuilder::SemaphoresWait(a), SubmitAnyBuilder::CommandBuffer(_, _)) => {
@@ -118,9 +146,10 @@ where
                     "Can't merge two queue submits that both have a fence"
                 );
 
-                submit_info_a
-                    .wait_semaphores
-                    .extend(submit_info_b.wait_semaphores);
+                merge_semaphore_waits(
+                    &mut submit_info_a.wait_semaphores,
+                    submit_info_b.wait_semaphores,
+                );
                 submit_info_a
                     .command_buffers
                     .extend(submit_info_b.command_buffers);
@@ -287,3 +316,63 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::merge_semaphore_waits;
+    use crate::{
+        command_buffer::SemaphoreSubmitInfo,
+        sync::{
+            semaphore::{Semaphore, SemaphoreCreateInfo, SemaphoreType},
+            PipelineStages,
+        },
+    };
+    use smallvec::{smallvec, SmallVec};
+    use std::sync::Arc;
+
+    #[test]
+    fn merge_timeline_waits() {
+        let (device, _) = gfx_dev_and_queue!(timeline_semaphore);
+
+        let timeline = Arc::new(
+            Semaphore::new(
+                device.clone(),
+                SemaphoreCreateInfo {
+                    semaphore_type: SemaphoreType::Timeline,
+                    ..Default::default()
+                },
+            )
+            .unwrap(),
+        );
+        let binary_a = Arc::new(Semaphore::new(device.clone(), Default::default()).unwrap());
+        let binary_b = Arc::new(Semaphore::new(device, Default::default()).unwrap());
+
+        let mut a: SmallVec<[_; 8]> = smallvec![
+            SemaphoreSubmitInfo {
+                value: 3,
+                stages: PipelineStages::COMPUTE_SHADER,
+                ..SemaphoreSubmitInfo::semaphore(timeline.clone())
+            },
+            SemaphoreSubmitInfo::semaphore(binary_a),
+        ];
+        merge_semaphore_waits(
+            &mut a,
+            [
+                SemaphoreSubmitInfo {
+                    value: 5,
+                    stages: PipelineStages::VERTEX_SHADER,
+                    ..SemaphoreSubmitInfo::semaphore(timeline.clone())
+                },
+                SemaphoreSubmitInfo::semaphore(binary_b),
+            ],
+        );
+
+        assert_eq!(a.len(), 3);
+        assert!(Arc::ptr_eq(&a[0].semaphore, &timeline));
+        assert_eq!(a[0].value, 5);
+        assert_eq!(
+            a[0].stages,
+            PipelineStages::COMPUTE_SHADER | PipelineStages::VERTEX_SHADER,
+        );
+    }
+}
@@ -81,6 +81,21 @@
 //!
 //! TODO: this is not yet implemented
 //!
+//! ## Timeline semaphores
+//!
+//! If the [`timeline_semaphore`](crate::device::Features::timeline_semaphore) feature is
+//! enabled, `then_signal_timeline_semaphore()` can be used instead of `then_signal_semaphore()`.
+//! This signals a value on a timeline semaphore that you provide, instead of allocating a new
+//! binary semaphore. When futures that signal timeline semaphores are joined, for example to
+//! bring work from several queues back together, the operation that follows waits on all of the
+//! values in a single submission, and multiple values signaled on the same timeline semaphore
+//! only result in one wait.
+//!
+//! With `wait_timeline_semaphore()`, you can also build a future that waits for a value on a
+//! timeline semaphore, without being tied to the operation that signals it. Operations that come
+//! after this future can be submitted before the signal operation has been submitted, for
+//! example on another queue or from another thread.
+//!
 //! # Fences
 //!
 //! A `Fence` is an object that is used to signal the CPU when an operation on the GPU is finished.
@@ -95,12 +110,14 @@ pub use self::{
     join::JoinFuture,
     now::{now, NowFuture},
     semaphore_signal::SemaphoreSignalFuture,
+    semaphore_wait::{wait_timeline_semaphore, SemaphoreWaitFuture},
 };
 use super::{fence::Fence, semaphore::Semaphore};
 use crate::{
     buffer::Buffer,
     command_buffer::{
-        CommandBufferExecError, CommandBufferExecFuture, PrimaryCommandBufferAbstract, SubmitInfo,
+        CommandBufferExecError, CommandBufferExecFuture, PrimaryCommandBufferAbstract,
+        SemaphoreSubmitInfo, SubmitInfo,
     },
     device::{DeviceOwned, Queue},
     image::{Image, ImageLayout},
@@ -120,6 +137,7 @@ mod fence_signal;
 mod join;
 mod now;
 mod semaphore_signal;
+mod semaphore_wait;
 
 /// Represents an event that will happen on the GPU in the future.
 ///
@@ -308,6 +326,51 @@ pub unsafe trait GpuFuture: DeviceOwned {
         Ok(f)
     }
 
+    /// Signals a timeline semaphore with `value` after this future. Returns another future that
+    /// represents the signal.
+    ///
+    /// Unlike [`then_signal_semaphore`](GpuFuture::then_signal_semaphore), this doesn't allocate
+    /// a new binary semaphore. When several futures that signal timeline semaphores are
+    /// [joined](GpuFuture::join), their waits are merged into a single wait list, and waits on
+    /// the same timeline semaphore are combined into one wait on the highest value.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `semaphore` is not a [timeline semaphore], or was not created from the same
+    ///   device as this future.
+    ///
+    /// [timeline semaphore]: crate::sync::semaphore::SemaphoreType::Timeline
+    #[inline]
+    fn then_signal_timeline_semaphore(
+        self,
+        semaphore: Arc<Semaphore>,
+        value: u64,
+    ) -> SemaphoreSignalFuture<Self>
+    where
+        Self: Sized,
+    {
+        semaphore_signal::then_signal_timeline_semaphore(self, semaphore, value)
+    }
+
+    /// Signals a timeline semaphore with `value` after this future and flushes it. Returns
+    /// another future that represents the moment when the semaphore is signalled.
+    ///
+    /// This is a just a shortcut for `then_signal_timeline_semaphore()` followed with `flush()`.
+    #[inline]
+    fn then_signal_timeline_semaphore_and_flush(
+        self,
+        semaphore: Arc<Semaphore>,
+        value: u64,
+    ) -> Result<SemaphoreSignalFuture<Self>, Validated<VulkanError>>
+    where
+        Self: Sized,
+    {
+        let f = self.then_signal_timeline_semaphore(semaphore, value);
+        f.flush()?;
+
+        Ok(f)
+    }
+
     /// Signals a fence after this future. Returns another future that represents the signal.
     ///
     /// > **Note**: More often than not you want to immediately flush the future after calling this
@@ -462,7 +525,7 @@ where
 #[derive(Debug)]
 pub enum SubmitAnyBuilder {
     Empty,
-    SemaphoresWait(SmallVec<[Arc<Semaphore>; 8]>),
+    SemaphoresWait(SmallVec<[SemaphoreSubmitInfo; 8]>),
     CommandBuffer(SubmitInfo, Option<Arc<Fence>>),
     QueuePresent(PresentInfo),
     BindSparse(SmallVec<[BindSparseInfo; 1]>, Option<Arc<Fence>>),
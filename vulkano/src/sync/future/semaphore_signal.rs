@@ -14,8 +14,12 @@ use crate::{
     device::{Device, DeviceOwned, Queue},
     image::{Image, ImageLayout},
     swapchain::Swapchain,
-    sync::{future::AccessError, semaphore::Semaphore, PipelineStages},
-    DeviceSize, Validated, ValidationError, VulkanError,
+    sync::{
+        future::AccessError,
+        semaphore::{Semaphore, SemaphoreType},
+        PipelineStages,
+    },
+    DeviceSize, Validated, ValidationError, VulkanError, VulkanObject,
 };
 use parking_lot::Mutex;
 use smallvec::smallvec;
@@ -40,6 +44,34 @@ where
     SemaphoreSignalFuture {
         previous: future,
         semaphore: Arc::new(Semaphore::from_pool(device).unwrap()),
+        value: 0,
+        wait_submitted: Mutex::new(false),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// Builds a new timeline semaphore signal future.
+///
+/// # Panics
+///
+/// - Panics if `semaphore` is not a timeline semaphore, or was not created from the same device
+///   as `future`.
+pub fn then_signal_timeline_semaphore<F>(
+    future: F,
+    semaphore: Arc<Semaphore>,
+    value: u64,
+) -> SemaphoreSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    assert_eq!(future.device().handle(), semaphore.device().handle());
+    assert_eq!(semaphore.semaphore_type(), SemaphoreType::Timeline);
+    assert!(future.queue().is_some()); // TODO: document
+
+    SemaphoreSignalFuture {
+        previous: future,
+        semaphore,
+        value,
         wait_submitted: Mutex::new(false),
         finished: AtomicBool::new(false),
     }
@@ -54,6 +86,8 @@ where
 {
     previous: F,
     semaphore: Arc<Semaphore>,
+    // The value to signal, if `semaphore` is a timeline semaphore.
+    value: u64,
     // True if the signaling command has already been submitted.
     // If flush is called multiple times, we want to block so that only one flushing is executed.
     // Therefore we use a `Mutex<bool>` and not an `AtomicBool`.
@@ -61,6 +95,20 @@ where
     finished: AtomicBool,
 }
 
+impl<F> SemaphoreSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    fn semaphore_submit_info(&self) -> SemaphoreSubmitInfo {
+        SemaphoreSubmitInfo {
+            value: self.value,
+            // TODO: correct stages ; hard
+            stages: PipelineStages::ALL_COMMANDS,
+            ..SemaphoreSubmitInfo::semaphore(self.semaphore.clone())
+        }
+    }
+}
+
 unsafe impl<F> GpuFuture for SemaphoreSignalFuture<F>
 where
     F: GpuFuture,
@@ -70,9 +118,11 @@ where
     }
 
     unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, Validated<VulkanError>> {
-        // Flushing the signaling part, since it must always be submitted before the waiting part.
+        // Flushing the signaling part, since for binary semaphores it must always be submitted
+        // before the waiting part. Timeline semaphores don't have this requirement, but the
+        // signal still needs to be submitted by someone, and this future is the only owner of it.
         self.flush()?;
-        let sem = smallvec![self.semaphore.clone()];
+        let sem = smallvec![self.semaphore_submit_info()];
 
         Ok(SubmitAnyBuilder::SemaphoresWait(sem))
     }
@@ -92,9 +142,7 @@ where
                     queue.with(|mut q| {
                         q.submit_unchecked(
                             [SubmitInfo {
-                                signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
-                                    self.semaphore.clone(),
-                                )],
+                                signal_semaphores: vec![self.semaphore_submit_info()],
                                 ..Default::default()
                             }],
                             None,
@@ -105,19 +153,8 @@ where
                     queue.with(|mut q| {
                         q.submit_unchecked(
                             [SubmitInfo {
-                                wait_semaphores: semaphores
-                                    .into_iter()
-                                    .map(|semaphore| {
-                                        SemaphoreSubmitInfo {
-                                            // TODO: correct stages ; hard
-                                            stages: PipelineStages::ALL_COMMANDS,
-                                            ..SemaphoreSubmitInfo::semaphore(semaphore)
-                                        }
-                                    })
-                                    .collect(),
-                                signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
-                                    self.semaphore.clone(),
-                                )],
+                                wait_semaphores: semaphores.into_vec(),
+                                signal_semaphores: vec![self.semaphore_submit_info()],
                                 ..Default::default()
                             }],
                             None,
@@ -129,7 +166,7 @@ where
 
                     submit_info
                         .signal_semaphores
-                        .push(SemaphoreSubmitInfo::semaphore(self.semaphore.clone()));
+                        .push(self.semaphore_submit_info());
 
                     queue.with(|mut q| {
                         q.submit_with_future(submit_info, fence, &self.previous, &queue)
@@ -188,9 +225,7 @@ where
                         // FIXME: problematic because if we return an error and flush() is called again, then we'll submit the present twice
                         q.submit_unchecked(
                             [SubmitInfo {
-                                signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
-                                    self.semaphore.clone(),
-                                )],
+                                signal_semaphores: vec![self.semaphore_submit_info()],
                                 ..Default::default()
                             }],
                             None,
@@ -272,8 +307,12 @@ where
         if !*self.finished.get_mut() && !thread::panicking() {
             // TODO: handle errors?
             self.flush().unwrap();
-            // Block until the queue finished.
-            self.queue().unwrap().with(|mut q| q.wait_idle()).unwrap();
+            // Block until the queue finished. If the device was lost, the queue won't execute
+            // anything anymore, and the resources are released anyway.
+            match self.queue().unwrap().with(|mut q| q.wait_idle()) {
+                Ok(()) | Err(VulkanError::DeviceLost) => (),
+                Err(err) => panic!("{:?}", err),
+            }
             unsafe { self.previous.signal_finished() };
         }
     }
@@ -0,0 +1,128 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{AccessCheckError, GpuFuture, SubmitAnyBuilder};
+use crate::{
+    buffer::Buffer,
+    command_buffer::SemaphoreSubmitInfo,
+    device::{Device, DeviceOwned, Queue},
+    image::{Image, ImageLayout},
+    swapchain::Swapchain,
+    sync::{
+        semaphore::{Semaphore, SemaphoreType},
+        PipelineStages,
+    },
+    DeviceSize, Validated, VulkanError,
+};
+use smallvec::smallvec;
+use std::{ops::Range, sync::Arc};
+
+/// Builds a future that represents the moment when the counter of a timeline semaphore reaches
+/// `value`.
+///
+/// # Panics
+///
+/// - Panics if `semaphore` is not a timeline semaphore.
+pub fn wait_timeline_semaphore(semaphore: Arc<Semaphore>, value: u64) -> SemaphoreWaitFuture {
+    assert_eq!(semaphore.semaphore_type(), SemaphoreType::Timeline);
+
+    SemaphoreWaitFuture { semaphore, value }
+}
+
+/// Represents the moment when the counter of a timeline semaphore reaches a value.
+///
+/// Nothing is submitted for this future itself. Operations that are executed after it wait on
+/// the semaphore value as part of their own submission. The signal operation that sets the value
+/// does not need to be submitted yet at that point; it may be submitted later, on any queue of
+/// the device, or be performed from the host.
+///
+/// > **Note**: The signal operation must eventually be submitted. Waiting on the host for an
+/// > operation that comes after this future, for example by waiting on a fence, before
+/// > submitting the signal will block forever.
+pub struct SemaphoreWaitFuture {
+    semaphore: Arc<Semaphore>,
+    value: u64,
+}
+
+unsafe impl GpuFuture for SemaphoreWaitFuture {
+    #[inline]
+    fn cleanup_finished(&mut self) {}
+
+    #[inline]
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, Validated<VulkanError>> {
+        // Unlike for a binary semaphore, the wait on a timeline semaphore value can be submitted
+        // before the signal, so there is nothing to flush here.
+        Ok(SubmitAnyBuilder::SemaphoresWait(smallvec![
+            SemaphoreSubmitInfo {
+                value: self.value,
+                // TODO: correct stages ; hard
+                stages: PipelineStages::ALL_COMMANDS,
+                ..SemaphoreSubmitInfo::semaphore(self.semaphore.clone())
+            }
+        ]))
+    }
+
+    #[inline]
+    fn flush(&self) -> Result<(), Validated<VulkanError>> {
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn signal_finished(&self) {}
+
+    #[inline]
+    fn queue_change_allowed(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn queue(&self) -> Option<Arc<Queue>> {
+        None
+    }
+
+    #[inline]
+    fn check_buffer_access(
+        &self,
+        _buffer: &Buffer,
+        _range: Range<DeviceSize>,
+        _exclusive: bool,
+        _queue: &Queue,
+    ) -> Result<(), AccessCheckError> {
+        Err(AccessCheckError::Unknown)
+    }
+
+    #[inline]
+    fn check_image_access(
+        &self,
+        _image: &Image,
+        _range: Range<DeviceSize>,
+        _exclusive: bool,
+        _expected_layout: ImageLayout,
+        _queue: &Queue,
+    ) -> Result<(), AccessCheckError> {
+        Err(AccessCheckError::Unknown)
+    }
+
+    #[inline]
+    fn check_swapchain_image_acquired(
+        &self,
+        _swapchain: &Swapchain,
+        _image_index: u32,
+        _before: bool,
+    ) -> Result<(), AccessCheckError> {
+        Err(AccessCheckError::Unknown)
+    }
+}
+
+unsafe impl DeviceOwned for SemaphoreWaitFuture {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.semaphore.device()
+    }
+}
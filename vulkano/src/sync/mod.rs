@@ -22,7 +22,7 @@ pub use self::{
     future::{now, GpuFuture},
     pipeline::{
         AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo, ImageMemoryBarrier,
-        MemoryBarrier, PipelineStage, PipelineStages, QueueFamilyOwnershipTransfer,
+        MemoryBarrier, PipelineStage, PipelineStages, QueueFamilyOwnershipTransfer, ResourceUse,
     },
 };
 use crate::{device::Queue, ValidationError, VulkanError};
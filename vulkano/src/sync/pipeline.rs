@@ -8,7 +8,7 @@
 // according to those terms.
 
 use crate::{
-    buffer::Buffer,
+    buffer::{Buffer, Subbuffer},
     descriptor_set::layout::DescriptorType,
     device::{Device, QueueFlags},
     image::{
@@ -16,6 +16,7 @@ use crate::{
     },
     macros::{vulkan_bitflags, vulkan_bitflags_enum},
     shader::ShaderStages,
+    sync::Sharing,
     DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError,
 };
 use ahash::HashMap;
@@ -2427,6 +2428,20 @@ impl BufferMemoryBarrier {
         }
     }
 
+    /// Returns a barrier over the range of `subbuffer`, that makes the writes of the `from` use
+    /// available to the `to` use.
+    #[inline]
+    pub fn handoff(subbuffer: &Subbuffer<impl ?Sized>, from: ResourceUse, to: ResourceUse) -> Self {
+        Self {
+            src_stages: from.stages(),
+            src_access: from.access(),
+            dst_stages: to.stages(),
+            dst_access: to.access(),
+            range: subbuffer.offset()..subbuffer.offset() + subbuffer.size(),
+            ..Self::buffer(subbuffer.buffer().clone())
+        }
+    }
+
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             src_stages,
@@ -2943,9 +2958,28 @@ impl BufferMemoryBarrier {
             }
 
             // VUID-VkBufferMemoryBarrier2-srcQueueFamilyIndex-04087
-            // VUID-VkBufferMemoryBarrier2-buffer-04088
             // Ensured by the use of an enum.
 
+            if queue_family_ownership_transfer.is_exclusive()
+                != matches!(buffer.sharing(), Sharing::Exclusive)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: if queue_family_ownership_transfer.is_exclusive() {
+                        "`queue_family_ownership_transfer` is a transfer for an exclusive \
+                        resource, but `buffer` was not created with `Sharing::Exclusive`"
+                    } else {
+                        "`queue_family_ownership_transfer` is a transfer for a concurrent \
+                        resource, but `buffer` was not created with `Sharing::Concurrent`"
+                    }
+                    .into(),
+                    vuids: &[
+                        "VUID-VkBufferMemoryBarrier2-buffer-04088",
+                        "VUID-VkBufferMemoryBarrier2-buffer-04089",
+                    ],
+                    ..Default::default()
+                }));
+            }
+
             let queue_family_count =
                 device.physical_device().queue_family_properties().len() as u32;
 
@@ -3046,6 +3080,80 @@ impl BufferMemoryBarrier {
     }
 }
 
+/// A common way in which a buffer is used by the device, for building barriers between producers
+/// and consumers of the buffer's data.
+///
+/// Each value corresponds to one pipeline stage and the access types of that stage, as returned
+/// by [`stages`](Self::stages) and [`access`](Self::access).
+///
+/// Host access is not included. Host writes are made visible to the device when a command buffer
+/// is submitted, and device writes are made visible to the host with
+/// [`make_host_readable`](crate::command_buffer::AutoCommandBufferBuilder::make_host_readable).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ResourceUse {
+    /// Read by a compute shader, as a uniform or storage buffer.
+    ComputeShaderRead,
+
+    /// Written by a compute shader, as a storage buffer.
+    ComputeShaderWrite,
+
+    /// Read as a vertex buffer.
+    VertexRead,
+
+    /// Read as an index buffer.
+    IndexRead,
+
+    /// Read as an indirect buffer, by an indirect draw or dispatch command.
+    IndirectRead,
+
+    /// Read by a vertex shader, as a uniform or storage buffer.
+    VertexShaderRead,
+
+    /// Read by a fragment shader, as a uniform or storage buffer.
+    FragmentShaderRead,
+
+    /// Written by a fragment shader, as a storage buffer.
+    FragmentShaderWrite,
+
+    /// Read by a transfer command.
+    TransferRead,
+
+    /// Written by a transfer command.
+    TransferWrite,
+}
+
+impl ResourceUse {
+    /// Returns the pipeline stage in which the use happens.
+    #[inline]
+    pub fn stages(self) -> PipelineStages {
+        match self {
+            Self::ComputeShaderRead | Self::ComputeShaderWrite => PipelineStages::COMPUTE_SHADER,
+            Self::VertexRead | Self::IndexRead => PipelineStages::VERTEX_INPUT,
+            Self::IndirectRead => PipelineStages::DRAW_INDIRECT,
+            Self::VertexShaderRead => PipelineStages::VERTEX_SHADER,
+            Self::FragmentShaderRead | Self::FragmentShaderWrite => PipelineStages::FRAGMENT_SHADER,
+            Self::TransferRead | Self::TransferWrite => PipelineStages::ALL_TRANSFER,
+        }
+    }
+
+    /// Returns the types of memory access that the use performs.
+    #[inline]
+    pub fn access(self) -> AccessFlags {
+        match self {
+            Self::ComputeShaderRead | Self::VertexShaderRead | Self::FragmentShaderRead => {
+                AccessFlags::UNIFORM_READ | AccessFlags::SHADER_READ
+            }
+            Self::ComputeShaderWrite | Self::FragmentShaderWrite => AccessFlags::SHADER_WRITE,
+            Self::VertexRead => AccessFlags::VERTEX_ATTRIBUTE_READ,
+            Self::IndexRead => AccessFlags::INDEX_READ,
+            Self::IndirectRead => AccessFlags::INDIRECT_COMMAND_READ,
+            Self::TransferRead => AccessFlags::TRANSFER_READ,
+            Self::TransferWrite => AccessFlags::TRANSFER_WRITE,
+        }
+    }
+}
+
 /// A memory barrier that is applied to a single image.
 #[derive(Clone, Debug)]
 pub struct ImageMemoryBarrier {
@@ -3110,6 +3218,62 @@ impl ImageMemoryBarrier {
         }
     }
 
+    /// Returns a barrier over all subresources of `image`, that transitions them from
+    /// [`ImageLayout::Undefined`] to [`ImageLayout::TransferDstOptimal`], so that they can be
+    /// written by a transfer command, such as a copy from a buffer.
+    ///
+    /// The previous contents of the image are discarded.
+    #[inline]
+    pub fn undefined_to_transfer_dst(image: Arc<Image>) -> Self {
+        Self {
+            dst_stages: PipelineStages::ALL_TRANSFER,
+            dst_access: AccessFlags::TRANSFER_WRITE,
+            old_layout: ImageLayout::Undefined,
+            new_layout: ImageLayout::TransferDstOptimal,
+            subresource_range: image.subresource_range(),
+            ..Self::image(image)
+        }
+    }
+
+    /// Returns a barrier over all subresources of `image`, that makes the writes of a transfer
+    /// command available to fragment shaders, and transitions the subresources from
+    /// [`ImageLayout::TransferDstOptimal`] to [`ImageLayout::ShaderReadOnlyOptimal`].
+    ///
+    /// This is the usual barrier after uploading a texture. If the image is read in other
+    /// shader stages as well, add them to `dst_stages`.
+    #[inline]
+    pub fn transfer_dst_to_shader_read(image: Arc<Image>) -> Self {
+        Self {
+            src_stages: PipelineStages::ALL_TRANSFER,
+            src_access: AccessFlags::TRANSFER_WRITE,
+            dst_stages: PipelineStages::FRAGMENT_SHADER,
+            dst_access: AccessFlags::SHADER_READ,
+            old_layout: ImageLayout::TransferDstOptimal,
+            new_layout: ImageLayout::ShaderReadOnlyOptimal,
+            subresource_range: image.subresource_range(),
+            ..Self::image(image)
+        }
+    }
+
+    /// Returns a barrier over all subresources of `image`, that waits for the writes to it as a
+    /// color attachment, and transitions the subresources from
+    /// [`ImageLayout::ColorAttachmentOptimal`] to [`ImageLayout::PresentSrc`], so that it can be
+    /// presented to a swapchain.
+    ///
+    /// The destination scope is empty, because the presentation engine is synchronized with the
+    /// semaphores of the present operation instead.
+    #[inline]
+    pub fn color_attachment_to_present(image: Arc<Image>) -> Self {
+        Self {
+            src_stages: PipelineStages::COLOR_ATTACHMENT_OUTPUT,
+            src_access: AccessFlags::COLOR_ATTACHMENT_WRITE,
+            old_layout: ImageLayout::ColorAttachmentOptimal,
+            new_layout: ImageLayout::PresentSrc,
+            subresource_range: image.subresource_range(),
+            ..Self::image(image)
+        }
+    }
+
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             src_stages,
@@ -3592,8 +3756,18 @@ impl ImageMemoryBarrier {
         // VUID-VkImageMemoryBarrier2-synchronization2-07794
         // If the synchronization2 feature is not enabled, newLayout must not be VK_IMAGE_LAYOUT_ATTACHMENT_OPTIMAL_KHR or VK_IMAGE_LAYOUT_READ_ONLY_OPTIMAL_KHR
 
-        // VUID-VkImageMemoryBarrier2-attachmentFeedbackLoopLayout-07313
-        // If the attachmentFeedbackLoopLayout feature is not enabled, newLayout must not be VK_IMAGE_LAYOUT_ATTACHMENT_FEEDBACK_LOOP_OPTIMAL_EXT
+        if new_layout == ImageLayout::AttachmentFeedbackLoopOptimal
+            && !device.enabled_features().attachment_feedback_loop_layout
+        {
+            return Err(Box::new(ValidationError {
+                context: "new_layout".into(),
+                problem: "is `ImageLayout::AttachmentFeedbackLoopOptimal`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "attachment_feedback_loop_layout",
+                )])]),
+                vuids: &["VUID-VkImageMemoryBarrier2-attachmentFeedbackLoopLayout-07313"],
+            }));
+        }
 
         subresource_range
             .validate(device)
@@ -3755,9 +3929,28 @@ impl ImageMemoryBarrier {
             }
 
             // VUID-VkImageMemoryBarrier2-srcQueueFamilyIndex-04070
-            // VUID-VkImageMemoryBarrier2-image-04071
             // Ensured by the use of an enum.
 
+            if queue_family_ownership_transfer.is_exclusive()
+                != matches!(image.sharing(), Sharing::Exclusive)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: if queue_family_ownership_transfer.is_exclusive() {
+                        "`queue_family_ownership_transfer` is a transfer for an exclusive \
+                        resource, but `image` was not created with `Sharing::Exclusive`"
+                    } else {
+                        "`queue_family_ownership_transfer` is a transfer for a concurrent \
+                        resource, but `image` was not created with `Sharing::Concurrent`"
+                    }
+                    .into(),
+                    vuids: &[
+                        "VUID-VkImageMemoryBarrier2-image-04071",
+                        "VUID-VkImageMemoryBarrier2-image-04072",
+                    ],
+                    ..Default::default()
+                }));
+            }
+
             let queue_family_count =
                 device.physical_device().queue_family_properties().len() as u32;
 
@@ -3963,6 +4156,37 @@ impl ImageMemoryBarrier {
                         }));
                     }
                 }
+                ImageLayout::AttachmentFeedbackLoopOptimal => {
+                    if !old_layout.is_usage_supported(image.usage()) {
+                        return Err(Box::new(ValidationError {
+                            problem:
+                                "`old_layout` is `ImageLayout::AttachmentFeedbackLoopOptimal`, but \
+                                `image.usage()` does not contain \
+                                `ImageUsage::ATTACHMENT_FEEDBACK_LOOP`, at least one of \
+                                `ImageUsage::COLOR_ATTACHMENT` and \
+                                `ImageUsage::DEPTH_STENCIL_ATTACHMENT`, and at least one of \
+                                `ImageUsage::SAMPLED` and `ImageUsage::INPUT_ATTACHMENT`"
+                                    .into(),
+                            vuids: &[
+                                "VUID-VkImageMemoryBarrier2-srcQueueFamilyIndex-07006",
+                                "VUID-VkImageMemoryBarrier2-srcQueueFamilyIndex-07120",
+                            ],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                ImageLayout::FragmentDensityMapOptimal => {
+                    if !image.usage().intersects(ImageUsage::FRAGMENT_DENSITY_MAP) {
+                        return Err(Box::new(ValidationError {
+                            problem: "`old_layout` is `ImageLayout::FragmentDensityMapOptimal`, \
+                                but `image.usage()` does not contain \
+                                `ImageUsage::FRAGMENT_DENSITY_MAP`"
+                                .into(),
+                            vuids: &["VUID-VkImageMemoryBarrier2-srcQueueFamilyIndex-02088"],
+                            ..Default::default()
+                        }));
+                    }
+                }
                 ImageLayout::Undefined | ImageLayout::General | ImageLayout::PresentSrc => (),
             }
 
@@ -4078,6 +4302,37 @@ impl ImageMemoryBarrier {
                         }));
                     }
                 }
+                ImageLayout::AttachmentFeedbackLoopOptimal => {
+                    if !new_layout.is_usage_supported(image.usage()) {
+                        return Err(Box::new(ValidationError {
+                            problem:
+                                "`new_layout` is `ImageLayout::AttachmentFeedbackLoopOptimal`, but \
+                                `image.usage()` does not contain \
+                                `ImageUsage::ATTACHMENT_FEEDBACK_LOOP`, at least one of \
+                                `ImageUsage::COLOR_ATTACHMENT` and \
+                                `ImageUsage::DEPTH_STENCIL_ATTACHMENT`, and at least one of \
+                                `ImageUsage::SAMPLED` and `ImageUsage::INPUT_ATTACHMENT`"
+                                    .into(),
+                            vuids: &[
+                                "VUID-VkImageMemoryBarrier2-srcQueueFamilyIndex-07006",
+                                "VUID-VkImageMemoryBarrier2-srcQueueFamilyIndex-07120",
+                            ],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                ImageLayout::FragmentDensityMapOptimal => {
+                    if !image.usage().intersects(ImageUsage::FRAGMENT_DENSITY_MAP) {
+                        return Err(Box::new(ValidationError {
+                            problem: "`new_layout` is `ImageLayout::FragmentDensityMapOptimal`, \
+                                but `image.usage()` does not contain \
+                                `ImageUsage::FRAGMENT_DENSITY_MAP`"
+                                .into(),
+                            vuids: &["VUID-VkImageMemoryBarrier2-srcQueueFamilyIndex-02088"],
+                            ..Default::default()
+                        }));
+                    }
+                }
                 ImageLayout::General | ImageLayout::PresentSrc => (),
             }
 
@@ -4220,6 +4475,50 @@ pub enum QueueFamilyOwnershipTransfer {
     ConcurrentFromForeign,
 }
 
+impl QueueFamilyOwnershipTransfer {
+    /// Returns whether the transfer is for a resource with [`Sharing::Exclusive`].
+    ///
+    /// [`Sharing::Exclusive`]: crate::sync::Sharing::Exclusive
+    #[inline]
+    pub fn is_exclusive(self) -> bool {
+        matches!(
+            self,
+            Self::ExclusiveBetweenLocal { .. }
+                | Self::ExclusiveToExternal { .. }
+                | Self::ExclusiveFromExternal { .. }
+                | Self::ExclusiveToForeign { .. }
+                | Self::ExclusiveFromForeign { .. }
+        )
+    }
+
+    /// Returns whether a barrier with this transfer can be recorded in a command buffer for a
+    /// queue of the queue family `queue_family_index`.
+    ///
+    /// A transfer consists of a release operation, which must be executed on a queue of the
+    /// source queue family, and an acquire operation, which must be executed on a queue of the
+    /// destination queue family. Both operations are recorded with the same barrier. For
+    /// transfers of concurrent resources, the local queue family is not specified, so this
+    /// returns `true` for any queue family.
+    #[inline]
+    pub fn is_valid_for_queue_family(self, queue_family_index: u32) -> bool {
+        match self {
+            Self::ExclusiveBetweenLocal {
+                src_index,
+                dst_index,
+            } => queue_family_index == src_index || queue_family_index == dst_index,
+            Self::ExclusiveToExternal { src_index } | Self::ExclusiveToForeign { src_index } => {
+                queue_family_index == src_index
+            }
+            Self::ExclusiveFromExternal { dst_index }
+            | Self::ExclusiveFromForeign { dst_index } => queue_family_index == dst_index,
+            Self::ConcurrentToExternal
+            | Self::ConcurrentFromExternal
+            | Self::ConcurrentToForeign
+            | Self::ConcurrentFromForeign => true,
+        }
+    }
+}
+
 impl From<QueueFamilyOwnershipTransfer> for (u32, u32) {
     fn from(val: QueueFamilyOwnershipTransfer) -> Self {
         match val {
@@ -4258,3 +4557,181 @@ impl From<QueueFamilyOwnershipTransfer> for (u32, u32) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AccessFlags, BufferMemoryBarrier, ImageMemoryBarrier, PipelineStages,
+        QueueFamilyOwnershipTransfer, ResourceUse,
+    };
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        format::Format,
+        image::{Image, ImageCreateInfo, ImageLayout, ImageUsage},
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    };
+
+    #[test]
+    fn ownership_transfer_sharing_mode() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+
+        let buffer = Buffer::new_slice::<u32>(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+
+        // The buffer was created with `Sharing::Exclusive`.
+        let err = BufferMemoryBarrier {
+            queue_family_ownership_transfer: Some(
+                QueueFamilyOwnershipTransfer::ConcurrentToExternal,
+            ),
+            range: 0..buffer.size(),
+            ..BufferMemoryBarrier::buffer(buffer.buffer().clone())
+        }
+        .validate(&device)
+        .unwrap_err();
+        assert_eq!(
+            err.vuids,
+            &[
+                "VUID-VkBufferMemoryBarrier2-buffer-04088",
+                "VUID-VkBufferMemoryBarrier2-buffer-04089",
+            ],
+        );
+    }
+
+    #[test]
+    fn attachment_feedback_loop_layout_usage() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_attachment_feedback_loop_layout],
+            features: [attachment_feedback_loop_layout],
+        );
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+
+        let barrier = |usage| {
+            let image = Image::new(
+                &memory_allocator,
+                ImageCreateInfo {
+                    format: Format::R8G8B8A8_UNORM,
+                    extent: [16, 16, 1],
+                    usage,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap();
+
+            ImageMemoryBarrier {
+                old_layout: ImageLayout::Undefined,
+                new_layout: ImageLayout::AttachmentFeedbackLoopOptimal,
+                subresource_range: image.subresource_range(),
+                ..ImageMemoryBarrier::image(image)
+            }
+        };
+
+        // The `ATTACHMENT_FEEDBACK_LOOP` usage is missing.
+        let err = barrier(ImageUsage::COLOR_ATTACHMENT | ImageUsage::SAMPLED)
+            .validate(&device)
+            .unwrap_err();
+        assert!(err
+            .vuids
+            .contains(&"VUID-VkImageMemoryBarrier2-srcQueueFamilyIndex-07120"));
+
+        barrier(
+            ImageUsage::COLOR_ATTACHMENT
+                | ImageUsage::SAMPLED
+                | ImageUsage::ATTACHMENT_FEEDBACK_LOOP,
+        )
+        .validate(&device)
+        .unwrap();
+    }
+
+    #[test]
+    fn buffer_handoff() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+
+        let buffer = Buffer::new_slice::<u32>(
+            &memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER | BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+        let subbuffer = buffer.slice(4..12);
+
+        let barrier = BufferMemoryBarrier::handoff(
+            &subbuffer,
+            ResourceUse::ComputeShaderWrite,
+            ResourceUse::VertexRead,
+        );
+        assert_eq!(barrier.src_stages, PipelineStages::COMPUTE_SHADER);
+        assert_eq!(barrier.src_access, AccessFlags::SHADER_WRITE);
+        assert_eq!(barrier.dst_stages, PipelineStages::VERTEX_INPUT);
+        assert_eq!(barrier.dst_access, AccessFlags::VERTEX_ATTRIBUTE_READ);
+        assert_eq!(barrier.range, 16..48);
+        barrier.validate(&device).unwrap();
+    }
+
+    #[test]
+    fn image_layout_transition_presets() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+
+        let image = Image::new(
+            &memory_allocator,
+            ImageCreateInfo {
+                format: Format::R8G8B8A8_UNORM,
+                extent: [16, 16, 1],
+                array_layers: 2,
+                mip_levels: 3,
+                usage: ImageUsage::TRANSFER_DST
+                    | ImageUsage::SAMPLED
+                    | ImageUsage::COLOR_ATTACHMENT,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        let barrier = ImageMemoryBarrier::undefined_to_transfer_dst(image.clone());
+        assert_eq!(barrier.src_stages, PipelineStages::empty());
+        assert_eq!(barrier.src_access, AccessFlags::empty());
+        assert_eq!(barrier.dst_stages, PipelineStages::ALL_TRANSFER);
+        assert_eq!(barrier.dst_access, AccessFlags::TRANSFER_WRITE);
+        assert_eq!(barrier.old_layout, ImageLayout::Undefined);
+        assert_eq!(barrier.new_layout, ImageLayout::TransferDstOptimal);
+        assert_eq!(barrier.subresource_range, image.subresource_range());
+        barrier.validate(&device).unwrap();
+
+        let barrier = ImageMemoryBarrier::transfer_dst_to_shader_read(image.clone());
+        assert_eq!(barrier.src_stages, PipelineStages::ALL_TRANSFER);
+        assert_eq!(barrier.src_access, AccessFlags::TRANSFER_WRITE);
+        assert_eq!(barrier.dst_stages, PipelineStages::FRAGMENT_SHADER);
+        assert_eq!(barrier.dst_access, AccessFlags::SHADER_READ);
+        assert_eq!(barrier.old_layout, ImageLayout::TransferDstOptimal);
+        assert_eq!(barrier.new_layout, ImageLayout::ShaderReadOnlyOptimal);
+        assert_eq!(barrier.subresource_range, image.subresource_range());
+        barrier.validate(&device).unwrap();
+
+        // `ImageLayout::PresentSrc` requires the `khr_swapchain` extension, so this one is not
+        // validated.
+        let barrier = ImageMemoryBarrier::color_attachment_to_present(image.clone());
+        assert_eq!(barrier.src_stages, PipelineStages::COLOR_ATTACHMENT_OUTPUT);
+        assert_eq!(barrier.src_access, AccessFlags::COLOR_ATTACHMENT_WRITE);
+        assert_eq!(barrier.dst_stages, PipelineStages::empty());
+        assert_eq!(barrier.dst_access, AccessFlags::empty());
+        assert_eq!(barrier.old_layout, ImageLayout::ColorAttachmentOptimal);
+        assert_eq!(barrier.new_layout, ImageLayout::PresentSrc);
+        assert_eq!(barrier.subresource_range, image.subresource_range());
+    }
+}
@@ -13,7 +13,7 @@
 use crate::{
     device::{physical::PhysicalDevice, Device, DeviceOwned, Queue},
     instance::InstanceOwnedDebugWrapper,
-    macros::{impl_id_counter, vulkan_bitflags, vulkan_bitflags_enum},
+    macros::{impl_id_counter, vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum},
     Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version, VulkanError,
     VulkanObject,
 };
@@ -30,7 +30,10 @@ use std::{
 /// Used to provide synchronization between command buffers during their execution.
 ///
 /// It is similar to a fence, except that it is purely on the GPU side. The CPU can't query a
-/// semaphore's status or wait for it to be signaled.
+/// binary semaphore's status or wait for it to be signaled.
+///
+/// A semaphore can be created as a [timeline semaphore](SemaphoreType::Timeline), which holds a
+/// counter value instead of a signaled state.
 #[derive(Debug)]
 pub struct Semaphore {
     handle: ash::vk::Semaphore,
@@ -38,6 +41,7 @@ pub struct Semaphore {
     id: NonZeroU64,
     must_put_in_pool: bool,
 
+    semaphore_type: SemaphoreType,
     export_handle_types: ExternalSemaphoreHandleTypes,
 
     state: Mutex<SemaphoreState>,
@@ -72,6 +76,8 @@ impl Semaphore {
         create_info: SemaphoreCreateInfo,
     ) -> Result<Semaphore, VulkanError> {
         let SemaphoreCreateInfo {
+            semaphore_type,
+            initial_value,
             export_handle_types,
             _ne: _,
         } = create_info;
@@ -80,8 +86,17 @@ impl Semaphore {
             flags: ash::vk::SemaphoreCreateFlags::empty(),
             ..Default::default()
         };
+        let mut semaphore_type_create_info_vk = None;
         let mut export_semaphore_create_info_vk = None;
 
+        if semaphore_type != SemaphoreType::Binary {
+            let _ = semaphore_type_create_info_vk.insert(ash::vk::SemaphoreTypeCreateInfo {
+                semaphore_type: semaphore_type.into(),
+                initial_value,
+                ..Default::default()
+            });
+        }
+
         if !export_handle_types.is_empty() {
             let _ = export_semaphore_create_info_vk.insert(ash::vk::ExportSemaphoreCreateInfo {
                 handle_types: export_handle_types.into(),
@@ -89,6 +104,11 @@ impl Semaphore {
             });
         };
 
+        if let Some(info) = semaphore_type_create_info_vk.as_mut() {
+            info.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = info as *const _ as *const _;
+        }
+
         if let Some(info) = export_semaphore_create_info_vk.as_mut() {
             info.p_next = create_info_vk.p_next;
             create_info_vk.p_next = info as *const _ as *const _;
@@ -126,6 +146,7 @@ impl Semaphore {
                 device: InstanceOwnedDebugWrapper(device),
                 id: Self::next_id(),
                 must_put_in_pool: true,
+                semaphore_type: SemaphoreType::Binary,
                 export_handle_types: ExternalSemaphoreHandleTypes::empty(),
                 state: Mutex::new(Default::default()),
             },
@@ -154,6 +175,8 @@ impl Semaphore {
         create_info: SemaphoreCreateInfo,
     ) -> Semaphore {
         let SemaphoreCreateInfo {
+            semaphore_type,
+            initial_value: _,
             export_handle_types,
             _ne: _,
         } = create_info;
@@ -163,11 +186,64 @@ impl Semaphore {
             device: InstanceOwnedDebugWrapper(device),
             id: Self::next_id(),
             must_put_in_pool: false,
+            semaphore_type,
             export_handle_types,
             state: Mutex::new(Default::default()),
         }
     }
 
+    /// Returns the type of the semaphore.
+    #[inline]
+    pub fn semaphore_type(&self) -> SemaphoreType {
+        self.semaphore_type
+    }
+
+    /// Returns the current value of the counter of a timeline semaphore.
+    #[inline]
+    pub fn counter_value(&self) -> Result<u64, Validated<VulkanError>> {
+        self.validate_counter_value()?;
+
+        unsafe { Ok(self.counter_value_unchecked()?) }
+    }
+
+    fn validate_counter_value(&self) -> Result<(), Box<ValidationError>> {
+        if self.semaphore_type != SemaphoreType::Timeline {
+            return Err(Box::new(ValidationError {
+                context: "self.semaphore_type()".into(),
+                problem: "is not `SemaphoreType::Timeline`".into(),
+                vuids: &["VUID-vkGetSemaphoreCounterValue-semaphore-03255"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn counter_value_unchecked(&self) -> Result<u64, VulkanError> {
+        let fns = self.device.fns();
+        let mut output = MaybeUninit::uninit();
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.get_semaphore_counter_value)(
+                self.device.handle(),
+                self.handle,
+                output.as_mut_ptr(),
+            )
+        } else {
+            (fns.khr_timeline_semaphore.get_semaphore_counter_value_khr)(
+                self.device.handle(),
+                self.handle,
+                output.as_mut_ptr(),
+            )
+        }
+        .result()
+        .map_err(|err| self.device.check_lost(VulkanError::from(err)))?;
+
+        Ok(output.assume_init())
+    }
+
     /// Exports the semaphore into a POSIX file descriptor. The caller owns the returned `File`.
     #[cfg(unix)]
     #[inline]
@@ -1026,7 +1102,7 @@ impl SemaphoreState {
     /// If the semaphore does not have a pending operation and has no external references,
     /// returns the current status.
     #[inline]
-    fn is_signaled(&self) -> Option<bool> {
+    pub(crate) fn is_signaled(&self) -> Option<bool> {
         // If any of these is true, we can't be certain of the status.
         if self.pending_signal.is_some()
             || self.pending_wait.is_some()
@@ -1039,12 +1115,12 @@ impl SemaphoreState {
     }
 
     #[inline]
-    fn is_signal_pending(&self) -> bool {
+    pub(crate) fn is_signal_pending(&self) -> bool {
         self.pending_signal.is_some()
     }
 
     #[inline]
-    fn is_wait_pending(&self) -> bool {
+    pub(crate) fn is_wait_pending(&self) -> bool {
         self.pending_wait.is_some()
     }
 
@@ -1143,6 +1219,21 @@ impl From<ExternalSemaphoreHandleType> for ImportType {
 /// Parameters to create a new `Semaphore`.
 #[derive(Clone, Debug)]
 pub struct SemaphoreCreateInfo {
+    /// The type of semaphore to create.
+    ///
+    /// If this is not [`SemaphoreType::Binary`], the
+    /// [`timeline_semaphore`](crate::device::Features::timeline_semaphore) feature must be enabled
+    /// on the device.
+    ///
+    /// The default value is [`SemaphoreType::Binary`].
+    pub semaphore_type: SemaphoreType,
+
+    /// If `semaphore_type` is [`SemaphoreType::Timeline`], specifies the initial value of the
+    /// semaphore's counter. For binary semaphores, this must be 0.
+    ///
+    /// The default value is 0.
+    pub initial_value: u64,
+
     /// The handle types that can be exported from the semaphore.
     ///
     /// The default value is [`ExternalSemaphoreHandleTypes::empty()`].
@@ -1155,6 +1246,8 @@ impl Default for SemaphoreCreateInfo {
     #[inline]
     fn default() -> Self {
         Self {
+            semaphore_type: SemaphoreType::Binary,
+            initial_value: 0,
             export_handle_types: ExternalSemaphoreHandleTypes::empty(),
             _ne: crate::NonExhaustive(()),
         }
@@ -1164,10 +1257,46 @@ impl Default for SemaphoreCreateInfo {
 impl SemaphoreCreateInfo {
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
+            semaphore_type,
+            initial_value,
             export_handle_types,
             _ne: _,
         } = self;
 
+        semaphore_type
+            .validate_device(device)
+            .map_err(|err| ValidationError {
+                context: "semaphore_type".into(),
+                vuids: &["VUID-VkSemaphoreTypeCreateInfo-semaphoreType-parameter"],
+                ..ValidationError::from_requirement(err)
+            })?;
+
+        match semaphore_type {
+            SemaphoreType::Binary => {
+                if initial_value != 0 {
+                    return Err(Box::new(ValidationError {
+                        problem: "`semaphore_type` is `SemaphoreType::Binary`, but \
+                            `initial_value` is not 0"
+                            .into(),
+                        vuids: &["VUID-VkSemaphoreTypeCreateInfo-semaphoreType-03279"],
+                        ..Default::default()
+                    }));
+                }
+            }
+            SemaphoreType::Timeline => {
+                if !device.enabled_features().timeline_semaphore {
+                    return Err(Box::new(ValidationError {
+                        context: "semaphore_type".into(),
+                        problem: "is `SemaphoreType::Timeline`".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "timeline_semaphore",
+                        )])]),
+                        vuids: &["VUID-VkSemaphoreTypeCreateInfo-timelineSemaphore-03252"],
+                    }));
+                }
+            }
+        }
+
         if !export_handle_types.is_empty() {
             if !(device.api_version() >= Version::V1_1
                 || device.enabled_extensions().khr_external_semaphore)
@@ -1239,6 +1368,29 @@ impl SemaphoreCreateInfo {
     }
 }
 
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The type of a semaphore.
+    SemaphoreType = SemaphoreType(i32);
+
+    /// A semaphore that has two states, unsignaled and signaled. It is signaled by a single queue
+    /// operation, and unsignaled again by a single wait operation.
+    Binary = BINARY,
+
+    /// A semaphore that holds a monotonically increasing 64-bit counter. Signal operations set
+    /// the counter to a specific value, and wait operations wait until the counter has reached
+    /// at least a given value.
+    ///
+    /// Unlike binary semaphores, any number of operations can wait on the same value, and a wait
+    /// operation does not change the state of the semaphore.
+    Timeline = TIMELINE
+    RequiresOneOf([
+        RequiresAllOf([APIVersion(V1_2)]),
+        RequiresAllOf([DeviceExtension(khr_timeline_semaphore)]),
+    ]),
+}
+
 vulkan_bitflags_enum! {
     #[non_exhaustive]
 
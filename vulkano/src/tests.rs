@@ -27,14 +27,29 @@ macro_rules! instance {
 }
 
 /// Creates a device and a queue for graphics operations.
+///
+/// The device is created with the listed features, and optionally extensions, enabled. Returns if
+/// there is no physical device that supports them.
 macro_rules! gfx_dev_and_queue {
     ($($feature:ident),*) => ({
+        gfx_dev_and_queue!(extensions: [], features: [$($feature),*])
+    });
+
+    (
+        extensions: [$($extension:ident),* $(,)?],
+        features: [$($feature:ident),* $(,)?] $(,)?
+    ) => ({
         use crate::device::physical::PhysicalDeviceType;
         use crate::device::{Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo};
         use crate::device::Features;
 
         let instance = instance!();
-        let enabled_extensions = DeviceExtensions::empty();
+        let enabled_extensions = DeviceExtensions {
+            $(
+                $extension: true,
+            )*
+            .. DeviceExtensions::empty()
+        };
         let enabled_features = Features {
             $(
                 $feature: true,